@@ -0,0 +1,93 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Timings of short-range functions and self-energies of interaction schemes.
+//!
+//! Run with `cargo bench --bench schemes`, optionally followed by a filter on the
+//! benchmark names, _e.g._ `cargo bench --bench schemes -- ewald`.
+//! Each benchmark reports the mean time per call in nanoseconds, where the short-range
+//! functions are timed as the sum of f0, f1, f2, and f3 at a reduced distance.
+//! Running the benchmark on two commits gives the speed-up of a change.
+//! Poisson schemes are timed both with const generic orders, `poisson<C, D>`, and with
+//! orders given at runtime, `poisson(C, D)`.
+
+use coulomb::pairwise::{
+    MultipoleEnergy, Poisson, RealSpaceEwald, RuntimePoisson, ShortRangeFunction, Stenqvist,
+};
+use std::hint::black_box;
+use std::time::Instant;
+
+/// Number of timed calls per benchmark
+const ITERATIONS: u32 = 2_000_000;
+
+/// Print the mean time per call of `f`, after a warm-up, unless filtered out by `filter`
+fn bench(filter: &Option<String>, name: &str, mut f: impl FnMut() -> f64) {
+    if filter
+        .as_ref()
+        .is_some_and(|filter| !name.contains(filter.as_str()))
+    {
+        return;
+    }
+    for _ in 0..ITERATIONS / 10 {
+        black_box(f());
+    }
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    let nanoseconds = start.elapsed().as_secs_f64() * 1e9 / f64::from(ITERATIONS);
+    println!("{name:<40} {nanoseconds:>10.2} ns");
+}
+
+/// Sum of the short-range function and its first three derivatives at `q`
+fn short_range<T: ShortRangeFunction>(scheme: &T, q: f64) -> f64 {
+    scheme.short_range_f0(q)
+        + scheme.short_range_f1(q)
+        + scheme.short_range_f2(q)
+        + scheme.short_range_f3(q)
+}
+
+fn main() {
+    // `cargo bench` passes `--bench`, which is not a filter
+    let filter = std::env::args().skip(1).find(|arg| !arg.starts_with("--"));
+    let q = 0.4;
+
+    for (name, debye_length) in [("ewald", None), ("ewald screened", Some(30.0))] {
+        let scheme = RealSpaceEwald::new(12.0, 0.2, debye_length);
+        bench(&filter, &format!("{name} self_energy"), || {
+            black_box(&scheme).self_energy(&[1.0], &[1.0], &[])
+        });
+        bench(&filter, &format!("{name} f0..f3"), || {
+            short_range(black_box(&scheme), black_box(q))
+        });
+    }
+
+    for (name, debye_length) in [("", None), (" screened", Some(30.0))] {
+        let stenqvist = Stenqvist::new(12.0, debye_length);
+        bench(&filter, &format!("poisson<3, 3>{name} f0..f3"), || {
+            short_range(black_box(&stenqvist), black_box(q))
+        });
+        let poisson = Poisson::<8, 8>::new(12.0, debye_length);
+        bench(&filter, &format!("poisson<8, 8>{name} f0..f3"), || {
+            short_range(black_box(&poisson), black_box(q))
+        });
+        // same orders given at runtime, to compare with the const generic schemes above
+        for (c, d) in [(3, 3), (8, 8)] {
+            let runtime = RuntimePoisson::new(c, d, 12.0, debye_length).unwrap();
+            bench(&filter, &format!("poisson({c}, {d}){name} f0..f3"), || {
+                short_range(black_box(&runtime), black_box(q))
+            });
+        }
+    }
+}
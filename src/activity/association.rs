@@ -0,0 +1,187 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Association constants of ion pairs.
+
+use crate::permittivity::RelativePermittivity;
+use crate::{bjerrum_length, Result, AVOGADRO_CONSTANT, LITER_PER_ANGSTROM3};
+use core::f64::consts::PI;
+
+/// Number of Simpson intervals for the pairing integral
+const INTERVALS: usize = 2000;
+
+/// Reduced coupling, b = λ𝐵|z₁z₂|/a, or `None` if the ions repel
+fn coupling(
+    valency1: f64,
+    valency2: f64,
+    contact_distance: f64,
+    permittivity_model: &impl RelativePermittivity,
+    temperature: f64,
+) -> Result<Option<f64>> {
+    let permittivity = permittivity_model.permittivity(temperature)?;
+    let product = valency1 * valency2;
+    Ok((product < 0.0)
+        .then(|| bjerrum_length(temperature, permittivity) * product.abs() / contact_distance))
+}
+
+/// Bjerrum association constant of an ion pair in l/mol.
+///
+/// Ions with valencies `valency1` and `valency2` are considered paired when closer than the
+/// Bjerrum distance, $R = \lambda_B |z_1 z_2| / 2$, giving
+///
+/// $$ K_A = 4 \pi N_A \int_a^R r^2 e^{\lambda_B |z_1 z_2| / r} dr $$
+///
+/// where $a$ is the contact distance in angstrom and the Bjerrum length, $\lambda_B$, is found
+/// from `permittivity_model` at `temperature` (K).
+/// The integral is evaluated numerically and is zero if $a \geq R$ or if the ions have the
+/// same sign.
+/// See Bjerrum, Kgl. Danske Vidensk. Selskab. Math.-Fys. Medd. 7, 1 (1926).
+///
+/// # Errors
+/// Returns an error if the temperature is outside the range of the permittivity model.
+///
+/// # Examples
+/// ~~~
+/// use coulomb::{activity::bjerrum_association_constant, permittivity::Permittivity};
+/// let water = bjerrum_association_constant(2.0, -2.0, 4.0, &Permittivity::Water, 298.15);
+/// let methanol = bjerrum_association_constant(2.0, -2.0, 4.0, &Permittivity::Fixed(32.6), 298.15);
+/// assert!(methanol.unwrap() > water.unwrap());
+/// ~~~
+pub fn bjerrum_association_constant(
+    valency1: f64,
+    valency2: f64,
+    contact_distance: f64,
+    permittivity_model: &impl RelativePermittivity,
+    temperature: f64,
+) -> Result<f64> {
+    let Some(b) = coupling(
+        valency1,
+        valency2,
+        contact_distance,
+        permittivity_model,
+        temperature,
+    )?
+    else {
+        return Ok(0.0);
+    };
+    if b <= 2.0 {
+        return Ok(0.0);
+    }
+    // Simpson's rule in x = b a / r from 2 to b, with r² dr = -(b a)³ x⁻⁴ dx
+    let integrand = |x: f64| x.powi(-4) * x.exp();
+    let step = (b - 2.0) / INTERVALS as f64;
+    let integral = (0..=INTERVALS)
+        .map(|i| {
+            let weight = match i {
+                0 | INTERVALS => 1.0,
+                _ if i % 2 == 1 => 4.0,
+                _ => 2.0,
+            };
+            weight * integrand(2.0 + i as f64 * step)
+        })
+        .sum::<f64>()
+        * step
+        / 3.0;
+    let length = b * contact_distance;
+    Ok(4.0 * PI * length.powi(3) * integral * AVOGADRO_CONSTANT * LITER_PER_ANGSTROM3)
+}
+
+/// Fuoss association constant of an ion pair in l/mol.
+///
+/// Ions are paired only at contact, $a$, giving
+///
+/// $$ K_A = \frac{4 \pi N_A a^3}{3} e^{\lambda_B |z_1 z_2| / a} $$
+///
+/// with arguments as for [`bjerrum_association_constant`], and zero if the ions have the same
+/// sign.
+/// See Fuoss, <https://doi.org/10.1021/ja01552a004>.
+///
+/// # Errors
+/// Returns an error if the temperature is outside the range of the permittivity model.
+pub fn fuoss_association_constant(
+    valency1: f64,
+    valency2: f64,
+    contact_distance: f64,
+    permittivity_model: &impl RelativePermittivity,
+    temperature: f64,
+) -> Result<f64> {
+    Ok(coupling(
+        valency1,
+        valency2,
+        contact_distance,
+        permittivity_model,
+        temperature,
+    )?
+    .map_or(0.0, |b| {
+        4.0 * PI / 3.0
+            * contact_distance.powi(3)
+            * b.exp()
+            * AVOGADRO_CONSTANT
+            * LITER_PER_ANGSTROM3
+    }))
+}
+
+#[test]
+fn test_association_constant() {
+    use crate::permittivity::ConstantPermittivity;
+    use approx::assert_relative_eq;
+    let temperature = 298.15;
+    let permittivity = ConstantPermittivity::new(20.0);
+    let (z1, z2, a) = (2.0, -1.0, 3.0);
+    let b = bjerrum_length(temperature, 20.0) * 2.0 / a;
+
+    // ∫ x⁻⁴ eˣ dx = -eˣ (1/3x³ + 1/6x² + 1/6x) + Ei(x)/6 with the exponential integral as a series
+    let exponential_integral = |x: f64| {
+        let mut term = 1.0;
+        let mut sum = 0.5772156649015329 + x.ln();
+        for k in 1..200 {
+            term *= x / k as f64;
+            sum += term / k as f64;
+        }
+        sum
+    };
+    let antiderivative = |x: f64| {
+        -x.exp() * (1.0 / (3.0 * x.powi(3)) + 1.0 / (6.0 * x * x) + 1.0 / (6.0 * x))
+            + exponential_integral(x) / 6.0
+    };
+    let expected = 4.0
+        * PI
+        * (b * a).powi(3)
+        * (antiderivative(b) - antiderivative(2.0))
+        * AVOGADRO_CONSTANT
+        * LITER_PER_ANGSTROM3;
+    assert_relative_eq!(
+        bjerrum_association_constant(z1, z2, a, &permittivity, temperature).unwrap(),
+        expected,
+        max_relative = 1e-9
+    );
+    assert_relative_eq!(
+        fuoss_association_constant(z1, z2, a, &permittivity, temperature).unwrap(),
+        4.0 * PI / 3.0 * a.powi(3) * b.exp() * AVOGADRO_CONSTANT * LITER_PER_ANGSTROM3
+    );
+
+    // No pairing for like charges or beyond the Bjerrum distance
+    assert_eq!(
+        bjerrum_association_constant(1.0, 1.0, a, &permittivity, temperature).unwrap(),
+        0.0
+    );
+    assert_eq!(
+        fuoss_association_constant(-1.0, -1.0, a, &permittivity, temperature).unwrap(),
+        0.0
+    );
+    assert_eq!(
+        bjerrum_association_constant(1.0, -1.0, 100.0, &permittivity, temperature).unwrap(),
+        0.0
+    );
+}
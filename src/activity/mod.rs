@@ -0,0 +1,476 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Activity coefficients of ions in electrolyte solutions.
+//!
+//! In the Debye-Hückel limiting law, the activity coefficient of an ion with valency $z_i$ is
+//!
+//! $$ \ln \gamma_i = -\frac{z_i^2 \lambda_B \kappa}{2} $$
+//!
+//! where $\lambda_B$ is the Bjerrum length and $\kappa$ the inverse Debye length, both
+//! found from the temperature, permittivity, and ionic strength of the solution.
+//! The mean activity coefficient of a salt with stoichiometric coefficients $\nu_i$ is
+//!
+//! $$ \ln \gamma_\pm = \frac{\sum_i \nu_i \ln \gamma_i}{\sum_i \nu_i} $$
+//!
+//! Corrections for higher ionic strengths are available through [`ActivityModel`], while
+//! [`Pitzer`] covers concentrated solutions of single salts.
+//! The mean spherical approximation, [`Msa`], accounts for the finite size of ions.
+//! Ion pairing is described by association constants, see [`bjerrum_association_constant`].
+//!
+//! # Examples
+//! ~~~
+//! # use approx::assert_relative_eq;
+//! use coulomb::{activity, Medium, Salt};
+//! let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.001);
+//! let gamma = activity::mean_activity_coefficient(&Salt::SodiumChloride, &medium).unwrap();
+//! assert_relative_eq!(gamma.log10(), -0.5108 * f64::sqrt(0.001), max_relative = 1e-3);
+//! ~~~
+
+use crate::permittivity::RelativePermittivity;
+use crate::{bjerrum_length, DebyeLength, IonicStrength, Result, Salt, Temperature};
+use core::f64::consts::LN_10;
+
+mod association;
+mod msa;
+mod pitzer;
+pub use association::{bjerrum_association_constant, fuoss_association_constant};
+pub use msa::Msa;
+pub use pitzer::Pitzer;
+
+/// Bjerrum length times inverse Debye length, λ𝐵κ, or zero without salt
+fn reduced_screening<T>(solution: &T) -> Result<f64>
+where
+    T: Temperature + RelativePermittivity + IonicStrength,
+{
+    let temperature = solution.temperature();
+    let permittivity = solution.permittivity(temperature)?;
+    Ok(solution.kappa().map_or(0.0, |kappa| {
+        bjerrum_length(temperature, permittivity) * kappa
+    }))
+}
+
+/// Model for activity coefficients of ions.
+///
+/// Model                  | $\ln \gamma_i / (-z_i^2 \lambda_B \kappa / 2)$           | Validity
+/// ---------------------- | ------------------------------------------------------ | ----------
+/// `LimitingLaw`          | $1$                                                    | I ≲ 0.005 M
+/// `Extended`             | $1 / (1 + \kappa a)$                                   | I ≲ 0.1 M
+/// `Davies`               | $1 / (1 + \sqrt{I}) - 0.3 \sqrt{I}$                     | I ≲ 0.5 M
+/// `Sit`                  | $1 / (1 + 1.5 \sqrt{I})$                               | I ≲ 3 M
+///
+/// where $a$ is the ion size and $I$ the ionic strength in mol/l.
+/// The Davies equation is empirical, see <https://doi.org/10.1039/JR9380002093>.
+///
+/// In the specific ion interaction theory (SIT), short-range interactions between oppositely
+/// charged ions add $\ln 10 \sum_j \varepsilon(i,j) m_j$ to $\ln \gamma_i$, where $m_j$ is the
+/// concentration of counter-ion $j$.
+/// Interaction coefficients are tabulated in thermodynamic databases, see
+/// Grenthe _et al._, <https://doi.org/10.1787/9789264057760-en>.
+/// Ions are identified by their valencies and molarities are used in place of molalities.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::{activity::ActivityModel, Medium, Salt};
+/// let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+/// let davies = ActivityModel::Davies.mean_activity_coefficient(&Salt::SodiumChloride, &medium);
+/// assert_relative_eq!(davies.unwrap(), 0.7808, max_relative = 1e-4);
+/// ~~~
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ActivityModel {
+    /// Debye-Hückel limiting law
+    #[default]
+    LimitingLaw,
+    /// Extended Debye-Hückel with ion size, UNIT: [ angstrom ]
+    Extended {
+        /// Distance of closest approach, UNIT: [ angstrom ]
+        ion_size: f64,
+    },
+    /// Davies equation
+    Davies,
+    /// Specific ion interaction theory
+    Sit {
+        /// Interaction coefficients, ε(i,j), between ions of valencies zᵢ and zⱼ, UNIT: [ l / mol ]
+        coefficients: Vec<(isize, isize, f64)>,
+    },
+}
+
+impl ActivityModel {
+    /// Correction factor to the limiting law, see table above
+    fn correction<T>(&self, solution: &T) -> f64
+    where
+        T: Temperature + RelativePermittivity + IonicStrength,
+    {
+        match self {
+            Self::LimitingLaw => 1.0,
+            Self::Extended { ion_size } => {
+                (1.0 + solution.kappa().unwrap_or(0.0) * ion_size).recip()
+            }
+            Self::Davies => {
+                let sqrt_ionic_strength = solution.ionic_strength().unwrap_or(0.0).sqrt();
+                (1.0 + sqrt_ionic_strength).recip() - 0.3 * sqrt_ionic_strength
+            }
+            Self::Sit { .. } => {
+                (1.0 + 1.5 * solution.ionic_strength().unwrap_or(0.0).sqrt()).recip()
+            }
+        }
+    }
+
+    /// SIT interaction coefficient, ε(i,j), or zero if not given
+    fn interaction(&self, valency_i: isize, valency_j: isize) -> f64 {
+        match self {
+            Self::Sit { coefficients } => coefficients
+                .iter()
+                .filter(|(a, b, _)| {
+                    (*a, *b) == (valency_i, valency_j) || (*a, *b) == (valency_j, valency_i)
+                })
+                .map(|(_, _, epsilon)| epsilon)
+                .sum(),
+            _ => 0.0,
+        }
+    }
+
+    /// Single-ion activity coefficient, γᵢ.
+    ///
+    /// For SIT, the counter-ion concentration of each interaction is found from the ionic
+    /// strength, assuming the solution contains a single salt of the two ions.
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is outside the range of the permittivity model.
+    pub fn activity_coefficient<T>(&self, valency: isize, solution: &T) -> Result<f64>
+    where
+        T: Temperature + RelativePermittivity + IonicStrength,
+    {
+        let z2 = valency.pow(2) as f64;
+        let screening = reduced_screening(solution)? * self.correction(solution);
+        let ionic_strength = solution.ionic_strength().unwrap_or(0.0);
+        let specific: f64 = match self {
+            Self::Sit { coefficients } => coefficients
+                .iter()
+                .filter_map(|&(a, b, epsilon)| match (a == valency, b == valency) {
+                    (true, _) => Some((b, epsilon)),
+                    (_, true) => Some((a, epsilon)),
+                    _ => None,
+                })
+                .filter(|(counter, _)| counter.signum() == -valency.signum())
+                .map(|(counter, epsilon)| {
+                    let (z_i, z_j) = (valency.abs() as f64, counter.abs() as f64);
+                    epsilon * 2.0 * ionic_strength / (z_j * (z_i + z_j))
+                })
+                .sum(),
+            _ => 0.0,
+        };
+        Ok((-0.5 * z2 * screening + LN_10 * specific).exp())
+    }
+
+    /// Mean activity coefficient, γ±, of a salt.
+    ///
+    /// The ionic strength is that of the `solution`, which may contain other salts.
+    ///
+    /// # Errors
+    /// Returns an error if the stoichiometry of the salt cannot be resolved or if the temperature
+    /// is outside the range of the permittivity model.
+    pub fn mean_activity_coefficient<T>(&self, salt: &Salt, solution: &T) -> Result<f64>
+    where
+        T: Temperature + RelativePermittivity + IonicStrength,
+    {
+        let screening = reduced_screening(solution)? * self.correction(solution);
+        let stoichiometry = salt.stoichiometry()?;
+        let valencies = salt.valencies();
+        let nu: usize = stoichiometry.iter().sum();
+        // Salt molarity, assuming the ionic strength is due to this salt only
+        let molarity = solution.ionic_strength().unwrap_or(0.0) / salt.ionic_strength(1.0)?;
+        let ln_gamma: f64 = valencies
+            .iter()
+            .zip(&stoichiometry)
+            .map(|(z_i, nu_i)| {
+                let specific: f64 = valencies
+                    .iter()
+                    .zip(&stoichiometry)
+                    .filter(|(z_j, _)| z_j.signum() == -z_i.signum())
+                    .map(|(z_j, nu_j)| self.interaction(*z_i, *z_j) * (*nu_j as f64) * molarity)
+                    .sum();
+                *nu_i as f64 * (-0.5 * z_i.pow(2) as f64 * screening + LN_10 * specific)
+            })
+            .sum();
+        Ok((ln_gamma / nu as f64).exp())
+    }
+
+    /// Osmotic coefficient, φ, of a salt solution.
+    ///
+    /// Found from the mean activity coefficient through the Gibbs-Duhem relation,
+    ///
+    /// $$ \phi = 1 + \ln \gamma_\pm(c) - \frac{1}{c} \int_0^c \ln \gamma_\pm(c') \, dc' $$
+    ///
+    /// where the integral over the salt concentration, $c$, is evaluated numerically
+    /// assuming the ionic strength of the `solution` is due to `salt` only.
+    ///
+    /// # Errors
+    /// Returns an error if the stoichiometry of the salt cannot be resolved or if the temperature
+    /// is outside the range of the permittivity model.
+    pub fn osmotic_coefficient<T>(&self, salt: &Salt, solution: &T) -> Result<f64>
+    where
+        T: Temperature + RelativePermittivity + IonicStrength,
+    {
+        const INTERVALS: usize = 64;
+        let Some(ionic_strength) = solution.ionic_strength() else {
+            return Ok(1.0);
+        };
+        let ln_gamma = |fraction: f64| {
+            let diluted = Diluted {
+                solution,
+                ionic_strength: ionic_strength * fraction,
+            };
+            self.mean_activity_coefficient(salt, &diluted).map(f64::ln)
+        };
+        // Simpson's rule with c' = c t² to remove the square root singularity at t = 0
+        let mut integral = 0.0;
+        for i in 1..=INTERVALS {
+            let t = i as f64 / INTERVALS as f64;
+            let weight = match i {
+                INTERVALS => 1.0,
+                _ if i % 2 == 1 => 4.0,
+                _ => 2.0,
+            };
+            integral += weight * 2.0 * t * ln_gamma(t * t)?;
+        }
+        integral /= 3.0 * INTERVALS as f64;
+        Ok(1.0 + ln_gamma(1.0)? - integral)
+    }
+
+    /// Osmotic pressure, Π = φ ν c R T, of a salt solution where ν c is the total ion concentration.
+    ///
+    /// # Errors
+    /// Returns an error if the stoichiometry of the salt cannot be resolved or if the temperature
+    /// is outside the range of the permittivity model.
+    #[cfg(feature = "uom")]
+    pub fn osmotic_pressure<T>(&self, salt: &Salt, solution: &T) -> Result<crate::units::Pressure>
+    where
+        T: Temperature + RelativePermittivity + IonicStrength,
+    {
+        use crate::units::{pascal, Pressure};
+        let molarity = solution.ionic_strength().unwrap_or(0.0) / salt.ionic_strength(1.0)?;
+        let nu: usize = salt.stoichiometry()?.iter().sum();
+        let phi = self.osmotic_coefficient(salt, solution)?;
+        Ok(Pressure::new::<pascal>(
+            phi * nu as f64 * molarity * 1e3 * crate::MOLAR_GAS_CONSTANT * solution.temperature(),
+        ))
+    }
+}
+
+/// Solution with the ionic strength replaced
+struct Diluted<'a, T> {
+    solution: &'a T,
+    ionic_strength: f64,
+}
+
+impl<T: Temperature> Temperature for Diluted<'_, T> {
+    fn temperature(&self) -> f64 {
+        self.solution.temperature()
+    }
+}
+
+impl<T: RelativePermittivity> RelativePermittivity for Diluted<'_, T> {
+    fn permittivity(&self, temperature: f64) -> Result<f64> {
+        self.solution.permittivity(temperature)
+    }
+}
+
+impl<T> IonicStrength for Diluted<'_, T> {
+    fn ionic_strength(&self) -> Option<f64> {
+        (self.ionic_strength > 0.0).then_some(self.ionic_strength)
+    }
+}
+
+/// Single-ion activity coefficient, γᵢ, from the Debye-Hückel limiting law.
+///
+/// # Errors
+/// Returns an error if the temperature is outside the range of the permittivity model.
+pub fn activity_coefficient<T>(valency: isize, solution: &T) -> Result<f64>
+where
+    T: Temperature + RelativePermittivity + IonicStrength,
+{
+    ActivityModel::LimitingLaw.activity_coefficient(valency, solution)
+}
+
+/// Mean activity coefficient, γ±, of a salt from the Debye-Hückel limiting law.
+///
+/// See [`ActivityModel::mean_activity_coefficient`].
+///
+/// # Errors
+/// Returns an error if the stoichiometry of the salt cannot be resolved or if the temperature is
+/// outside the range of the permittivity model.
+pub fn mean_activity_coefficient<T>(salt: &Salt, solution: &T) -> Result<f64>
+where
+    T: Temperature + RelativePermittivity + IonicStrength,
+{
+    ActivityModel::LimitingLaw.mean_activity_coefficient(salt, solution)
+}
+
+#[test]
+fn test_activity() {
+    use crate::Medium;
+    use approx::assert_relative_eq;
+
+    // γ± = γ₊^(ν₊/ν) γ₋^(ν₋/ν) and log₁₀γ± = -A |z₊z₋| √I
+    let medium = Medium::salt_water(298.15, Salt::CalciumChloride, 0.002);
+    let gamma_plus = activity_coefficient(2, &medium).unwrap();
+    let gamma_minus = activity_coefficient(-1, &medium).unwrap();
+    let gamma = mean_activity_coefficient(&Salt::CalciumChloride, &medium).unwrap();
+    assert_relative_eq!(gamma, (gamma_plus * gamma_minus.powi(2)).cbrt());
+    assert_relative_eq!(
+        gamma.log10(),
+        -0.5108 * 2.0 * f64::sqrt(0.006),
+        max_relative = 1e-3
+    );
+
+    // No salt
+    let water = Medium::neat_water(298.15);
+    assert_eq!(activity_coefficient(3, &water).unwrap(), 1.0);
+    assert!(mean_activity_coefficient(&Salt::Custom(vec![1, 1]), &medium).is_err());
+
+    // Ion size and empirical corrections reduce the deviation from ideality
+    let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+    let models = [
+        ActivityModel::LimitingLaw,
+        ActivityModel::Extended { ion_size: 4.0 },
+        ActivityModel::Davies,
+    ];
+    let gamma = models.map(|model| {
+        model
+            .mean_activity_coefficient(&Salt::SodiumChloride, &medium)
+            .unwrap()
+    });
+    assert!(gamma[0] < gamma[1] && gamma[0] < gamma[2]);
+    let kappa = medium.kappa().unwrap();
+    assert_relative_eq!(
+        gamma[1].ln(),
+        gamma[0].ln() / (1.0 + 4.0 * kappa),
+        max_relative = 1e-12
+    );
+    // Tabulated value at 25°C from log₁₀γ = -A z² (√I/(1 + √I) - 0.3 I) with A = 0.509
+    let sqrt_i = 0.1f64.sqrt();
+    assert_relative_eq!(
+        gamma[2].log10(),
+        -0.509 * (sqrt_i / (1.0 + sqrt_i) - 0.03),
+        max_relative = 5e-3
+    );
+
+    // SIT for NaCl with ε(Na⁺,Cl⁻) = 0.03 and log₁₀γ± = -A √I/(1 + 1.5√I) + ε m
+    let sit = ActivityModel::Sit {
+        coefficients: vec![(1, -1, 0.03)],
+    };
+    let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 1.0);
+    let gamma = sit
+        .mean_activity_coefficient(&Salt::SodiumChloride, &medium)
+        .unwrap();
+    assert_relative_eq!(gamma.log10(), -0.5108 / 2.5 + 0.03, max_relative = 2e-3);
+    let gamma_plus = sit.activity_coefficient(1, &medium).unwrap();
+    let gamma_minus = sit.activity_coefficient(-1, &medium).unwrap();
+    assert_relative_eq!(
+        gamma,
+        (gamma_plus * gamma_minus).sqrt(),
+        max_relative = 1e-12
+    );
+
+    // Counter-ion concentrations of an asymmetric salt
+    let sit = ActivityModel::Sit {
+        coefficients: vec![(-1, 2, 0.02)],
+    };
+    let medium = Medium::salt_water(298.15, Salt::CalciumChloride, 0.5);
+    let gamma_plus = sit.activity_coefficient(2, &medium).unwrap();
+    let gamma_minus = sit.activity_coefficient(-1, &medium).unwrap();
+    assert_relative_eq!(
+        sit.mean_activity_coefficient(&Salt::CalciumChloride, &medium)
+            .unwrap(),
+        (gamma_plus * gamma_minus.powi(2)).cbrt(),
+        max_relative = 1e-12
+    );
+    let no_interactions = ActivityModel::Sit {
+        coefficients: vec![],
+    };
+    assert_relative_eq!(
+        gamma_plus / no_interactions.activity_coefficient(2, &medium).unwrap(),
+        10f64.powf(0.02 * 1.0)
+    );
+}
+
+#[test]
+fn test_osmotic_coefficient() {
+    use crate::{DebyeLength, Medium};
+    use approx::assert_relative_eq;
+
+    // Limiting law, φ - 1 = ln γ± / 3
+    let medium = Medium::salt_water(298.15, Salt::CalciumChloride, 0.001);
+    let model = ActivityModel::LimitingLaw;
+    let salt = Salt::CalciumChloride;
+    assert_relative_eq!(
+        model.osmotic_coefficient(&salt, &medium).unwrap() - 1.0,
+        model
+            .mean_activity_coefficient(&salt, &medium)
+            .unwrap()
+            .ln()
+            / 3.0,
+        max_relative = 1e-8
+    );
+
+    // Extended Debye-Hückel in closed form with x = κa
+    let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+    let ion_size = 4.0;
+    let model = ActivityModel::Extended { ion_size };
+    let x = medium.kappa().unwrap() * ion_size;
+    let ln_gamma_limiting = ActivityModel::LimitingLaw
+        .mean_activity_coefficient(&Salt::SodiumChloride, &medium)
+        .unwrap()
+        .ln();
+    let sigma = 3.0 / x.powi(3) * (1.0 + x - 1.0 / (1.0 + x) - 2.0 * (1.0 + x).ln());
+    assert_relative_eq!(
+        model
+            .osmotic_coefficient(&Salt::SodiumChloride, &medium)
+            .unwrap()
+            - 1.0,
+        ln_gamma_limiting * sigma / 3.0,
+        max_relative = 1e-6
+    );
+
+    // No salt
+    let water = Medium::neat_water(298.15);
+    assert_eq!(
+        model
+            .osmotic_coefficient(&Salt::SodiumChloride, &water)
+            .unwrap(),
+        1.0
+    );
+}
+
+#[cfg(feature = "uom")]
+#[test]
+fn test_osmotic_pressure() {
+    use crate::{units::*, Medium};
+    use approx::assert_relative_eq;
+    // Van 't Hoff's law, Π = 2cRT, with an osmotic coefficient of unity
+    let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+    let model = ActivityModel::Davies;
+    let salt = Salt::SodiumChloride;
+    let phi = model.osmotic_coefficient(&salt, &medium).unwrap();
+    let pressure = model.osmotic_pressure(&salt, &medium).unwrap();
+    assert_relative_eq!(
+        pressure.get::<pascal>() / phi,
+        2.0 * 100.0 * crate::MOLAR_GAS_CONSTANT * 298.15,
+        max_relative = 1e-12
+    );
+    assert!(phi < 1.0);
+}
@@ -0,0 +1,383 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Mean spherical approximation for the primitive model of electrolytes.
+
+use crate::{Result, Salt, AVOGADRO_CONSTANT, LITER_PER_ANGSTROM3};
+use core::f64::consts::PI;
+
+/// Mean spherical approximation (MSA) for charged hard spheres in a dielectric continuum.
+///
+/// For ions with valencies $z_i$, diameters $\sigma_i$, and number densities $\rho_i$, the
+/// screening parameter, $\Gamma$, solves
+///
+/// $$ \Gamma^2 = \pi \lambda_B \sum_k \rho_k \left ( \frac{z_k - \eta \sigma_k^2}{1 + \Gamma \sigma_k} \right )^2 $$
+///
+/// where $\lambda_B$ is the Bjerrum length and
+/// $\eta = \frac{\pi}{2 \Delta \Omega} \sum_k \frac{\rho_k \sigma_k z_k}{1 + \Gamma \sigma_k}$
+/// with $\Delta = 1 - \frac{\pi}{6} \sum_k \rho_k \sigma_k^3$ and
+/// $\Omega = 1 + \frac{\pi}{2 \Delta} \sum_k \frac{\rho_k \sigma_k^3}{1 + \Gamma \sigma_k}$.
+/// In the restricted primitive model, where all ions have equal size, $\eta = 0$ and
+/// $2 \Gamma \sigma = \sqrt{1 + 2 \kappa \sigma} - 1$ with $\kappa$ the inverse Debye length.
+/// For point ions, $2 \Gamma = \kappa$, and the Debye-Hückel limiting law is recovered.
+///
+/// Only the electrostatic contributions are included; hard-sphere terms can be added from,
+/// _e.g._, the Boublík-Mansoori-Carnahan-Starling-Leland equation of state.
+/// See Blum, <https://doi.org/10.1080/00268977500101151>, and
+/// Blum and Høye, <https://doi.org/10.1021/j100586a007>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::{activity::Msa, Salt};
+/// let msa = Msa::restricted(7.0, &Salt::SodiumChloride, 4.25, 0.1).unwrap();
+/// let gamma = msa.mean_activity_coefficient(&Salt::SodiumChloride).unwrap();
+/// assert!(gamma < 1.0);
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Msa {
+    /// Bjerrum length, UNIT: [ angstrom ]
+    bjerrum_length: f64,
+    /// Valency of each ion species
+    valencies: Vec<f64>,
+    /// Diameter of each ion species, UNIT: [ angstrom ]
+    diameters: Vec<f64>,
+    /// Number density of each ion species, UNIT: [ angstrom^-3 ]
+    densities: Vec<f64>,
+}
+
+/// Intermediate quantities at a given Γ
+struct Auxiliary {
+    delta: f64,
+    omega: f64,
+    eta: f64,
+}
+
+impl Msa {
+    /// Relative tolerance for the screening parameter
+    const TOLERANCE: f64 = 1e-14;
+    /// Maximum number of iterations for the screening parameter
+    const MAX_ITERATIONS: usize = 1000;
+
+    /// New model for ion species with given valencies, diameters (angstrom), and molarities (mol/l).
+    ///
+    /// # Errors
+    /// Returns an error if the solution is not electroneutral.
+    ///
+    /// # Panics
+    /// Panics if the slices differ in length.
+    pub fn new(
+        bjerrum_length: f64,
+        valencies: &[f64],
+        diameters: &[f64],
+        molarities: &[f64],
+    ) -> Result<Self> {
+        assert_eq!(valencies.len(), diameters.len());
+        assert_eq!(valencies.len(), molarities.len());
+        let net_charge: f64 = valencies.iter().zip(molarities).map(|(z, c)| z * c).sum();
+        let total_charge: f64 = valencies
+            .iter()
+            .zip(molarities)
+            .map(|(z, c)| (z * c).abs())
+            .sum();
+        if net_charge.abs() > 1e-10 * total_charge {
+            return Err(crate::Error::Unsupported(
+                "MSA for non-electroneutral solutions",
+            ));
+        }
+        Ok(Self {
+            bjerrum_length,
+            valencies: valencies.to_vec(),
+            diameters: diameters.to_vec(),
+            densities: molarities
+                .iter()
+                .map(|c| c * AVOGADRO_CONSTANT * LITER_PER_ANGSTROM3)
+                .collect(),
+        })
+    }
+
+    /// Restricted primitive model of a salt at `molarity` (mol/l) with equally sized ions.
+    ///
+    /// # Errors
+    /// Returns an error if the stoichiometry of the salt cannot be resolved.
+    pub fn restricted(
+        bjerrum_length: f64,
+        salt: &Salt,
+        diameter: f64,
+        molarity: f64,
+    ) -> Result<Self> {
+        let valencies: Vec<f64> = salt.valencies().iter().map(|z| *z as f64).collect();
+        let molarities: Vec<f64> = salt
+            .stoichiometry()?
+            .iter()
+            .map(|nu| *nu as f64 * molarity)
+            .collect();
+        Self::new(
+            bjerrum_length,
+            &valencies,
+            &vec![diameter; valencies.len()],
+            &molarities,
+        )
+    }
+
+    /// Primitive model of a salt of explicit ions at `molarity` (mol/l), using the ion diameters.
+    ///
+    /// # Errors
+    /// Returns an error if the salt is not defined by explicit ions, see [`Salt::from_ions`],
+    /// or if its stoichiometry cannot be resolved.
+    pub fn from_salt(bjerrum_length: f64, salt: &Salt, molarity: f64) -> Result<Self> {
+        let ions = salt
+            .ions()
+            .ok_or(crate::Error::Unsupported("MSA without ion sizes"))?;
+        let stoichiometry = salt.stoichiometry()?;
+        let valencies: Vec<f64> = ions.iter().map(|(ion, _)| ion.valency as f64).collect();
+        let diameters: Vec<f64> = ions.iter().map(|(ion, _)| ion.diameter()).collect();
+        let molarities: Vec<f64> = stoichiometry
+            .iter()
+            .map(|nu| *nu as f64 * molarity)
+            .collect();
+        Self::new(bjerrum_length, &valencies, &diameters, &molarities)
+    }
+
+    /// Iterator over (valency, diameter, number density) of each species
+    fn species(&self) -> impl Iterator<Item = (f64, f64, f64)> + '_ {
+        self.valencies
+            .iter()
+            .zip(&self.diameters)
+            .zip(&self.densities)
+            .map(|((z, sigma), rho)| (*z, *sigma, *rho))
+    }
+
+    fn auxiliary(&self, gamma: f64) -> Auxiliary {
+        let delta = 1.0
+            - PI / 6.0
+                * self
+                    .species()
+                    .map(|(_, s, rho)| rho * s.powi(3))
+                    .sum::<f64>();
+        let omega = 1.0
+            + PI / (2.0 * delta)
+                * self
+                    .species()
+                    .map(|(_, s, rho)| rho * s.powi(3) / (1.0 + gamma * s))
+                    .sum::<f64>();
+        let p_n = self
+            .species()
+            .map(|(z, s, rho)| rho * s * z / (1.0 + gamma * s))
+            .sum::<f64>()
+            / omega;
+        Auxiliary {
+            delta,
+            omega,
+            eta: PI * p_n / (2.0 * delta),
+        }
+    }
+
+    /// Screening parameter, Γ, UNIT: [ angstrom^-1 ]
+    ///
+    /// # Errors
+    /// Returns an error if the iterative solution does not converge.
+    pub fn screening_parameter(&self) -> Result<f64> {
+        let kappa_squared = 4.0
+            * PI
+            * self.bjerrum_length
+            * self.species().map(|(z, _, rho)| rho * z * z).sum::<f64>();
+        let mut gamma = 0.5 * kappa_squared.sqrt();
+        for _ in 0..Self::MAX_ITERATIONS {
+            let eta = self.auxiliary(gamma).eta;
+            let new_gamma = (PI
+                * self.bjerrum_length
+                * self
+                    .species()
+                    .map(|(z, s, rho)| rho * ((z - eta * s * s) / (1.0 + gamma * s)).powi(2))
+                    .sum::<f64>())
+            .sqrt();
+            if (new_gamma - gamma).abs() <= Self::TOLERANCE * new_gamma {
+                return Ok(new_gamma);
+            }
+            gamma = new_gamma;
+        }
+        Err(crate::Error::NotConverged(Self::MAX_ITERATIONS))
+    }
+
+    /// Total number density of ions, UNIT: [ angstrom^-3 ]
+    fn total_density(&self) -> f64 {
+        self.densities.iter().sum()
+    }
+
+    /// Electrostatic excess energy per ion, UNIT: [ kT ]
+    ///
+    /// # Errors
+    /// Returns an error if the screening parameter cannot be found.
+    pub fn excess_energy(&self) -> Result<f64> {
+        let gamma = self.screening_parameter()?;
+        let Auxiliary { delta, omega, eta } = self.auxiliary(gamma);
+        let p_n = eta * 2.0 * delta / PI;
+        let energy_density = -self.bjerrum_length
+            * (gamma
+                * self
+                    .species()
+                    .map(|(z, s, rho)| rho * z * z / (1.0 + gamma * s))
+                    .sum::<f64>()
+                + PI / (2.0 * delta) * omega * p_n * p_n);
+        Ok(energy_density / self.total_density())
+    }
+
+    /// Electrostatic excess Helmholtz energy per ion, UNIT: [ kT ]
+    ///
+    /// # Errors
+    /// Returns an error if the screening parameter cannot be found.
+    pub fn excess_helmholtz_energy(&self) -> Result<f64> {
+        let gamma = self.screening_parameter()?;
+        Ok(self.excess_energy()? + gamma.powi(3) / (3.0 * PI * self.total_density()))
+    }
+
+    /// Electrostatic activity coefficient, γᵢ, of each ion species.
+    ///
+    /// # Errors
+    /// Returns an error if the screening parameter cannot be found.
+    pub fn activity_coefficients(&self) -> Result<Vec<f64>> {
+        let gamma = self.screening_parameter()?;
+        let eta = self.auxiliary(gamma).eta;
+        Ok(self
+            .species()
+            .map(|(z, s, _)| {
+                let ln_gamma = -self.bjerrum_length
+                    * ((gamma * z * z + eta * s * (2.0 * z - eta * s * s)) / (1.0 + gamma * s)
+                        + 2.0 / 3.0 * eta * eta * s.powi(3));
+                ln_gamma.exp()
+            })
+            .collect())
+    }
+
+    /// Mean activity coefficient, γ±, of a salt whose ions are the species of the model.
+    ///
+    /// # Errors
+    /// Returns an error if the salt does not match the ion species or if the screening
+    /// parameter cannot be found.
+    pub fn mean_activity_coefficient(&self, salt: &Salt) -> Result<f64> {
+        if salt.valencies().len() != self.valencies.len() {
+            return Err(crate::Error::Stoichiometry);
+        }
+        let stoichiometry = salt.stoichiometry()?;
+        let nu: usize = stoichiometry.iter().sum();
+        let ln_gamma: f64 = self
+            .activity_coefficients()?
+            .iter()
+            .zip(&stoichiometry)
+            .map(|(gamma, nu)| *nu as f64 * gamma.ln())
+            .sum();
+        Ok((ln_gamma / nu as f64).exp())
+    }
+
+    /// Electrostatic contribution to the osmotic coefficient, φ.
+    ///
+    /// Found from $\phi - 1 = \left ( \sum_i \rho_i \ln \gamma_i - \beta A / V \right ) / \sum_i \rho_i$,
+    /// which in the restricted primitive model reduces to $-\Gamma^3 / 3 \pi \sum_i \rho_i$.
+    ///
+    /// # Errors
+    /// Returns an error if the screening parameter cannot be found.
+    pub fn osmotic_coefficient(&self) -> Result<f64> {
+        let chemical_potential: f64 = self
+            .activity_coefficients()?
+            .iter()
+            .zip(&self.densities)
+            .map(|(gamma, rho)| rho * gamma.ln())
+            .sum::<f64>()
+            / self.total_density();
+        Ok(1.0 + chemical_potential - self.excess_helmholtz_energy()?)
+    }
+}
+
+#[test]
+fn test_msa() {
+    use crate::debye_length;
+    use approx::assert_relative_eq;
+    let bjerrum_length = 7.0;
+    let (diameter, molarity) = (4.0, 0.2);
+
+    // Restricted primitive model
+    let msa = Msa::restricted(bjerrum_length, &Salt::CalciumChloride, diameter, molarity).unwrap();
+    let ionic_strength = Salt::CalciumChloride.ionic_strength(molarity).unwrap();
+    let kappa =
+        (8.0 * PI * bjerrum_length * ionic_strength * AVOGADRO_CONSTANT * LITER_PER_ANGSTROM3)
+            .sqrt();
+    let gamma = msa.screening_parameter().unwrap();
+    assert_relative_eq!(
+        2.0 * gamma * diameter,
+        (1.0 + 2.0 * kappa * diameter).sqrt() - 1.0,
+        max_relative = 1e-12
+    );
+    let density = 3.0 * molarity * AVOGADRO_CONSTANT * LITER_PER_ANGSTROM3;
+    assert_relative_eq!(
+        msa.osmotic_coefficient().unwrap() - 1.0,
+        -gamma.powi(3) / (3.0 * PI * density),
+        max_relative = 1e-10
+    );
+    let ions = vec![
+        (crate::Ion::new(2, 0.5 * diameter), 1),
+        (crate::Ion::new(-1, 0.5 * diameter), 2),
+    ];
+    let salt = Salt::from_ions(ions).unwrap();
+    assert_eq!(
+        Msa::from_salt(bjerrum_length, &salt, molarity).unwrap(),
+        msa
+    );
+    assert!(Msa::from_salt(bjerrum_length, &Salt::CalciumChloride, molarity).is_err());
+    let coefficients = msa.activity_coefficients().unwrap();
+    assert_relative_eq!(
+        coefficients[0].ln(),
+        -bjerrum_length * 4.0 * gamma / (1.0 + gamma * diameter),
+        max_relative = 1e-12
+    );
+
+    // Dilute limit recovers Debye-Hückel, 2Γ = κ
+    let dilute = Msa::restricted(bjerrum_length, &Salt::SodiumChloride, diameter, 1e-7).unwrap();
+    let kappa = debye_length(298.15, 80.0, 1e-7).recip()
+        * (bjerrum_length / crate::bjerrum_length(298.15, 80.0)).sqrt();
+    assert_relative_eq!(
+        2.0 * dilute.screening_parameter().unwrap(),
+        kappa,
+        max_relative = 1e-3
+    );
+
+    // Unrestricted: activity coefficients are derivatives of the Helmholtz energy
+    let valencies = [2.0, -1.0, 1.0];
+    let diameters = [5.0, 3.6, 2.4];
+    let molarities = [0.3, 0.8, 0.2];
+    let msa = Msa::new(bjerrum_length, &valencies, &diameters, &molarities).unwrap();
+    let coefficients = msa.activity_coefficients().unwrap();
+    let helmholtz = |densities: Vec<f64>| {
+        let msa = Msa {
+            densities,
+            ..msa.clone()
+        };
+        msa.excess_helmholtz_energy().unwrap() * msa.total_density()
+    };
+    let h = 1e-9;
+    for (i, gamma) in coefficients.iter().enumerate() {
+        let mut plus = msa.densities.clone();
+        let mut minus = msa.densities.clone();
+        plus[i] += h;
+        minus[i] -= h;
+        assert_relative_eq!(
+            gamma.ln(),
+            (helmholtz(plus) - helmholtz(minus)) / (2.0 * h),
+            max_relative = 1e-5
+        );
+    }
+    assert!(msa.osmotic_coefficient().unwrap() < 1.0);
+    assert!(Msa::new(bjerrum_length, &[1.0, -1.0], &[4.0, 4.0], &[0.1, 0.2]).is_err());
+}
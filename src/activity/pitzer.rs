@@ -0,0 +1,208 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Pitzer ion-interaction model for single salts.
+
+use crate::permittivity::{RelativePermittivity, WATER};
+use crate::{bjerrum_length, Result, Salt, AVOGADRO_CONSTANT};
+use core::f64::consts::PI;
+
+/// Pitzer ion-interaction model for a single salt in water.
+///
+/// For a salt $M_{\nu_M} X_{\nu_X}$ at molality $m$, the osmotic and mean activity
+/// coefficients are
+///
+/// $$ \phi - 1 = |z_M z_X| f^\phi + m \frac{2 \nu_M \nu_X}{\nu} B^\phi + m^2 \frac{2 (\nu_M \nu_X)^{3/2}}{\nu} C^\phi $$
+/// $$ \ln \gamma_\pm = |z_M z_X| f^\gamma + m \frac{2 \nu_M \nu_X}{\nu} B^\gamma + m^2 \frac{3 (\nu_M \nu_X)^{3/2}}{\nu} C^\phi $$
+///
+/// where $f^\phi$ and $f^\gamma$ are Debye-Hückel terms with the slope $A_\phi$, and
+/// $B^\phi$ and $B^\gamma$ depend on the ionic strength through the parameters $\beta^{(0)}$ and
+/// $\beta^{(1)}$.
+/// The temperature enters through $A_\phi$, using the permittivity of water, whereas the
+/// interaction parameters are for 25°C.
+/// Parameters for some common salts are from Pitzer and Mayorga,
+/// <https://doi.org/10.1021/j100621a026>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::{activity::Pitzer, Salt};
+/// let pitzer = Pitzer::from_salt(&Salt::SodiumChloride).unwrap();
+/// let gamma = pitzer.mean_activity_coefficient(1.0, 298.15).unwrap();
+/// assert_relative_eq!(gamma, 0.657, max_relative = 5e-3);
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pitzer {
+    /// Salt with one cation and one anion
+    salt: Salt,
+    /// β⁽⁰⁾, UNIT: [ kg / mol ]
+    beta0: f64,
+    /// β⁽¹⁾, UNIT: [ kg / mol ]
+    beta1: f64,
+    /// Cᵠ, UNIT: [ ( kg / mol )^2 ]
+    c_phi: f64,
+}
+
+impl Pitzer {
+    /// Parameter α, UNIT: [ ( kg / mol )^½ ]
+    const ALPHA: f64 = 2.0;
+    /// Parameter b, UNIT: [ ( kg / mol )^½ ]
+    const B: f64 = 1.2;
+    /// Density of water, UNIT: [ kg / l ]
+    const WATER_DENSITY: f64 = 0.997;
+
+    /// New model for a salt with interaction parameters β⁽⁰⁾, β⁽¹⁾, and Cᵠ.
+    ///
+    /// # Errors
+    /// Returns an error unless the salt has exactly one cation and one anion.
+    pub fn new(salt: Salt, beta0: f64, beta1: f64, c_phi: f64) -> Result<Self> {
+        if salt.stoichiometry()?.len() != 2 {
+            return Err(crate::Error::Unsupported(
+                "Pitzer model for salts with more than two ions",
+            ));
+        }
+        Ok(Self {
+            salt,
+            beta0,
+            beta1,
+            c_phi,
+        })
+    }
+
+    /// Model with tabulated parameters at 25°C, or `None` if the salt is not tabulated.
+    pub fn from_salt(salt: &Salt) -> Option<Self> {
+        let (beta0, beta1, c_phi) = match salt {
+            Salt::SodiumChloride => (0.0765, 0.2664, 0.00127),
+            Salt::CalciumChloride => (0.3159, 1.614, -0.00034),
+            Salt::SodiumSulfate => (0.01958, 1.113, 0.00497),
+            _ => return None,
+        };
+        Self::new(salt.clone(), beta0, beta1, c_phi).ok()
+    }
+
+    /// Debye-Hückel slope for the osmotic coefficient, Aᵠ, of water, UNIT: [ ( kg / mol )^½ ]
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is outside the range of the permittivity model.
+    pub fn debye_huckel_slope(temperature: f64) -> Result<f64> {
+        let permittivity = WATER.permittivity(temperature)?;
+        let bjerrum_length = bjerrum_length(temperature, permittivity) * 1e-10; // meter
+        let number_density = 2.0 * PI * AVOGADRO_CONSTANT * Self::WATER_DENSITY * 1e3;
+        Ok(number_density.sqrt() * bjerrum_length.powf(1.5) / 3.0)
+    }
+
+    /// Valencies, (z_M, z_X), and stoichiometric coefficients, (ν_M, ν_X)
+    fn ions(&self) -> Result<((f64, f64), (f64, f64))> {
+        let valencies = self.salt.valencies();
+        let nu = self.salt.stoichiometry()?;
+        Ok((
+            (valencies[0] as f64, valencies[1] as f64),
+            (nu[0] as f64, nu[1] as f64),
+        ))
+    }
+
+    /// Ionic strength in molal units
+    fn ionic_strength(&self, molality: f64) -> Result<f64> {
+        let ((z_m, z_x), (nu_m, nu_x)) = self.ions()?;
+        Ok(0.5 * molality * (nu_m * z_m * z_m + nu_x * z_x * z_x))
+    }
+
+    /// Osmotic coefficient, φ, at `molality` (mol/kg) and `temperature` (K).
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is outside the range of the permittivity model, or if
+    /// the stoichiometry of the salt cannot be resolved.
+    pub fn osmotic_coefficient(&self, molality: f64, temperature: f64) -> Result<f64> {
+        let ((z_m, z_x), (nu_m, nu_x)) = self.ions()?;
+        let nu = nu_m + nu_x;
+        let sqrt_i = self.ionic_strength(molality)?.sqrt();
+        let f = -Self::debye_huckel_slope(temperature)? * sqrt_i / (1.0 + Self::B * sqrt_i);
+        let b = self.beta0 + self.beta1 * (-Self::ALPHA * sqrt_i).exp();
+        Ok(1.0
+            + (z_m * z_x).abs() * f
+            + molality * 2.0 * nu_m * nu_x / nu * b
+            + molality.powi(2) * 2.0 * (nu_m * nu_x).powf(1.5) / nu * self.c_phi)
+    }
+
+    /// Mean activity coefficient, γ±, at `molality` (mol/kg) and `temperature` (K).
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is outside the range of the permittivity model, or if
+    /// the stoichiometry of the salt cannot be resolved.
+    pub fn mean_activity_coefficient(&self, molality: f64, temperature: f64) -> Result<f64> {
+        let ((z_m, z_x), (nu_m, nu_x)) = self.ions()?;
+        let nu = nu_m + nu_x;
+        let ionic_strength = self.ionic_strength(molality)?;
+        let sqrt_i = ionic_strength.sqrt();
+        let f = -Self::debye_huckel_slope(temperature)?
+            * (sqrt_i / (1.0 + Self::B * sqrt_i) + 2.0 / Self::B * (1.0 + Self::B * sqrt_i).ln());
+        let x = Self::ALPHA * sqrt_i;
+        let b = match ionic_strength {
+            0.0 => 2.0 * (self.beta0 + self.beta1),
+            _ => {
+                2.0 * self.beta0
+                    + 2.0 * self.beta1 / (x * x) * (1.0 - (1.0 + x - 0.5 * x * x) * (-x).exp())
+            }
+        };
+        let ln_gamma = (z_m * z_x).abs() * f
+            + molality * 2.0 * nu_m * nu_x / nu * b
+            + molality.powi(2) * 3.0 * (nu_m * nu_x).powf(1.5) / nu * self.c_phi;
+        Ok(ln_gamma.exp())
+    }
+}
+
+#[test]
+fn test_pitzer() {
+    use approx::assert_relative_eq;
+    assert_relative_eq!(
+        Pitzer::debye_huckel_slope(298.15).unwrap(),
+        0.3915,
+        max_relative = 2e-3
+    );
+
+    // Experimental NaCl data at 25°C
+    let nacl = Pitzer::from_salt(&Salt::SodiumChloride).unwrap();
+    for (molality, gamma, phi) in [
+        (0.1, 0.778, 0.932),
+        (1.0, 0.657, 0.936),
+        (3.0, 0.714, 1.045),
+    ] {
+        assert_relative_eq!(
+            nacl.mean_activity_coefficient(molality, 298.15).unwrap(),
+            gamma,
+            max_relative = 5e-3
+        );
+        assert_relative_eq!(
+            nacl.osmotic_coefficient(molality, 298.15).unwrap(),
+            phi,
+            max_relative = 5e-3
+        );
+    }
+
+    // Gibbs-Duhem: d[m(φ - 1)]/dm = m d(ln γ±)/dm
+    let cacl2 = Pitzer::from_salt(&Salt::CalciumChloride).unwrap();
+    let (m, h) = (0.8, 1e-5);
+    let ln_gamma = |m: f64| cacl2.mean_activity_coefficient(m, 298.15).unwrap().ln();
+    let excess = |m: f64| m * (cacl2.osmotic_coefficient(m, 298.15).unwrap() - 1.0);
+    assert_relative_eq!(
+        (excess(m + h) - excess(m - h)) / (2.0 * h),
+        m * (ln_gamma(m + h) - ln_gamma(m - h)) / (2.0 * h),
+        max_relative = 1e-6
+    );
+
+    assert_eq!(nacl.mean_activity_coefficient(0.0, 298.15).unwrap(), 1.0);
+    assert!(Pitzer::from_salt(&Salt::PotassiumAlum).is_none());
+    assert!(Pitzer::new(Salt::PotassiumAlum, 0.0, 0.0, 0.0).is_err());
+}
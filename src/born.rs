@@ -0,0 +1,74 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+use crate::permittivity::RelativePermittivity;
+use crate::{Result, TO_CHEMISTRY_UNIT};
+
+/// Born solvation free energy of an ion in kJ/mol, ΔG = -z²e²/8πε₀a × (1 - 1/εᵣ).
+///
+/// The charge number, `charge`, is transferred from vacuum into a dielectric continuum
+/// described by `permittivity_model` at `temperature` (K), and `radius` is the Born radius in
+/// angstrom.
+/// Returns the free energy and, if dεᵣ/d𝑇 is available from the model, its temperature
+/// derivative in kJ/mol/K, dΔG/d𝑇 = -z²e²/8πε₀a × εᵣ⁻² dεᵣ/d𝑇.
+/// The solvation entropy is ΔS = -dΔG/d𝑇.
+///
+/// More information at <https://doi.org/10.1007/BF01881023>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::{born_energy, permittivity::Permittivity, TO_CHEMISTRY_UNIT};
+/// let (energy, derivative) = born_energy(1.0, 2.0, &Permittivity::Fixed(80.0), 298.15).unwrap();
+/// assert_relative_eq!(energy, -TO_CHEMISTRY_UNIT / 4.0 * (1.0 - 1.0 / 80.0));
+/// assert_eq!(derivative, Some(0.0));
+/// ~~~
+pub fn born_energy(
+    charge: f64,
+    radius: f64,
+    permittivity_model: &impl RelativePermittivity,
+    temperature: f64,
+) -> Result<(f64, Option<f64>)> {
+    let permittivity = permittivity_model.permittivity(temperature)?;
+    let prefactor = -TO_CHEMISTRY_UNIT * charge * charge / (2.0 * radius);
+    let derivative = permittivity_model
+        .d_permittivity_d_temperature(temperature)
+        .map(|d| prefactor * d / (permittivity * permittivity));
+    Ok((prefactor * (1.0 - permittivity.recip()), derivative))
+}
+
+#[test]
+fn test_born_energy() {
+    use crate::permittivity::Permittivity;
+    use crate::Medium;
+    use approx::assert_relative_eq;
+
+    let temperature = 298.15;
+    let (energy, derivative) = born_energy(-2.0, 1.5, &Permittivity::Water, temperature).unwrap();
+    let medium = Medium::neat_water(temperature);
+    assert_relative_eq!(
+        energy,
+        -TO_CHEMISTRY_UNIT * 4.0 / 3.0 * (1.0 - 1.0 / medium.permittivity())
+    );
+    // Solvation free energy becomes less favorable as water loses permittivity upon heating
+    let h = 1e-3;
+    let energy_at = |t| born_energy(-2.0, 1.5, &medium, t).unwrap().0;
+    assert!(derivative.unwrap() > 0.0);
+    assert_relative_eq!(
+        derivative.unwrap(),
+        (energy_at(temperature + h) - energy_at(temperature - h)) / (2.0 * h),
+        max_relative = 1e-6
+    );
+    assert!(born_energy(1.0, 1.0, &Permittivity::Water, 200.0).is_err());
+}
@@ -0,0 +1,626 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Potentials and fields of uniformly charged bodies.
+//!
+//! Closed-form expressions for simple bodies are useful for building and validating mesoscale
+//! models.
+//! All bodies are centered at the origin with their symmetry axis along $z$:
+//!
+//! Body        | Charge                | Potential
+//! ----------- | --------------------- | -------------------------------------------------------------
+//! [`Line`]    | $\lambda$ per length  | $-2 \lambda \ln (\rho / \rho_0)$
+//! [`Ring`]    | $Q$                   | $2 Q K(m) / \pi \sqrt{(a + \rho)^2 + z^2}$, $m = 4 a \rho / [(a + \rho)^2 + z^2]$
+//! [`Disc`]    | $Q$                   | $2 Q (\sqrt{a^2 + z^2} - \lvert z \rvert) / a^2$ on the axis
+//! [`Plane`]   | $\sigma$ per area     | $-2 \pi \sigma \lvert z \rvert$
+//! [`Plate`]   | $\sigma$ per area     | closed form for a $w \times h$ rectangle
+//! [`Sphere`]  | $Q$                   | $Q (3 a^2 - r^2) / 2 a^3$ inside, $Q / r$ outside
+//! [`SphericalShell`] | $Q$            | $Q / a$ inside, $Q / r$ outside
+//!
+//! where $\rho$ is the distance from the $z$-axis, $a$ the radius, and $K$ the complete elliptic
+//! integral of the first kind.
+//! Spheres and shells can optionally be screened by salt outside the body.
+//!
+//! As in the [`pairwise`](crate::pairwise) module, potentials are in units of
+//! ( input charge ) / ( input length ) and fields in ( input charge ) / ( input length )².
+//! For a uniform dielectric, divide by the relative permittivity and multiply by _e.g._
+//! [`TO_CHEMISTRY_UNIT`](crate::TO_CHEMISTRY_UNIT).
+//!
+//! # Examples
+//! ~~~
+//! # use approx::assert_relative_eq;
+//! use coulomb::charged_bodies::{ChargedBody, Ring};
+//! let ring = Ring::new(2.0, 5.0);
+//! // On the axis, all charge is at the same distance
+//! assert_relative_eq!(ring.potential([0.0, 0.0, 12.0]), 2.0 / 13.0);
+//! ~~~
+
+use crate::math::elliptic_ke;
+use crate::{DebyeLength, NalgebraVector3, Vector3};
+use core::f64::consts::PI;
+
+/// Electric potential and field of a charged body.
+pub trait ChargedBody {
+    /// Electric potential at `position`, UNIT: [ ( input charge ) / ( input length ) ]
+    fn potential(&self, position: impl Into<Vector3>) -> f64;
+
+    /// Electric field at `position`, UNIT: [ ( input charge ) / ( input length )^2 ]
+    fn field(&self, position: impl Into<Vector3>) -> Vector3;
+}
+
+/// Infinite line along the z-axis with uniform linear charge density.
+///
+/// The potential is zero at the reference distance, $\rho_0$, from the line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line {
+    /// Charge per length, λ, UNIT: [ ( input charge ) / ( input length ) ]
+    pub charge_density: f64,
+    /// Distance from the line where the potential is zero, UNIT: [ input length ]
+    pub reference_distance: f64,
+}
+
+impl Line {
+    /// New line with a linear charge density and reference distance for zero potential.
+    pub const fn new(charge_density: f64, reference_distance: f64) -> Self {
+        Self {
+            charge_density,
+            reference_distance,
+        }
+    }
+}
+
+impl ChargedBody for Line {
+    fn potential(&self, position: impl Into<Vector3>) -> f64 {
+        let position: Vector3 = position.into();
+        let rho = position.x.hypot(position.y);
+        -2.0 * self.charge_density * (rho / self.reference_distance).ln()
+    }
+
+    fn field(&self, position: impl Into<Vector3>) -> Vector3 {
+        let position: Vector3 = position.into();
+        let rho2 = position.x * position.x + position.y * position.y;
+        let scale = 2.0 * self.charge_density / rho2;
+        [scale * position.x, scale * position.y, 0.0].into()
+    }
+}
+
+/// Ring of radius $a$ in the xy-plane with total charge $Q$.
+///
+/// Off the axis, the potential and field are given by complete elliptic integrals.
+/// See _e.g._ Jackson, _Classical Electrodynamics_, section 3.3.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ring {
+    /// Total charge, UNIT: [ input charge ]
+    pub charge: f64,
+    /// Radius, UNIT: [ input length ]
+    pub radius: f64,
+}
+
+impl Ring {
+    /// New ring with total charge and radius.
+    pub const fn new(charge: f64, radius: f64) -> Self {
+        Self { charge, radius }
+    }
+}
+
+impl ChargedBody for Ring {
+    fn potential(&self, position: impl Into<Vector3>) -> f64 {
+        let position: Vector3 = position.into();
+        let (rho, z, a) = (position.x.hypot(position.y), position.z, self.radius);
+        let far = (a + rho).hypot(z);
+        let (k, _) = elliptic_ke(4.0 * a * rho / (far * far));
+        2.0 * self.charge * k / (PI * far)
+    }
+
+    fn field(&self, position: impl Into<Vector3>) -> Vector3 {
+        let position: Vector3 = position.into();
+        let (rho, z, a) = (position.x.hypot(position.y), position.z, self.radius);
+        let far = (a + rho).hypot(z);
+        let near2 = (a - rho).powi(2) + z * z;
+        let (k, e) = elliptic_ke(4.0 * a * rho / (far * far));
+        let field_z = 2.0 * self.charge * z * e / (PI * near2 * far);
+        if rho < f64::EPSILON * a {
+            return [0.0, 0.0, field_z].into();
+        }
+        let field_rho =
+            self.charge / (PI * rho * far) * (k - (a * a - rho * rho + z * z) / near2 * e);
+        [
+            field_rho * position.x / rho,
+            field_rho * position.y / rho,
+            field_z,
+        ]
+        .into()
+    }
+}
+
+/// Disc of radius $a$ in the xy-plane with uniform surface charge and total charge $Q$.
+///
+/// Only the potential and field on the symmetry axis have simple closed forms; off the axis,
+/// elliptic integrals of the third kind are required.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Disc {
+    /// Total charge, UNIT: [ input charge ]
+    pub charge: f64,
+    /// Radius, UNIT: [ input length ]
+    pub radius: f64,
+}
+
+impl Disc {
+    /// New disc with total charge and radius.
+    pub const fn new(charge: f64, radius: f64) -> Self {
+        Self { charge, radius }
+    }
+
+    /// Surface charge density, σ = Q / πa², UNIT: [ ( input charge ) / ( input length )^2 ]
+    pub fn charge_density(&self) -> f64 {
+        self.charge / (PI * self.radius * self.radius)
+    }
+
+    /// Potential at height `z` on the axis, 2πσ(√(a² + z²) - |z|)
+    pub fn axial_potential(&self, z: f64) -> f64 {
+        2.0 * PI * self.charge_density() * (self.radius.hypot(z) - z.abs())
+    }
+
+    /// Field along the axis at height `z` ≠ 0, 2πσ sign(z)(1 - |z| / √(a² + z²))
+    pub fn axial_field(&self, z: f64) -> f64 {
+        2.0 * PI * self.charge_density() * (z.signum() - z / self.radius.hypot(z))
+    }
+}
+
+/// Infinite plane at z = 0 with uniform surface charge density.
+///
+/// The potential is zero at the plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plane {
+    /// Charge per area, σ, UNIT: [ ( input charge ) / ( input length )^2 ]
+    pub charge_density: f64,
+}
+
+impl Plane {
+    /// New plane with a surface charge density.
+    pub const fn new(charge_density: f64) -> Self {
+        Self { charge_density }
+    }
+}
+
+impl ChargedBody for Plane {
+    fn potential(&self, position: impl Into<Vector3>) -> f64 {
+        -2.0 * PI * self.charge_density * position.into().z.abs()
+    }
+
+    fn field(&self, position: impl Into<Vector3>) -> Vector3 {
+        [
+            0.0,
+            0.0,
+            2.0 * PI * self.charge_density * position.into().z.signum(),
+        ]
+        .into()
+    }
+}
+
+/// Rectangular plate in the xy-plane with uniform surface charge density.
+///
+/// With $u$ and $v$ the x and y distances from a point to the plate edges, the potential is a
+/// sum over the four corners, $\pm \sigma F(u, v)$, of
+///
+/// $$ F(u, v) = u \ln(v + R) + v \ln(u + R) - z \arctan \frac{u v}{z R}, \quad R = \sqrt{u^2 + v^2 + z^2} $$
+///
+/// The field is discontinuous across the plate and should be evaluated at $z \neq 0$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plate {
+    /// Charge per area, σ, UNIT: [ ( input charge ) / ( input length )^2 ]
+    pub charge_density: f64,
+    /// Side length along x, UNIT: [ input length ]
+    pub width: f64,
+    /// Side length along y, UNIT: [ input length ]
+    pub height: f64,
+}
+
+impl Plate {
+    /// New plate with a surface charge density and side lengths along x and y.
+    pub const fn new(charge_density: f64, width: f64, height: f64) -> Self {
+        Self {
+            charge_density,
+            width,
+            height,
+        }
+    }
+
+    /// Signed corner offsets, (±1, u, v), relative to `position`
+    fn corners(&self, position: &NalgebraVector3) -> [(f64, f64, f64); 4] {
+        let (x1, x2) = (
+            -0.5 * self.width - position.x,
+            0.5 * self.width - position.x,
+        );
+        let (y1, y2) = (
+            -0.5 * self.height - position.y,
+            0.5 * self.height - position.y,
+        );
+        [(1.0, x2, y2), (-1.0, x1, y2), (-1.0, x2, y1), (1.0, x1, y1)]
+    }
+}
+
+/// ln(a + √(a² + b²)) evaluated without cancellation for negative `a`
+fn log_sum(a: f64, b2: f64) -> f64 {
+    let b = b2.sqrt();
+    (a / b).asinh() + b.ln()
+}
+
+/// z arctan(uv / zR), which vanishes in the plane of the plate
+fn solid_angle_term(u: f64, v: f64, z: f64, r: f64) -> f64 {
+    if z == 0.0 {
+        0.0
+    } else {
+        z * (u * v / (z * r)).atan()
+    }
+}
+
+impl ChargedBody for Plate {
+    fn potential(&self, position: impl Into<Vector3>) -> f64 {
+        let position: NalgebraVector3 = position.into().into();
+        let z = position.z;
+        self.corners(&position)
+            .iter()
+            .map(|(sign, u, v)| {
+                let r = (u * u + v * v + z * z).sqrt();
+                sign * (u * log_sum(*v, u * u + z * z) + v * log_sum(*u, v * v + z * z)
+                    - solid_angle_term(*u, *v, z, r))
+            })
+            .sum::<f64>()
+            * self.charge_density
+    }
+
+    fn field(&self, position: impl Into<Vector3>) -> Vector3 {
+        let position: NalgebraVector3 = position.into().into();
+        let z = position.z;
+        self.corners(&position)
+            .iter()
+            .map(|(sign, u, v)| {
+                let r = (u * u + v * v + z * z).sqrt();
+                NalgebraVector3::new(
+                    log_sum(*v, u * u + z * z),
+                    log_sum(*u, v * v + z * z),
+                    (u * v / (z * r)).atan(),
+                ) * *sign
+            })
+            .sum::<NalgebraVector3>()
+            .scale(self.charge_density)
+            .into()
+    }
+}
+
+/// Potential outside a sphere of radius `radius`, screened by salt beyond the surface
+fn screened_exterior(charge: f64, radius: f64, kappa: Option<f64>, r: f64) -> f64 {
+    kappa.map_or(charge / r, |kappa| {
+        charge * (-kappa * (r - radius)).exp() / ((1.0 + kappa * radius) * r)
+    })
+}
+
+/// Radial field outside a sphere of radius `radius`, screened by salt beyond the surface
+fn screened_exterior_field(charge: f64, radius: f64, kappa: Option<f64>, r: f64) -> f64 {
+    kappa.map_or(charge / (r * r), |kappa| {
+        screened_exterior(charge, radius, Some(kappa), r) * (1.0 + kappa * r) / r
+    })
+}
+
+/// Uniformly charged sphere of radius $a$ with total charge $Q$, centered at the origin.
+///
+/// Inside the sphere, the potential is parabolic,
+///
+/// $$ \phi(r) = \frac{Q}{a (1 + \kappa a)} + \frac{Q (a^2 - r^2)}{2 a^3}, \quad r \le a $$
+///
+/// and outside, salt optionally screens the charge,
+///
+/// $$ \phi(r) = \frac{Q e^{-\kappa (r - a)}}{(1 + \kappa a) r}, \quad r > a $$
+///
+/// where salt is excluded from the interior.
+/// Without salt, $\kappa = 0$, the exterior potential is that of a point charge.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::charged_bodies::{ChargedBody, Sphere};
+/// let sphere = Sphere::new(2.0, 4.0, None);
+/// assert_relative_eq!(sphere.potential([0.0, 0.0, 0.0]), 1.5 * 2.0 / 4.0);
+/// assert_relative_eq!(sphere.potential([0.0, 8.0, 0.0]), 2.0 / 8.0);
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sphere {
+    /// Total charge, UNIT: [ input charge ]
+    pub charge: f64,
+    /// Radius, UNIT: [ input length ]
+    pub radius: f64,
+    /// Optional Debye length outside the sphere, UNIT: [ input length ]
+    pub debye_length: Option<f64>,
+}
+
+impl Sphere {
+    /// New sphere with total charge, radius, and optional Debye length outside the sphere.
+    pub const fn new(charge: f64, radius: f64, debye_length: Option<f64>) -> Self {
+        Self {
+            charge,
+            radius,
+            debye_length,
+        }
+    }
+
+    /// Electrostatic self-energy, ½∫ρφ dV, UNIT: [ ( input charge )^2 / ( input length ) ]
+    ///
+    /// Reduces to $3 Q^2 / 5 a$ without salt.
+    pub fn self_energy(&self) -> f64 {
+        let a = self.radius;
+        0.5 * self.charge
+            * self.charge
+            * ((a * (1.0 + self.kappa().unwrap_or(0.0) * a)).recip() + (5.0 * a).recip())
+    }
+}
+
+impl DebyeLength for Sphere {
+    fn kappa(&self) -> Option<f64> {
+        self.debye_length.map(f64::recip)
+    }
+    fn set_debye_length(&mut self, debye_length: Option<f64>) -> crate::Result<()> {
+        crate::debye_length::check_debye_length(debye_length)?;
+        self.debye_length = debye_length;
+        Ok(())
+    }
+}
+
+impl crate::SaltSensitive for Sphere {}
+
+impl ChargedBody for Sphere {
+    fn potential(&self, position: impl Into<Vector3>) -> f64 {
+        let r = NalgebraVector3::from(position.into()).norm();
+        let (q, a) = (self.charge, self.radius);
+        let surface = screened_exterior(q, a, self.kappa(), r.max(a));
+        if r <= a {
+            surface + q * (a * a - r * r) / (2.0 * a.powi(3))
+        } else {
+            surface
+        }
+    }
+
+    fn field(&self, position: impl Into<Vector3>) -> Vector3 {
+        let position: NalgebraVector3 = position.into().into();
+        let r = position.norm();
+        let (q, a) = (self.charge, self.radius);
+        let scale = if r <= a {
+            q / a.powi(3)
+        } else {
+            screened_exterior_field(q, a, self.kappa(), r) / r
+        };
+        (position * scale).into()
+    }
+}
+
+/// Spherical shell of radius $a$ with uniform surface charge and total charge $Q$, centered at
+/// the origin.
+///
+/// The potential is constant inside the shell, $\phi = Q / a (1 + \kappa a)$, and outside it
+/// is the same as for a [`Sphere`] with the same charge.
+/// Salt is excluded from the interior.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SphericalShell {
+    /// Total charge, UNIT: [ input charge ]
+    pub charge: f64,
+    /// Radius, UNIT: [ input length ]
+    pub radius: f64,
+    /// Optional Debye length outside the shell, UNIT: [ input length ]
+    pub debye_length: Option<f64>,
+}
+
+impl SphericalShell {
+    /// New shell with total charge, radius, and optional Debye length outside the shell.
+    pub const fn new(charge: f64, radius: f64, debye_length: Option<f64>) -> Self {
+        Self {
+            charge,
+            radius,
+            debye_length,
+        }
+    }
+
+    /// Electrostatic self-energy, ½Qφ(a), UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn self_energy(&self) -> f64 {
+        0.5 * self.charge * self.potential([0.0, 0.0, 0.0])
+    }
+}
+
+impl DebyeLength for SphericalShell {
+    fn kappa(&self) -> Option<f64> {
+        self.debye_length.map(f64::recip)
+    }
+    fn set_debye_length(&mut self, debye_length: Option<f64>) -> crate::Result<()> {
+        crate::debye_length::check_debye_length(debye_length)?;
+        self.debye_length = debye_length;
+        Ok(())
+    }
+}
+
+impl crate::SaltSensitive for SphericalShell {}
+
+impl ChargedBody for SphericalShell {
+    fn potential(&self, position: impl Into<Vector3>) -> f64 {
+        let r = NalgebraVector3::from(position.into()).norm();
+        screened_exterior(self.charge, self.radius, self.kappa(), r.max(self.radius))
+    }
+
+    fn field(&self, position: impl Into<Vector3>) -> Vector3 {
+        let position: NalgebraVector3 = position.into().into();
+        let r = position.norm();
+        if r <= self.radius {
+            return [0.0, 0.0, 0.0].into();
+        }
+        let scale = screened_exterior_field(self.charge, self.radius, self.kappa(), r) / r;
+        (position * scale).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    /// Check that the field is the negative gradient of the potential
+    fn assert_gradient(body: &impl ChargedBody, position: [f64; 3]) {
+        let h = 1e-5;
+        let field: NalgebraVector3 = body.field(position).into();
+        for i in 0..3 {
+            let (mut plus, mut minus) = (position, position);
+            plus[i] += h;
+            minus[i] -= h;
+            let numerical = -(body.potential(plus) - body.potential(minus)) / (2.0 * h);
+            assert_relative_eq!(field[i], numerical, max_relative = 1e-6, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_line_and_plane() {
+        let line = Line::new(0.5, 10.0);
+        assert_eq!(line.potential([6.0, 8.0, -3.0]), 0.0);
+        assert_relative_eq!(line.potential([0.0, 5.0, 1.0]), -(0.5f64).ln());
+        assert_gradient(&line, [1.0, -2.0, 3.0]);
+
+        let plane = Plane::new(-0.1);
+        assert_relative_eq!(plane.potential([3.0, 1.0, -2.0]), 0.4 * PI);
+        assert_gradient(&plane, [1.0, 2.0, -0.5]);
+    }
+
+    #[test]
+    fn test_ring_and_disc() {
+        let (charge, radius) = (3.0, 2.0);
+        let ring = Ring::new(charge, radius);
+        // Sum over point charges on the ring
+        let n = 2000;
+        let position = NalgebraVector3::new(1.1, -0.6, 0.8);
+        let (potential, field) = (0..n)
+            .map(|i| {
+                let angle = 2.0 * PI * i as f64 / n as f64;
+                let r = position - NalgebraVector3::new(angle.cos(), angle.sin(), 0.0) * radius;
+                let q = charge / n as f64;
+                (q / r.norm(), r * (q / r.norm().powi(3)))
+            })
+            .fold((0.0, NalgebraVector3::zeros()), |(p, f), (dp, df)| {
+                (p + dp, f + df)
+            });
+        assert_relative_eq!(ring.potential(position), potential, max_relative = 1e-10);
+        assert_relative_eq!(
+            NalgebraVector3::from(ring.field(position)),
+            field,
+            max_relative = 1e-10
+        );
+        assert_gradient(&ring, [0.3, 2.5, -1.0]);
+        assert_relative_eq!(
+            NalgebraVector3::from(ring.field([0.0, 0.0, 3.0])),
+            NalgebraVector3::new(0.0, 0.0, charge * 3.0 / 13.0f64.powf(1.5))
+        );
+
+        // Disc on the axis approaches a point charge far away and a plane close by
+        let disc = Disc::new(charge, radius);
+        assert_relative_eq!(disc.axial_potential(1e4), charge / 1e4, max_relative = 1e-6);
+        assert_relative_eq!(
+            disc.axial_field(1e-9),
+            2.0 * PI * disc.charge_density(),
+            max_relative = 1e-6
+        );
+        let h = 1e-6;
+        assert_relative_eq!(
+            disc.axial_field(-1.5),
+            -(disc.axial_potential(-1.5 + h) - disc.axial_potential(-1.5 - h)) / (2.0 * h),
+            max_relative = 1e-8
+        );
+    }
+
+    #[test]
+    fn test_plate() {
+        let plate = Plate::new(0.2, 3.0, 2.0);
+        // Far away, the plate is a point charge
+        let far = [300.0, -400.0, 1200.0];
+        assert_relative_eq!(
+            plate.potential(far),
+            0.2 * 6.0 / 1300.0,
+            max_relative = 1e-5
+        );
+        assert_gradient(&plate, [0.4, -1.7, 0.9]);
+        assert_gradient(&plate, [-5.0, 3.0, -2.0]);
+        // Reference value from numerical quadrature
+        assert_relative_eq!(
+            plate.potential([0.4, -1.7, 0.9]),
+            0.2 * 2.94197207220502,
+            max_relative = 1e-12
+        );
+        // Close to the center, the plate is an infinite plane
+        assert_relative_eq!(
+            plate.field([0.0, 0.0, -1e-8]).z,
+            -2.0 * PI * 0.2,
+            max_relative = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_sphere_and_shell() {
+        for debye_length in [None, Some(7.0)] {
+            let sphere = Sphere::new(-3.0, 5.0, debye_length);
+            let shell = SphericalShell::new(-3.0, 5.0, debye_length);
+            for position in [[1.0, -2.0, 0.5], [3.0, 6.0, -4.0], [0.0, 0.0, 4.999]] {
+                assert_gradient(&sphere, position);
+                assert_gradient(&shell, position);
+            }
+            // Continuous at the surface and identical outside
+            let (inside, outside) = ([0.0, 0.0, 5.0 - 1e-12], [0.0, 0.0, 5.0 + 1e-12]);
+            assert_relative_eq!(
+                sphere.potential(inside),
+                sphere.potential(outside),
+                max_relative = 1e-10
+            );
+            assert_relative_eq!(
+                shell.potential(inside),
+                shell.potential(outside),
+                max_relative = 1e-10
+            );
+            assert_relative_eq!(
+                sphere.field(inside).z,
+                sphere.field(outside).z,
+                max_relative = 1e-10
+            );
+            assert_relative_eq!(sphere.potential(outside), shell.potential(outside));
+            assert_eq!(shell.field([1.0, 2.0, 3.0]).z, 0.0);
+        }
+        // Gauss' law outside and point-charge limit without salt
+        let sphere = Sphere::new(2.0, 3.0, None);
+        assert_relative_eq!(sphere.field([0.0, 10.0, 0.0]).y, 2.0 / 100.0);
+        assert_relative_eq!(sphere.self_energy(), 0.6 * 4.0 / 3.0);
+
+        // Self-energy is ½∫ρφ dV by radial quadrature
+        let sphere = Sphere::new(2.0, 3.0, Some(4.0));
+        let n = 10000;
+        let dr = sphere.radius / n as f64;
+        let density = sphere.charge / (4.0 / 3.0 * PI * sphere.radius.powi(3));
+        let energy: f64 = (0..n)
+            .map(|i| {
+                let r = (i as f64 + 0.5) * dr;
+                0.5 * density * sphere.potential([r, 0.0, 0.0]) * 4.0 * PI * r * r * dr
+            })
+            .sum();
+        assert_relative_eq!(sphere.self_energy(), energy, max_relative = 1e-6);
+        let shell = SphericalShell::new(2.0, 3.0, Some(4.0));
+        assert_relative_eq!(shell.self_energy(), 2.0 / (3.0 * (1.0 + 0.75)));
+    }
+}
@@ -22,11 +22,49 @@ pub trait DebyeLength {
     fn kappa(&self) -> Option<f64>;
 
     /// Tries to set the debye length to a new value.
+    ///
+    /// # Errors
+    /// Returns an error if the Debye length is not positive and finite, or if the
+    /// implementor does not support changing it.
     fn set_debye_length(&mut self, _debye_length: Option<f64>) -> crate::Result<()> {
         Err(crate::Error::Unsupported("setting the Debye length"))
     }
 }
 
+/// Checks that a Debye length, if given, is positive and finite
+pub(crate) fn check_debye_length(debye_length: Option<f64>) -> crate::Result<()> {
+    match debye_length {
+        Some(d) if !(d > 0.0 && d.is_finite()) => Err(crate::Error::InvalidDebyeLength),
+        _ => Ok(()),
+    }
+}
+
+/// Marker for objects whose salt screening can be changed in place.
+///
+/// Implementors support [`DebyeLength::set_debye_length`], so that
+/// the ionic strength can be updated during, _e.g._, titration or semi-grand canonical
+/// simulations without reconstructing the object.
+///
+/// # Examples
+/// ~~~
+/// use coulomb::{pairwise::Plain, DebyeLength, Medium, SaltSensitive, Salt};
+/// let mut scheme = Plain::without_cutoff();
+/// let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+/// scheme.update_screening(&medium).unwrap();
+/// assert_eq!(scheme.debye_length(), medium.debye_length());
+/// ~~~
+pub trait SaltSensitive: DebyeLength {
+    /// Set the Debye length from the salt, temperature, and permittivity of a medium
+    ///
+    /// A medium without salt removes the screening.
+    ///
+    /// # Errors
+    /// Returns an error if [`DebyeLength::set_debye_length`] fails for the implementor.
+    fn update_screening(&mut self, medium: &crate::Medium) -> crate::Result<()> {
+        self.set_debye_length(medium.debye_length())
+    }
+}
+
 // Implement DebyeLength for all types that implement Temperature and RelativePermittivity
 impl<T> DebyeLength for T
 where
@@ -81,3 +119,35 @@ pub fn debye_length(kelvin: f64, relative_permittivity: f64, ionic_strength: f64
         .sqrt()
         .recip()
 }
+
+/// Calculates the Wigner-Seitz radius in angstrom, 𝑎 = (3/4πρ)^⅓, from the number density, ρ, in inverse cubic angstrom.
+///
+/// This is the radius of a sphere whose volume equals the mean volume per particle.
+///
+/// # Examples
+/// ~~~
+/// use coulomb::wigner_seitz_radius;
+/// let density = 3.0 / (4.0 * std::f64::consts::PI * 1000.0);
+/// approx::assert_relative_eq!(wigner_seitz_radius(density), 10.0);
+/// ~~~
+pub fn wigner_seitz_radius(number_density: f64) -> f64 {
+    (3.0 / (4.0 * PI * number_density)).cbrt()
+}
+
+/// Calculates the electrostatic coupling parameter, Γ = λ𝐵𝑧²/𝑎, of particles with valency 𝑧.
+///
+/// The Wigner-Seitz radius, 𝑎, is found from the number density in inverse cubic angstrom and
+/// the Bjerrum length, λ𝐵, should be given in angstrom.
+/// Particles are weakly coupled for Γ ≪ 1, where Debye-Hückel theory applies, and
+/// strongly coupled for Γ ≳ 1.
+///
+/// # Examples
+/// ~~~
+/// use coulomb::{coupling_parameter, wigner_seitz_radius};
+/// let density = 1e-4;
+/// let gamma = coupling_parameter(7.0, 2.0, density);
+/// approx::assert_relative_eq!(gamma, 7.0 * 4.0 / wigner_seitz_radius(density));
+/// ~~~
+pub fn coupling_parameter(bjerrum_length: f64, valency: f64, number_density: f64) -> f64 {
+    bjerrum_length * valency * valency / wigner_seitz_radius(number_density)
+}
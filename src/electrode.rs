@@ -0,0 +1,319 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Electrodes held at constant potential.
+//!
+//! Electrode atoms carry Gaussian charges, $q_i$, of width $\sigma$ whose magnitudes fluctuate
+//! to keep each atom at a fixed potential, $V_i$.
+//! The electrostatic energy of the electrode in the presence of point charges, $Q_k$, of the
+//! electrolyte is
+//!
+//! $$ U = \frac{1}{2} \boldsymbol{q}^T \mathsf{A} \boldsymbol{q} + \boldsymbol{q}^T \boldsymbol{b} $$
+//!
+//! where $\mathsf{A}$ contains the interactions between electrode charges, including their
+//! self-energies, and $b_i$ is the potential from the electrolyte at electrode atom $i$.
+//! The charges minimize $U - \boldsymbol{q}^T \boldsymbol{V}$, _i.e._
+//! $\mathsf{A} \boldsymbol{q} = \boldsymbol{V} - \boldsymbol{b}$, optionally with the constraint
+//! that the electrodes are overall neutral.
+//! Since the charges are variational, forces are found at fixed charges.
+//! See Siepmann and Sprik, <https://doi.org/10.1063/1.469927>, and
+//! Reed _et al._, <https://doi.org/10.1063/1.2768535>.
+//!
+//! As in the [`pairwise`](crate::pairwise) module, energies are in units of
+//! [ ( input charge )^2 / ( input length ) ] in vacuum.
+//!
+//! # Examples
+//! ~~~
+//! # use approx::assert_relative_eq;
+//! use coulomb::electrode::ConstantPotential;
+//! let electrode = ConstantPotential::new(0.5);
+//! let positions = [[0.0, 0.0, 0.0].into(), [0.0, 0.0, 10.0].into()];
+//! let result = electrode.solve(&positions, &[-1.0, 1.0], &[], &[]).unwrap();
+//! assert_relative_eq!(result.charges[0], -result.charges[1]);
+//! assert!(result.charges[1] > 0.0);
+//! ~~~
+
+use crate::pairwise::Gaussian;
+use crate::{NalgebraVector3, Vector3};
+use nalgebra::{DMatrix, DVector};
+
+/// Method for solving the electrode charges.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Method {
+    /// Direct solution with a Cholesky factorization (default).
+    #[default]
+    Matrix,
+    /// Conjugate gradient iteration.
+    ConjugateGradient {
+        /// Convergence criterion on the residual norm, UNIT: [ ( input charge ) / ( input length ) ]
+        tolerance: f64,
+        /// Maximum number of iterations
+        max_iterations: usize,
+    },
+}
+
+/// Constant-potential electrode with Gaussian charges.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConstantPotential {
+    /// Width of the Gaussian electrode charges, UNIT: [ input length ]
+    pub width: f64,
+    /// Constrain the sum of electrode charges to zero
+    pub electroneutral: bool,
+    /// Solution method
+    pub method: Method,
+}
+
+/// Electrode charges and derived properties.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElectrodeCharges {
+    /// Charge of each electrode atom
+    pub charges: Vec<f64>,
+    /// Electrostatic energy, $U$, excluding interactions within the electrolyte,
+    /// UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub energy: f64,
+    /// Force on each electrode atom, UNIT: [ ( input charge )^2 / ( input length )^2 ]
+    pub electrode_forces: Vec<Vector3>,
+    /// Force on each electrolyte charge from the electrode, UNIT: [ ( input charge )^2 / ( input length )^2 ]
+    pub ion_forces: Vec<Vector3>,
+    /// Number of iterations used
+    pub iterations: usize,
+}
+
+impl ConstantPotential {
+    /// New electrode with Gaussian charges of `width` and overall neutrality.
+    pub fn new(width: f64) -> Self {
+        Self {
+            width,
+            electroneutral: true,
+            method: Method::default(),
+        }
+    }
+
+    /// Interaction matrix between electrode charges, $\mathsf{A}$
+    fn interaction_matrix(&self, positions: &[NalgebraVector3]) -> DMatrix<f64> {
+        let gaussian = Gaussian::new(self.width, self.width);
+        DMatrix::from_fn(positions.len(), positions.len(), |i, j| {
+            gaussian.ion_ion_energy(1.0, 1.0, (positions[i] - positions[j]).norm())
+        })
+    }
+
+    /// Solve A x = b, returning the solution and number of iterations
+    fn linear_solve(
+        &self,
+        matrix: &DMatrix<f64>,
+        rhs: DVector<f64>,
+    ) -> crate::Result<(DVector<f64>, usize)> {
+        match self.method {
+            Method::Matrix => matrix
+                .clone()
+                .cholesky()
+                .map(|cholesky| (cholesky.solve(&rhs), 1))
+                .ok_or(crate::Error::SingularMatrix),
+            Method::ConjugateGradient {
+                tolerance,
+                max_iterations,
+            } => {
+                let mut x = DVector::zeros(rhs.len());
+                let mut residual = rhs;
+                let mut direction = residual.clone();
+                let mut residual_squared = residual.norm_squared();
+                for iteration in 0..=max_iterations {
+                    if residual_squared.sqrt() < tolerance {
+                        return Ok((x, iteration));
+                    }
+                    let a_direction = matrix * &direction;
+                    let step = residual_squared / direction.dot(&a_direction);
+                    x += step * &direction;
+                    residual -= step * a_direction;
+                    let new_residual_squared = residual.norm_squared();
+                    direction = &residual + (new_residual_squared / residual_squared) * direction;
+                    residual_squared = new_residual_squared;
+                }
+                Err(crate::Error::NotConverged(max_iterations))
+            }
+        }
+    }
+
+    /// Solve for electrode charges at `potentials` in the presence of electrolyte point charges.
+    ///
+    /// Potentials have units of [ ( input charge ) / ( input length ) ].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the electrode or the electrolyte slices differ in length.
+    pub fn solve(
+        &self,
+        electrode_positions: &[Vector3],
+        potentials: &[f64],
+        ion_positions: &[Vector3],
+        ion_charges: &[f64],
+    ) -> crate::Result<ElectrodeCharges> {
+        assert_eq!(electrode_positions.len(), potentials.len());
+        assert_eq!(ion_positions.len(), ion_charges.len());
+        let electrode: Vec<NalgebraVector3> =
+            electrode_positions.iter().map(|&r| r.into()).collect();
+        let ions: Vec<NalgebraVector3> = ion_positions.iter().map(|&r| r.into()).collect();
+        let point_gaussian = Gaussian::new(self.width, 0.0);
+
+        // Potential from the electrolyte at each electrode atom
+        let b = DVector::from_iterator(
+            electrode.len(),
+            electrode.iter().map(|r| {
+                ions.iter()
+                    .zip(ion_charges)
+                    .map(|(s, q)| point_gaussian.ion_potential(*q, (r - s).norm()))
+                    .sum::<f64>()
+            }),
+        );
+        let matrix = self.interaction_matrix(&electrode);
+        let (mut charges, mut iterations) =
+            self.linear_solve(&matrix, DVector::from_column_slice(potentials) - &b)?;
+        if self.electroneutral {
+            // Lagrange multiplier shifts all potentials equally
+            let ones = DVector::from_element(electrode.len(), 1.0);
+            let (response, more_iterations) = self.linear_solve(&matrix, ones)?;
+            charges -= response.clone() * (charges.sum() / response.sum());
+            iterations += more_iterations;
+        }
+        let energy = 0.5 * charges.dot(&(&matrix * &charges)) + charges.dot(&b);
+
+        let gaussian = Gaussian::new(self.width, self.width);
+        let mut electrode_forces = vec![NalgebraVector3::zeros(); electrode.len()];
+        for i in 0..electrode.len() {
+            for j in (i + 1)..electrode.len() {
+                let force: NalgebraVector3 = gaussian
+                    .ion_ion_force(charges[i], charges[j], electrode[j] - electrode[i])
+                    .into();
+                electrode_forces[j] += force;
+                electrode_forces[i] -= force;
+            }
+        }
+        let mut ion_forces = vec![NalgebraVector3::zeros(); ions.len()];
+        for (i, r) in electrode.iter().enumerate() {
+            for (k, s) in ions.iter().enumerate() {
+                let force: NalgebraVector3 = point_gaussian
+                    .ion_ion_force(charges[i], ion_charges[k], s - r)
+                    .into();
+                ion_forces[k] += force;
+                electrode_forces[i] -= force;
+            }
+        }
+        Ok(ElectrodeCharges {
+            charges: charges.iter().copied().collect(),
+            energy,
+            electrode_forces: electrode_forces.into_iter().map(Into::into).collect(),
+            ion_forces: ion_forces.into_iter().map(Into::into).collect(),
+            iterations,
+        })
+    }
+}
+
+#[test]
+fn test_constant_potential() {
+    use approx::assert_relative_eq;
+    use core::f64::consts::PI;
+
+    // Isolated electrode atom
+    let mut electrode = ConstantPotential::new(0.8);
+    electrode.electroneutral = false;
+    let result = electrode
+        .solve(&[[0.0, 0.0, 0.0].into()], &[0.3], &[], &[])
+        .unwrap();
+    assert_relative_eq!(result.charges[0], 0.3 * PI.sqrt() * 0.8);
+
+    // Two planar electrodes with an ion pair in between
+    let mut electrode_positions = Vec::new();
+    let mut potentials = Vec::new();
+    for (z, potential) in [(0.0, -0.5), (8.0, 0.5)] {
+        for (x, y) in [(0.0, 0.0), (2.0, 0.0), (0.0, 2.0), (2.0, 2.0)] {
+            electrode_positions.push([x, y, z].into());
+            potentials.push(potential);
+        }
+    }
+    let ion_positions = vec![[1.0, 1.2, 3.0].into(), [0.7, 1.0, 5.5].into()];
+    let ion_charges = [1.0, -1.0];
+    let cg = Method::ConjugateGradient {
+        tolerance: 1e-12,
+        max_iterations: 100,
+    };
+    for electroneutral in [false, true] {
+        let mut electrode = ConstantPotential::new(0.5);
+        electrode.electroneutral = electroneutral;
+        let direct = electrode
+            .solve(
+                &electrode_positions,
+                &potentials,
+                &ion_positions,
+                &ion_charges,
+            )
+            .unwrap();
+        electrode.method = cg;
+        let iterative = electrode
+            .solve(
+                &electrode_positions,
+                &potentials,
+                &ion_positions,
+                &ion_charges,
+            )
+            .unwrap();
+        for (a, b) in direct.charges.iter().zip(&iterative.charges) {
+            assert_relative_eq!(a, b, epsilon = 1e-10);
+        }
+        if electroneutral {
+            assert_relative_eq!(direct.charges.iter().sum::<f64>(), 0.0, epsilon = 1e-12);
+        }
+
+        // Forces at fixed charges are the gradient of the constant-potential energy
+        let legendre_energy = |ion_positions: &[Vector3]| {
+            let result = electrode
+                .solve(
+                    &electrode_positions,
+                    &potentials,
+                    ion_positions,
+                    &ion_charges,
+                )
+                .unwrap();
+            let work: f64 = result
+                .charges
+                .iter()
+                .zip(&potentials)
+                .map(|(q, v)| q * v)
+                .sum();
+            result.energy - work
+        };
+        let h = 1e-6;
+        for (k, force) in direct.ion_forces.iter().enumerate() {
+            let force = NalgebraVector3::from(*force);
+            for a in 0..3 {
+                let displaced = |dx: f64| {
+                    let mut positions = ion_positions.clone();
+                    let mut r = NalgebraVector3::from(positions[k]);
+                    r[a] += dx;
+                    positions[k] = r.into();
+                    legendre_energy(&positions)
+                };
+                let gradient = (displaced(h) - displaced(-h)) / (2.0 * h);
+                assert_relative_eq!(force[a], -gradient, epsilon = 1e-7);
+            }
+        }
+        // Newton's third law
+        let total: NalgebraVector3 = direct
+            .electrode_forces
+            .iter()
+            .chain(&direct.ion_forces)
+            .map(|f| NalgebraVector3::from(*f))
+            .sum();
+        assert_relative_eq!(total.norm(), 0.0, epsilon = 1e-12);
+    }
+}
@@ -4,19 +4,49 @@ pub enum Error {
     /// Cannot resolve stoichiometry; provide both positive and negative ions.
     #[error("cannot resolve stoichiometry; provide both positive and negative ions")]
     Stoichiometry,
+    /// Chemical formula could not be parsed.
+    #[error("cannot parse chemical formula: {0}")]
+    InvalidFormula(String),
+    /// Salt name not found in the list of named salts.
+    #[error("unknown salt: {0}")]
+    UnknownSalt(String),
+    /// Solvent name not found in the list of named permittivity models.
+    #[error("unknown solvent: {0}")]
+    UnknownSolvent(String),
+    /// Temperature must be given, positive, and finite.
+    #[error("temperature must be given, positive, and finite")]
+    InvalidTemperature,
     /// Temperature out of range for the permittivity model.
     #[error("temperature out of range for permittivity model")]
     TemperatureOutOfRange,
+    /// Pressure out of range for the permittivity model.
+    #[error("pressure out of range for permittivity model")]
+    PressureOutOfRange,
+    /// Debye length must be positive and finite.
+    #[error("Debye length must be positive and finite")]
+    InvalidDebyeLength,
     /// Molarity must be positive and finite.
     #[error("molarity must be positive and finite")]
     InvalidMolarity,
+    /// Fractions must be non-negative with a positive sum.
+    #[error("fractions must be non-negative with a positive sum")]
+    InvalidFraction,
     /// Cannot set molarity without a salt.
     #[error("cannot set molarity without a salt")]
     MissingSalt,
+    /// Orders, C and D, do not give a valid Poisson scheme.
+    #[error("invalid orders of Poisson scheme: {0}")]
+    InvalidPoissonOrders(&'static str),
     /// Operation not supported.
     #[error("operation not supported: {0}")]
     Unsupported(&'static str),
     /// Spline error.
     #[error("spline error: {0}")]
     Spline(&'static str),
+    /// Iterative solver did not converge.
+    #[error("solver did not converge in {0} iterations")]
+    NotConverged(usize),
+    /// Linear system has no unique solution.
+    #[error("singular linear system")]
+    SingularMatrix,
 }
@@ -0,0 +1,199 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Barnes-Hut tree code with monopole and dipole node moments.
+
+use super::{FieldEvaluation, Octree};
+use crate::multipole_expansion::MultipoleExpansion;
+use crate::pairwise::{MultipoleField, MultipolePotential, Plain};
+use crate::{NalgebraVector3, Vector3};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Barnes-Hut tree code with open boundaries
+///
+/// For each particle, the [`Octree`] is traversed from the root and a node is replaced by its
+/// charge and dipole moment about the node center if $r < \theta d$, where $r$ is the node
+/// radius, $d$ the distance from the particle to the node center, and $\theta$ the opening
+/// angle.
+/// Otherwise, the children are visited, and particles in leaves interact directly.
+/// The cost scales as $\mathcal{O}(N \log N)$ and the relative error roughly as $\theta^3$,
+/// which makes this a lighter, moderate accuracy alternative to [`FastMultipole`](super::FastMultipole).
+/// See Barnes and Hut, <https://doi.org/10.1038/324446a0>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::fmm::BarnesHut;
+/// let positions: Vec<_> = (0..1000)
+///     .map(|i| [(i % 10) as f64, ((i / 10) % 10) as f64, (i / 100) as f64].into())
+///     .collect();
+/// let charges = vec![1.0; 1000];
+/// let evaluation = BarnesHut::new(0.3).evaluate(&positions, &charges);
+/// let exact: f64 = (1..1000)
+///     .map(|j| {
+///         let r = positions[j];
+///         1.0 / (r.x * r.x + r.y * r.y + r.z * r.z).sqrt()
+///     })
+///     .sum();
+/// assert_relative_eq!(evaluation.potentials[0], exact, max_relative = 5e-3);
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct BarnesHut {
+    /// Opening angle, θ
+    opening_angle: f64,
+    /// Largest number of particles in a leaf node
+    leaf_size: usize,
+}
+
+impl BarnesHut {
+    /// Default number of particles in a leaf node
+    const LEAF_SIZE: usize = 8;
+
+    /// New tree code with opening angle, θ > 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the opening angle is not positive.
+    pub fn new(opening_angle: f64) -> Self {
+        assert!(opening_angle > 0.0);
+        Self {
+            opening_angle,
+            leaf_size: Self::LEAF_SIZE,
+        }
+    }
+
+    /// Set the largest number of particles in a leaf node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `leaf_size` is zero.
+    pub fn with_leaf_size(mut self, leaf_size: usize) -> Self {
+        assert!(leaf_size > 0);
+        self.leaf_size = leaf_size;
+        self
+    }
+
+    /// Opening angle, θ
+    pub const fn opening_angle(&self) -> f64 {
+        self.opening_angle
+    }
+
+    /// Largest number of particles in a leaf node
+    pub const fn leaf_size(&self) -> usize {
+        self.leaf_size
+    }
+
+    /// Potential and field at each particle due to all other particles.
+    ///
+    /// Particles must have distinct positions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `positions` and `charges` differ in length.
+    pub fn evaluate(&self, positions: &[Vector3], charges: &[f64]) -> FieldEvaluation {
+        assert_eq!(positions.len(), charges.len());
+        let tree = Octree::new(positions, self.leaf_size);
+        let moments: Vec<MultipoleExpansion> = tree
+            .nodes()
+            .iter()
+            .map(|node| {
+                let particles = tree.particles(node);
+                let positions: Vec<Vector3> = particles.iter().map(|i| positions[*i]).collect();
+                let charges: Vec<f64> = particles.iter().map(|i| charges[*i]).collect();
+                MultipoleExpansion::from_charges(1, node.center(), &positions, &charges)
+            })
+            .collect();
+        let coulomb = Plain::without_cutoff();
+        let mut evaluation = FieldEvaluation {
+            potentials: vec![0.0; positions.len()],
+            fields: vec![[0.0; 3].into(); positions.len()],
+        };
+        let mut stack = Vec::new();
+        for (i, position) in positions.iter().enumerate() {
+            let position = NalgebraVector3::from(*position);
+            let (mut potential, mut field) = (0.0, NalgebraVector3::zeros());
+            stack.push(0);
+            while let Some(index) = stack.pop() {
+                let node = &tree.nodes()[index];
+                let r = position - node.center_vector();
+                if node.radius() < self.opening_angle * r.norm() {
+                    let (charge, dipole) =
+                        (moments[index].charge(), moments[index].dipole_moment());
+                    potential += coulomb.ion_potential(charge, r.norm())
+                        + coulomb.dipole_potential(dipole, r);
+                    field += NalgebraVector3::from(coulomb.ion_field(charge, r))
+                        + NalgebraVector3::from(coulomb.dipole_field(dipole, r));
+                } else if node.is_leaf() {
+                    for j in tree.particles(node).iter().filter(|j| **j != i) {
+                        let r = position - NalgebraVector3::from(positions[*j]);
+                        potential += charges[*j] / r.norm();
+                        field += charges[*j] * r / r.norm().powi(3);
+                    }
+                } else {
+                    stack.extend_from_slice(node.children());
+                }
+            }
+            evaluation.potentials[i] = potential;
+            evaluation.fields[i] = field.into();
+        }
+        evaluation
+    }
+}
+
+#[test]
+fn test_barnes_hut() {
+    use core::iter::zip;
+    let mut random = crate::test_utils::Random::new(13);
+    let positions = random.positions(400, [0.0; 3], [10.0; 3]);
+    let charges: Vec<f64> = (0..400).map(|_| random.uniform()).collect();
+    let exact = super::FastMultipole::new(0, 0.5)
+        .with_leaf_size(400)
+        .evaluate(&positions, &charges);
+
+    // Largest errors relative to the largest potential and field magnitudes
+    let fields = |e: &FieldEvaluation| -> Vec<NalgebraVector3> {
+        e.fields.iter().map(|f| NalgebraVector3::from(*f)).collect()
+    };
+    let (exact_fields, potential_scale) = (
+        fields(&exact),
+        exact
+            .potentials
+            .iter()
+            .fold(0.0, |m: f64, p| m.max(p.abs())),
+    );
+    let field_scale = exact_fields.iter().fold(0.0, |m: f64, f| m.max(f.norm()));
+    let error = |evaluation: &FieldEvaluation| {
+        let potential = (0..400)
+            .map(|i| (evaluation.potentials[i] - exact.potentials[i]).abs())
+            .fold(0.0, f64::max);
+        let field = zip(fields(evaluation), &exact_fields)
+            .map(|(a, b)| (a - b).norm())
+            .fold(0.0, f64::max);
+        (potential / potential_scale, field / field_scale)
+    };
+    let coarse = error(&BarnesHut::new(0.8).evaluate(&positions, &charges));
+    let fine = error(&BarnesHut::new(0.3).evaluate(&positions, &charges));
+    assert!(fine.0 < coarse.0 && fine.1 < coarse.1);
+    assert!(fine.0 < 5e-3 && fine.1 < 5e-3);
+
+    // Small opening angles fall back to direct summation
+    let direct = error(&BarnesHut::new(1e-6).evaluate(&positions, &charges));
+    assert!(direct.0 < 1e-12 && direct.1 < 1e-12);
+}
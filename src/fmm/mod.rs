@@ -0,0 +1,425 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Fast multipole method for large sets of point charges with open boundaries.
+//!
+//! The charges are sorted in an adaptive [`Octree`] and the interactions between well
+//! separated nodes are evaluated through multipole and local expansions of order $p$,
+//! see the [`multipole_expansion`](crate::multipole_expansion) module, while nearby particles
+//! interact directly.
+//! Nodes $A$ and $B$ are well separated if $r_A + r_B < \theta d$, where $r$ are the node
+//! radii, $d$ the distance between the node centers, and $\theta < 1$ the opening angle.
+//! The node pairs are found by a dual tree traversal, and the cost scales as $\mathcal{O}(N)$.
+//! The error decreases with increasing order and decreasing opening angle.
+//! See Dehnen, <https://doi.org/10.1006/jcph.2002.7026>.
+//! The simpler [`BarnesHut`] tree code uses only monopole and dipole moments of the nodes.
+//!
+//! Potentials and fields are for the plain, unscreened Coulomb operator, _i.e._ the
+//! [`Plain`](crate::pairwise::Plain) scheme without cutoff.
+//!
+//! # Examples
+//! ~~~
+//! # use approx::assert_relative_eq;
+//! use coulomb::fmm::FastMultipole;
+//! let positions: Vec<_> = (0..1000)
+//!     .map(|i| [(i % 10) as f64, ((i / 10) % 10) as f64, (i / 100) as f64].into())
+//!     .collect();
+//! let charges: Vec<f64> = (0..1000).map(|i| if i % 3 == 0 { 1.0 } else { -0.5 }).collect();
+//! let fmm = FastMultipole::new(10, 0.4);
+//! let evaluation = fmm.evaluate(&positions, &charges);
+//! let exact: f64 = (1..1000)
+//!     .map(|j| {
+//!         let r = positions[j];
+//!         charges[j] / (r.x * r.x + r.y * r.y + r.z * r.z).sqrt()
+//!     })
+//!     .sum();
+//! assert_relative_eq!(evaluation.potentials[0], exact, epsilon = 1e-4);
+//! ~~~
+
+mod barnes_hut;
+mod octree;
+pub use barnes_hut::BarnesHut;
+pub use octree::{Octree, OctreeNode};
+
+use crate::multipole_expansion::{
+    evaluate_local, multipole_to_local, translate_local, translate_multipole, MultipoleExpansion,
+    RotationTranslator,
+};
+use crate::{NalgebraVector3, Vector3};
+use num_complex::Complex64;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Electric potentials and fields at a set of point charges
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldEvaluation {
+    /// Potential at each particle from all other particles, UNIT: [ ( input charge ) / ( input length ) ]
+    pub potentials: Vec<f64>,
+    /// Field at each particle from all other particles, UNIT: [ ( input charge ) / ( input length )^2 ]
+    pub fields: Vec<Vector3>,
+}
+
+impl FieldEvaluation {
+    /// Total energy, ½∑𝑞ᵢΦᵢ, UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn energy(&self, charges: &[f64]) -> f64 {
+        0.5 * self
+            .potentials
+            .iter()
+            .zip(charges)
+            .map(|(potential, charge)| potential * charge)
+            .sum::<f64>()
+    }
+
+    /// Force, 𝑞ᵢ𝐄ᵢ, on each particle, UNIT: [ ( input charge )^2 / ( input length )^2 ]
+    pub fn forces(&self, charges: &[f64]) -> Vec<Vector3> {
+        self.fields
+            .iter()
+            .zip(charges)
+            .map(|(field, charge)| (*charge * NalgebraVector3::from(*field)).into())
+            .collect()
+    }
+}
+
+/// Fast multipole method with open boundaries
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct FastMultipole {
+    /// Order, 𝑝, of the multipole and local expansions
+    order: usize,
+    /// Opening angle, θ
+    opening_angle: f64,
+    /// Largest number of particles in a leaf node
+    leaf_size: usize,
+}
+
+impl FastMultipole {
+    /// Default number of particles in a leaf node
+    const LEAF_SIZE: usize = 32;
+
+    /// Smallest order where translations use a [`RotationTranslator`]
+    pub const ROTATION_ORDER: usize = 14;
+
+    /// New solver with expansion order, 𝑝, and opening angle, 0 < θ < 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the opening angle is not in the interval (0, 1).
+    pub fn new(order: usize, opening_angle: f64) -> Self {
+        assert!(opening_angle > 0.0 && opening_angle < 1.0);
+        Self {
+            order,
+            opening_angle,
+            leaf_size: Self::LEAF_SIZE,
+        }
+    }
+
+    /// Set the largest number of particles in a leaf node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `leaf_size` is zero.
+    pub fn with_leaf_size(mut self, leaf_size: usize) -> Self {
+        assert!(leaf_size > 0);
+        self.leaf_size = leaf_size;
+        self
+    }
+
+    /// Order, 𝑝, of the multipole and local expansions
+    pub const fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Opening angle, θ
+    pub const fn opening_angle(&self) -> f64 {
+        self.opening_angle
+    }
+
+    /// Largest number of particles in a leaf node
+    pub const fn leaf_size(&self) -> usize {
+        self.leaf_size
+    }
+
+    /// Potential and field at each particle due to all other particles.
+    ///
+    /// Particles must have distinct positions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `positions` and `charges` differ in length.
+    pub fn evaluate(&self, positions: &[Vector3], charges: &[f64]) -> FieldEvaluation {
+        assert_eq!(positions.len(), charges.len());
+        let tree = Octree::new(positions, self.leaf_size);
+        let mut solver = Solver {
+            tree: &tree,
+            positions: positions.iter().map(|p| (*p).into()).collect(),
+            charges,
+            order: self.order,
+            opening_angle: self.opening_angle,
+            translator: (self.order >= Self::ROTATION_ORDER)
+                .then(|| RotationTranslator::new(self.order)),
+            moments: Vec::new(),
+            locals: vec![Vec::new(); tree.nodes().len()],
+            potentials: vec![0.0; positions.len()],
+            fields: vec![NalgebraVector3::zeros(); positions.len()],
+        };
+        solver.upward_pass();
+        solver.interact_self(0);
+        solver.downward_pass();
+        FieldEvaluation {
+            potentials: solver.potentials,
+            fields: solver.fields.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Working state of a single evaluation
+struct Solver<'a> {
+    tree: &'a Octree,
+    positions: Vec<NalgebraVector3>,
+    charges: &'a [f64],
+    order: usize,
+    opening_angle: f64,
+    /// Rotation based translations for high orders
+    translator: Option<RotationTranslator>,
+    /// Multipole moments of each node about its center
+    moments: Vec<Vec<Complex64>>,
+    /// Local expansion coefficients of each node about its center; empty if zero
+    locals: Vec<Vec<Complex64>>,
+    potentials: Vec<f64>,
+    fields: Vec<NalgebraVector3>,
+}
+
+impl Solver<'_> {
+    /// Multipole moments of all nodes, from leaves (P2M) and children (M2M)
+    fn upward_pass(&mut self) {
+        let nodes = self.tree.nodes();
+        self.moments = vec![Vec::new(); nodes.len()];
+        for (index, node) in nodes.iter().enumerate().rev() {
+            self.moments[index] = if node.is_leaf() {
+                let particles = self.tree.particles(node);
+                let positions: Vec<Vector3> = particles
+                    .iter()
+                    .map(|i| self.positions[*i].into())
+                    .collect();
+                let charges: Vec<f64> = particles.iter().map(|i| self.charges[*i]).collect();
+                MultipoleExpansion::from_charges(self.order, node.center(), &positions, &charges)
+                    .moments()
+                    .to_vec()
+            } else {
+                node.children()
+                    .iter()
+                    .map(|child| {
+                        let d = nodes[*child].center_vector() - node.center_vector();
+                        match &self.translator {
+                            Some(translator) => {
+                                translator.shift_multipole(&self.moments[*child], d)
+                            }
+                            None => translate_multipole(&self.moments[*child], self.order, d),
+                        }
+                    })
+                    .reduce(|sum, moments| zip_add(sum, &moments))
+                    .unwrap_or_default()
+            };
+        }
+    }
+
+    /// Interactions within a node
+    fn interact_self(&mut self, index: usize) {
+        let node = &self.tree.nodes()[index];
+        if node.is_leaf() {
+            let particles = self.tree.particles(node);
+            for (n, i) in particles.iter().enumerate() {
+                for j in &particles[n + 1..] {
+                    self.direct(*i, *j);
+                }
+            }
+            return;
+        }
+        let children = node.children();
+        for (n, a) in children.iter().enumerate() {
+            self.interact_self(*a);
+            for b in &children[n + 1..] {
+                self.interact(*a, *b);
+            }
+        }
+    }
+
+    /// Interactions between two distinct nodes
+    fn interact(&mut self, a: usize, b: usize) {
+        let nodes = self.tree.nodes();
+        let (node_a, node_b) = (&nodes[a], &nodes[b]);
+        let t = node_b.center_vector() - node_a.center_vector();
+        if node_a.radius() + node_b.radius() < self.opening_angle * t.norm() {
+            self.add_local(b, self.multipole_to_local(a, t));
+            self.add_local(a, self.multipole_to_local(b, -t));
+        } else if node_a.is_leaf() && node_b.is_leaf() {
+            for i in self.tree.particles(node_a) {
+                for j in self.tree.particles(node_b) {
+                    self.direct(*i, *j);
+                }
+            }
+        } else if node_b.is_leaf() || (!node_a.is_leaf() && node_a.radius() > node_b.radius()) {
+            for child in node_a.children() {
+                self.interact(*child, b);
+            }
+        } else {
+            for child in node_b.children() {
+                self.interact(a, *child);
+            }
+        }
+    }
+
+    /// Local expansion coefficients from the moments of a node separated by `t` (M2L)
+    fn multipole_to_local(&self, index: usize, t: NalgebraVector3) -> Vec<Complex64> {
+        match &self.translator {
+            Some(translator) => translator.shift_to_local(&self.moments[index], t),
+            None => multipole_to_local(&self.moments[index], self.order, t),
+        }
+    }
+
+    /// Direct interaction between two particles (P2P)
+    fn direct(&mut self, i: usize, j: usize) {
+        let r = self.positions[j] - self.positions[i];
+        let inverse = 1.0 / r.norm();
+        let field = r * inverse.powi(3);
+        self.potentials[i] += self.charges[j] * inverse;
+        self.potentials[j] += self.charges[i] * inverse;
+        self.fields[i] -= self.charges[j] * field;
+        self.fields[j] += self.charges[i] * field;
+    }
+
+    fn add_local(&mut self, index: usize, local: Vec<Complex64>) {
+        let current = core::mem::take(&mut self.locals[index]);
+        self.locals[index] = if current.is_empty() {
+            local
+        } else {
+            zip_add(current, &local)
+        };
+    }
+
+    /// Shift local expansions to the children (L2L) and evaluate at particles in leaves (L2P)
+    fn downward_pass(&mut self) {
+        let nodes = self.tree.nodes();
+        for (index, node) in nodes.iter().enumerate() {
+            if self.locals[index].is_empty() {
+                continue;
+            }
+            if node.is_leaf() {
+                for i in self.tree.particles(node) {
+                    let y = self.positions[*i] - node.center_vector();
+                    let (potential, field) = evaluate_local(&self.locals[index], self.order, y);
+                    self.potentials[*i] += potential;
+                    self.fields[*i] += field;
+                }
+                continue;
+            }
+            for child in node.children() {
+                let e = nodes[*child].center_vector() - node.center_vector();
+                let local = match &self.translator {
+                    Some(translator) => translator.shift_local(&self.locals[index], e),
+                    None => translate_local(&self.locals[index], self.order, e),
+                };
+                self.add_local(*child, local);
+            }
+        }
+    }
+}
+
+/// Element-wise sum of two coefficient arrays
+fn zip_add(mut sum: Vec<Complex64>, values: &[Complex64]) -> Vec<Complex64> {
+    for (s, v) in sum.iter_mut().zip(values) {
+        *s += v;
+    }
+    sum
+}
+
+#[test]
+fn test_fast_multipole() {
+    use approx::assert_relative_eq;
+    let mut random = crate::test_utils::Random::new(5);
+    // Clustered charges to exercise the adaptive tree
+    let positions: Vec<Vector3> = (0..600)
+        .map(|i| {
+            let scale = if i % 4 == 0 { 20.0 } else { 2.0 };
+            [
+                scale * random.uniform(),
+                scale * random.uniform(),
+                scale * random.uniform() + (i % 3) as f64 * 7.0,
+            ]
+            .into()
+        })
+        .collect();
+    let charges: Vec<f64> = (0..600).map(|_| random.uniform() - 0.5).collect();
+
+    // Direct summation
+    let n = positions.len();
+    let mut potentials = vec![0.0; n];
+    let mut fields = vec![NalgebraVector3::zeros(); n];
+    for i in 0..n {
+        for j in 0..n {
+            if i != j {
+                let r = NalgebraVector3::from(positions[i]) - NalgebraVector3::from(positions[j]);
+                potentials[i] += charges[j] / r.norm();
+                fields[i] += charges[j] * r / r.norm().powi(3);
+            }
+        }
+    }
+    let error = |evaluation: &FieldEvaluation| {
+        let potential = (0..n)
+            .map(|i| (evaluation.potentials[i] - potentials[i]).abs())
+            .fold(0.0, f64::max);
+        let field = (0..n)
+            .map(|i| (NalgebraVector3::from(evaluation.fields[i]) - fields[i]).norm())
+            .fold(0.0, f64::max);
+        (potential, field)
+    };
+    let low = error(
+        &FastMultipole::new(2, 0.6)
+            .with_leaf_size(8)
+            .evaluate(&positions, &charges),
+    );
+    let high = FastMultipole::new(10, 0.3)
+        .with_leaf_size(8)
+        .evaluate(&positions, &charges);
+    let (potential_error, field_error) = error(&high);
+    assert!(potential_error < 1e-6 && field_error < 1e-5);
+    assert!(low.0 > potential_error && low.1 > field_error);
+
+    // High orders use rotation based translations
+    let rotated = error(
+        &FastMultipole::new(FastMultipole::ROTATION_ORDER, 0.3)
+            .with_leaf_size(8)
+            .evaluate(&positions, &charges),
+    );
+    assert!(rotated.0 < 1e-7 && rotated.1 < 1e-6);
+
+    let exact_energy = 0.5 * (0..n).map(|i| potentials[i] * charges[i]).sum::<f64>();
+    assert_relative_eq!(high.energy(&charges), exact_energy, epsilon = 1e-6);
+    let forces = high.forces(&charges);
+    assert_relative_eq!(
+        NalgebraVector3::from(forces[7]),
+        charges[7] * fields[7],
+        epsilon = 1e-6
+    );
+
+    // Everything is direct with a single leaf
+    let direct = FastMultipole::new(0, 0.5)
+        .with_leaf_size(n)
+        .evaluate(&positions, &charges);
+    assert!(error(&direct).0 < 1e-12);
+}
@@ -0,0 +1,228 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Adaptive octree for hierarchical methods.
+
+use crate::{NalgebraVector3, Vector3};
+use core::ops::Range;
+
+/// Node of an [`Octree`], _i.e._ a cubic box with its particles
+#[derive(Debug, Clone, PartialEq)]
+pub struct OctreeNode {
+    /// Center of the box
+    center: NalgebraVector3,
+    /// Half the side length of the box
+    half_width: f64,
+    /// Largest distance from the center to a particle in the node
+    radius: f64,
+    /// Range of particles in the sorted indices of the tree
+    range: Range<usize>,
+    /// Indices of non-empty child nodes
+    children: Vec<usize>,
+}
+
+impl OctreeNode {
+    /// Center of the box
+    pub fn center(&self) -> Vector3 {
+        self.center.into()
+    }
+
+    /// Half the side length of the box, UNIT: [ input length ]
+    pub const fn half_width(&self) -> f64 {
+        self.half_width
+    }
+
+    /// Largest distance from the center to a particle in the node, UNIT: [ input length ]
+    pub const fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Indices of non-empty child nodes in [`Octree::nodes`]
+    pub fn children(&self) -> &[usize] {
+        &self.children
+    }
+
+    /// True if the node has no children
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Number of particles in the node
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    /// True if the node has no particles
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    pub(crate) fn center_vector(&self) -> NalgebraVector3 {
+        self.center
+    }
+}
+
+/// Adaptive octree of particle positions.
+///
+/// The root node is the smallest cube enclosing all particles and nodes with more than
+/// `leaf_size` particles are split into up to eight non-empty children.
+/// Nodes are stored with parents before their children, starting with the root.
+///
+/// # Examples
+/// ~~~
+/// use coulomb::fmm::Octree;
+/// let positions: Vec<_> = (0..10).map(|i| [i as f64, 0.0, 0.0].into()).collect();
+/// let tree = Octree::new(&positions, 2);
+/// let root = &tree.nodes()[0];
+/// assert_eq!(root.len(), 10);
+/// assert!(tree.nodes().iter().all(|node| !node.is_leaf() || node.len() <= 2));
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+pub struct Octree {
+    /// Nodes with the root first
+    nodes: Vec<OctreeNode>,
+    /// Particle indices sorted so that each node holds a contiguous range
+    indices: Vec<usize>,
+}
+
+impl Octree {
+    /// Maximum depth of the tree, limiting the splitting of coincident particles
+    pub const MAX_DEPTH: usize = 24;
+
+    /// Build a tree with at most `leaf_size` particles in each leaf, unless at maximum depth.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `leaf_size` is zero.
+    pub fn new(positions: &[Vector3], leaf_size: usize) -> Self {
+        assert!(leaf_size > 0);
+        let points: Vec<NalgebraVector3> = positions.iter().map(|p| (*p).into()).collect();
+        let (lower, upper) = points.iter().fold(
+            (
+                NalgebraVector3::repeat(f64::INFINITY),
+                NalgebraVector3::repeat(f64::NEG_INFINITY),
+            ),
+            |(lower, upper), p| (lower.inf(p), upper.sup(p)),
+        );
+        let (center, half_width) = if points.is_empty() {
+            (NalgebraVector3::zeros(), 0.0)
+        } else {
+            ((lower + upper) / 2.0, (upper - lower).max() / 2.0)
+        };
+        let mut tree = Self {
+            nodes: Vec::new(),
+            indices: (0..points.len()).collect(),
+        };
+        tree.split(&points, center, half_width, 0..points.len(), leaf_size, 0);
+        tree
+    }
+
+    /// Add a node and, recursively, its children. Returns the index of the node.
+    fn split(
+        &mut self,
+        points: &[NalgebraVector3],
+        center: NalgebraVector3,
+        half_width: f64,
+        range: Range<usize>,
+        leaf_size: usize,
+        depth: usize,
+    ) -> usize {
+        let radius = self.indices[range.clone()]
+            .iter()
+            .map(|i| (points[*i] - center).norm())
+            .fold(0.0, f64::max);
+        let node = self.nodes.len();
+        self.nodes.push(OctreeNode {
+            center,
+            half_width,
+            radius,
+            range: range.clone(),
+            children: Vec::new(),
+        });
+        if range.len() <= leaf_size || depth >= Self::MAX_DEPTH {
+            return node;
+        }
+        let octant = |p: &NalgebraVector3| {
+            usize::from(p.x >= center.x)
+                | usize::from(p.y >= center.y) << 1
+                | usize::from(p.z >= center.z) << 2
+        };
+        self.indices[range.clone()].sort_by_key(|i| octant(&points[*i]));
+        let mut start = range.start;
+        let mut children = Vec::new();
+        for code in 0..8 {
+            let end = start
+                + self.indices[start..range.end]
+                    .iter()
+                    .take_while(|i| octant(&points[**i]) == code)
+                    .count();
+            if end > start {
+                let sign = |bit: usize| if code & bit != 0 { 0.5 } else { -0.5 };
+                let offset = NalgebraVector3::new(sign(1), sign(2), sign(4)) * half_width;
+                children.push(self.split(
+                    points,
+                    center + offset,
+                    half_width / 2.0,
+                    start..end,
+                    leaf_size,
+                    depth + 1,
+                ));
+            }
+            start = end;
+        }
+        self.nodes[node].children = children;
+        node
+    }
+
+    /// Nodes with the root first and parents before their children
+    pub fn nodes(&self) -> &[OctreeNode] {
+        &self.nodes
+    }
+
+    /// Indices of the particles in a node
+    pub fn particles(&self, node: &OctreeNode) -> &[usize] {
+        &self.indices[node.range.clone()]
+    }
+}
+
+#[test]
+fn test_octree() {
+    let mut random = crate::test_utils::Random::new(3);
+    let positions = random.positions(200, [0.0, 0.0, -3.0], [1.0, 2.0, -2.0]);
+    let tree = Octree::new(&positions, 8);
+    let nodes = tree.nodes();
+    let mut leaf_particles: Vec<usize> = nodes
+        .iter()
+        .filter(|node| node.is_leaf())
+        .flat_map(|node| tree.particles(node).to_vec())
+        .collect();
+    leaf_particles.sort_unstable();
+    assert_eq!(leaf_particles, (0..200).collect::<Vec<_>>());
+    for node in nodes {
+        let center = node.center_vector();
+        for i in tree.particles(node) {
+            let r = NalgebraVector3::from(positions[*i]) - center;
+            assert!(r.amax() <= node.half_width() * (1.0 + 1e-12));
+            assert!(r.norm() <= node.radius());
+        }
+        let count: usize = node.children().iter().map(|c| nodes[*c].len()).sum();
+        assert!(node.is_leaf() && node.len() <= 8 || count == node.len());
+        assert!(node.children().iter().all(|c| *c > 0));
+    }
+
+    // Coincident particles stop at the maximum depth
+    let tree = Octree::new(&[[1.0; 3].into(); 3], 1);
+    assert_eq!(tree.nodes().len(), Octree::MAX_DEPTH + 1);
+    assert!(Octree::new(&[], 4).nodes()[0].is_empty());
+}
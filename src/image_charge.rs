@@ -0,0 +1,313 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Image charges at a planar dielectric interface.
+//!
+//! Two dielectric half-spaces meet at the plane $z = 0$.
+//! A charge, $q$, in a medium with relative permittivity $\varepsilon_i$ polarizes the interface
+//! to a medium with $\varepsilon_j$ and, in its own half-space, this is equivalent to an image charge
+//!
+//! $$ q' = \Delta_i q, \quad \Delta_i = \frac{\varepsilon_i - \varepsilon_j}{\varepsilon_i + \varepsilon_j} $$
+//!
+//! at the mirror position.
+//! In the other half-space, the charge appears as $2 \varepsilon_j q / (\varepsilon_i + \varepsilon_j)$
+//! at its real position.
+//! See _e.g._ Jackson, _Classical Electrodynamics_, section 4.4.
+//!
+//! Unlike the [`pairwise`](crate::pairwise) module, potentials, energies, and forces here
+//! include the relative permittivities and should only be multiplied by _e.g._
+//! [`TO_CHEMISTRY_UNIT`](crate::TO_CHEMISTRY_UNIT).
+//!
+//! # Examples
+//! ~~~
+//! # use approx::assert_relative_eq;
+//! use coulomb::image_charge::PlanarInterface;
+//! // Water above a low-dielectric membrane
+//! let interface = PlanarInterface::new(80.0, 2.0);
+//! let energy = interface.ion_self_energy(1.0, [0.0, 0.0, 5.0]);
+//! assert_relative_eq!(energy, 78.0 / 82.0 / (4.0 * 80.0 * 5.0));
+//! ~~~
+
+use crate::{NalgebraVector3, Vector3};
+
+/// Planar interface between two dielectric half-spaces.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlanarInterface {
+    /// Relative permittivity above the interface, z > 0
+    upper: f64,
+    /// Relative permittivity below the interface, z < 0
+    lower: f64,
+}
+
+impl PlanarInterface {
+    /// New interface with relative permittivities above (z > 0) and below (z < 0) the plane z = 0.
+    pub const fn new(upper_permittivity: f64, lower_permittivity: f64) -> Self {
+        Self {
+            upper: upper_permittivity,
+            lower: lower_permittivity,
+        }
+    }
+
+    /// Relative permittivity of the medium containing `position` and of the opposite medium.
+    ///
+    /// Positions on the plane are taken to belong to the upper medium.
+    fn media(&self, position: &NalgebraVector3) -> (f64, f64) {
+        if position.z >= 0.0 {
+            (self.upper, self.lower)
+        } else {
+            (self.lower, self.upper)
+        }
+    }
+
+    /// Relative permittivity at `position`.
+    pub fn permittivity(&self, position: impl Into<Vector3>) -> f64 {
+        self.media(&position.into().into()).0
+    }
+
+    /// Image charge factor, $\Delta_i$, for a charge at `position`.
+    pub fn image_factor(&self, position: impl Into<Vector3>) -> f64 {
+        let (eps_i, eps_j) = self.media(&position.into().into());
+        (eps_i - eps_j) / (eps_i + eps_j)
+    }
+
+    /// Effective point charges (prefactor and position) seen from `target` for a unit
+    /// charge at `source`, including the division by the permittivity.
+    fn images(
+        &self,
+        source: &NalgebraVector3,
+        target: &NalgebraVector3,
+    ) -> [(f64, NalgebraVector3); 2] {
+        let (eps_i, eps_j) = self.media(source);
+        if (source.z >= 0.0) == (target.z >= 0.0) {
+            let mirror = NalgebraVector3::new(source.x, source.y, -source.z);
+            let delta = (eps_i - eps_j) / (eps_i + eps_j);
+            [(eps_i.recip(), *source), (delta / eps_i, mirror)]
+        } else {
+            [(2.0 / (eps_i + eps_j), *source), (0.0, *source)]
+        }
+    }
+
+    /// Electrostatic potential at `target` from a `charge` at `source`.
+    ///
+    /// UNIT: [ ( input charge ) / ( input length ) ]
+    pub fn ion_potential(
+        &self,
+        charge: f64,
+        source: impl Into<Vector3>,
+        target: impl Into<Vector3>,
+    ) -> f64 {
+        let (source, target) = (source.into().into(), target.into().into());
+        self.images(&source, &target)
+            .iter()
+            .filter(|(prefactor, _)| *prefactor != 0.0)
+            .map(|(prefactor, position)| charge * prefactor / (target - position).norm())
+            .sum()
+    }
+
+    /// Electric field at `target` from a `charge` at `source`.
+    ///
+    /// UNIT: [ ( input charge ) / ( input length )^2 ]
+    pub fn ion_field(
+        &self,
+        charge: f64,
+        source: impl Into<Vector3>,
+        target: impl Into<Vector3>,
+    ) -> Vector3 {
+        let (source, target) = (source.into().into(), target.into().into());
+        self.images(&source, &target)
+            .iter()
+            .filter(|(prefactor, _)| *prefactor != 0.0)
+            .map(|(prefactor, position)| {
+                let r: NalgebraVector3 = target - position;
+                r * (charge * prefactor / r.norm().powi(3))
+            })
+            .sum::<NalgebraVector3>()
+            .into()
+    }
+
+    /// Interaction energy between two charges, including their mutual image interactions.
+    ///
+    /// UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn ion_ion_energy(
+        &self,
+        charge1: f64,
+        position1: impl Into<Vector3>,
+        charge2: f64,
+        position2: impl Into<Vector3>,
+    ) -> f64 {
+        charge2 * self.ion_potential(charge1, position1, position2)
+    }
+
+    /// Force on charge 2 from charge 1 and its image, UNIT: [ ( input charge )^2 / ( input length )^2 ]
+    pub fn ion_ion_force(
+        &self,
+        charge1: f64,
+        position1: impl Into<Vector3>,
+        charge2: f64,
+        position2: impl Into<Vector3>,
+    ) -> Vector3 {
+        let field: NalgebraVector3 = self.ion_field(charge1, position1, position2).into();
+        (field * charge2).into()
+    }
+
+    /// Interaction energy between a charge and a point dipole, UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn ion_dipole_energy(
+        &self,
+        charge: f64,
+        charge_position: impl Into<Vector3>,
+        dipole: impl Into<Vector3>,
+        dipole_position: impl Into<Vector3>,
+    ) -> f64 {
+        let field: NalgebraVector3 = self
+            .ion_field(charge, charge_position, dipole_position)
+            .into();
+        -NalgebraVector3::from(dipole.into()).dot(&field)
+    }
+
+    /// Self-energy of a charge with its own image, $\Delta_i q^2 / 4 \varepsilon_i |z|$.
+    ///
+    /// UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn ion_self_energy(&self, charge: f64, position: impl Into<Vector3>) -> f64 {
+        let position: NalgebraVector3 = position.into().into();
+        let (eps_i, _) = self.media(&position);
+        self.image_factor(position) * charge * charge / (4.0 * eps_i * position.z.abs())
+    }
+
+    /// Force on a charge from its own image, UNIT: [ ( input charge )^2 / ( input length )^2 ]
+    pub fn ion_self_force(&self, charge: f64, position: impl Into<Vector3>) -> Vector3 {
+        let position: NalgebraVector3 = position.into().into();
+        let energy = self.ion_self_energy(charge, position);
+        [0.0, 0.0, energy / position.z].into()
+    }
+
+    /// Self-energy of a point dipole with its own image,
+    /// $\Delta_i (\mu_x^2 + \mu_y^2 + 2 \mu_z^2) / 16 \varepsilon_i |z|^3$.
+    ///
+    /// UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn dipole_self_energy(
+        &self,
+        dipole: impl Into<Vector3>,
+        position: impl Into<Vector3>,
+    ) -> f64 {
+        let dipole: NalgebraVector3 = dipole.into().into();
+        let position: NalgebraVector3 = position.into().into();
+        let (eps_i, _) = self.media(&position);
+        self.image_factor(position) * (dipole.norm_squared() + dipole.z * dipole.z)
+            / (16.0 * eps_i * position.z.abs().powi(3))
+    }
+
+    /// Force on a point dipole from its own image, UNIT: [ ( input charge )^2 / ( input length )^2 ]
+    pub fn dipole_self_force(
+        &self,
+        dipole: impl Into<Vector3>,
+        position: impl Into<Vector3>,
+    ) -> Vector3 {
+        let position: NalgebraVector3 = position.into().into();
+        let energy = self.dipole_self_energy(dipole, position);
+        [0.0, 0.0, 3.0 * energy / position.z].into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_boundary_conditions() {
+        let interface = PlanarInterface::new(80.0, 2.0);
+        let h = 1e-10;
+        for source in [[0.3, -0.2, 1.5], [0.3, -0.2, -1.5]] {
+            let target = |z: f64| [1.1, 0.4, z];
+            // Continuous potential and normal displacement field
+            assert_relative_eq!(
+                interface.ion_potential(1.0, source, target(h)),
+                interface.ion_potential(1.0, source, target(-h)),
+                max_relative = 1e-5
+            );
+            assert_relative_eq!(
+                80.0 * interface.ion_field(1.0, source, target(h)).z,
+                2.0 * interface.ion_field(1.0, source, target(-h)).z,
+                max_relative = 1e-5
+            );
+            // Continuous tangential field
+            assert_relative_eq!(
+                interface.ion_field(1.0, source, target(h)).x,
+                interface.ion_field(1.0, source, target(-h)).x,
+                max_relative = 1e-5
+            );
+        }
+        // Reciprocity
+        let (a, b) = ([0.3, -0.2, 1.5], [1.1, 0.4, -0.7]);
+        assert_relative_eq!(
+            interface.ion_ion_energy(1.0, a, -2.0, b),
+            interface.ion_ion_energy(-2.0, b, 1.0, a)
+        );
+        // Homogeneous medium
+        let uniform = PlanarInterface::new(10.0, 10.0);
+        assert_relative_eq!(
+            uniform.ion_ion_energy(1.0, a, -2.0, b),
+            -0.2 / 5.84f64.sqrt()
+        );
+        assert_eq!(uniform.ion_self_energy(1.0, a), 0.0);
+    }
+
+    #[test]
+    fn test_self_image() {
+        let interface = PlanarInterface::new(4.0, 60.0);
+        let position = NalgebraVector3::new(0.2, 0.1, 1.3);
+        let (charge, dipole) = (1.5, NalgebraVector3::new(0.4, -0.3, 0.8));
+        let h = 1e-6;
+        let dz = NalgebraVector3::new(0.0, 0.0, h);
+
+        // Half the interaction with the image
+        let image = NalgebraVector3::new(position.x, position.y, -position.z);
+        let delta = interface.image_factor(position);
+        let eps = interface.permittivity(position);
+        assert_relative_eq!(
+            interface.ion_self_energy(charge, position),
+            0.5 * charge * charge * delta / (eps * (position - image).norm())
+        );
+        let force = interface.ion_self_force(charge, position);
+        let gradient = (interface.ion_self_energy(charge, position + dz)
+            - interface.ion_self_energy(charge, position - dz))
+            / (2.0 * h);
+        assert!(force.z < 0.0); // attracted to the high dielectric
+        assert_relative_eq!(force.z, -gradient, max_relative = 1e-6);
+
+        // Dipole as two nearby charges
+        let d = 1e-4;
+        let q = dipole.norm() / d;
+        let (plus, minus) = (
+            position + dipole.normalize() * 0.5 * d,
+            position - dipole.normalize() * 0.5 * d,
+        );
+        let image_energy = |source: NalgebraVector3, charge: f64| {
+            let mirror = NalgebraVector3::new(source.x, source.y, -source.z);
+            let prefactor = charge * delta / eps;
+            prefactor / (plus - mirror).norm() * q - prefactor / (minus - mirror).norm() * q
+        };
+        assert_relative_eq!(
+            interface.dipole_self_energy(dipole, position),
+            0.5 * (image_energy(plus, q) + image_energy(minus, -q)),
+            max_relative = 1e-5
+        );
+        let force = interface.dipole_self_force(dipole, position);
+        let gradient = (interface.dipole_self_energy(dipole, position + dz)
+            - interface.dipole_self_energy(dipole, position - dz))
+            / (2.0 * h);
+        assert_relative_eq!(force.z, -gradient, max_relative = 1e-6);
+    }
+}
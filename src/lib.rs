@@ -106,23 +106,43 @@ pub use error::Error;
 /// A type alias for `Result<T, Error>`.
 pub type Result<T> = std::result::Result<T, Error>;
 
+pub mod activity;
+mod born;
+pub use born::born_energy;
+pub mod charged_bodies;
 mod cutoff;
 pub use cutoff::Cutoff;
+pub mod electrode;
+pub mod fmm;
+pub mod image_charge;
 mod math;
 mod medium;
+pub mod multipole_expansion;
+pub mod neighbor;
 pub mod pairwise;
+pub mod pbc;
 pub mod permittivity;
+pub mod poisson_boltzmann;
+pub mod polarization;
 pub mod reciprocal;
 mod salt;
+pub mod solvation;
 mod spline;
-pub use medium::Medium;
-pub use salt::Salt;
+pub mod system;
+#[cfg(test)]
+mod test_utils;
+pub mod thermodynamics;
+pub use medium::{Medium, MediumBuilder};
+pub use salt::{Ion, Salt};
 mod temperature;
 pub use temperature::Temperature;
 mod ionic_strength;
 pub use ionic_strength::IonicStrength;
 mod debye_length;
-pub use debye_length::{bjerrum_length, debye_length, DebyeLength};
+pub use debye_length::{
+    bjerrum_length, coupling_parameter, debye_length, wigner_seitz_radius, DebyeLength,
+    SaltSensitive,
+};
 
 use std::f64::consts::PI;
 
@@ -18,6 +18,116 @@
 #[cfg(test)]
 use approx::assert_relative_eq;
 
+/// Complementary error function, erfc(x)
+///
+/// By default, erfc is evaluated to full double precision using rational approximations
+/// from [`libm`](https://crates.io/crates/libm).
+/// If the `fast-erfc` feature is enabled, the cheaper approximation in [`erfc_x_fast`]
+/// is used instead which trades accuracy (absolute error ≤ 1.5 × 10⁻⁷) for speed.
+/// Otherwise, if the `mixed-precision` feature is enabled, erfc is evaluated in single
+/// precision, see [`erfc_x_single`].
+/// This affects all real-space Ewald schemes where erfc is evaluated for every pair.
+#[inline]
+pub(crate) fn erfc_x(x: f64) -> f64 {
+    #[cfg(feature = "fast-erfc")]
+    {
+        erfc_x_fast(x)
+    }
+    #[cfg(all(feature = "mixed-precision", not(feature = "fast-erfc")))]
+    {
+        erfc_x_single(x)
+    }
+    #[cfg(not(any(feature = "fast-erfc", feature = "mixed-precision")))]
+    {
+        libm::erfc(x)
+    }
+}
+
+/// Complementary error function, erfc(x), evaluated in single precision
+///
+/// The relative error is below (1 + x²) × 2 × 10⁻⁷ for 0 ≤ x ≤ 5, _i.e._ at most
+/// 5 × 10⁻⁶, and is dominated by rounding of the argument for large x.
+/// The result underflows to zero for x ≳ 9.2 where erfc(x) < 10⁻³⁸.
+#[inline]
+#[cfg_attr(
+    any(not(feature = "mixed-precision"), feature = "fast-erfc"),
+    allow(dead_code)
+)]
+pub(crate) fn erfc_x_single(x: f64) -> f64 {
+    f64::from(libm::erfcf(x as f32))
+}
+
+/// Scaled complementary error function, erfcx(x) = erfc(x) exp(x²)
+///
+/// Products of erfc with a large exponential are evaluated as erfcx(x) exp(y - x²) without
+/// the intermediate over- and underflow of erfc(x) exp(y).
+/// Features select the precision as for [`erfc_x`], while an asymptotic expansion accurate
+/// to double precision is used for x ≥ 9 where erfc(x) < 10⁻³⁶.
+#[inline]
+pub(crate) fn erfcx_x(x: f64) -> f64 {
+    if x >= 9.0 {
+        return erfcx_asymptotic(x);
+    }
+    #[cfg(feature = "fast-erfc")]
+    {
+        if x < 0.0 {
+            2.0 * f64::exp(x * x) - erfcx_x_fast(-x)
+        } else {
+            erfcx_x_fast(x)
+        }
+    }
+    #[cfg(all(feature = "mixed-precision", not(feature = "fast-erfc")))]
+    {
+        let x = x as f32;
+        f64::from(libm::erfcf(x) * (x * x).exp())
+    }
+    #[cfg(not(any(feature = "fast-erfc", feature = "mixed-precision")))]
+    {
+        libm::erfc(x) * f64::exp(x * x)
+    }
+}
+
+/// Asymptotic expansion of erfcx(x) = erfc(x) exp(x²), with a relative error below 10⁻¹⁵
+/// for x ≥ 9
+fn erfcx_asymptotic(x: f64) -> f64 {
+    let inv_2x2 = 0.5 / (x * x);
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for n in 1..12 {
+        term *= -f64::from(2 * n - 1) * inv_2x2;
+        sum += term;
+    }
+    sum / (x * core::f64::consts::PI.sqrt())
+}
+
+/// Exponential function, exp(x)
+///
+/// If the `mixed-precision` feature is enabled, exp is evaluated in single precision,
+/// see [`exp_x_single`].
+/// This affects the real-space Ewald schemes where exp is evaluated for every pair.
+#[inline]
+pub(crate) fn exp_x(x: f64) -> f64 {
+    #[cfg(feature = "mixed-precision")]
+    {
+        exp_x_single(x)
+    }
+    #[cfg(not(feature = "mixed-precision"))]
+    {
+        x.exp()
+    }
+}
+
+/// Exponential function, exp(x), evaluated in single precision
+///
+/// The relative error is below (1 + |x|) × 1.2 × 10⁻⁷, where the term proportional to |x|
+/// is due to rounding of the argument.
+/// The result overflows for x > 88.7 and underflows to zero for x < -103.
+#[inline]
+#[cfg_attr(not(feature = "mixed-precision"), allow(dead_code))]
+pub(crate) fn exp_x_single(x: f64) -> f64 {
+    f64::from((x as f32).exp())
+}
+
 /// Approximation of erfc-function
 ///
 /// # Arguments
@@ -31,25 +141,51 @@ use approx::assert_relative_eq;
 /// t = 1 / (1 + px)
 /// |epsilon(x)| <= 1.5 * 10^-7
 ///
-/// # Warning
-/// Needs modification if x < 0
+/// Only a single exponential and a fifth order polynomial are evaluated, which
+/// is typically 2-3 times faster than the full precision `erfc_x`.
+/// Negative arguments are handled using erfc(-x) = 2 - erfc(x).
 #[inline]
-pub(crate) fn erfc_x(x: f64) -> f64 {
+#[cfg_attr(not(feature = "fast-erfc"), allow(dead_code))]
+pub(crate) fn erfc_x_fast(x: f64) -> f64 {
+    if x < 0.0 {
+        return 2.0 - erfc_x_fast(-x);
+    }
+    erfcx_x_fast(x) * f64::exp(-x * x)
+}
+
+/// Scaled approximation, erfc(x) exp(x²) for x ≥ 0, see [`erfc_x_fast`]
+#[inline]
+#[cfg_attr(not(feature = "fast-erfc"), allow(dead_code))]
+fn erfcx_x_fast(x: f64) -> f64 {
     let t = 1.0 / (1.0 + 0.3275911 * x);
     let a1 = 0.254829592;
     let a2 = -0.284496736;
     let a3 = 1.421413741;
     let a4 = -1.453152027;
     let a5 = 1.061405429;
-    t * (a1 + t * (a2 + t * (a3 + t * (a4 + t * a5)))) * f64::exp(-x * x)
+    t * (a1 + t * (a2 + t * (a3 + t * (a4 + t * a5))))
 }
 
-/// Approximation of erf-function, see `erfc_x` for details
+/// Error function, erf(x) = 1 - erfc(x), see `erfc_x` for details
 #[inline]
 pub(crate) fn erf_x(x: f64) -> f64 {
     1.0 - erfc_x(x)
 }
 
+/// Derivatives of erf(b x) with respect to x, from zeroth to fourth order
+pub(crate) fn erf_derivatives(b: f64, x: f64) -> [f64; 5] {
+    let bx = b * x;
+    // Higher derivatives follow from Hermite polynomials
+    let prefactor = core::f64::consts::FRAC_2_SQRT_PI * (-bx * bx).exp();
+    [
+        erf_x(bx),
+        prefactor * b,
+        prefactor * b.powi(2) * (-2.0 * bx),
+        prefactor * b.powi(3) * (4.0 * bx * bx - 2.0),
+        prefactor * b.powi(4) * (12.0 * bx - 8.0 * bx.powi(3)),
+    ]
+}
+
 pub(crate) fn _q_pochhammer_symbol(q: f64, l: i32, p: i32) -> f64 {
     let ct = (1..=p)
         .map(|n| (1..=(n + l)).map(|k| q.powi(k - 1)).sum::<f64>())
@@ -123,10 +259,175 @@ pub(crate) fn _q_pochhammer_symbol_second_derivative(q: f64, l: i32, p: i32) ->
     ct * dd_dt + 2.0 * d_ct * d_dt + dd_ct * dt
 }
 
+/// Modified Bessel function of the second kind, Kᵥ(x), for x > 0
+///
+/// Evaluated from the integral representation, Kᵥ(x) = ∫₀^∞ exp(-x cosh t) cosh(νt) dt,
+/// using the trapezoidal rule which converges exponentially for this integrand.
+pub(crate) fn bessel_k(nu: f64, x: f64) -> f64 {
+    const STEP: f64 = 0.05;
+    // Truncate where the integrand drops below exp(-700)
+    let t_max = (700.0 / x).max(1.0).acosh() + 1.0;
+    let n = (t_max / STEP).ceil() as usize;
+    let sum: f64 = (1..=n)
+        .map(|i| {
+            let t = i as f64 * STEP;
+            (-x * t.cosh()).exp() * (nu * t).cosh()
+        })
+        .sum();
+    STEP * (0.5 * (-x).exp() + sum)
+}
+
+/// Complete elliptic integrals of the first and second kind, (K(m), E(m)), for parameter 0 ≤ m < 1
+///
+/// Evaluated with the arithmetic-geometric mean which converges quadratically.
+pub(crate) fn elliptic_ke(m: f64) -> (f64, f64) {
+    let (mut a, mut b) = (1.0, (1.0 - m).sqrt());
+    let mut c = m.sqrt();
+    let (mut power, mut sum) = (0.5, 0.5 * m);
+    while c.abs() > f64::EPSILON * a {
+        c = 0.5 * (a - b);
+        (a, b) = (0.5 * (a + b), (a * b).sqrt());
+        power *= 2.0;
+        sum += power * c * c;
+    }
+    let k = core::f64::consts::FRAC_PI_2 / a;
+    (k, k * (1.0 - sum))
+}
+
+/// Langevin function, L(x) = coth(x) - 1/x
+///
+/// A series expansion is used for small arguments to avoid cancellation.
+pub(crate) fn langevin(x: f64) -> f64 {
+    if x.abs() < 1e-2 {
+        let x2 = x * x;
+        x / 3.0 * (1.0 - x2 / 15.0 + 2.0 * x2 * x2 / 315.0)
+    } else {
+        1.0 / x.tanh() - 1.0 / x
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_langevin() {
+        // Reference values from `mpmath.coth(x) - 1/x`
+        let reference = [
+            (1e-4, 3.3333333311111113e-5),
+            (0.1, 0.03331113225398961),
+            (1.0, 0.3130352854993313),
+            (5.0, 0.8000908039820194),
+            (50.0, 0.98),
+        ];
+        for (x, l) in reference {
+            assert_relative_eq!(langevin(x), l, max_relative = 1e-12);
+            assert_relative_eq!(langevin(-x), -l, max_relative = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_erfc() {
+        // Reference values from `scipy.special.erfc`
+        let reference = [
+            (-1.0, 1.842700792949715),
+            (0.0, 1.0),
+            (0.5, 0.4795001221869535),
+            (1.45, 0.040304974362540776),
+            (3.0, 2.2090496998585438e-05),
+        ];
+        for (x, erfc) in reference {
+            assert_relative_eq!(erfc_x_fast(x), erfc, epsilon = 1.5e-7);
+            assert_relative_eq!(
+                erfc_x(x),
+                erfc,
+                epsilon = 1e-15 + crate::test_utils::ERFC_ERROR
+            );
+        }
+        assert_relative_eq!(erf_x(0.5) + erfc_x(0.5), 1.0);
+    }
+
+    #[test]
+    fn test_erfcx() {
+        // Reference values from `mpmath.erfc(x) * mpmath.exp(x**2)`
+        let reference = [
+            (-0.5, 1.952360489182557),
+            (0.0, 1.0),
+            (0.5, 0.6156903441929259),
+            (3.0, 0.17900115118138995),
+            (9.5, 0.05906467835256389),
+            (30.0, 0.01879588886141675),
+            (100.0, 0.005641613782989433),
+        ];
+        for (x, erfcx) in reference {
+            // Error bounds of `erfc_x` and `exp_x` carry over below the asymptotic regime
+            let (epsilon, max_relative) = if x >= 9.0 {
+                (0.0, 1e-15)
+            } else if cfg!(feature = "fast-erfc") {
+                (1.5e-7 * f64::exp(x * x), 0.0)
+            } else if cfg!(feature = "mixed-precision") {
+                (0.0, 3.2e-7 * (1.0 + x * x))
+            } else {
+                (0.0, 1e-14)
+            };
+            assert_relative_eq!(
+                erfcx_x(x),
+                erfcx,
+                epsilon = epsilon,
+                max_relative = max_relative
+            );
+        }
+    }
+
+    #[test]
+    fn test_single_precision() {
+        // Relative errors compared to full double precision
+        for x in (0..=500).map(|i| f64::from(i) / 100.0) {
+            let erfc = libm::erfc(x);
+            assert!((erfc_x_single(x) - erfc).abs() <= 2e-7 * (1.0 + x * x) * erfc);
+            assert!((erfc_x_single(-x) - (2.0 - erfc)).abs() <= 1e-6);
+        }
+        for x in (-2000..=800).map(|i| f64::from(i) / 100.0) {
+            assert!((exp_x_single(x) - x.exp()).abs() <= 1.2e-7 * (1.0 + x.abs()) * x.exp());
+        }
+        #[cfg(all(feature = "mixed-precision", not(feature = "fast-erfc")))]
+        assert_eq!(erfc_x(0.5), erfc_x_single(0.5));
+        #[cfg(feature = "mixed-precision")]
+        assert_eq!(exp_x(0.5), exp_x_single(0.5));
+    }
+
+    #[test]
+    fn test_bessel_k() {
+        // Reference values from `mpmath.besselk`
+        let reference = [
+            (0.0, 0.1, 2.4270690247020164),
+            (0.0, 1.0, 0.42102443824070834),
+            (1.0, 1.0, 0.6019072301972346),
+            (1.0, 2.5, 0.07389081634774707),
+            (1.0, 0.01, 99.97389411829624),
+        ];
+        for (nu, x, k) in reference {
+            assert_relative_eq!(bessel_k(nu, x), k, max_relative = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_elliptic_ke() {
+        use core::f64::consts::FRAC_PI_2;
+        // Reference values from `mpmath.ellipk` and `mpmath.ellipe`
+        let reference = [
+            (0.0, FRAC_PI_2, FRAC_PI_2),
+            (0.3, 1.713889448178791, 1.4453630644126654),
+            (0.9, 2.5780921133481733, 1.1047747327040733),
+            (0.999, 4.841132560550297, 1.0021707908344453),
+        ];
+        for (m, k, e) in reference {
+            let (k_m, e_m) = elliptic_ke(m);
+            assert_relative_eq!(k_m, k, max_relative = 1e-14);
+            assert_relative_eq!(e_m, e, max_relative = 1e-14);
+        }
+    }
+
     #[test]
     fn test_q_pochhammer_symbol() {
         assert_eq!(_q_pochhammer_symbol(0.5, 0, 0), 1.0);
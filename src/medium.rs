@@ -47,6 +47,27 @@ use serde::{Deserialize, Serialize};
 /// approx::assert_abs_diff_eq!(medium.ionic_strength().unwrap(), 0.3);
 /// approx::assert_abs_diff_eq!(medium.debye_length().unwrap(), 5.548902662386284);
 /// ~~~
+/// ## Salt mixtures
+/// ~~~
+/// # use coulomb::{Medium, IonicStrength, Salt};
+/// let mut medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.15);
+/// medium.add_salt(Salt::Custom(vec![2, -1]), 0.001).unwrap(); // MgCl₂
+/// approx::assert_abs_diff_eq!(medium.ionic_strength().unwrap(), 0.153);
+/// let (cation, anion) = medium.mean_valencies().unwrap();
+/// approx::assert_abs_diff_eq!(cation, 0.152 / 0.151);
+/// approx::assert_abs_diff_eq!(anion, -1.0);
+/// ~~~
+/// ## Builder
+/// ~~~
+/// # use coulomb::{Medium, IonicStrength, Salt, permittivity::Permittivity};
+/// let medium = Medium::builder()
+///     .temperature(298.15)
+///     .solvent(Permittivity::Water)
+///     .salt(Salt::SodiumChloride, 0.1)
+///     .build()
+///     .unwrap();
+/// approx::assert_abs_diff_eq!(medium.ionic_strength().unwrap(), 0.1);
+/// ~~~
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(
     feature = "serde",
@@ -58,6 +79,12 @@ pub struct Medium {
     permittivity: Permittivity,
     /// Salt type and molarity (mol/l)
     salt: Option<(Salt, f64)>,
+    /// Further salts in a mixture and their molarities (mol/l)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    additional_salts: Vec<(Salt, f64)>,
     /// Temperature in Kelvin
     temperature: f64,
 }
@@ -68,6 +95,7 @@ impl Medium {
         Self {
             permittivity,
             salt,
+            additional_salts: Vec::new(),
             temperature,
         }
     }
@@ -76,6 +104,7 @@ impl Medium {
         Self {
             permittivity: Permittivity::Water,
             salt: None,
+            additional_salts: Vec::new(),
             temperature,
         }
     }
@@ -84,16 +113,110 @@ impl Medium {
         Self {
             permittivity: Permittivity::Water,
             salt: Some((salt, molarity)),
+            additional_salts: Vec::new(),
             temperature,
         }
     }
 
-    /// Get molarity of the salt solution, if any
+    /// Builder for a medium with validation of temperature, permittivity, and salts
+    pub fn builder() -> MediumBuilder {
+        MediumBuilder::default()
+    }
+
+    /// Medium with salt of explicit ions and their stoichiometric coefficients at given molarity (mol/l).
+    ///
+    /// # Examples
+    /// ~~~
+    /// # use coulomb::{Ion, IonicStrength, Medium, permittivity::Permittivity};
+    /// let ions = vec![(Ion::new(2, 3.0), 1), (Ion::new(-1, 1.5), 2)];
+    /// let medium = Medium::from_ions(298.15, Permittivity::Water, ions, 0.1).unwrap();
+    /// approx::assert_abs_diff_eq!(medium.ionic_strength().unwrap(), 0.3);
+    /// ~~~
+    ///
+    /// # Errors
+    /// Returns an error if the ions are not electroneutral or if the molarity is invalid.
+    pub fn from_ions(
+        temperature: f64,
+        permittivity: Permittivity,
+        ions: Vec<(Ion, usize)>,
+        molarity: f64,
+    ) -> Result<Self> {
+        let mut medium = Self::new(temperature, permittivity, None);
+        medium.add_salt(Salt::from_ions(ions)?, molarity)?;
+        Ok(medium)
+    }
+
+    /// Get the salt type, if any. For mixtures, this is the first salt.
+    pub fn salt(&self) -> Option<&Salt> {
+        self.salt.as_ref().map(|(salt, _)| salt)
+    }
+
+    /// Get molarity of the salt solution, if any. For mixtures, this is the first salt.
     pub fn molarity(&self) -> Option<f64> {
         self.salt.as_ref().map(|(_, molarity)| molarity).copied()
     }
 
+    /// Add a salt with given molarity (mol/l) to form a mixture.
+    ///
+    /// # Errors
+    /// Returns an error if the molarity is negative or not finite.
+    pub fn add_salt(&mut self, salt: Salt, molarity: f64) -> Result<()> {
+        if molarity.is_sign_negative() || !molarity.is_finite() {
+            return Err(crate::Error::InvalidMolarity);
+        }
+        match self.salt {
+            None => self.salt = Some((salt, molarity)),
+            Some(_) => self.additional_salts.push((salt, molarity)),
+        }
+        Ok(())
+    }
+
+    /// All salts and their molarities (mol/l)
+    pub fn salts(&self) -> impl Iterator<Item = &(Salt, f64)> {
+        self.salt.iter().chain(self.additional_salts.iter())
+    }
+
+    /// Valency and total molarity (mol/l) of each ion species, combining ions of equal valency
+    ///
+    /// Ions are listed in order of first appearance and salts with unresolvable stoichiometry
+    /// are skipped.
+    pub fn ions(&self) -> Vec<(isize, f64)> {
+        let mut ions: Vec<(isize, f64)> = Vec::new();
+        for (salt, molarity) in self.salts() {
+            let Ok(stoichiometry) = salt.stoichiometry() else {
+                continue;
+            };
+            for (valency, nu) in salt.valencies().into_iter().zip(stoichiometry) {
+                let concentration = nu as f64 * molarity;
+                match ions.iter_mut().find(|(z, _)| *z == valency) {
+                    Some((_, total)) => *total += concentration,
+                    None => ions.push((valency, concentration)),
+                }
+            }
+        }
+        ions
+    }
+
+    /// Concentration-weighted mean valencies of cations and anions, Σcᵢzᵢ / Σcᵢ
+    ///
+    /// Returns `None` unless both cations and anions are present at non-zero concentration.
+    pub fn mean_valencies(&self) -> Option<(f64, f64)> {
+        let ions = self.ions();
+        let mean = |positive: bool| {
+            let (charge, total) = ions
+                .iter()
+                .filter(|(z, _)| z.is_positive() == positive)
+                .fold((0.0, 0.0), |(charge, total), (z, c)| {
+                    (charge + *z as f64 * c, total + c)
+                });
+            (total > 0.0).then(|| charge / total)
+        };
+        Some((mean(true)?, mean(false)?))
+    }
+
     /// Change the molarity of the salt solution. Error if no salt type is defined.
+    ///
+    /// For mixtures, only the first salt is changed.
     pub fn set_molarity(&mut self, molality: f64) -> Result<()> {
         if molality.is_sign_negative() || !molality.is_finite() {
             return Err(crate::Error::InvalidMolarity);
@@ -116,6 +239,156 @@ impl Medium {
     pub fn permittivity(&self) -> f64 {
         self.permittivity.permittivity(self.temperature).unwrap()
     }
+
+    /// Wigner-Seitz radius (angstrom) from the total number density of ions, if any salt
+    pub fn wigner_seitz_radius(&self) -> Option<f64> {
+        let molarity: f64 = self.ions().iter().map(|(_, c)| c).sum();
+        (molarity > 0.0)
+            .then(|| wigner_seitz_radius(molarity * AVOGADRO_CONSTANT * LITER_PER_ANGSTROM3))
+    }
+
+    /// Electrostatic coupling parameter, Γ = λ𝐵𝑧²/𝑎, of an ion with valency 𝑧, if any salt.
+    ///
+    /// The Wigner-Seitz radius, 𝑎, is found from the total number density of ions.
+    ///
+    /// # Examples
+    /// ~~~
+    /// # use coulomb::{Medium, Salt};
+    /// let medium = Medium::salt_water(298.15, Salt::LanthanumChloride, 0.1);
+    /// let monovalent = medium.coupling_parameter(1).unwrap();
+    /// assert!(monovalent < 1.0);
+    /// assert!(medium.coupling_parameter(3).unwrap() > 1.0);
+    /// ~~~
+    pub fn coupling_parameter(&self, valency: isize) -> Option<f64> {
+        self.wigner_seitz_radius()
+            .map(|radius| self.bjerrum_length() * (valency * valency) as f64 / radius)
+    }
+
+    /// Evaluate a property of the medium at each temperature (K), returning (T, value) pairs.
+    ///
+    /// Salt and permittivity model are kept fixed while the temperature is varied.
+    ///
+    /// # Examples
+    /// ~~~
+    /// # use coulomb::{Medium, Salt};
+    /// let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+    /// let temperatures = (0..=4).map(|i| 280.0 + 10.0 * i as f64);
+    /// let permittivities = medium.temperature_sweep(temperatures, Medium::permittivity).unwrap();
+    /// assert_eq!(permittivities.len(), 5);
+    /// assert!(permittivities[0].1 > permittivities[4].1);
+    /// ~~~
+    ///
+    /// # Errors
+    /// Returns an error if a temperature is outside the range of the permittivity model.
+    pub fn temperature_sweep<T>(
+        &self,
+        temperatures: impl IntoIterator<Item = f64>,
+        property: impl Fn(&Self) -> T,
+    ) -> Result<Vec<(f64, T)>> {
+        let mut medium = self.clone();
+        temperatures
+            .into_iter()
+            .map(|temperature| {
+                medium.set_temperature(temperature)?;
+                Ok((temperature, property(&medium)))
+            })
+            .collect()
+    }
+
+    /// Bjerrum length (angstrom) at each temperature (K), see [`Medium::temperature_sweep`].
+    ///
+    /// # Errors
+    /// Returns an error if a temperature is outside the range of the permittivity model.
+    pub fn bjerrum_length_sweep(
+        &self,
+        temperatures: impl IntoIterator<Item = f64>,
+    ) -> Result<Vec<(f64, f64)>> {
+        self.temperature_sweep(temperatures, Self::bjerrum_length)
+    }
+
+    /// Debye length (angstrom) at each temperature (K), see [`Medium::temperature_sweep`].
+    ///
+    /// # Errors
+    /// Returns an error if the medium contains no salt or if a temperature is outside the range
+    /// of the permittivity model.
+    pub fn debye_length_sweep(
+        &self,
+        temperatures: impl IntoIterator<Item = f64>,
+    ) -> Result<Vec<(f64, f64)>> {
+        self.temperature_sweep(temperatures, Self::debye_length)?
+            .into_iter()
+            .map(|(temperature, debye_length)| {
+                Ok((temperature, debye_length.ok_or(crate::Error::MissingSalt)?))
+            })
+            .collect()
+    }
+
+    /// Reduced inverse Debye length, κσ, for a length σ (angstrom) at each temperature (K).
+    ///
+    /// # Errors
+    /// Returns an error if the medium contains no salt or if a temperature is outside the range
+    /// of the permittivity model.
+    pub fn kappa_sigma_sweep(
+        &self,
+        temperatures: impl IntoIterator<Item = f64>,
+        sigma: f64,
+    ) -> Result<Vec<(f64, f64)>> {
+        Ok(self
+            .debye_length_sweep(temperatures)?
+            .into_iter()
+            .map(|(temperature, debye_length)| (temperature, sigma / debye_length))
+            .collect())
+    }
+}
+
+/// Builder for [`Medium`], created by [`Medium::builder`].
+///
+/// The temperature is required while the solvent defaults to water and the salt to none.
+#[derive(Clone, Debug, Default)]
+pub struct MediumBuilder {
+    temperature: Option<f64>,
+    permittivity: Option<Permittivity>,
+    salts: Vec<(Salt, f64)>,
+}
+
+impl MediumBuilder {
+    /// Set the temperature, UNIT: [ K ]
+    pub const fn temperature(mut self, temperature: f64) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Set the solvent permittivity model. Default is [`Permittivity::Water`].
+    pub fn solvent(mut self, permittivity: Permittivity) -> Self {
+        self.permittivity = Some(permittivity);
+        self
+    }
+
+    /// Add a salt with given molarity (mol/l). Repeat to form a mixture.
+    pub fn salt(mut self, salt: Salt, molarity: f64) -> Self {
+        self.salts.push((salt, molarity));
+        self
+    }
+
+    /// Build the medium.
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is missing or not positive, if it is outside the range
+    /// of the permittivity model, or if a salt has invalid molarity or stoichiometry.
+    pub fn build(self) -> Result<Medium> {
+        let temperature = self
+            .temperature
+            .filter(|temperature| temperature.is_finite() && *temperature > 0.0)
+            .ok_or(crate::Error::InvalidTemperature)?;
+        let permittivity = self.permittivity.unwrap_or(Permittivity::Water);
+        permittivity.permittivity(temperature)?;
+        let mut medium = Medium::new(temperature, permittivity, None);
+        for (salt, molarity) in self.salts {
+            salt.stoichiometry()?;
+            medium.add_salt(salt, molarity)?;
+        }
+        Ok(medium)
+    }
 }
 
 impl Display for Medium {
@@ -137,6 +410,9 @@ impl Display for Medium {
                 salt
             )?;
         };
+        for (salt, molarity) in &self.additional_salts {
+            write!(f, ", {:.2} M {}", molarity, salt)?;
+        }
         Ok(())
     }
 }
@@ -159,13 +435,17 @@ impl RelativePermittivity for Medium {
     fn permittivity(&self, temperature: f64) -> Result<f64> {
         self.permittivity.permittivity(temperature)
     }
+    fn d_permittivity_d_temperature(&self, temperature: f64) -> Option<f64> {
+        self.permittivity.d_permittivity_d_temperature(temperature)
+    }
 }
 
 impl IonicStrength for Medium {
     fn ionic_strength(&self) -> Option<f64> {
-        self.salt
-            .as_ref()
-            .and_then(|salt| salt.0.ionic_strength(salt.1).ok())
+        self.salt.as_ref()?;
+        self.salts()
+            .map(|(salt, molarity)| salt.ionic_strength(*molarity).ok())
+            .sum()
     }
 }
 
@@ -174,3 +454,131 @@ impl From<Medium> for ConstantPermittivity {
         ConstantPermittivity::new(medium.permittivity())
     }
 }
+
+#[test]
+fn test_medium_builder() {
+    use crate::Error;
+    let medium = Medium::builder()
+        .temperature(298.15)
+        .salt(Salt::SodiumChloride, 0.1)
+        .salt(Salt::CalciumChloride, 0.01)
+        .build()
+        .unwrap();
+    let mut expected = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+    expected.add_salt(Salt::CalciumChloride, 0.01).unwrap();
+    assert_eq!(medium, expected);
+    assert_eq!(
+        Medium::builder().temperature(300.0).build().unwrap(),
+        Medium::neat_water(300.0)
+    );
+
+    let error = |builder: MediumBuilder| builder.build().unwrap_err();
+    assert!(matches!(
+        error(Medium::builder()),
+        Error::InvalidTemperature
+    ));
+    assert!(matches!(
+        error(Medium::builder().temperature(-1.0)),
+        Error::InvalidTemperature
+    ));
+    assert!(matches!(
+        error(Medium::builder().temperature(500.0)),
+        Error::TemperatureOutOfRange
+    ));
+    let salty = Medium::builder().temperature(298.15);
+    assert!(matches!(
+        error(salty.clone().salt(Salt::SodiumChloride, f64::NAN)),
+        Error::InvalidMolarity
+    ));
+    assert!(matches!(
+        error(salty.salt(Salt::Custom(vec![1, 2]), 0.1)),
+        Error::Stoichiometry
+    ));
+}
+
+#[test]
+fn test_temperature_sweep() {
+    use approx::assert_relative_eq;
+    let temperatures = [280.0, 300.0, 320.0];
+    let medium = Medium::salt_water(298.15, Salt::CalciumChloride, 0.05);
+    let bjerrum = medium.bjerrum_length_sweep(temperatures).unwrap();
+    let debye = medium.debye_length_sweep(temperatures).unwrap();
+    let kappa_sigma = medium.kappa_sigma_sweep(temperatures, 4.0).unwrap();
+    for (i, temperature) in temperatures.into_iter().enumerate() {
+        let permittivity = permittivity::WATER.permittivity(temperature).unwrap();
+        let ionic_strength = medium.ionic_strength().unwrap();
+        assert_eq!(bjerrum[i].0, temperature);
+        assert_relative_eq!(bjerrum[i].1, bjerrum_length(temperature, permittivity));
+        assert_relative_eq!(
+            debye[i].1,
+            debye_length(temperature, permittivity, ionic_strength)
+        );
+        assert_relative_eq!(kappa_sigma[i].1, 4.0 / debye[i].1);
+    }
+    // Original medium is unchanged
+    assert_eq!(medium.temperature(), 298.15);
+
+    assert!(matches!(
+        Medium::neat_water(298.15).debye_length_sweep(temperatures),
+        Err(crate::Error::MissingSalt)
+    ));
+    assert!(matches!(
+        medium.bjerrum_length_sweep([300.0, 1000.0]),
+        Err(crate::Error::TemperatureOutOfRange)
+    ));
+}
+
+#[test]
+fn test_coupling_parameter() {
+    use approx::assert_relative_eq;
+    assert!(Medium::neat_water(298.15).coupling_parameter(1).is_none());
+    let medium = Medium::salt_water(298.15, Salt::CalciumChloride, 0.1);
+    // Three ions per formula unit
+    let density = 0.3 * AVOGADRO_CONSTANT * LITER_PER_ANGSTROM3;
+    assert_relative_eq!(
+        medium.wigner_seitz_radius().unwrap(),
+        wigner_seitz_radius(density)
+    );
+    assert_relative_eq!(
+        medium.coupling_parameter(2).unwrap(),
+        coupling_parameter(medium.bjerrum_length(), 2.0, density)
+    );
+}
+
+#[test]
+fn test_salt_mixture() {
+    use approx::assert_relative_eq;
+    let mut medium = Medium::neat_water(298.15);
+    assert!(medium.mean_valencies().is_none());
+    medium.add_salt(Salt::SodiumChloride, 0.1).unwrap();
+    let single = medium.clone();
+    medium.add_salt(Salt::CalciumChloride, 0.05).unwrap();
+    medium.add_salt(Salt::SodiumSulfate, 0.01).unwrap();
+    assert!(medium.add_salt(Salt::SodiumChloride, -1.0).is_err());
+    assert_eq!(medium.salt(), single.salt());
+    assert_eq!(medium.salts().count(), 3);
+    let expected = [(1, 0.12), (-1, 0.2), (2, 0.05), (-2, 0.01)];
+    assert_eq!(medium.ions().len(), expected.len());
+    for ((z, c), (z_ref, c_ref)) in medium.ions().into_iter().zip(expected) {
+        assert_eq!(z, z_ref);
+        assert_relative_eq!(c, c_ref);
+    }
+
+    // Ionic strength from the combined ions, ½Σcᵢzᵢ²
+    let ionic_strength = 0.5 * (0.12 + 0.2 + 4.0 * 0.05 + 4.0 * 0.01);
+    assert_relative_eq!(medium.ionic_strength().unwrap(), ionic_strength);
+    assert_relative_eq!(
+        medium.debye_length().unwrap(),
+        single.debye_length().unwrap() * (0.1 / ionic_strength).sqrt()
+    );
+    let (cation, anion) = medium.mean_valencies().unwrap();
+    assert_relative_eq!(cation, (0.12 + 2.0 * 0.05) / 0.17);
+    assert_relative_eq!(anion, (-0.2 - 2.0 * 0.01) / 0.21);
+    assert!(medium
+        .to_string()
+        .ends_with("0.05 M 🧂Salt = CaCl₂, 0.01 M 🧂Salt = Na₂SO₄"));
+
+    // Salt at zero concentration has no mean valencies
+    let zero = Medium::salt_water(298.15, Salt::SodiumChloride, 0.0);
+    assert!(zero.mean_valencies().is_none());
+}
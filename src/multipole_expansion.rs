@@ -0,0 +1,1011 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Multipole expansions of arbitrary order using solid harmonics.
+//!
+//! The functions in [`pairwise`](crate::pairwise) are restricted to ions, dipoles and quadrupoles.
+//! Here, a charge distribution is instead represented by its spherical multipole moments
+//! up to an arbitrary order, $\ell$, about an expansion center,
+//!
+//! $$ M_\ell^m = \sum_i q_i \overline{R_\ell^m(\boldsymbol{r}_i)} $$
+//!
+//! where the regular and irregular solid harmonics are
+//!
+//! $$ R_\ell^m(\boldsymbol{r}) = \frac{r^\ell P_\ell^m(\cos\theta) e^{im\phi}}{(\ell+m)!}, \quad
+//!    I_\ell^m(\boldsymbol{r}) = \frac{(\ell-m)! P_\ell^m(\cos\theta) e^{im\phi}}{r^{\ell+1}} $$
+//!
+//! and $P_\ell^m$ are associated Legendre polynomials including the Condon-Shortley phase.
+//! With this normalization, $1/|\boldsymbol{r}-\boldsymbol{a}| = \sum_{\ell m} \overline{R_\ell^m(\boldsymbol{a})} I_\ell^m(\boldsymbol{r})$
+//! for $a < r$.
+//! Since charges are real, $M_\ell^{-m} = (-1)^m \overline{M_\ell^m}$ and only moments with
+//! $m \ge 0$ are stored; the real and imaginary parts are the cosine and sine components of
+//! the real spherical harmonic representation.
+//!
+//! Potentials and energies are for the plain, unscreened Coulomb operator, _i.e._ the
+//! [`Plain`](crate::pairwise::Plain) scheme without cutoff, and are exact in the limit of
+//! infinite order if the charge distributions do not overlap.
+//!
+//! Expansions can be shifted to new centers and converted to a [`LocalExpansion`] of the
+//! potential near a distant point.
+//! These translation operators are the building blocks of hierarchical solvers such as the
+//! [`fmm`](crate::fmm) module, and [`RotationTranslator`] provides faster versions for high orders.
+//!
+//! # Examples
+//! ~~~
+//! # use approx::assert_relative_eq;
+//! use coulomb::multipole_expansion::MultipoleExpansion;
+//! let positions = [[0.0, 0.0, 0.5].into(), [0.0, 0.0, -0.5].into()];
+//! let a = MultipoleExpansion::from_charges(8, [0.0, 0.0, 0.0], &positions, &[1.0, -1.0]);
+//! let b = MultipoleExpansion::from_charges(8, [0.0, 0.0, 5.0], &[[0.0, 0.0, 5.0].into()], &[1.0]);
+//! let exact = 1.0 / 4.5 - 1.0 / 5.5;
+//! assert_relative_eq!(a.energy(&b), exact, epsilon = 1e-8);
+//! ~~~
+
+use crate::{NalgebraVector3, Vector3};
+use core::f64::consts::{FRAC_PI_2, PI};
+use nalgebra::DMatrix;
+use num_complex::Complex64;
+
+/// Index of $(\ell, m)$ with $m \ge 0$ in a triangular array.
+#[inline]
+const fn index(l: usize, m: usize) -> usize {
+    l * (l + 1) / 2 + m
+}
+
+/// Number of elements in a triangular array holding all $\ell \le$ `order` and $0 \le m \le \ell$.
+#[inline]
+const fn size(order: usize) -> usize {
+    (order + 1) * (order + 2) / 2
+}
+
+/// Look up $(\ell, m)$ in a triangular array, using $X_\ell^{-m} = (-1)^m \overline{X_\ell^m}$ for negative $m$.
+#[inline]
+fn get(values: &[Complex64], l: usize, m: isize) -> Complex64 {
+    let value = values[index(l, m.unsigned_abs())];
+    match (m < 0, m % 2 == 0) {
+        (false, _) => value,
+        (true, true) => value.conj(),
+        (true, false) => -value.conj(),
+    }
+}
+
+/// Regular solid harmonics, $R_\ell^m(\boldsymbol{r})$, for $\ell \le$ `order` and $m \ge 0$.
+///
+/// The values are stored in a triangular array with $(\ell, m)$ at index $\ell(\ell+1)/2 + m$.
+pub fn regular_solid_harmonics(order: usize, r: impl Into<Vector3>) -> Vec<Complex64> {
+    let r: NalgebraVector3 = r.into().into();
+    let (z, r2) = (r.z, r.norm_squared());
+    let xy = Complex64::new(r.x, r.y);
+    let mut values = vec![Complex64::default(); size(order)];
+    values[0] = Complex64::new(1.0, 0.0);
+    for m in 0..=order {
+        if m > 0 {
+            values[index(m, m)] = -xy * values[index(m - 1, m - 1)] / (2 * m) as f64;
+        }
+        if m < order {
+            values[index(m + 1, m)] = values[index(m, m)] * z;
+        }
+        for l in (m + 2)..=order {
+            values[index(l, m)] = (values[index(l - 1, m)] * ((2 * l - 1) as f64 * z)
+                - values[index(l - 2, m)] * r2)
+                / ((l + m) * (l - m)) as f64;
+        }
+    }
+    values
+}
+
+/// Irregular solid harmonics, $I_\ell^m(\boldsymbol{r})$, for $\ell \le$ `order` and $m \ge 0$.
+///
+/// The values are stored in a triangular array with $(\ell, m)$ at index $\ell(\ell+1)/2 + m$.
+pub fn irregular_solid_harmonics(order: usize, r: impl Into<Vector3>) -> Vec<Complex64> {
+    let r: NalgebraVector3 = r.into().into();
+    let (z, r2) = (r.z, r.norm_squared());
+    let xy = Complex64::new(r.x, r.y);
+    let mut values = vec![Complex64::default(); size(order)];
+    values[0] = Complex64::new(1.0 / r2.sqrt(), 0.0);
+    for m in 0..=order {
+        if m > 0 {
+            values[index(m, m)] = -xy * values[index(m - 1, m - 1)] * ((2 * m - 1) as f64 / r2);
+        }
+        if m < order {
+            values[index(m + 1, m)] = values[index(m, m)] * ((2 * m + 1) as f64 * z / r2);
+        }
+        for l in (m + 2)..=order {
+            values[index(l, m)] = (values[index(l - 1, m)] * ((2 * l - 1) as f64 * z)
+                - values[index(l - 2, m)] * ((l - 1) * (l - 1) - m * m) as f64)
+                / r2;
+        }
+    }
+    values
+}
+
+/// Shift multipole moments by `d`, the old minus the new expansion center (M2M).
+///
+/// $$ M_\ell^{\prime m} = \sum_{jk} \overline{R_j^k(\boldsymbol{d})} M_{\ell-j}^{m-k} $$
+pub(crate) fn translate_multipole(
+    moments: &[Complex64],
+    order: usize,
+    d: impl Into<Vector3>,
+) -> Vec<Complex64> {
+    let regular = regular_solid_harmonics(order, d);
+    let mut shifted = vec![Complex64::default(); size(order)];
+    for l in 0..=order {
+        for m in 0..=l as isize {
+            let mut sum = Complex64::default();
+            for j in 0..=l {
+                let (j_signed, k_max) = (j as isize, (l - j) as isize);
+                for k in (-j_signed).max(m - k_max)..=j_signed.min(m + k_max) {
+                    sum += get(&regular, j, k).conj() * get(moments, l - j, m - k);
+                }
+            }
+            shifted[index(l, m as usize)] = sum;
+        }
+    }
+    shifted
+}
+
+/// Local expansion coefficients from multipole moments (M2L).
+///
+/// The separation is `t`, the local minus the multipole expansion center, and the potential
+/// near the local center is $\Phi(\boldsymbol{c} + \boldsymbol{y}) = \sum_{\ell m} L_\ell^m \overline{R_\ell^m(\boldsymbol{y})}$
+/// with
+///
+/// $$ L_\ell^m = (-1)^\ell \sum_{jk} M_j^k I_{j+\ell}^{k+m}(\boldsymbol{t}) $$
+pub(crate) fn multipole_to_local(
+    moments: &[Complex64],
+    order: usize,
+    t: impl Into<Vector3>,
+) -> Vec<Complex64> {
+    let irregular = irregular_solid_harmonics(order, t);
+    let mut local = vec![Complex64::default(); size(order)];
+    for l in 0..=order {
+        let sign = if l % 2 == 0 { 1.0 } else { -1.0 };
+        for m in 0..=l as isize {
+            let mut sum = Complex64::default();
+            for j in 0..=(order - l) {
+                let j_signed = j as isize;
+                let k_max = (j + l) as isize;
+                for k in (-j_signed).max(-k_max - m)..=j_signed.min(k_max - m) {
+                    sum += get(moments, j, k) * get(&irregular, j + l, k + m);
+                }
+            }
+            local[index(l, m as usize)] = sum * sign;
+        }
+    }
+    local
+}
+
+/// Shift local expansion coefficients by `e`, the new minus the old expansion center (L2L).
+///
+/// $$ L_n^{\prime p} = \sum_{jk} L_{n+j}^{p+k} \overline{R_j^k(\boldsymbol{e})} $$
+pub(crate) fn translate_local(
+    local: &[Complex64],
+    order: usize,
+    e: impl Into<Vector3>,
+) -> Vec<Complex64> {
+    translate_local_to_order(local, order, order, e)
+}
+
+/// Shift local expansion coefficients, keeping only the coefficients up to `target_order`.
+fn translate_local_to_order(
+    local: &[Complex64],
+    order: usize,
+    target_order: usize,
+    e: impl Into<Vector3>,
+) -> Vec<Complex64> {
+    let regular = regular_solid_harmonics(order, e);
+    let mut shifted = vec![Complex64::default(); size(target_order)];
+    for n in 0..=target_order.min(order) {
+        for p in 0..=n as isize {
+            let mut sum = Complex64::default();
+            for j in 0..=(order - n) {
+                let j_signed = j as isize;
+                let k_max = (n + j) as isize;
+                for k in (-j_signed).max(-k_max - p)..=j_signed.min(k_max - p) {
+                    sum += get(local, n + j, p + k) * get(&regular, j, k).conj();
+                }
+            }
+            shifted[index(n, p as usize)] = sum;
+        }
+    }
+    shifted
+}
+
+/// Potential and electric field at `y` relative to the center of a local expansion (L2P).
+pub(crate) fn evaluate_local(
+    local: &[Complex64],
+    order: usize,
+    y: impl Into<Vector3>,
+) -> (f64, NalgebraVector3) {
+    let shifted = translate_local_to_order(local, order, 1, y);
+    let potential = shifted[0].re;
+    if order == 0 {
+        return (potential, NalgebraVector3::zeros());
+    }
+    let (l10, l11) = (shifted[index(1, 0)], shifted[index(1, 1)]);
+    (potential, NalgebraVector3::new(l11.re, l11.im, -l10.re))
+}
+
+/// Ratio $\sqrt{(\ell+m)!(\ell-m)!} / \ell!$ between solid harmonics and Racah normalized spherical harmonics.
+fn racah_scale(l: usize, m: usize) -> f64 {
+    (1..=m)
+        .map(|k| (l + k) as f64 / (l - m + k) as f64)
+        .product::<f64>()
+        .sqrt()
+}
+
+/// Rotation matrix, $\Delta^\ell$, of Racah normalized spherical harmonics for a rotation by π/2 about the y axis.
+///
+/// Rows and columns are indexed by $m + \ell$.
+fn wigner_delta(l: usize) -> DMatrix<f64> {
+    let size = 2 * l + 1;
+    let mut generator = DMatrix::<f64>::zeros(size, size);
+    for i in 0..size - 1 {
+        let m = i as f64 - l as f64;
+        let value = 0.5 * ((l as f64 - m) * (l as f64 + m + 1.0)).sqrt();
+        generator[(i + 1, i)] = value;
+        generator[(i, i + 1)] = -value;
+    }
+    (generator * FRAC_PI_2).exp()
+}
+
+/// Translations of multipole and local expansions by rotation to the z axis.
+///
+/// Translating an expansion of order $p$ along an arbitrary direction involves
+/// $\mathcal{O}(p^4)$ operations.
+/// Here, the coefficients are instead rotated so that the translation is along the z axis,
+/// where it couples only equal $m$, translated, and rotated back, each step involving
+/// $\mathcal{O}(p^3)$ operations.
+/// The rotation matrices are computed once when the translator is created, and this is
+/// faster than the direct translations of [`MultipoleExpansion`] and [`LocalExpansion`]
+/// for orders beyond roughly twelve.
+/// See White and Head-Gordon, <https://doi.org/10.1063/1.471565>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::multipole_expansion::{MultipoleExpansion, RotationTranslator};
+/// let positions = [[0.1, 0.2, 0.3].into(), [-0.3, 0.1, 0.0].into()];
+/// let expansion = MultipoleExpansion::from_charges(16, [0.0; 3], &positions, &[1.0, -0.5]);
+/// let translator = RotationTranslator::new(16);
+/// let local = translator.multipole_to_local(&expansion, [5.0, -4.0, 3.0]);
+/// assert_relative_eq!(
+///     local.potential([5.1, -4.0, 3.2]),
+///     expansion.potential([5.1, -4.0, 3.2]),
+///     epsilon = 1e-12
+/// );
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+pub struct RotationTranslator {
+    /// Maximum order, ℓ
+    order: usize,
+    /// Rotation matrices by π/2 about the y axis for each ℓ
+    deltas: Vec<DMatrix<f64>>,
+    /// Ratios between solid and Racah normalized harmonics in a triangular array
+    scales: Vec<f64>,
+}
+
+impl RotationTranslator {
+    /// Create a translator for expansions of given `order`.
+    pub fn new(order: usize) -> Self {
+        Self {
+            order,
+            deltas: (0..=order).map(wigner_delta).collect(),
+            scales: (0..=order)
+                .flat_map(|l| (0..=l).map(move |m| racah_scale(l, m)))
+                .collect(),
+        }
+    }
+
+    /// Maximum order, ℓ, of the translated expansions.
+    pub const fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Rotation, $P(a) \Delta P(b) \Delta^T P(c)$, of coefficients where $P(\alpha)$ multiplies by $e^{im\alpha}$.
+    ///
+    /// Multipole moments are scaled to Racah normalization before the rotation, and local
+    /// expansion coefficients (`local = true`) after.
+    /// All steps preserve $X_\ell^{-m} = (-1)^m \overline{X_\ell^m}$, so only $m \ge 0$ is stored,
+    /// and rows of $\Delta$ are columns of $\Delta^T = \Delta(-\pi/2)$, _i.e._
+    /// $\Delta_{m'm} = (-1)^{m'-m} \Delta_{mm'}$.
+    fn rotate(&self, values: &[Complex64], [a, b, c]: [f64; 3], local: bool) -> Vec<Complex64> {
+        let phases = |angle: f64| -> Vec<Complex64> {
+            (0..=self.order)
+                .map(|m| Complex64::from_polar(1.0, m as f64 * angle))
+                .collect()
+        };
+        let (phases_a, phases_b, phases_c) = (phases(a), phases(b), phases(c));
+        let sign = |m: usize| if m % 2 == 0 { 1.0 } else { -1.0 };
+        // Product of a column of Δ, indexed by m + ℓ, and coefficients for m ≥ 0
+        let dot = |column: &[f64], values: &[Complex64]| -> Complex64 {
+            let l = values.len() - 1;
+            (1..=l).fold(values[0] * column[l], |sum, m| {
+                sum + values[m] * column[l + m] + values[m].conj() * (sign(m) * column[l - m])
+            })
+        };
+        let mut rotated = vec![Complex64::default(); size(self.order)];
+        let (mut block, mut product) = (Vec::new(), Vec::new());
+        for (l, delta) in self.deltas.iter().enumerate() {
+            let scale = |m: usize| {
+                let scale = self.scales[index(l, m)];
+                if local {
+                    1.0 / scale
+                } else {
+                    scale
+                }
+            };
+            block.clear();
+            block.extend((0..=l).map(|m| values[index(l, m)] * scale(m) * phases_c[m]));
+            product.clear();
+            product.extend(
+                (0..=l)
+                    .map(|m| dot(delta.column(l + m).as_slice(), &block) * phases_b[m] * sign(m)),
+            );
+            for m in 0..=l {
+                let sum = dot(delta.column(l + m).as_slice(), &product) * sign(m);
+                rotated[index(l, m)] = sum * phases_a[m] / scale(m);
+            }
+        }
+        rotated
+    }
+
+    /// Azimuthal and polar rotation angles, φ and γ, taking `t` onto the z axis, and the signed
+    /// distance along z.
+    ///
+    /// The rotation is $R_y(\gamma) R_z(-\phi)$ with $|\gamma| \le \pi/2$ so that `t`
+    /// ends up along either the positive or the negative z axis.
+    fn alignment(t: NalgebraVector3) -> (f64, f64, f64) {
+        let distance = t.norm();
+        let (phi, theta) = (t.y.atan2(t.x), t.x.hypot(t.y).atan2(t.z));
+        if theta <= FRAC_PI_2 {
+            (phi, -theta, distance)
+        } else {
+            (phi, PI - theta, -distance)
+        }
+    }
+
+    /// Shift multipole moments by `d`, the old minus the new expansion center (M2M).
+    pub(crate) fn shift_multipole(
+        &self,
+        moments: &[Complex64],
+        d: NalgebraVector3,
+    ) -> Vec<Complex64> {
+        let (phi, gamma, z) = Self::alignment(d);
+        let rotated = self.rotate(moments, [-FRAC_PI_2, gamma, FRAC_PI_2 + phi], false);
+        let regular = regular_solid_harmonics(self.order, [0.0, 0.0, z]);
+        let mut shifted = vec![Complex64::default(); size(self.order)];
+        for l in 0..=self.order {
+            for m in 0..=l {
+                shifted[index(l, m)] = (0..=(l - m))
+                    .map(|j| rotated[index(l - j, m)] * regular[index(j, 0)].re)
+                    .sum();
+            }
+        }
+        self.rotate(&shifted, [-FRAC_PI_2 - phi, -gamma, FRAC_PI_2], false)
+    }
+
+    /// Local expansion coefficients from multipole moments separated by `t`, the local minus
+    /// the multipole expansion center (M2L).
+    pub(crate) fn shift_to_local(
+        &self,
+        moments: &[Complex64],
+        t: NalgebraVector3,
+    ) -> Vec<Complex64> {
+        let (phi, gamma, z) = Self::alignment(t);
+        let rotated = self.rotate(moments, [-FRAC_PI_2, gamma, FRAC_PI_2 + phi], false);
+        let irregular = irregular_solid_harmonics(self.order, [0.0, 0.0, z]);
+        let mut local = vec![Complex64::default(); size(self.order)];
+        for l in 0..=self.order {
+            let sign = if l % 2 == 0 { 1.0 } else { -1.0 };
+            for m in 0..=l.min(self.order - l) {
+                let sum: Complex64 = (m..=(self.order - l))
+                    .map(|j| get(&rotated, j, -(m as isize)) * irregular[index(j + l, 0)].re)
+                    .sum();
+                local[index(l, m)] = sum * sign;
+            }
+        }
+        self.rotate(&local, [FRAC_PI_2 + phi, gamma, -FRAC_PI_2], true)
+    }
+
+    /// Shift local expansion coefficients by `e`, the new minus the old expansion center (L2L).
+    pub(crate) fn shift_local(&self, local: &[Complex64], e: NalgebraVector3) -> Vec<Complex64> {
+        let (phi, gamma, z) = Self::alignment(e);
+        let rotated = self.rotate(local, [FRAC_PI_2, -gamma, -FRAC_PI_2 - phi], true);
+        let regular = regular_solid_harmonics(self.order, [0.0, 0.0, z]);
+        let mut shifted = vec![Complex64::default(); size(self.order)];
+        for n in 0..=self.order {
+            for p in 0..=n {
+                shifted[index(n, p)] = (0..=(self.order - n))
+                    .map(|j| rotated[index(n + j, p)] * regular[index(j, 0)].re)
+                    .sum();
+            }
+        }
+        self.rotate(&shifted, [FRAC_PI_2 + phi, gamma, -FRAC_PI_2], true)
+    }
+
+    /// Multipole expansion shifted to a new `center` (M2M).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the order of the expansion differs from the order of the translator.
+    pub fn translate_multipole(
+        &self,
+        expansion: &MultipoleExpansion,
+        center: impl Into<Vector3>,
+    ) -> MultipoleExpansion {
+        assert_eq!(expansion.order, self.order);
+        let center: NalgebraVector3 = center.into().into();
+        MultipoleExpansion {
+            order: self.order,
+            center,
+            moments: self.shift_multipole(&expansion.moments, expansion.center - center),
+        }
+    }
+
+    /// Local expansion about `center` of the potential from a multipole expansion (M2L).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the order of the expansion differs from the order of the translator.
+    pub fn multipole_to_local(
+        &self,
+        expansion: &MultipoleExpansion,
+        center: impl Into<Vector3>,
+    ) -> LocalExpansion {
+        assert_eq!(expansion.order, self.order);
+        let center: NalgebraVector3 = center.into().into();
+        LocalExpansion {
+            order: self.order,
+            center,
+            coefficients: self.shift_to_local(&expansion.moments, center - expansion.center),
+        }
+    }
+
+    /// Local expansion shifted to a new `center` (L2L).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the order of the expansion differs from the order of the translator.
+    pub fn translate_local(
+        &self,
+        local: &LocalExpansion,
+        center: impl Into<Vector3>,
+    ) -> LocalExpansion {
+        assert_eq!(local.order, self.order);
+        let center: NalgebraVector3 = center.into().into();
+        LocalExpansion {
+            order: self.order,
+            center,
+            coefficients: self.shift_local(&local.coefficients, center - local.center),
+        }
+    }
+}
+
+/// Spherical multipole expansion of a charge distribution about a center.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultipoleExpansion {
+    /// Maximum order, ℓ
+    order: usize,
+    /// Expansion center
+    center: NalgebraVector3,
+    /// Multipole moments for m ≥ 0
+    moments: Vec<Complex64>,
+}
+
+impl MultipoleExpansion {
+    /// Create an empty expansion of given `order` about `center`.
+    pub fn new(order: usize, center: impl Into<Vector3>) -> Self {
+        Self {
+            order,
+            center: center.into().into(),
+            moments: vec![Complex64::default(); size(order)],
+        }
+    }
+
+    /// Create an expansion of given `order` about `center` from point charges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `positions` and `charges` differ in length.
+    pub fn from_charges(
+        order: usize,
+        center: impl Into<Vector3>,
+        positions: &[Vector3],
+        charges: &[f64],
+    ) -> Self {
+        assert_eq!(positions.len(), charges.len());
+        let mut expansion = Self::new(order, center);
+        for (position, charge) in positions.iter().zip(charges) {
+            expansion.add_charge(*position, *charge);
+        }
+        expansion
+    }
+
+    /// Add a point charge to the expansion.
+    pub fn add_charge(&mut self, position: impl Into<Vector3>, charge: f64) {
+        let position: NalgebraVector3 = position.into().into();
+        let regular = regular_solid_harmonics(self.order, position - self.center);
+        for (moment, r) in self.moments.iter_mut().zip(regular) {
+            *moment += r.conj() * charge;
+        }
+    }
+
+    /// Expansion shifted to a new `center` (M2M).
+    ///
+    /// The shifted moments are identical to the moments of the charges about the new center.
+    pub fn translate(&self, center: impl Into<Vector3>) -> Self {
+        let center: NalgebraVector3 = center.into().into();
+        Self {
+            order: self.order,
+            center,
+            moments: translate_multipole(&self.moments, self.order, self.center - center),
+        }
+    }
+
+    /// Add the moments of another expansion of the same order, shifted to the center of this expansion (M2M).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the orders of the two expansions differ.
+    pub fn add_expansion(&mut self, other: &Self) {
+        assert_eq!(self.order, other.order);
+        let shifted = translate_multipole(&other.moments, self.order, other.center - self.center);
+        for (moment, value) in self.moments.iter_mut().zip(shifted) {
+            *moment += value;
+        }
+    }
+
+    /// Local expansion about `center` of the potential from this expansion (M2L).
+    ///
+    /// The local expansion has the same order and converges for positions closer to `center`
+    /// than the distance from `center` to the nearest charge.
+    pub fn to_local(&self, center: impl Into<Vector3>) -> LocalExpansion {
+        let mut local = LocalExpansion::new(self.order, center);
+        local.add_multipole(self);
+        local
+    }
+
+    /// Multipole moments for m ≥ 0 in a triangular array
+    pub(crate) fn moments(&self) -> &[Complex64] {
+        &self.moments
+    }
+
+    /// Maximum order, ℓ, of the expansion.
+    pub const fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Expansion center.
+    pub fn center(&self) -> Vector3 {
+        self.center.into()
+    }
+
+    /// Multipole moment, $M_\ell^m$, for $-\ell \le m \le \ell$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `l` exceeds the order of the expansion or if `|m| > l`.
+    pub fn moment(&self, l: usize, m: isize) -> Complex64 {
+        assert!(l <= self.order && m.unsigned_abs() <= l);
+        get(&self.moments, l, m)
+    }
+
+    /// Monopole moment, _i.e._ the total charge.
+    pub fn charge(&self) -> f64 {
+        self.moments[0].re
+    }
+
+    /// Cartesian dipole moment about the expansion center.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the order of the expansion is zero.
+    pub fn dipole_moment(&self) -> Vector3 {
+        assert!(self.order >= 1);
+        let m11 = self.moments[index(1, 1)];
+        [-2.0 * m11.re, 2.0 * m11.im, self.moments[index(1, 0)].re].into()
+    }
+
+    /// Electric potential at `position` outside the charge distribution.
+    ///
+    /// UNIT: [ ( input charge ) / ( input length ) ]
+    pub fn potential(&self, position: impl Into<Vector3>) -> f64 {
+        let position: NalgebraVector3 = position.into().into();
+        let irregular = irregular_solid_harmonics(self.order, position - self.center);
+        (0..=self.order)
+            .map(|l| {
+                let m0 = (self.moments[index(l, 0)] * irregular[index(l, 0)]).re;
+                let m_pos: f64 = (1..=l)
+                    .map(|m| (self.moments[index(l, m)] * irregular[index(l, m)]).re)
+                    .sum();
+                m0 + 2.0 * m_pos
+            })
+            .sum()
+    }
+
+    /// Interaction energy with another, non-overlapping expansion.
+    ///
+    /// The energy is evaluated from the moments of both expansions and the irregular
+    /// solid harmonics of the separation between the two centers.
+    ///
+    /// UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn energy(&self, other: &Self) -> f64 {
+        let order = self.order + other.order;
+        let irregular = irregular_solid_harmonics(order, other.center - self.center);
+        let mut energy = Complex64::default();
+        for l in 0..=order {
+            for m in -(l as isize)..=(l as isize) {
+                let mut sum = Complex64::default();
+                for j in l.saturating_sub(other.order)..=l.min(self.order) {
+                    let sign = if (l - j) % 2 == 0 { 1.0 } else { -1.0 };
+                    let (j_signed, k_max) = (j as isize, (l - j) as isize);
+                    for k in (-j_signed).max(m - k_max)..=j_signed.min(m + k_max) {
+                        sum += get(&self.moments, j, k) * get(&other.moments, l - j, m - k) * sign;
+                    }
+                }
+                energy += get(&irregular, l, m) * sum;
+            }
+        }
+        energy.re
+    }
+}
+
+/// Local expansion about a center of the potential from distant charges.
+///
+/// The potential near the center, $\boldsymbol{c}$, is
+/// $\Phi(\boldsymbol{c} + \boldsymbol{y}) = \sum_{\ell m} L_\ell^m \overline{R_\ell^m(\boldsymbol{y})}$
+/// which converges for $y$ smaller than the distance to the nearest charge.
+/// Local expansions are obtained from multipole expansions using
+/// [`MultipoleExpansion::to_local`] or [`LocalExpansion::add_multipole`].
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::multipole_expansion::{LocalExpansion, MultipoleExpansion};
+/// let source = MultipoleExpansion::from_charges(10, [0.0; 3], &[[0.2, 0.0, 0.1].into()], &[2.0]);
+/// let mut local = LocalExpansion::new(10, [6.0, 0.0, 0.0]);
+/// local.add_multipole(&source);
+/// let local = local.translate([6.0, 0.5, 0.0]);
+/// let exact = 2.0 / (5.8_f64.powi(2) + 0.6_f64.powi(2) + 0.1_f64.powi(2)).sqrt();
+/// assert_relative_eq!(local.potential([6.0, 0.6, 0.2]), exact, epsilon = 1e-10);
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalExpansion {
+    /// Maximum order, ℓ
+    order: usize,
+    /// Expansion center
+    center: NalgebraVector3,
+    /// Local expansion coefficients for m ≥ 0
+    coefficients: Vec<Complex64>,
+}
+
+impl LocalExpansion {
+    /// Create an empty expansion of given `order` about `center`.
+    pub fn new(order: usize, center: impl Into<Vector3>) -> Self {
+        Self {
+            order,
+            center: center.into().into(),
+            coefficients: vec![Complex64::default(); size(order)],
+        }
+    }
+
+    /// Maximum order, ℓ, of the expansion.
+    pub const fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Expansion center.
+    pub fn center(&self) -> Vector3 {
+        self.center.into()
+    }
+
+    /// Local expansion coefficient, $L_\ell^m$, for $-\ell \le m \le \ell$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `l` exceeds the order of the expansion or if `|m| > l`.
+    pub fn coefficient(&self, l: usize, m: isize) -> Complex64 {
+        assert!(l <= self.order && m.unsigned_abs() <= l);
+        get(&self.coefficients, l, m)
+    }
+
+    /// Add the potential from a distant multipole expansion of the same order (M2L).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the orders of the two expansions differ.
+    pub fn add_multipole(&mut self, expansion: &MultipoleExpansion) {
+        assert_eq!(self.order, expansion.order);
+        let local = multipole_to_local(
+            &expansion.moments,
+            self.order,
+            self.center - expansion.center,
+        );
+        for (coefficient, value) in self.coefficients.iter_mut().zip(local) {
+            *coefficient += value;
+        }
+    }
+
+    /// Expansion shifted to a new `center` (L2L).
+    ///
+    /// Coefficients of the shifted expansion are exact for the truncated expansion, so that
+    /// no error is added by the shift.
+    pub fn translate(&self, center: impl Into<Vector3>) -> Self {
+        let center: NalgebraVector3 = center.into().into();
+        Self {
+            order: self.order,
+            center,
+            coefficients: translate_local(&self.coefficients, self.order, center - self.center),
+        }
+    }
+
+    /// Electric potential at `position` near the expansion center.
+    ///
+    /// UNIT: [ ( input charge ) / ( input length ) ]
+    pub fn potential(&self, position: impl Into<Vector3>) -> f64 {
+        let position: NalgebraVector3 = position.into().into();
+        evaluate_local(&self.coefficients, self.order, position - self.center).0
+    }
+
+    /// Electric field at `position` near the expansion center.
+    ///
+    /// UNIT: [ ( input charge ) / ( input length )^2 ]
+    pub fn field(&self, position: impl Into<Vector3>) -> Vector3 {
+        let position: NalgebraVector3 = position.into().into();
+        evaluate_local(&self.coefficients, self.order, position - self.center)
+            .1
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    /// Test cluster of point charges about `center`
+    fn cluster(center: [f64; 3], seed: f64) -> (Vec<Vector3>, Vec<f64>) {
+        let positions = (0..5)
+            .map(|i| {
+                let x = (seed + i as f64).sin();
+                let y = (2.0 * seed + 3.0 * i as f64).cos();
+                let z = (0.5 * seed - i as f64).sin();
+                [center[0] + x, center[1] + y, center[2] + z].into()
+            })
+            .collect();
+        let charges = (0..5).map(|i| (seed * i as f64).cos()).collect();
+        (positions, charges)
+    }
+
+    #[test]
+    fn test_solid_harmonics() {
+        let (a, b) = ([0.3, -0.2, 0.5], [-0.1, 0.4, 0.2]);
+        let sum = [a[0] + b[0], a[1] + b[1], a[2] + b[2]];
+        let order = 6;
+        let (ra, rb, rsum) = (
+            regular_solid_harmonics(order, a),
+            regular_solid_harmonics(order, b),
+            regular_solid_harmonics(order, sum),
+        );
+        // Addition theorem for regular solid harmonics
+        for l in 0..=order {
+            for m in 0..=l as isize {
+                let mut value = Complex64::default();
+                for j in 0..=l {
+                    for k in -(j as isize)..=(j as isize) {
+                        if (m - k).unsigned_abs() <= l - j {
+                            value += get(&ra, j, k) * get(&rb, l - j, m - k);
+                        }
+                    }
+                }
+                let expected = get(&rsum, l, m);
+                assert_relative_eq!(value.re, expected.re, epsilon = 1e-14);
+                assert_relative_eq!(value.im, expected.im, epsilon = 1e-14);
+            }
+        }
+        // Known low order values
+        let r = [0.3, -0.2, 0.5];
+        let regular = regular_solid_harmonics(2, r);
+        assert_relative_eq!(regular[index(1, 0)].re, 0.5);
+        assert_relative_eq!(regular[index(1, 1)].re, -0.15);
+        assert_relative_eq!(regular[index(1, 1)].im, 0.1);
+        assert_relative_eq!(regular[index(2, 0)].re, (3.0 * 0.25 - 0.38) / 4.0);
+        let irregular = irregular_solid_harmonics(2, r);
+        let r2: f64 = 0.38;
+        assert_relative_eq!(irregular[0].re, 1.0 / r2.sqrt());
+        assert_relative_eq!(irregular[index(1, 0)].re, 0.5 / r2.powf(1.5));
+        assert_relative_eq!(
+            irregular[index(2, 0)].re,
+            (0.75 - r2) / r2.powf(2.5),
+            epsilon = 1e-14
+        );
+    }
+
+    #[test]
+    fn test_potential() {
+        let (positions, charges) = cluster([0.0, 0.0, 0.0], 0.7);
+        let expansion = MultipoleExpansion::from_charges(12, [0.0, 0.0, 0.0], &positions, &charges);
+        let target = NalgebraVector3::new(4.0, -3.0, 6.0);
+        let exact: f64 = positions
+            .iter()
+            .zip(&charges)
+            .map(|(p, q)| q / (target - NalgebraVector3::from(*p)).norm())
+            .sum();
+        assert_relative_eq!(expansion.potential(target), exact, epsilon = 1e-9);
+
+        // Low order moments are the charge and Cartesian dipole moment
+        let dipole: NalgebraVector3 = positions
+            .iter()
+            .zip(&charges)
+            .map(|(p, q)| NalgebraVector3::from(*p) * *q)
+            .sum();
+        assert_relative_eq!(expansion.charge(), charges.iter().sum::<f64>());
+        let mu: NalgebraVector3 = expansion.dipole_moment().into();
+        assert_relative_eq!(mu, dipole, epsilon = 1e-14);
+        assert_relative_eq!(expansion.moment(1, -1).re, -expansion.moment(1, 1).re);
+    }
+
+    #[test]
+    fn test_translations() {
+        let (positions, charges) = cluster([0.0, 0.0, 0.0], 1.3);
+        let order = 14;
+        let center = NalgebraVector3::new(0.1, 0.0, 0.05);
+        let expansion = MultipoleExpansion::from_charges(order, center, &positions, &charges);
+        let exact = |x: NalgebraVector3| -> f64 {
+            positions
+                .iter()
+                .zip(&charges)
+                .map(|(p, q)| q / (x - NalgebraVector3::from(*p)).norm())
+                .sum()
+        };
+
+        // Shifted moments equal moments about the new center
+        let new_center = NalgebraVector3::new(-0.2, 0.3, 0.1);
+        let shifted = translate_multipole(expansion.moments(), order, center - new_center);
+        let direct = MultipoleExpansion::from_charges(order, new_center, &positions, &charges);
+        for (a, b) in shifted.iter().zip(direct.moments()) {
+            assert_relative_eq!(a.re, b.re, epsilon = 1e-12);
+            assert_relative_eq!(a.im, b.im, epsilon = 1e-12);
+        }
+
+        // Local expansion reproduces the potential and field near its center
+        let local_center = NalgebraVector3::new(8.0, -6.0, 5.0);
+        let local = multipole_to_local(expansion.moments(), order, local_center - center);
+        let local = translate_local(&local, order, NalgebraVector3::new(0.2, 0.1, -0.1));
+        let local_center = local_center + NalgebraVector3::new(0.2, 0.1, -0.1);
+        let x = local_center + NalgebraVector3::new(0.3, -0.2, 0.4);
+        let (potential, field) = evaluate_local(&local, order, x - local_center);
+        assert_relative_eq!(potential, exact(x), epsilon = 1e-10);
+        let h = 1e-5;
+        for i in 0..3 {
+            let dx = NalgebraVector3::ith(i, h);
+            let gradient = (exact(x + dx) - exact(x - dx)) / (2.0 * h);
+            assert_relative_eq!(field[i], -gradient, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_rotation_translator() {
+        let (positions, charges) = cluster([0.0, 0.0, 0.0], 0.4);
+        let order = 12;
+        let translator = RotationTranslator::new(order);
+        let expansion = MultipoleExpansion::from_charges(order, [0.0; 3], &positions, &charges);
+        // Largest difference relative to the largest coefficient of the same order
+        let assert_equal = |a: &[Complex64], b: &[Complex64]| {
+            for l in 0..=order {
+                let scale = (0..=l).map(|m| b[index(l, m)].norm()).fold(0.0, f64::max);
+                for m in 0..=l {
+                    assert!((a[index(l, m)] - b[index(l, m)]).norm() <= 1e-10 * scale);
+                }
+            }
+        };
+        // Directions in both hemispheres and along the z axis
+        let directions = [
+            [3.0, -4.0, 5.0],
+            [-2.0, 1.0, -6.0],
+            [0.0, 0.0, 7.0],
+            [0.0, 0.0, -7.0],
+            [6.0, 1.0, 0.0],
+            [1e-9, 0.0, -5.0],
+        ];
+        for t in directions.map(NalgebraVector3::from) {
+            let shifted = translator.translate_multipole(&expansion, t * 0.1);
+            let direct = expansion.translate(t * 0.1);
+            assert_equal(shifted.moments(), direct.moments());
+
+            let local = translator.multipole_to_local(&expansion, t);
+            let direct = expansion.to_local(t);
+            assert_equal(&local.coefficients, &direct.coefficients);
+
+            let shifted = translator.translate_local(&local, t * 1.05);
+            let direct = direct.translate(t * 1.05);
+            assert_equal(&shifted.coefficients, &direct.coefficients);
+        }
+    }
+
+    #[test]
+    fn test_local_expansion() {
+        let (positions, charges) = cluster([0.0, 0.0, 0.0], 2.1);
+        let order = 14;
+        let exact = |x: NalgebraVector3| -> f64 {
+            positions
+                .iter()
+                .zip(&charges)
+                .map(|(p, q)| q / (x - NalgebraVector3::from(*p)).norm())
+                .sum()
+        };
+        // Sum of expansions of two subsets about different centers
+        let mut expansion = MultipoleExpansion::from_charges(
+            order,
+            [0.1, 0.0, 0.0],
+            &positions[..2],
+            &charges[..2],
+        );
+        let other = MultipoleExpansion::from_charges(
+            order,
+            [0.0, -0.2, 0.1],
+            &positions[2..],
+            &charges[2..],
+        );
+        expansion.add_expansion(&other);
+        let x = NalgebraVector3::new(-3.0, 7.0, 4.0);
+        assert_relative_eq!(expansion.potential(x), exact(x), epsilon = 1e-10);
+
+        let local = expansion.to_local([-3.2, 6.7, 4.1]);
+        assert_eq!(local.order(), order);
+        assert_relative_eq!(local.potential(x), exact(x), epsilon = 1e-10);
+        assert_relative_eq!(local.coefficient(2, -1).re, -local.coefficient(2, 1).re);
+        let field: NalgebraVector3 = local.translate(x).field(x).into();
+        let h = 1e-5;
+        for i in 0..3 {
+            let dx = NalgebraVector3::ith(i, h);
+            let gradient = (exact(x + dx) - exact(x - dx)) / (2.0 * h);
+            assert_relative_eq!(field[i], -gradient, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_energy() {
+        let (center_a, center_b) = ([0.5, -0.2, 0.1], [4.0, 5.0, -3.0]);
+        let (positions_a, charges_a) = cluster(center_a, 0.3);
+        let (positions_b, charges_b) = cluster(center_b, 1.9);
+        let mut exact = 0.0;
+        for (pa, qa) in positions_a.iter().zip(&charges_a) {
+            for (pb, qb) in positions_b.iter().zip(&charges_b) {
+                exact += qa * qb / (NalgebraVector3::from(*pa) - NalgebraVector3::from(*pb)).norm();
+            }
+        }
+        // Error decreases with increasing order
+        let error = |order: usize| {
+            let a = MultipoleExpansion::from_charges(order, center_a, &positions_a, &charges_a);
+            let b = MultipoleExpansion::from_charges(order, center_b, &positions_b, &charges_b);
+            assert_relative_eq!(a.energy(&b), b.energy(&a), epsilon = 1e-12);
+            (a.energy(&b) - exact).abs()
+        };
+        assert!(error(2) < error(0));
+        assert!(error(6) < error(2));
+        assert!(error(12) < 1e-8);
+
+        // Charge-dipole energy agrees with the pairwise module
+        use crate::pairwise::{MultipoleEnergy, Plain};
+        let a = MultipoleExpansion::from_charges(1, center_a, &[center_a.into()], &[2.0]);
+        let mut b = MultipoleExpansion::new(1, center_b);
+        b.add_charge([center_b[0] + 1e-3, center_b[1], center_b[2]], 1e3);
+        b.add_charge([center_b[0] - 1e-3, center_b[1], center_b[2]], -1e3);
+        let r: NalgebraVector3 = NalgebraVector3::from(center_b) - NalgebraVector3::from(center_a);
+        let expected = Plain::without_cutoff().ion_dipole_energy(2.0, b.dipole_moment(), r);
+        assert_relative_eq!(a.energy(&b), expected, epsilon = 1e-12);
+    }
+}
@@ -0,0 +1,432 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Cell lists and Verlet neighbor lists.
+//!
+//! With a finite cutoff, only particles in the same or adjacent cells of a grid with
+//! a spacing of at least the cutoff can interact.
+//! A [`CellList`] bins particles into such a grid, reducing the cost of finding all
+//! interacting pairs from O(N²) to O(N).
+//! A [`NeighborList`] stores pairs within the cutoff plus a _skin_ and is reused until
+//! a particle has moved more than half the skin.
+//!
+//! # Examples
+//! ~~~
+//! use coulomb::{neighbor::CellList, pbc::Cell};
+//! let positions = [[0.0, 0.0, 0.0].into(), [0.0, 0.0, 9.0].into(), [5.0, 5.0, 5.0].into()];
+//! let cell_list = CellList::new(&positions, &Cell::Cubic(10.0), 2.0);
+//! // Candidate pairs, possibly beyond the cutoff
+//! assert!(cell_list.pairs().contains(&(0, 1)));
+//! ~~~
+
+use crate::pbc::Cell;
+use crate::{NalgebraMatrix3, NalgebraVector3, Vector3};
+
+/// Mapping from positions to fractional grid coordinates
+#[derive(Debug, Clone, PartialEq)]
+enum Frame {
+    /// Bounding box of the particles with open boundaries
+    Open {
+        origin: NalgebraVector3,
+        scale: NalgebraVector3,
+    },
+    /// Periodic cell with the inverse lattice matrix
+    Periodic { inverse: NalgebraMatrix3 },
+}
+
+/// Linked-cell list binning particles into a grid with spacing of at least the cutoff.
+///
+/// For periodic cells, the grid follows the lattice vectors, and bins wrap around the
+/// cell boundaries.
+/// For open boundaries, the grid spans the bounding box of the particles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellList {
+    /// Cutoff setting the minimum grid spacing, UNIT: [ input length ]
+    cutoff: f64,
+    /// Number of bins along each lattice vector
+    dims: [usize; 3],
+    /// Position to grid mapping
+    frame: Frame,
+    /// Particle indices in each bin
+    bins: Vec<Vec<usize>>,
+}
+
+impl CellList {
+    /// Bin positions into a grid matching `cutoff`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cutoff is not positive.
+    pub fn new(positions: &[Vector3], cell: &Cell, cutoff: f64) -> Self {
+        assert!(cutoff > 0.0);
+        // Limit the number of bins for short cutoffs and few particles
+        let max_dims = (2.0 * (positions.len() as f64).cbrt()).ceil().max(1.0);
+        let to_dims = |widths: NalgebraVector3| {
+            widths.map(|width| {
+                let n = (width / cutoff).floor().min(max_dims);
+                if n.is_finite() && n >= 1.0 {
+                    n as usize
+                } else {
+                    1
+                }
+            })
+        };
+        let (dims, frame) = match cell.box_matrix() {
+            Some(matrix) => {
+                let matrix = NalgebraMatrix3::from(matrix);
+                let volume = matrix.determinant().abs();
+                let (a, b, c) = (matrix.column(0), matrix.column(1), matrix.column(2));
+                // Perpendicular widths between opposite faces
+                let widths = NalgebraVector3::new(
+                    volume / b.cross(&c).norm(),
+                    volume / c.cross(&a).norm(),
+                    volume / a.cross(&b).norm(),
+                );
+                let inverse = matrix.try_inverse().unwrap_or_else(NalgebraMatrix3::zeros);
+                (to_dims(widths), Frame::Periodic { inverse })
+            }
+            None => {
+                let mut lower = NalgebraVector3::repeat(f64::INFINITY);
+                let mut upper = NalgebraVector3::repeat(f64::NEG_INFINITY);
+                for position in positions.iter().map(|p| NalgebraVector3::from(*p)) {
+                    lower = lower.inf(&position);
+                    upper = upper.sup(&position);
+                }
+                let extent = (upper - lower).map(|x| x.max(0.0));
+                let dims = to_dims(extent);
+                let scale = NalgebraVector3::from_fn(|d, _| match extent[d] > 0.0 {
+                    true => dims[d] as f64 / extent[d],
+                    false => 0.0,
+                });
+                let origin = lower.map(|x| if x.is_finite() { x } else { 0.0 });
+                (dims, Frame::Open { origin, scale })
+            }
+        };
+        let mut cell_list = Self {
+            cutoff,
+            dims: [dims.x, dims.y, dims.z],
+            frame,
+            bins: vec![Vec::new(); dims.x * dims.y * dims.z],
+        };
+        for (index, position) in positions.iter().enumerate() {
+            let bin = cell_list.flat_index(cell_list.bin(*position));
+            cell_list.bins[bin].push(index);
+        }
+        cell_list
+    }
+
+    /// Number of bins along each lattice vector
+    pub const fn dims(&self) -> [usize; 3] {
+        self.dims
+    }
+
+    /// Cutoff setting the minimum grid spacing
+    pub const fn cutoff(&self) -> f64 {
+        self.cutoff
+    }
+
+    /// Move a particle to the bin of its new position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the particle is not in the bin of its old position.
+    pub fn move_particle(
+        &mut self,
+        index: usize,
+        old_position: impl Into<Vector3>,
+        new_position: impl Into<Vector3>,
+    ) {
+        let from = self.flat_index(self.bin(old_position.into()));
+        let to = self.flat_index(self.bin(new_position.into()));
+        if from != to {
+            let slot = self.bins[from]
+                .iter()
+                .position(|i| *i == index)
+                .expect("particle not in cell list");
+            self.bins[from].swap_remove(slot);
+            self.bins[to].push(index);
+        }
+    }
+
+    /// Grid coordinates of the bin containing a position
+    fn bin(&self, position: Vector3) -> [usize; 3] {
+        let position = NalgebraVector3::from(position);
+        let grid = match &self.frame {
+            Frame::Open { origin, scale } => (position - origin).component_mul(scale),
+            Frame::Periodic { inverse } => {
+                let fractional = inverse * position;
+                (fractional - fractional.map(f64::floor))
+                    .component_mul(&NalgebraVector3::from_fn(|d, _| self.dims[d] as f64))
+            }
+        };
+        core::array::from_fn(|d| (grid[d].max(0.0) as usize).min(self.dims[d] - 1))
+    }
+
+    fn flat_index(&self, bin: [usize; 3]) -> usize {
+        (bin[0] * self.dims[1] + bin[1]) * self.dims[2] + bin[2]
+    }
+
+    /// Flat indices of a bin and its adjacent bins, without duplicates
+    fn adjacent_bins(&self, bin: [usize; 3]) -> Vec<usize> {
+        let periodic = matches!(self.frame, Frame::Periodic { .. });
+        let mut adjacent = Vec::with_capacity(27);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let mut neighbor = [0; 3];
+                    let in_grid = [dx, dy, dz].iter().enumerate().all(|(d, offset)| {
+                        let n = self.dims[d] as isize;
+                        let i = bin[d] as isize + offset;
+                        neighbor[d] = match periodic {
+                            true => i.rem_euclid(n),
+                            false => i,
+                        } as usize;
+                        periodic || (0..n).contains(&i)
+                    });
+                    if in_grid {
+                        adjacent.push(self.flat_index(neighbor));
+                    }
+                }
+            }
+        }
+        adjacent.sort_unstable();
+        adjacent.dedup();
+        adjacent
+    }
+
+    /// Indices of particles that may be within the cutoff of a position
+    pub fn neighbors(&self, position: impl Into<Vector3>) -> Vec<usize> {
+        self.adjacent_bins(self.bin(position.into()))
+            .into_iter()
+            .flat_map(|bin| self.bins[bin].iter().copied())
+            .collect()
+    }
+
+    /// Unique pairs, (i, j) with i < j, of particles that may be within the cutoff
+    ///
+    /// All pairs within the cutoff are included, but pairs further apart may be as well.
+    pub fn pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for i in 0..self.dims[0] {
+            for j in 0..self.dims[1] {
+                for k in 0..self.dims[2] {
+                    let bin = self.flat_index([i, j, k]);
+                    for neighbor in self.adjacent_bins([i, j, k]) {
+                        if neighbor == bin {
+                            let indices = &self.bins[bin];
+                            for (n, a) in indices.iter().enumerate() {
+                                pairs.extend(
+                                    indices[n + 1..].iter().map(|b| (*a.min(b), *a.max(b))),
+                                );
+                            }
+                        } else if neighbor > bin {
+                            for a in &self.bins[bin] {
+                                pairs.extend(
+                                    self.bins[neighbor].iter().map(|b| (*a.min(b), *a.max(b))),
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs
+    }
+}
+
+/// Verlet list of pairs within the cutoff plus a skin distance.
+///
+/// The list remains valid until a particle has moved more than half the skin since the
+/// last build, whereafter [`NeighborList::update`] rebuilds it using a [`CellList`].
+///
+/// # Examples
+/// ~~~
+/// use coulomb::{neighbor::NeighborList, pbc::Cell};
+/// let mut positions = vec![[0.0, 0.0, 0.0].into(), [0.0, 0.0, 2.5].into()];
+/// let cell = Cell::Cubic(10.0);
+/// let mut list = NeighborList::new(&positions, &cell, 2.0, 1.0);
+/// assert_eq!(list.pairs(), &[(0, 1)]);
+/// positions[1].z = 3.5;
+/// assert!(list.update(&positions, &cell));
+/// assert!(list.pairs().is_empty());
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+pub struct NeighborList {
+    /// Interaction cutoff, UNIT: [ input length ]
+    cutoff: f64,
+    /// Skin distance added to the cutoff, UNIT: [ input length ]
+    skin: f64,
+    /// Pairs within the cutoff plus skin
+    pairs: Vec<(usize, usize)>,
+    /// Positions at the last build
+    reference: Vec<Vector3>,
+}
+
+impl NeighborList {
+    /// Build a list of pairs within `cutoff` + `skin`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cutoff is not positive or if the skin is negative.
+    pub fn new(positions: &[Vector3], cell: &Cell, cutoff: f64, skin: f64) -> Self {
+        assert!(skin >= 0.0);
+        let mut list = Self {
+            cutoff,
+            skin,
+            pairs: Vec::new(),
+            reference: Vec::new(),
+        };
+        list.build(positions, cell);
+        list
+    }
+
+    /// Interaction cutoff
+    pub const fn cutoff(&self) -> f64 {
+        self.cutoff
+    }
+
+    /// Skin distance
+    pub const fn skin(&self) -> f64 {
+        self.skin
+    }
+
+    /// Pairs, (i, j) with i < j, within the cutoff plus skin at the last build
+    pub fn pairs(&self) -> &[(usize, usize)] {
+        &self.pairs
+    }
+
+    /// Rebuild the list from scratch
+    pub fn build(&mut self, positions: &[Vector3], cell: &Cell) {
+        let range_squared = (self.cutoff + self.skin).powi(2);
+        self.pairs = CellList::new(positions, cell, self.cutoff + self.skin)
+            .pairs()
+            .into_iter()
+            .filter(|(i, j)| {
+                NalgebraVector3::from(cell.distance(positions[*i], positions[*j])).norm_squared()
+                    < range_squared
+            })
+            .collect();
+        self.reference = positions.to_vec();
+    }
+
+    /// True if a particle has moved more than half the skin, or if particles were added
+    /// or removed, since the last build
+    pub fn needs_rebuild(&self, positions: &[Vector3], cell: &Cell) -> bool {
+        let max_squared = (0.5 * self.skin).powi(2);
+        positions.len() != self.reference.len()
+            || positions
+                .iter()
+                .zip(&self.reference)
+                .any(|(position, reference)| {
+                    NalgebraVector3::from(cell.distance(*reference, *position)).norm_squared()
+                        > max_squared
+                })
+    }
+
+    /// Rebuild the list if needed; returns true if rebuilt
+    pub fn update(&mut self, positions: &[Vector3], cell: &Cell) -> bool {
+        let rebuild = self.needs_rebuild(positions, cell);
+        if rebuild {
+            self.build(positions, cell);
+        }
+        rebuild
+    }
+}
+
+#[test]
+fn test_cell_list() {
+    use crate::pbc::TriclinicCell;
+    // Deterministic pseudo-random positions
+    let mut random = crate::test_utils::Random::new(12345);
+    let positions = random.positions(300, [-10.0; 3], [10.0; 3]);
+    let cutoff = 4.0;
+    let triclinic =
+        TriclinicCell::new([20.0, 0.0, 0.0], [4.0, 19.0, 0.0], [-3.0, 2.0, 18.0]).unwrap();
+    for cell in [
+        Cell::Open,
+        Cell::Cubic(20.0),
+        Cell::Orthorhombic([20.0, 9.0, 30.0].into()),
+        Cell::Cubic(6.0),
+        Cell::Triclinic(triclinic),
+    ] {
+        let within = |(i, j): &(usize, usize)| {
+            NalgebraVector3::from(cell.distance(positions[*i], positions[*j])).norm() < cutoff
+        };
+        let expected: Vec<_> = (0..positions.len())
+            .flat_map(|i| (i + 1..positions.len()).map(move |j| (i, j)))
+            .filter(within)
+            .collect();
+        let cell_list = CellList::new(&positions, &cell, cutoff);
+        let pairs: Vec<_> = cell_list.pairs().into_iter().filter(within).collect();
+        assert_eq!(pairs, expected);
+        assert!(cell_list.pairs().windows(2).all(|w| w[0] < w[1]));
+
+        // Candidates around a single particle
+        let mut candidates = cell_list.neighbors(positions[7]);
+        candidates.sort_unstable();
+        for (i, j) in &expected {
+            if *i == 7 {
+                assert!(candidates.binary_search(j).is_ok());
+            }
+        }
+    }
+    assert_eq!(
+        CellList::new(&positions, &Cell::Cubic(20.0), cutoff).dims(),
+        [5, 5, 5]
+    );
+    assert!(CellList::new(&[], &Cell::Open, cutoff).pairs().is_empty());
+
+    // Moving particles keeps the list equal to a rebuilt one
+    let cell = Cell::Cubic(20.0);
+    let mut moved = positions.clone();
+    let mut cell_list = CellList::new(&positions, &cell, cutoff);
+    for (index, new_position) in [(3, [9.9, -9.9, 0.0]), (5, [1.0, 2.0, 3.0]), (3, [0.0; 3])] {
+        cell_list.move_particle(index, moved[index], new_position);
+        moved[index] = new_position.into();
+    }
+    assert_eq!(
+        cell_list.pairs(),
+        CellList::new(&moved, &cell, cutoff).pairs()
+    );
+}
+
+#[test]
+fn test_neighbor_list() {
+    let cell = Cell::Cubic(10.0);
+    let mut positions: Vec<Vector3> = vec![
+        [0.0, 0.0, 0.0].into(),
+        [0.0, 0.0, 2.2].into(),
+        [0.0, 0.0, -4.0].into(),
+    ];
+    let mut list = NeighborList::new(&positions, &cell, 2.0, 0.5);
+    assert_eq!(list.pairs(), &[(0, 1)]);
+    assert!(!list.update(&positions, &cell));
+
+    // Small moves keep the list, also across the periodic boundary
+    positions[2].z = -4.2;
+    assert!(!list.needs_rebuild(&positions, &cell));
+    positions[2].z = 5.85;
+    assert!(!list.needs_rebuild(&positions, &cell));
+
+    positions[2].z = 4.4;
+    assert!(list.needs_rebuild(&positions, &cell));
+    assert!(list.update(&positions, &cell));
+    assert_eq!(list.pairs(), &[(0, 1), (1, 2)]);
+    assert!(!list.update(&positions, &cell));
+
+    positions.push([0.0, 1.0, -1.0].into());
+    assert!(list.update(&positions, &cell));
+    assert_eq!(list.pairs(), &[(0, 1), (0, 3), (1, 2)]);
+}
@@ -0,0 +1,202 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Coupling-parameter derivatives for thermodynamic integration.
+
+use super::{Multipole, MultipoleEnergy};
+use crate::Vector3;
+
+/// Particles of a pair whose multipole moments are scaled by a coupling parameter, λ.
+///
+/// All multipole energies are bilinear in the moments of the two particles, so scaling one
+/// particle gives $U(\lambda) = \lambda U_1$ while scaling both gives $U(\lambda) = \lambda^2 U_1$,
+/// where $U_1$ is the fully coupled energy.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::pairwise::{Coupling, Multipole, Plain};
+/// let plain = Plain::without_cutoff();
+/// let (a, b) = (Multipole::new(1.0), Multipole::new(-1.0).with_dipole([0.0, 0.0, 1.0]));
+/// let du = Coupling::Both.pair_energy_derivative(&plain, &a, &b, [0.0, 0.0, 3.0], 0.5);
+/// assert_relative_eq!(du, -1.0 / 3.0 - 1.0 / 9.0);
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Coupling {
+    /// Moments of the first particle are scaled
+    First,
+    /// Moments of the second particle are scaled
+    Second,
+    /// Moments of both particles are scaled
+    Both,
+}
+
+impl Coupling {
+    /// Energy at coupling `lambda` from the fully coupled energy.
+    pub fn energy(&self, coupled_energy: f64, lambda: f64) -> f64 {
+        match self {
+            Self::First | Self::Second => lambda * coupled_energy,
+            Self::Both => lambda * lambda * coupled_energy,
+        }
+    }
+
+    /// Derivative, ∂U/∂λ, at coupling `lambda` from the fully coupled energy.
+    pub fn energy_derivative(&self, coupled_energy: f64, lambda: f64) -> f64 {
+        match self {
+            Self::First | Self::Second => coupled_energy,
+            Self::Both => 2.0 * lambda * coupled_energy,
+        }
+    }
+
+    /// Derivative, ∂U/∂λ, of the multipole pair energy at coupling `lambda`.
+    ///
+    /// See [`MultipoleEnergy::pair_energy`] for arguments.
+    pub fn pair_energy_derivative<T: MultipoleEnergy>(
+        &self,
+        scheme: &T,
+        a: &Multipole,
+        b: &Multipole,
+        r: impl Into<Vector3>,
+        lambda: f64,
+    ) -> f64 {
+        self.energy_derivative(scheme.pair_energy(a, b, r), lambda)
+    }
+}
+
+/// Soft-core charge-charge interaction for alchemical transformations.
+///
+/// The distance is replaced by $r_{sc} = \sqrt{r^2 + \alpha (1 - \lambda)}$ and the energy is
+///
+/// $$ U(\lambda) = \lambda \, u(r_{sc}) $$
+///
+/// where $u$ is the charge-charge energy of an interaction scheme.
+/// This removes the singularity at $r = 0$ as the charges are decoupled, $\lambda \to 0$,
+/// see _e.g._ <https://doi.org/10.1016/0009-2614(94)00397-1>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::pairwise::{Plain, SoftCore};
+/// let soft_core = SoftCore::new(4.0);
+/// let plain = Plain::without_cutoff();
+/// assert_relative_eq!(soft_core.energy(&plain, 1.0, 1.0, 0.0, 0.5), 0.5 / f64::sqrt(2.0));
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoftCore {
+    /// Soft-core parameter, UNIT: [ ( input length )^2 ]
+    pub alpha: f64,
+}
+
+impl SoftCore {
+    /// New soft-core interaction with parameter `alpha`, UNIT: [ ( input length )^2 ]
+    pub const fn new(alpha: f64) -> Self {
+        Self { alpha }
+    }
+
+    /// Soft-core distance, $r_{sc}$
+    fn distance(&self, distance: f64, lambda: f64) -> f64 {
+        (distance * distance + self.alpha * (1.0 - lambda)).sqrt()
+    }
+
+    /// Soft-core charge-charge energy at coupling `lambda`.
+    pub fn energy<T: MultipoleEnergy>(
+        &self,
+        scheme: &T,
+        charge1: f64,
+        charge2: f64,
+        distance: f64,
+        lambda: f64,
+    ) -> f64 {
+        lambda * scheme.ion_ion_energy(charge1, charge2, self.distance(distance, lambda))
+    }
+
+    /// Derivative, ∂U/∂λ, of the soft-core charge-charge energy at coupling `lambda`.
+    pub fn energy_derivative<T: MultipoleEnergy>(
+        &self,
+        scheme: &T,
+        charge1: f64,
+        charge2: f64,
+        distance: f64,
+        lambda: f64,
+    ) -> f64 {
+        let r_sc = self.distance(distance, lambda);
+        // ∂u/∂r_sc from the field and ∂r_sc/∂λ = -α / 2r_sc
+        let du_dr = -charge2 * scheme.ion_field_scalar(charge1, r_sc);
+        scheme.ion_ion_energy(charge1, charge2, r_sc) - lambda * du_dr * 0.5 * self.alpha / r_sc
+    }
+}
+
+#[test]
+fn test_coupling() {
+    use super::{Plain, RealSpaceEwald};
+    use approx::assert_relative_eq;
+
+    let ewald = RealSpaceEwald::new_without_salt(12.0, 0.2);
+    let plain = Plain::without_cutoff();
+    let a = Multipole::new(1.2).with_dipole([0.3, -0.4, 0.9]);
+    let b = Multipole::new(-0.7)
+        .with_dipole([-0.5, 0.2, 0.1])
+        .with_quadrupole([[0.2, 0.1, 0.0], [0.1, -0.3, 0.05], [0.0, 0.05, 0.1]]);
+    let r = [1.9, 0.6, -1.1];
+    let h = 1e-6;
+    let scaled = |m: &Multipole, lambda: f64| Multipole {
+        charge: lambda * m.charge,
+        dipole: m
+            .dipole
+            .map(|d| [lambda * d.x, lambda * d.y, lambda * d.z].into()),
+        quadrupole: m.quadrupole.map(|q| {
+            let q: crate::NalgebraMatrix3 = q.into();
+            (q * lambda).into()
+        }),
+    };
+    for coupling in [Coupling::First, Coupling::Second, Coupling::Both] {
+        let energy = |lambda: f64| {
+            let (la, lb) = match coupling {
+                Coupling::First => (lambda, 1.0),
+                Coupling::Second => (1.0, lambda),
+                Coupling::Both => (lambda, lambda),
+            };
+            ewald.pair_energy(&scaled(&a, la), &scaled(&b, lb), r)
+        };
+        let lambda = 0.35;
+        assert_relative_eq!(
+            coupling.energy(ewald.pair_energy(&a, &b, r), lambda),
+            energy(lambda),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            coupling.pair_energy_derivative(&ewald, &a, &b, r, lambda),
+            (energy(lambda + h) - energy(lambda - h)) / (2.0 * h),
+            epsilon = 1e-8
+        );
+    }
+
+    let soft_core = SoftCore::new(3.0);
+    for lambda in [0.0, 0.4, 0.9] {
+        for distance in [0.0, 0.8, 2.5] {
+            let energy = |l: f64| soft_core.energy(&plain, 1.3, -0.9, distance, l);
+            assert_relative_eq!(
+                soft_core.energy_derivative(&plain, 1.3, -0.9, distance, lambda),
+                (energy(lambda + h) - energy(lambda - h)) / (2.0 * h),
+                epsilon = 1e-7
+            );
+        }
+    }
+    assert_relative_eq!(
+        soft_core.energy(&plain, 1.3, -0.9, 2.5, 1.0),
+        -1.3 * 0.9 / 2.5
+    );
+}
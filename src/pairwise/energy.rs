@@ -17,20 +17,22 @@
 
 //! Multipole interaction energies.
 
-use super::{MultipoleField, MultipolePotential};
-use crate::{Matrix3, NalgebraVector3, Vector3};
+use super::{tensor::RadialDerivatives, Multipole, MultipoleField, MultipolePotential};
+use crate::{Matrix3, NalgebraMatrix3, NalgebraVector3, Vector3};
 
 /// Interaction energy between multipoles.
 pub trait MultipoleEnergy: MultipolePotential + MultipoleField {
-    /// Self-energy of monopoles and dipoles
+    /// Self-energy of monopoles, dipoles and quadrupoles
     ///
     /// The self-energy is described by:
     ///
-    /// $$u_{self} = \sum_i c_1 z_j^2 / R_c + c_2 \mu_i^2 / R_c^3 + ...$$
+    /// $$u_{self} = \sum_i c_1 z_i^2 / R_c + c_2 \mu_i^2 / R_c^3 + c_3 \left( (\mathrm{tr}\,Q_i)^2 + 2 Q_i : Q_i \right) / R_c^5$$
     ///
-    /// where $c_1$ and $c_2$ are constants specific for the interaction scheme.
+    /// where $c_1$, $c_2$, and $c_3$ are constants specific for the interaction scheme.
+    /// The monopoles and dipoles are given as charges and dipole moment magnitudes, while the
+    /// quadrupoles are full second moments as in [`MultipolePotential::quadrupole_potential`].
     ///
-    fn self_energy(&self, monopoles: &[f64], dipoles: &[f64]) -> f64 {
+    fn self_energy(&self, monopoles: &[f64], dipoles: &[f64], quadrupoles: &[Matrix3]) -> f64 {
         let mut sum: f64 = 0.0;
         let prefactor = self.self_energy_prefactors();
         if let Some(c1) = prefactor.monopole {
@@ -39,6 +41,17 @@ pub trait MultipoleEnergy: MultipolePotential + MultipoleField {
         if let Some(c2) = prefactor.dipole {
             sum += c2 * dipoles.iter().map(|mu| mu * mu).sum::<f64>() / self.cutoff().powi(3);
         }
+        if let Some(c3) = prefactor.quadrupole {
+            sum += c3
+                * quadrupoles
+                    .iter()
+                    .map(|quad| {
+                        let quad = NalgebraMatrix3::from(*quad);
+                        quad.trace().powi(2) + 2.0 * quad.dot(&quad)
+                    })
+                    .sum::<f64>()
+                / self.cutoff().powi(5);
+        }
         sum
     }
     /// Interaction energy between two point charges
@@ -146,4 +159,190 @@ pub trait MultipoleEnergy: MultipolePotential + MultipoleField {
         let r: NalgebraVector3 = r.into().into();
         charge * self.quadrupole_potential(quad, -r) // potential of quadrupole interacting with charge
     }
+
+    /// Interaction energy between a point dipole and a point quadrupole
+    ///
+    /// - `dipole`: Dipole moment, UNIT: [(input length) x (input charge)]
+    /// - `quad`: Quadrupole moment, UNIT: [(input length)^2 x (input charge)]
+    /// - `r`: Distance-vector between quadrupole and dipole, r = r_Q - r_mu, UNIT: [input length]
+    ///
+    /// Returns the interaction energy, UNIT: [(input charge)^2 / (input length)]
+    ///
+    /// The interaction energy between a dipole and a quadrupole is described by:
+    ///
+    /// $$u(\mu, Q, r) = -\mu \cdot E(Q, -r) = \frac{1}{2} \sum_{abc} \mu_a Q_{bc} \partial_a\partial_b\partial_c T(-r)$$
+    ///
+    /// where $E(Q, -r)$ is the field from the quadrupole at the location of the dipole and
+    /// $T(r) = S(q) e^{-\kappa r} / r$ is the interaction kernel of the scheme.
+    fn dipole_quadrupole_energy(
+        &self,
+        dipole: impl Into<Vector3>,
+        quad: impl Into<Matrix3>,
+        r: impl Into<Vector3>,
+    ) -> f64 {
+        let dipole: NalgebraVector3 = dipole.into().into();
+        let quad: NalgebraMatrix3 = quad.into().into();
+        let r: NalgebraVector3 = r.into().into();
+        RadialDerivatives::new(self, -r, 3).map_or(0.0, |t| 0.5 * t.contract_rank3(&dipole, &quad))
+    }
+
+    /// Interaction energy between two point quadrupoles
+    ///
+    /// - `quad1`: Quadrupole moment of particle 1, UNIT: [(input length)^2 x (input charge)]
+    /// - `quad2`: Quadrupole moment of particle 2, UNIT: [(input length)^2 x (input charge)]
+    /// - `r`: Distance-vector between quadrupoles, r = r_Q_2 - r_Q_1, UNIT: [input length]
+    ///
+    /// Returns the interaction energy, UNIT: [(input charge)^2 / (input length)]
+    ///
+    /// The quadrupoles are second moments of the charge distribution, $Q_{ab} = \sum_i q_i r_{i,a} r_{i,b}$,
+    /// as in [`MultipolePotential::quadrupole_potential`], and may or may not be traceless.
+    /// The interaction energy is described by:
+    ///
+    /// $$u(Q_1, Q_2, r) = \frac{1}{4} \sum_{abcd} Q_{1,ab} Q_{2,cd} \partial_a\partial_b\partial_c\partial_d T(r)$$
+    ///
+    /// where $T(r) = S(q) e^{-\kappa r} / r$ is the interaction kernel of the scheme.
+    /// This requires the fourth derivative of the short-range function, see
+    /// [`ShortRangeFunction::short_range_f4`](crate::pairwise::ShortRangeFunction::short_range_f4).
+    fn quadrupole_quadrupole_energy(
+        &self,
+        quad1: impl Into<Matrix3>,
+        quad2: impl Into<Matrix3>,
+        r: impl Into<Vector3>,
+    ) -> f64 {
+        let quad1: NalgebraMatrix3 = quad1.into().into();
+        let quad2: NalgebraMatrix3 = quad2.into().into();
+        RadialDerivatives::new(self, r.into().into(), 4)
+            .map_or(0.0, |t| 0.25 * t.contract_rank4(&quad1, &quad2))
+    }
+
+    /// Hessian of the interaction energy between two point charges
+    ///
+    /// - `charge1`: Point charge, UNIT: [input charge]
+    /// - `charge2`: Point charge, UNIT: [input charge]
+    /// - `r`: Distance-vector between charges, r = r_2 - r_1, UNIT: [input length]
+    ///
+    /// Returns the 3×3 matrix of second derivatives, $H_{ab} = \partial^2 u / \partial r_a \partial r_b$,
+    /// UNIT: [(input charge)^2 / (input length)^3]
+    ///
+    /// The blocks of the full 6×6 Hessian with respect to particle positions are
+    /// $\partial^2 u / \partial \boldsymbol{r}_1^2 = \partial^2 u / \partial \boldsymbol{r}_2^2 = H$ and
+    /// $\partial^2 u / \partial \boldsymbol{r}_1 \partial \boldsymbol{r}_2 = -H$.
+    /// With the interaction kernel $T(r) = S(q) e^{-\kappa r} / r$,
+    ///
+    /// $$H_{ab} = z_1 z_2 \partial_a\partial_b T(r)$$
+    fn ion_ion_hessian(&self, charge1: f64, charge2: f64, r: impl Into<Vector3>) -> Matrix3 {
+        RadialDerivatives::new(self, r.into().into(), 2)
+            .map_or(NalgebraMatrix3::zeros(), |t| charge1 * charge2 * t.rank2())
+            .into()
+    }
+
+    /// Hessian of the interaction energy between a point charge and a point dipole
+    ///
+    /// - `charge`: Point charge, UNIT: [input charge]
+    /// - `dipole`: Dipole moment, UNIT: [(input length) x (input charge)]
+    /// - `r`: Distance-vector between dipole and charge, r = r_mu - r_z, UNIT: [input length]
+    ///
+    /// Returns the 3×3 matrix of second derivatives, $H_{ab} = \partial^2 u / \partial r_a \partial r_b$,
+    /// UNIT: [(input charge)^2 / (input length)^2]
+    ///
+    /// The blocks of the full 6×6 Hessian follow as for [`MultipoleEnergy::ion_ion_hessian`].
+    /// The dipole orientation is held fixed and
+    ///
+    /// $$H_{ab} = z \sum_c \mu_c \partial_a\partial_b\partial_c T(r)$$
+    fn ion_dipole_hessian(
+        &self,
+        charge: f64,
+        dipole: impl Into<Vector3>,
+        r: impl Into<Vector3>,
+    ) -> Matrix3 {
+        let dipole: NalgebraVector3 = dipole.into().into();
+        RadialDerivatives::new(self, r.into().into(), 3)
+            .map_or(NalgebraMatrix3::zeros(), |t| {
+                charge * t.contract_rank3_matrix(&dipole)
+            })
+            .into()
+    }
+
+    /// Total interaction energy between two composite multipoles
+    ///
+    /// - `a`: Multipole of particle A
+    /// - `b`: Multipole of particle B
+    /// - `r`: Distance-vector between particles, r = r_b - r_a, UNIT: [input length]
+    ///
+    /// Returns the interaction energy, UNIT: [(input charge)^2 / (input length)]
+    ///
+    /// This is the sum of all ion, dipole and quadrupole cross terms present in `a` and `b`.
+    fn pair_energy(&self, a: &Multipole, b: &Multipole, r: impl Into<Vector3>) -> f64 {
+        let r: NalgebraVector3 = r.into().into();
+        let mut energy = 0.0;
+        if let Some(za) = a.nonzero_charge() {
+            if let Some(zb) = b.nonzero_charge() {
+                energy += self.ion_ion_energy(za, zb, r.norm());
+            }
+            if let Some(mu_b) = b.dipole {
+                energy += self.ion_dipole_energy(za, mu_b, r);
+            }
+            if let Some(quad_b) = b.quadrupole {
+                energy += self.ion_quadrupole_energy(za, quad_b, r);
+            }
+        }
+        if let Some(mu_a) = a.dipole {
+            if let Some(zb) = b.nonzero_charge() {
+                energy += self.ion_dipole_energy(zb, mu_a, -r);
+            }
+            if let Some(mu_b) = b.dipole {
+                energy += self.dipole_dipole_energy(mu_a, mu_b, r);
+            }
+            if let Some(quad_b) = b.quadrupole {
+                energy += self.dipole_quadrupole_energy(mu_a, quad_b, r);
+            }
+        }
+        if let Some(quad_a) = a.quadrupole {
+            if let Some(zb) = b.nonzero_charge() {
+                energy += self.ion_quadrupole_energy(zb, quad_a, -r);
+            }
+            if let Some(mu_b) = b.dipole {
+                energy += self.dipole_quadrupole_energy(mu_b, quad_a, -r);
+            }
+            if let Some(quad_b) = b.quadrupole {
+                energy += self.quadrupole_quadrupole_energy(quad_a, quad_b, r);
+            }
+        }
+        energy
+    }
+
+    /// Derivative of the total energy of point charges with respect to one of the charges
+    ///
+    /// - `index`: Index of the charge to differentiate with respect to
+    /// - `positions`: Positions of all charges, UNIT: [input length]
+    /// - `charges`: All charges, UNIT: [input charge]
+    ///
+    /// Returns ∂U/∂qᵢ, UNIT: [(input charge) / (input length)]
+    ///
+    /// The total energy is the pair energy summed over all pairs plus the self-energy, so
+    /// the derivative is the potential at particle `index` from all other charges plus
+    /// 2c₁qᵢ/r꜀ from the self-energy.
+    /// Reciprocal-space contributions are not included.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `positions` and `charges` differ in length.
+    fn d_energy_d_charge(&self, index: usize, positions: &[Vector3], charges: &[f64]) -> f64 {
+        assert_eq!(positions.len(), charges.len());
+        let position = NalgebraVector3::from(positions[index]);
+        let potential: f64 = positions
+            .iter()
+            .zip(charges)
+            .enumerate()
+            .filter(|(j, _)| *j != index)
+            .map(|(_, (r, z))| {
+                self.ion_potential(*z, (NalgebraVector3::from(*r) - position).norm())
+            })
+            .sum();
+        let self_energy = self
+            .self_energy_prefactors()
+            .monopole
+            .map_or(0.0, |c1| 2.0 * c1 * charges[index] / self.cutoff());
+        potential + self_energy
+    }
 }
@@ -15,7 +15,7 @@
 // See the license for the specific language governing permissions and
 // limitations under the license.
 
-use super::ShortRangeFunction;
+use super::{tensor::RadialDerivatives, ShortRangeFunction};
 use crate::{Cutoff, Matrix3, NalgebraMatrix3, NalgebraVector3, Vector3};
 
 /// Field due to electric multipoles.
@@ -160,4 +160,62 @@ pub trait MultipoleField: ShortRangeFunction + Cutoff {
         };
         result.into()
     }
+
+    /// Gradient of the electrostatic field from a point charge.
+    ///
+    /// - `charge`: Point charge [UNIT: input charge]
+    /// - `r`: Distance vector from the charge [UNIT: input length]
+    ///
+    /// Returns the symmetric 3×3 field gradient, ∂Eₐ/∂r_b [UNIT: (input charge) / (input length)^3]
+    ///
+    /// With the interaction kernel, T(r) = S(q) exp(-κr) / r, the gradient is
+    /// ∇𝐄(𝑧, 𝐫) = -𝑧∇∇T = -𝑧 (𝐈 t₁ + 𝐫𝐫ᵀ t₂) where tₙ = (𝑟⁻¹ 𝑑/𝑑𝑟)ⁿ T(𝑟).
+    /// This is used for forces and torques on quadrupoles, and for polarizable sites.
+    fn ion_field_gradient(&self, charge: f64, r: impl Into<Vector3>) -> Matrix3 {
+        RadialDerivatives::new(self, r.into().into(), 2)
+            .map_or(NalgebraMatrix3::zeros(), |t| -charge * t.rank2())
+            .into()
+    }
+
+    /// Gradient of the electrostatic field from a point dipole.
+    ///
+    /// - `dipole`: Point dipole [UNIT: (input length) x (input charge)]
+    /// - `r`: Distance vector from the dipole [UNIT: input length]
+    ///
+    /// Returns the symmetric 3×3 field gradient, ∂Eₐ/∂r_b [UNIT: (input charge) / (input length)^4]
+    ///
+    /// With the interaction kernel, T(r) = S(q) exp(-κr) / r, the gradient is
+    /// ∂Eₐ/∂r_b = ∑ μ_c ∂a∂b∂c T = (δₐ_b (𝛍·𝐫) + μₐ r_b + rₐ μ_b) t₂ + rₐ r_b (𝛍·𝐫) t₃
+    /// where tₙ = (𝑟⁻¹ 𝑑/𝑑𝑟)ⁿ T(𝑟).
+    fn dipole_field_gradient(&self, dipole: impl Into<Vector3>, r: impl Into<Vector3>) -> Matrix3 {
+        let dipole: NalgebraVector3 = dipole.into().into();
+        RadialDerivatives::new(self, r.into().into(), 3)
+            .map_or(NalgebraMatrix3::zeros(), |t| {
+                t.contract_rank3_matrix(&dipole)
+            })
+            .into()
+    }
+
+    /// Gradient of the electrostatic field from a point quadrupole.
+    ///
+    /// - `quad`: Point quadrupole [UNIT: (input length)^2 x (input charge)]
+    /// - `r`: Distance vector from the quadrupole [UNIT: input length]
+    ///
+    /// Returns the symmetric 3×3 field gradient, ∂Eₐ/∂r_b [UNIT: (input charge) / (input length)^5]
+    ///
+    /// With the interaction kernel, T(r) = S(q) exp(-κr) / r, the gradient is
+    /// ∂Eₐ/∂r_b = -½ ∑ Q_cd ∂a∂b∂c∂d T.
+    /// This requires the fourth derivative of the short-range function.
+    fn quadrupole_field_gradient(
+        &self,
+        quad: impl Into<Matrix3>,
+        r: impl Into<Vector3>,
+    ) -> Matrix3 {
+        let quad: NalgebraMatrix3 = quad.into().into();
+        RadialDerivatives::new(self, r.into().into(), 4)
+            .map_or(NalgebraMatrix3::zeros(), |t| {
+                -0.5 * t.contract_rank4_matrix(&quad)
+            })
+            .into()
+    }
 }
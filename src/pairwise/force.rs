@@ -15,8 +15,8 @@
 // See the license for the specific language governing permissions and
 // limitations under the license.
 
-use super::MultipoleField;
-use crate::{Matrix3, NalgebraVector3, Vector3};
+use super::{tensor::RadialDerivatives, Multipole, MultipoleField};
+use crate::{Matrix3, NalgebraMatrix3, NalgebraVector3, Vector3};
 
 /// Force between multipoles.
 pub trait MultipoleForce: MultipoleField {
@@ -153,4 +153,168 @@ pub trait MultipoleForce: MultipoleField {
         let field: NalgebraVector3 = self.quadrupole_field(quad, r).into();
         (charge * field).into()
     }
+
+    /// Interaction force between a point dipole and a point quadrupole.
+    ///
+    /// Parameters:
+    /// - `dipole`: Dipole moment (input length x input charge) [UNIT: (input length) x (input charge)]
+    /// - `quad`: Point quadrupole (input length^2 x input charge) [UNIT: (input length)^2 x (input charge)]
+    /// - `r`: Distance vector between particles, r = r_mu - r_Q (input length) [UNIT: input length]
+    ///
+    /// Returns:
+    /// Force on the dipole [UNIT: (input charge)^2 / (input length)^2]
+    ///
+    /// The force is the negative gradient of the interaction energy,
+    /// F(mu, quad, r) = -½ ∑ mu_b Q_cd ∂a∂b∂c∂d T(r),
+    /// where T(r) = S(q) exp(-κr) / r is the interaction kernel of the scheme.
+    /// The force on the quadrupole is equal in magnitude but opposite in direction.
+    fn dipole_quadrupole_force(
+        &self,
+        dipole: impl Into<Vector3>,
+        quad: impl Into<Matrix3>,
+        r: impl Into<Vector3>,
+    ) -> Vector3 {
+        let dipole: NalgebraVector3 = dipole.into().into();
+        let quad: NalgebraMatrix3 = quad.into().into();
+        RadialDerivatives::new(self, r.into().into(), 4)
+            .map_or(NalgebraVector3::zeros(), |t| {
+                -0.5 * t.contract_rank4_vector(&dipole, &quad)
+            })
+            .into()
+    }
+
+    /// Total force between two composite multipoles.
+    ///
+    /// Parameters:
+    /// - `a`: Multipole of particle A
+    /// - `b`: Multipole of particle B
+    /// - `r`: Distance vector between particles, r = r_b - r_a [UNIT: input length]
+    ///
+    /// Returns:
+    /// Force on particle B [UNIT: (input charge)^2 / (input length)^2]
+    ///
+    /// This is the sum of all ion, dipole and quadrupole cross terms present in `a` and `b`,
+    /// except the quadrupole-quadrupole term which is currently not supported.
+    /// The force on particle A is equal in magnitude but opposite in direction.
+    fn pair_force(&self, a: &Multipole, b: &Multipole, r: impl Into<Vector3>) -> Vector3 {
+        let r: NalgebraVector3 = r.into().into();
+        let mut force = NalgebraVector3::zeros();
+        if let Some(za) = a.nonzero_charge() {
+            if let Some(zb) = b.nonzero_charge() {
+                force += NalgebraVector3::from(self.ion_ion_force(za, zb, r));
+            }
+            if let Some(mu_b) = b.dipole {
+                force -= NalgebraVector3::from(self.ion_dipole_force(za, mu_b, -r));
+            }
+            if let Some(quad_b) = b.quadrupole {
+                force -= NalgebraVector3::from(self.ion_quadrupole_force(za, quad_b, -r));
+            }
+        }
+        if let Some(mu_a) = a.dipole {
+            if let Some(zb) = b.nonzero_charge() {
+                force += NalgebraVector3::from(self.ion_dipole_force(zb, mu_a, r));
+            }
+            if let Some(mu_b) = b.dipole {
+                force -= NalgebraVector3::from(self.dipole_dipole_force(mu_a, mu_b, r));
+            }
+            if let Some(quad_b) = b.quadrupole {
+                force -= NalgebraVector3::from(self.dipole_quadrupole_force(mu_a, quad_b, -r));
+            }
+        }
+        if let Some(quad_a) = a.quadrupole {
+            if let Some(zb) = b.nonzero_charge() {
+                force += NalgebraVector3::from(self.ion_quadrupole_force(zb, quad_a, r));
+            }
+            if let Some(mu_b) = b.dipole {
+                force += NalgebraVector3::from(self.dipole_quadrupole_force(mu_b, quad_a, r));
+            }
+        }
+        force.into()
+    }
+
+    /// Virial contribution of a pair of composite multipoles.
+    ///
+    /// Parameters:
+    /// - `a`: Multipole of particle A
+    /// - `b`: Multipole of particle B
+    /// - `r`: Distance vector between particles, r = r_b - r_a [UNIT: input length]
+    ///
+    /// Returns:
+    /// Virial tensor, 𝐅 ⊗ 𝐫 with elements Fᵢrⱼ [UNIT: (input charge)^2 / (input length)]
+    ///
+    /// 𝐅 is the force on particle B, see [`MultipoleForce::pair_force`].
+    /// The tensor is minus the derivative of the pair energy with respect to a homogeneous
+    /// strain of the distance vector, and the trace is the scalar virial, 𝐫 · 𝐅.
+    /// Orientations of dipoles and quadrupoles are kept fixed so the tensor is symmetric only
+    /// for point charges.
+    fn pair_virial(&self, a: &Multipole, b: &Multipole, r: impl Into<Vector3>) -> Matrix3 {
+        let r: NalgebraVector3 = r.into().into();
+        let force = NalgebraVector3::from(self.pair_force(a, b, r));
+        (force * r.transpose()).into()
+    }
+
+    /// Torque on a point dipole in the field of a composite multipole.
+    ///
+    /// Parameters:
+    /// - `dipole`: Dipole moment [UNIT: (input length) x (input charge)]
+    /// - `source`: Multipole generating the field
+    /// - `r`: Distance vector from the source to the dipole, r = r_mu - r_source [UNIT: input length]
+    ///
+    /// Returns:
+    /// Torque on the dipole [UNIT: (input charge)^2 / (input length)]
+    ///
+    /// The torque is τ = 𝛍 × 𝐄 where 𝐄 is the (screened) field from all moments of the source.
+    fn dipole_torque(
+        &self,
+        dipole: impl Into<Vector3>,
+        source: &Multipole,
+        r: impl Into<Vector3>,
+    ) -> Vector3 {
+        let dipole: NalgebraVector3 = dipole.into().into();
+        let r: NalgebraVector3 = r.into().into();
+        let mut field = NalgebraVector3::from(self.ion_field(source.charge, r));
+        if let Some(mu) = source.dipole {
+            field += NalgebraVector3::from(self.dipole_field(mu, r));
+        }
+        if let Some(quad) = source.quadrupole {
+            field += NalgebraVector3::from(self.quadrupole_field(quad, r));
+        }
+        dipole.cross(&field).into()
+    }
+
+    /// Torque on a point quadrupole in the field of a composite multipole.
+    ///
+    /// Parameters:
+    /// - `quad`: Quadrupole moment [UNIT: (input length)^2 x (input charge)]
+    /// - `source`: Multipole generating the field
+    /// - `r`: Distance vector from the source to the quadrupole, r = r_Q - r_source [UNIT: input length]
+    ///
+    /// Returns:
+    /// Torque on the quadrupole [UNIT: (input charge)^2 / (input length)]
+    ///
+    /// The torque is τᵢ = ∑ εᵢⱼₖ Qⱼₗ ∂ₗEₖ where ∂ₗEₖ is the (screened) field gradient
+    /// from all moments of the source.
+    fn quadrupole_torque(
+        &self,
+        quad: impl Into<Matrix3>,
+        source: &Multipole,
+        r: impl Into<Vector3>,
+    ) -> Vector3 {
+        let quad: NalgebraMatrix3 = quad.into().into();
+        let r: NalgebraVector3 = r.into().into();
+        let mut gradient = NalgebraMatrix3::from(self.ion_field_gradient(source.charge, r));
+        if let Some(mu) = source.dipole {
+            gradient += NalgebraMatrix3::from(self.dipole_field_gradient(mu, r));
+        }
+        if let Some(q) = source.quadrupole {
+            gradient += NalgebraMatrix3::from(self.quadrupole_field_gradient(q, r));
+        }
+        let m = quad * gradient.transpose();
+        NalgebraVector3::new(
+            m[(1, 2)] - m[(2, 1)],
+            m[(2, 0)] - m[(0, 2)],
+            m[(0, 1)] - m[(1, 0)],
+        )
+        .into()
+    }
 }
@@ -0,0 +1,236 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Interactions between Gaussian charge distributions.
+
+use super::tensor::RadialDerivatives;
+use crate::math::{erf_derivatives, erf_x};
+use crate::{NalgebraVector3, Vector3};
+use core::f64::consts::FRAC_2_SQRT_PI;
+
+/// Interactions between two Gaussian charge distributions.
+///
+/// A Gaussian charge of width $\sigma$ has the density
+/// $\rho(r) = q (2\pi\sigma^2)^{-3/2} e^{-r^2 / 2\sigma^2}$, and two such distributions
+/// interact through the erf-screened Coulomb kernel
+///
+/// $$ T(r) = \frac{\text{erf}(\beta r)}{r}, \quad \beta = \frac{1}{\sqrt{2(\sigma_1^2 + \sigma_2^2)}} $$
+///
+/// which is finite at contact and approaches $1/r$ at large separation.
+/// Dipoles are the corresponding derivatives of Gaussian distributions.
+/// A point charge is recovered with zero width.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::pairwise::Gaussian;
+/// let gaussian = Gaussian::new(1.0, 1.0);
+/// assert_relative_eq!(gaussian.ion_ion_energy(1.0, 1.0, 20.0), 1.0 / 20.0);
+/// assert_relative_eq!(gaussian.ion_ion_energy(1.0, 1.0, 0.0), 1.0 / f64::sqrt(core::f64::consts::PI));
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gaussian {
+    /// Inverse of the combined width, β
+    beta: f64,
+}
+
+impl Gaussian {
+    /// New pair interaction between Gaussian distributions of widths `width1` and `width2`.
+    pub fn new(width1: f64, width2: f64) -> Self {
+        Self {
+            beta: (2.0 * (width1 * width1 + width2 * width2)).sqrt().recip(),
+        }
+    }
+
+    /// Combined width of the pair, $\sqrt{\sigma_1^2 + \sigma_2^2}$
+    pub fn width(&self) -> f64 {
+        (2.0f64.sqrt() * self.beta).recip()
+    }
+
+    /// Reduced radial derivatives of the erf-screened kernel
+    pub(crate) fn derivatives(&self, r: NalgebraVector3, order: usize) -> RadialDerivatives {
+        let s = erf_derivatives(self.beta, r.norm());
+        RadialDerivatives::from_short_range(r, s, 0.0, order)
+    }
+
+    /// Inverse of the combined width scaled by √2, β
+    pub(crate) const fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// Electrostatic potential from a Gaussian charge distribution, smeared by the width of
+    /// the other distribution, UNIT: [ ( input charge ) / ( input length ) ]
+    pub fn ion_potential(&self, charge: f64, distance: f64) -> f64 {
+        if distance == 0.0 {
+            return charge * FRAC_2_SQRT_PI * self.beta;
+        }
+        charge * erf_x(self.beta * distance) / distance
+    }
+
+    /// Interaction energy between two Gaussian charges, UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn ion_ion_energy(&self, charge1: f64, charge2: f64, distance: f64) -> f64 {
+        charge2 * self.ion_potential(charge1, distance)
+    }
+
+    /// Force on Gaussian charge 2 from charge 1, where `r` = r₂ - r₁.
+    ///
+    /// UNIT: [ ( input charge )^2 / ( input length )^2 ]
+    pub fn ion_ion_force(&self, charge1: f64, charge2: f64, r: impl Into<Vector3>) -> Vector3 {
+        let r: NalgebraVector3 = r.into().into();
+        if r.norm_squared() == 0.0 {
+            return NalgebraVector3::zeros().into();
+        }
+        (-charge1 * charge2 * self.derivatives(r, 1).t[1] * r).into()
+    }
+
+    /// Interaction energy between a Gaussian charge and a Gaussian dipole, where `r` = r_μ - r_z.
+    ///
+    /// UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn ion_dipole_energy(
+        &self,
+        charge: f64,
+        dipole: impl Into<Vector3>,
+        r: impl Into<Vector3>,
+    ) -> f64 {
+        let r: NalgebraVector3 = r.into().into();
+        if r.norm_squared() == 0.0 {
+            return 0.0;
+        }
+        let dipole: NalgebraVector3 = dipole.into().into();
+        charge * self.derivatives(r, 1).t[1] * dipole.dot(&r)
+    }
+
+    /// Interaction energy between two Gaussian dipoles, where `r` = r₂ - r₁.
+    ///
+    /// UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn dipole_dipole_energy(
+        &self,
+        dipole1: impl Into<Vector3>,
+        dipole2: impl Into<Vector3>,
+        r: impl Into<Vector3>,
+    ) -> f64 {
+        let dipole1: NalgebraVector3 = dipole1.into().into();
+        let dipole2: NalgebraVector3 = dipole2.into().into();
+        let r: NalgebraVector3 = r.into().into();
+        if r.norm_squared() == 0.0 {
+            return 2.0 * FRAC_2_SQRT_PI * self.beta.powi(3) / 3.0 * dipole1.dot(&dipole2);
+        }
+        -dipole1.dot(&(self.derivatives(r, 2).rank2() * dipole2))
+    }
+
+    /// Self-energy of a Gaussian charge of given width, $q^2 / (2\sqrt{\pi}\sigma)$.
+    ///
+    /// UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn ion_self_energy(charge: f64, width: f64) -> f64 {
+        0.5 * Self::new(width, width).ion_ion_energy(charge, charge, 0.0)
+    }
+
+    /// Self-energy of a Gaussian dipole of given width, $\mu^2 / (12\sqrt{\pi}\sigma^3)$.
+    ///
+    /// UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn dipole_self_energy(dipole: impl Into<Vector3>, width: f64) -> f64 {
+        let dipole = dipole.into();
+        0.5 * Self::new(width, width).dipole_dipole_energy(dipole, dipole, [0.0; 3])
+    }
+}
+
+#[test]
+fn test_gaussian() {
+    use crate::test_utils::{erfc_step as step, ERFC_ERROR};
+    use approx::assert_relative_eq;
+    use core::f64::consts::PI;
+    let gaussian = Gaussian::new(0.8, 1.3);
+    assert_relative_eq!(gaussian.width(), f64::hypot(0.8, 1.3));
+    let (z1, z2) = (1.5, -0.7);
+    let mu1 = NalgebraVector3::new(0.3, -1.2, 0.8);
+    let mu2 = NalgebraVector3::new(-0.9, 0.4, 1.1);
+    let r = NalgebraVector3::new(0.9, -0.4, 1.2);
+
+    // Forces and energies are consistent, where an approximate `erf_x` adds an error to the
+    // difference quotient of up to the bound times the bare energy over the step
+    let h = step(1e-6, 1e-3);
+    let energy = |r: NalgebraVector3| gaussian.ion_ion_energy(z1, z2, r.norm());
+    let force = NalgebraVector3::from(gaussian.ion_ion_force(z1, z2, r));
+    for a in 0..3 {
+        let dr = NalgebraVector3::ith(a, h);
+        let gradient = (energy(r + dr) - energy(r - dr)) / (2.0 * h);
+        assert_relative_eq!(
+            force[a],
+            -gradient,
+            epsilon = 1e-8 + ERFC_ERROR * (z1 * z2 / r.norm()).abs() / h
+        );
+    }
+
+    // Dipoles approach the limit of two nearby opposite charges
+    let d = step(1e-4, 1e-2);
+    let dipole_as_charges = |r: NalgebraVector3, mu: NalgebraVector3| {
+        let q = mu.norm() / d;
+        let n = mu.normalize() * 0.5 * d;
+        q * (gaussian.ion_potential(z1, (r + n).norm())
+            - gaussian.ion_potential(z1, (r - n).norm()))
+    };
+    assert_relative_eq!(
+        gaussian.ion_dipole_energy(z1, mu1, r),
+        dipole_as_charges(r, mu1),
+        max_relative = 1e-6 + (d / r.norm()).powi(2),
+        epsilon = 2.0 * ERFC_ERROR * (z1 * mu1.norm() / r.norm()).abs() / d
+    );
+    let mut dipole_dipole = 0.0;
+    for s in [-1.0, 1.0] {
+        let q = s * mu1.norm() / d;
+        let p = r - mu1.normalize() * s * 0.5 * d;
+        dipole_dipole += gaussian.ion_dipole_energy(q, mu2, p);
+    }
+    assert_relative_eq!(
+        gaussian.dipole_dipole_energy(mu1, mu2, r),
+        dipole_dipole,
+        max_relative = 1e-6 + (d / r.norm()).powi(2),
+        epsilon = 2.0 * ERFC_ERROR * mu1.norm() * mu2.norm() / r.norm().powi(2) / d
+    );
+
+    // Coulomb at long range
+    let far = r * 20.0;
+    assert_relative_eq!(
+        gaussian.ion_ion_energy(z1, z2, far.norm()),
+        z1 * z2 / far.norm()
+    );
+    assert_relative_eq!(
+        gaussian.dipole_dipole_energy(mu1, mu2, far),
+        (mu1.dot(&mu2) - 3.0 * mu1.dot(&far) * mu2.dot(&far) / far.norm_squared())
+            / far.norm().powi(3),
+        max_relative = 1e-10
+    );
+
+    // Self-energies and the contact limit
+    let width = 0.6;
+    assert_relative_eq!(
+        Gaussian::ion_self_energy(z1, width),
+        z1 * z1 / (2.0 * PI.sqrt() * width)
+    );
+    assert_relative_eq!(
+        Gaussian::dipole_self_energy(mu1, width),
+        mu1.norm_squared() / (12.0 * PI.sqrt() * width.powi(3))
+    );
+    // The contact limit is approached quadratically in the separation, while an approximate
+    // `erf_x` adds an error up to the bound times the bare energy, ∝ r⁻³
+    let pair = Gaussian::new(width, width);
+    let r = if ERFC_ERROR > 0.0 { 0.1 } else { 1e-3 };
+    let self_energy = 2.0 * Gaussian::dipole_self_energy(mu1, width);
+    assert_relative_eq!(
+        pair.dipole_dipole_energy(mu1, mu1, NalgebraVector3::new(r, 0.0, 0.0)),
+        self_energy,
+        epsilon = (r / width).powi(2) * self_energy + ERFC_ERROR * mu1.norm_squared() / r.powi(3)
+    );
+}
@@ -31,6 +31,30 @@
 //! The generic Coulomb energy is recovered with
 //! $S(q) = 1$, $r_c = \infty$, and $\kappa = 0$.
 //!
+//! ## Multipoles
+//!
+//! Point charges (ions), dipoles, and quadrupoles are supported through the traits
+//! below. Quadrupoles are second moments of the charge distribution,
+//! $Q_{ab} = \sum_i q_i r_{i,a} r_{i,b}$.
+//!
+//! Source     | Potential              | Field              | Energy with ion           | Force on ion
+//! ---------- | ---------------------- | ------------------ | ------------------------- | -------------------------
+//! Ion        | `ion_potential`        | `ion_field`        | `ion_ion_energy`          | `ion_ion_force`
+//! Dipole     | `dipole_potential`     | `dipole_field`     | `ion_dipole_energy`       | `ion_dipole_force`
+//! Quadrupole | `quadrupole_potential` | `quadrupole_field` | `ion_quadrupole_energy`   | `ion_quadrupole_force`
+//!
+//! Particles carrying several moments can be described by a [`Multipole`] and all cross terms
+//! summed with [`MultipoleEnergy::pair_energy`] and [`MultipoleForce::pair_force`].
+//! Smeared charges with Gaussian and exponential distributions are described by [`Gaussian`]
+//! and [`Slater`].
+//! Coupling-parameter derivatives for thermodynamic integration are provided by [`Coupling`]
+//! and [`SoftCore`].
+//! The distance-dependent dielectric heuristics, $\varepsilon(r) \propto r$ and a sigmoidal
+//! $\varepsilon(r)$, are available as [`LinearDielectric`] and [`SigmoidalDielectric`].
+//! Screening from a hard-core contact distance, as used for colloids and proteins, is
+//! available as [`OffsetYukawa`].
+//! Schemes can be selected at runtime, _e.g._ from a configuration file, with [`SchemeKind`].
+//!
 //! ## Examples
 //! ~~~
 //! # use approx::assert_relative_eq;
@@ -42,20 +66,37 @@
 //! assert_relative_eq!(plain.ion_potential(charge, distance), charge / distance);
 //! ~~~
 
+mod coupling;
 mod energy;
 mod field;
 mod force;
+mod gaussian;
+mod multipole;
 mod potential;
 mod schemes;
+mod slater;
+pub(crate) mod tensor;
 use crate::DebyeLength;
 pub use schemes::{
-    ewald::*, ewald_truncated::EwaldTruncated, plain::Plain, poisson::*,
+    distance_dependent::{LinearDielectric, SigmoidalDielectric},
+    ewald::*,
+    ewald_truncated::EwaldTruncated,
+    offset_yukawa::OffsetYukawa,
+    plain::Plain,
+    poisson::*,
     reactionfield::ReactionField,
+    scheme_kind::SchemeKind,
 };
 
 pub use {
-    energy::MultipoleEnergy, field::MultipoleField, force::MultipoleForce,
+    coupling::{Coupling, SoftCore},
+    energy::MultipoleEnergy,
+    field::MultipoleField,
+    force::MultipoleForce,
+    gaussian::Gaussian,
+    multipole::{MomentOrigin, Multipole},
     potential::MultipolePotential,
+    slater::Slater,
 };
 #[cfg(feature = "uom")]
 mod uom;
@@ -143,7 +184,18 @@ pub trait ShortRangeFunction: DebyeLength {
         (self.short_range_f2(q + EPS) - self.short_range_f2(q - EPS)) / (2.0 * EPS)
     }
 
-    /// Prefactors for the self-energy of monopoles and dipoles.
+    /// Fourth derivative of the short-range function, 𝑑⁴𝑆(𝑞)/𝑑𝑞⁴.
+    ///
+    /// This is needed only for quadrupole-quadrupole and other higher order interactions.
+    /// The default implementation uses a numerical central difference of
+    /// `short_range_f3`. For better performance, this should be
+    /// overridden with an analytical expression.
+    fn short_range_f4(&self, q: f64) -> f64 {
+        const EPS: f64 = 1e-6;
+        (self.short_range_f3(q + EPS) - self.short_range_f3(q - EPS)) / (2.0 * EPS)
+    }
+
+    /// Prefactors for the self-energy of monopoles, dipoles and quadrupoles.
     ///
     /// If a prefactor is `None` the self-energy is not calculated. Self-energies
     /// are normally important only when inserting or deleting particles
@@ -157,7 +209,7 @@ pub trait ShortRangeFunction: DebyeLength {
     }
 }
 
-/// Prefactors for calculating the self-energy of monopoles and dipoles
+/// Prefactors for calculating the self-energy of monopoles, dipoles and quadrupoles
 ///
 /// Some short-range functions warrent a self-energy on multipoles. This
 /// is important for systems where the number of particles fluctuates, e.g.
@@ -169,11 +221,14 @@ pub struct SelfEnergyPrefactors {
     monopole: Option<f64>,
     /// Prefactor for the self-energy of dipoles, _c2_.
     dipole: Option<f64>,
+    /// Prefactor for the self-energy of quadrupoles, _c3_.
+    quadrupole: Option<f64>,
 }
 
 /// Test electric constant
 #[cfg(test)]
 mod tests {
+    use crate::test_utils::{erfc_step as step, ERFC_ERROR};
     use approx::assert_relative_eq;
 
     #[test]
@@ -189,4 +244,588 @@ mod tests {
             2.4460467895137676 // In kJ/mol, roughly 1 KT at room temperature
         );
     }
+
+    #[test]
+    fn test_quadrupole_quadrupole_energy() {
+        use crate::pairwise::*;
+        use crate::{NalgebraMatrix3 as Matrix3, NalgebraVector3 as Vector3};
+
+        let quad1 = Matrix3::new(3.0, 7.0, 8.0, 5.0, 9.0, 6.0, 2.0, 1.0, 4.0);
+        let quad2 = Matrix3::new(1.0, -2.0, 0.5, -2.0, 3.0, 1.0, 0.5, 1.0, -4.0);
+        let r = Vector3::new(6.0, -3.0, 4.0);
+
+        // u = ½ ∑ Q₁ ∂∂Φ(Q₂) using finite differences of the quadrupole potential
+        fn check(pot: &impl MultipoleEnergy, quad1: Matrix3, quad2: Matrix3, r: Vector3) {
+            let h = step(1e-3, 3e-2);
+            let phi = |x: Vector3| pot.quadrupole_potential(quad2, -r + x);
+            let mut hessian = Matrix3::zeros();
+            for a in 0..3 {
+                for b in 0..3 {
+                    let (ea, eb) = (Vector3::ith(a, h), Vector3::ith(b, h));
+                    hessian[(a, b)] = (phi(ea + eb) - phi(ea - eb) - phi(eb - ea) + phi(-ea - eb))
+                        / (4.0 * h * h);
+                }
+            }
+            let expected = 0.5 * quad1.dot(&hessian);
+            let energy = pot.quadrupole_quadrupole_energy(quad1, quad2, r);
+            // Second differences of the bare potential, |Q₂|/r³, contracted with Q₁
+            let bare = 1.5 * quad1.norm() * quad2.norm() / r.norm().powi(3);
+            assert_relative_eq!(
+                energy,
+                expected,
+                max_relative = 1e-5,
+                epsilon = ERFC_ERROR * bare / (h * h)
+            );
+        }
+        check(&Plain::new(20.0, Some(7.0)), quad1, quad2, r);
+        check(&Stenqvist::new(20.0, Some(7.0)), quad1, quad2, r);
+        check(&RealSpaceEwald::new(20.0, 0.15, Some(7.0)), quad1, quad2, r);
+        check(&EwaldTruncated::new(20.0, 0.15), quad1, quad2, r);
+        check(
+            &ReactionField::new_shifted(20.0, 80.0, 2.0),
+            quad1,
+            quad2,
+            r,
+        );
+    }
+
+    #[test]
+    fn test_ion_quadrupole_consistency() {
+        use crate::pairwise::*;
+        use crate::{NalgebraMatrix3 as Matrix3, NalgebraVector3 as Vector3};
+
+        let quad = Matrix3::new(3.0, 7.0, 8.0, 5.0, 9.0, 6.0, 2.0, 1.0, 4.0);
+        let charge = -2.0;
+        let r = Vector3::new(6.0, -3.0, 4.0);
+
+        // E = -∇Φ and F = z·E, where Φ is the quadrupole potential
+        fn check(pot: &impl MultipoleEnergy, charge: f64, quad: Matrix3, r: Vector3) {
+            let h = step(1e-5, 1e-2);
+            let gradient = Vector3::from_fn(|a, _| {
+                let dr = Vector3::ith(a, h);
+                (pot.quadrupole_potential(quad, r + dr) - pot.quadrupole_potential(quad, r - dr))
+                    / (2.0 * h)
+            });
+            let field: Vector3 = pot.quadrupole_field(quad, r).into();
+            let force: Vector3 = pot.ion_quadrupole_force(charge, quad, r).into();
+            let bare = quad.norm() / r.norm().powi(3);
+            for a in 0..3 {
+                assert_relative_eq!(
+                    field[a],
+                    -gradient[a],
+                    max_relative = 1e-6,
+                    epsilon = ERFC_ERROR * bare / h
+                );
+                assert_relative_eq!(force[a], charge * field[a], max_relative = 1e-12);
+            }
+            assert_relative_eq!(
+                pot.ion_quadrupole_energy(charge, quad, -r),
+                charge * pot.quadrupole_potential(quad, r),
+                max_relative = 1e-12
+            );
+        }
+        check(&Plain::new(20.0, Some(7.0)), charge, quad, r);
+        check(&Stenqvist::new(20.0, Some(7.0)), charge, quad, r);
+        check(&RealSpaceEwald::new(20.0, 0.15, Some(7.0)), charge, quad, r);
+        check(&EwaldTruncated::new(20.0, 0.15), charge, quad, r);
+        check(
+            &ReactionField::new_shifted(20.0, 80.0, 2.0),
+            charge,
+            quad,
+            r,
+        );
+    }
+
+    #[test]
+    fn test_dipole_quadrupole_consistency() {
+        use crate::pairwise::*;
+        use crate::{NalgebraMatrix3 as Matrix3, NalgebraVector3 as Vector3};
+
+        let quad = Matrix3::new(3.0, 7.0, 8.0, 5.0, 9.0, 6.0, 2.0, 1.0, 4.0);
+        let dipole = Vector3::new(19.0, 7.0, 11.0);
+        let r = Vector3::new(6.0, -3.0, 4.0);
+
+        // u = -μ·E(Q) and F = -∇u
+        fn check(
+            pot: &(impl MultipoleEnergy + MultipoleForce),
+            mu: Vector3,
+            quad: Matrix3,
+            r: Vector3,
+        ) {
+            let field: Vector3 = pot.quadrupole_field(quad, -r).into();
+            let energy = pot.dipole_quadrupole_energy(mu, quad, r);
+            assert_relative_eq!(energy, -mu.dot(&field), max_relative = 1e-10);
+
+            let h = step(1e-5, 1e-2);
+            let force: Vector3 = pot.dipole_quadrupole_force(mu, quad, -r).into();
+            let bare = mu.norm() * quad.norm() / r.norm().powi(4);
+            for a in 0..3 {
+                let dr = Vector3::ith(a, h);
+                let gradient = (pot.dipole_quadrupole_energy(mu, quad, r - dr)
+                    - pot.dipole_quadrupole_energy(mu, quad, r + dr))
+                    / (2.0 * h);
+                assert_relative_eq!(
+                    force[a],
+                    -gradient,
+                    max_relative = 1e-6,
+                    epsilon = ERFC_ERROR * bare / h
+                );
+            }
+        }
+        check(&Plain::new(20.0, Some(7.0)), dipole, quad, r);
+        check(&Stenqvist::new(20.0, Some(7.0)), dipole, quad, r);
+        check(&RealSpaceEwald::new(20.0, 0.15, Some(7.0)), dipole, quad, r);
+        check(&EwaldTruncated::new(20.0, 0.15), dipole, quad, r);
+        check(
+            &ReactionField::new_shifted(20.0, 80.0, 2.0),
+            dipole,
+            quad,
+            r,
+        );
+    }
+
+    #[test]
+    fn test_field_gradient() {
+        use crate::pairwise::*;
+        use crate::{NalgebraMatrix3 as Matrix3, NalgebraVector3 as Vector3};
+
+        let charge = -2.0;
+        let dipole = Vector3::new(19.0, 7.0, 11.0);
+        let r = Vector3::new(6.0, -3.0, 4.0);
+
+        // Compare with finite differences of the field
+        fn check(pot: &impl MultipoleField, charge: f64, dipole: Vector3, r: Vector3) {
+            let h = step(1e-5, 1e-2);
+            let ion_bare = charge.abs() / r.norm_squared();
+            let dipole_bare = dipole.norm() / r.norm().powi(3);
+            let ion_gradient: Matrix3 = pot.ion_field_gradient(charge, r).into();
+            let dipole_gradient: Matrix3 = pot.dipole_field_gradient(dipole, r).into();
+            for b in 0..3 {
+                let dr = Vector3::ith(b, h);
+                let ion_diff = (Vector3::from(pot.ion_field(charge, r + dr))
+                    - Vector3::from(pot.ion_field(charge, r - dr)))
+                    / (2.0 * h);
+                let dipole_diff = (Vector3::from(pot.dipole_field(dipole, r + dr))
+                    - Vector3::from(pot.dipole_field(dipole, r - dr)))
+                    / (2.0 * h);
+                for a in 0..3 {
+                    assert_relative_eq!(
+                        ion_gradient[(a, b)],
+                        ion_diff[a],
+                        epsilon = 1e-9 + ERFC_ERROR * ion_bare / h
+                    );
+                    assert_relative_eq!(
+                        dipole_gradient[(a, b)],
+                        dipole_diff[a],
+                        epsilon = 1e-8 + ERFC_ERROR * dipole_bare / h
+                    );
+                }
+            }
+        }
+        check(&Plain::new(20.0, Some(7.0)), charge, dipole, r);
+        check(&Stenqvist::new(20.0, Some(7.0)), charge, dipole, r);
+        check(
+            &RealSpaceEwald::new(20.0, 0.15, Some(7.0)),
+            charge,
+            dipole,
+            r,
+        );
+        check(&EwaldTruncated::new(20.0, 0.15), charge, dipole, r);
+        check(
+            &ReactionField::new_shifted(20.0, 80.0, 2.0),
+            charge,
+            dipole,
+            r,
+        );
+
+        // Traceless in the absence of screening (Laplace equation)
+        let plain = Plain::without_cutoff();
+        let gradient: Matrix3 = plain.dipole_field_gradient(dipole, r).into();
+        assert_relative_eq!(gradient.trace(), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(gradient, gradient.transpose(), epsilon = 1e-12);
+    }
+
+    /// Magnitude, m₁m₂/r with m = |z| + |μ|/r + |Q|/r², bounding the pair energy without damping
+    fn bare_pair_energy(
+        a: &crate::pairwise::Multipole,
+        b: &crate::pairwise::Multipole,
+        r: crate::NalgebraVector3,
+    ) -> f64 {
+        let r = r.norm();
+        let moments = |m: &crate::pairwise::Multipole| {
+            m.charge.abs()
+                + m.dipole
+                    .map_or(0.0, |mu| crate::NalgebraVector3::from(mu).norm() / r)
+                + m.quadrupole.map_or(0.0, |quad| {
+                    crate::NalgebraMatrix3::from(quad).norm() / r.powi(2)
+                })
+        };
+        moments(a) * moments(b) / r
+    }
+
+    #[test]
+    fn test_pair_energy_and_force() {
+        use crate::pairwise::*;
+        use crate::{NalgebraMatrix3 as Matrix3, NalgebraVector3 as Vector3};
+
+        let a = Multipole::new(-2.0)
+            .with_dipole([1.9, 0.7, 1.1])
+            .with_quadrupole(Matrix3::new(3.0, 0.7, 0.8, 0.7, 0.9, 0.6, 0.8, 0.6, 4.0));
+        let b = Multipole::new(1.0)
+            .with_dipole([-0.4, 1.2, 0.3])
+            .with_quadrupole(Matrix3::new(1.0, -0.2, 0.5, -0.2, 3.0, 0.1, 0.5, 0.1, -4.0));
+        let r = Vector3::new(6.0, -3.0, 4.0);
+
+        // Energy is the sum of all cross terms and the force is minus its gradient
+        fn check(
+            pot: &(impl MultipoleEnergy + MultipoleForce),
+            a: Multipole,
+            b: Multipole,
+            r: Vector3,
+        ) {
+            let (za, zb) = (a.charge, b.charge);
+            let (mu_a, mu_b) = (a.dipole.unwrap(), b.dipole.unwrap());
+            let (quad_a, quad_b) = (a.quadrupole.unwrap(), b.quadrupole.unwrap());
+            let expected = pot.ion_ion_energy(za, zb, r.norm())
+                + pot.ion_dipole_energy(za, mu_b, r)
+                + pot.ion_dipole_energy(zb, mu_a, -r)
+                + pot.dipole_dipole_energy(mu_a, mu_b, r)
+                + pot.ion_quadrupole_energy(za, quad_b, r)
+                + pot.ion_quadrupole_energy(zb, quad_a, -r)
+                + pot.dipole_quadrupole_energy(mu_a, quad_b, r)
+                + pot.dipole_quadrupole_energy(mu_b, quad_a, -r)
+                + pot.quadrupole_quadrupole_energy(quad_a, quad_b, r);
+            assert_relative_eq!(pot.pair_energy(&a, &b, r), expected, max_relative = 1e-12);
+            assert_relative_eq!(pot.pair_energy(&b, &a, -r), expected, max_relative = 1e-12);
+
+            // Quadrupole-quadrupole forces are not included
+            let a = Multipole {
+                quadrupole: None,
+                ..a
+            };
+            let h = step(1e-5, 1e-2);
+            let force: Vector3 = pot.pair_force(&a, &b, r).into();
+            let reverse: Vector3 = pot.pair_force(&b, &a, -r).into();
+            let bare = bare_pair_energy(&a, &b, r);
+            for i in 0..3 {
+                let dr = Vector3::ith(i, h);
+                let gradient =
+                    (pot.pair_energy(&a, &b, r + dr) - pot.pair_energy(&a, &b, r - dr)) / (2.0 * h);
+                let epsilon = ERFC_ERROR * bare / h;
+                assert_relative_eq!(force[i], -gradient, max_relative = 1e-6, epsilon = epsilon);
+                assert_relative_eq!(reverse[i], gradient, max_relative = 1e-6, epsilon = epsilon);
+            }
+
+            // Virial is minus the energy derivative under uniform scaling
+            let virial: Matrix3 = pot.pair_virial(&a, &b, r).into();
+            assert_relative_eq!(virial, force * r.transpose(), max_relative = 1e-12);
+            let scale = h / r.norm();
+            let derivative = (pot.pair_energy(&a, &b, r * (1.0 + scale))
+                - pot.pair_energy(&a, &b, r * (1.0 - scale)))
+                / (2.0 * scale);
+            assert_relative_eq!(
+                virial.trace(),
+                -derivative,
+                max_relative = 1e-6,
+                epsilon = ERFC_ERROR * bare / scale
+            );
+        }
+        check(&Plain::new(20.0, Some(7.0)), a, b, r);
+        check(&Stenqvist::new(20.0, Some(7.0)), a, b, r);
+        check(&RealSpaceEwald::new(20.0, 0.15, Some(7.0)), a, b, r);
+        check(&EwaldTruncated::new(20.0, 0.15), a, b, r);
+        check(&ReactionField::new_shifted(20.0, 80.0, 2.0), a, b, r);
+    }
+
+    #[test]
+    fn test_torque() {
+        use crate::pairwise::*;
+        use crate::{NalgebraMatrix3 as Matrix3, NalgebraVector3 as Vector3};
+        use nalgebra::Rotation3;
+
+        let source = Multipole::new(-2.0)
+            .with_dipole([1.9, 0.7, 1.1])
+            .with_quadrupole(Matrix3::new(3.0, 0.7, 0.8, 0.7, 0.9, 0.6, 0.8, 0.6, 4.0));
+        let dipole = Vector3::new(-0.4, 1.2, 0.3);
+        let quad = Matrix3::new(1.0, -0.2, 0.5, -0.2, 3.0, 0.1, 0.5, 0.1, -4.0);
+        let r = Vector3::new(6.0, -3.0, 4.0);
+
+        // Torque is minus the derivative of the energy with respect to rotation angle
+        fn check(
+            pot: &(impl MultipoleEnergy + MultipoleForce),
+            source: Multipole,
+            dipole: Vector3,
+            quad: Matrix3,
+            r: Vector3,
+        ) {
+            let h = step(1e-5, 1e-2);
+            let dipole_torque: Vector3 = pot.dipole_torque(dipole, &source, r).into();
+            let quad_torque: Vector3 = pot.quadrupole_torque(quad, &source, r).into();
+            for i in 0..3 {
+                let rotate = |angle: f64| Rotation3::from_axis_angle(&Vector3::ith_axis(i), angle);
+                let dipole_energy = |angle: f64| {
+                    let target = Multipole::new(0.0).with_dipole(rotate(angle) * dipole);
+                    pot.pair_energy(&source, &target, r)
+                };
+                let quad_energy = |angle: f64| {
+                    let rotation = rotate(angle);
+                    let target =
+                        Multipole::new(0.0).with_quadrupole(rotation * quad * rotation.transpose());
+                    pot.pair_energy(&source, &target, r)
+                };
+                let dipole_diff = (dipole_energy(h) - dipole_energy(-h)) / (2.0 * h);
+                let quad_diff = (quad_energy(h) - quad_energy(-h)) / (2.0 * h);
+                let dipole_bare =
+                    bare_pair_energy(&source, &Multipole::new(0.0).with_dipole(dipole), r);
+                let quad_bare =
+                    bare_pair_energy(&source, &Multipole::new(0.0).with_quadrupole(quad), r);
+                assert_relative_eq!(
+                    dipole_torque[i],
+                    -dipole_diff,
+                    epsilon = 1e-9 + ERFC_ERROR * dipole_bare / h
+                );
+                assert_relative_eq!(
+                    quad_torque[i],
+                    -quad_diff,
+                    epsilon = 1e-9 + ERFC_ERROR * quad_bare / h
+                );
+            }
+
+            // Quadrupole field gradient from finite differences of the field
+            let gradient: Matrix3 = pot.quadrupole_field_gradient(quad, r).into();
+            let bare = quad.norm() / r.norm().powi(4);
+            for b in 0..3 {
+                let dr = Vector3::ith(b, h);
+                let diff = (Vector3::from(pot.quadrupole_field(quad, r + dr))
+                    - Vector3::from(pot.quadrupole_field(quad, r - dr)))
+                    / (2.0 * h);
+                for a in 0..3 {
+                    assert_relative_eq!(
+                        gradient[(a, b)],
+                        diff[a],
+                        epsilon = 1e-9 + ERFC_ERROR * bare / h
+                    );
+                }
+            }
+        }
+        check(&Plain::new(20.0, Some(7.0)), source, dipole, quad, r);
+        check(&Stenqvist::new(20.0, Some(7.0)), source, dipole, quad, r);
+        check(
+            &RealSpaceEwald::new(20.0, 0.15, Some(7.0)),
+            source,
+            dipole,
+            quad,
+            r,
+        );
+        check(&EwaldTruncated::new(20.0, 0.15), source, dipole, quad, r);
+        check(
+            &ReactionField::new_shifted(20.0, 80.0, 2.0),
+            source,
+            dipole,
+            quad,
+            r,
+        );
+    }
+
+    #[test]
+    fn test_hessian() {
+        use crate::pairwise::*;
+        use crate::{NalgebraMatrix3 as Matrix3, NalgebraVector3 as Vector3};
+
+        let (charge1, charge2) = (-2.0, 1.5);
+        let dipole = Vector3::new(1.9, 0.7, 1.1);
+        let r = Vector3::new(6.0, -3.0, 4.0);
+
+        // Compare with finite differences of the energy
+        fn check(pot: &impl MultipoleEnergy, z1: f64, z2: f64, mu: Vector3, r: Vector3) {
+            let h = step(1e-4, 3e-2);
+            let ion_ion: Matrix3 = pot.ion_ion_hessian(z1, z2, r).into();
+            let ion_dipole: Matrix3 = pot.ion_dipole_hessian(z1, mu, r).into();
+            let u_ion = |x: Vector3| pot.ion_ion_energy(z1, z2, (r + x).norm());
+            let u_dipole = |x: Vector3| pot.ion_dipole_energy(z1, mu, r + x);
+            let diff = |u: &dyn Fn(Vector3) -> f64, a: usize, b: usize| {
+                let (ea, eb) = (Vector3::ith(a, h), Vector3::ith(b, h));
+                (u(ea + eb) - u(ea - eb) - u(eb - ea) + u(-ea - eb)) / (4.0 * h * h)
+            };
+            // Second differences of the bare energies
+            let ion_bare = (z1 * z2).abs() / r.norm();
+            let dipole_bare = z1.abs() * mu.norm() / r.norm_squared();
+            for a in 0..3 {
+                for b in 0..3 {
+                    assert_relative_eq!(
+                        ion_ion[(a, b)],
+                        diff(&u_ion, a, b),
+                        epsilon = 1e-8 + ERFC_ERROR * ion_bare / (h * h)
+                    );
+                    assert_relative_eq!(
+                        ion_dipole[(a, b)],
+                        diff(&u_dipole, a, b),
+                        epsilon = 1e-8 + ERFC_ERROR * dipole_bare / (h * h)
+                    );
+                }
+            }
+        }
+        check(&Plain::new(20.0, Some(7.0)), charge1, charge2, dipole, r);
+        check(
+            &Stenqvist::new(20.0, Some(7.0)),
+            charge1,
+            charge2,
+            dipole,
+            r,
+        );
+        check(
+            &RealSpaceEwald::new(20.0, 0.15, Some(7.0)),
+            charge1,
+            charge2,
+            dipole,
+            r,
+        );
+        check(
+            &EwaldTruncated::new(20.0, 0.15),
+            charge1,
+            charge2,
+            dipole,
+            r,
+        );
+        check(
+            &ReactionField::new_shifted(20.0, 80.0, 2.0),
+            charge1,
+            charge2,
+            dipole,
+            r,
+        );
+    }
+
+    #[test]
+    fn test_self_energy_limit() {
+        use crate::pairwise::*;
+        use crate::{NalgebraMatrix3 as Matrix3, NalgebraVector3 as Vector3};
+
+        // The self-energy is half the short-range correction to the bare pair energy at zero separation
+        fn check(pot: &impl MultipoleEnergy, alpha: f64, debye_length: Option<f64>) {
+            let plain = Plain::new(f64::INFINITY, debye_length);
+            let (z, mu) = (1.5, Vector3::new(0.3, -0.2, 0.6));
+            let quad = Matrix3::new(1.0, -0.2, 0.5, -0.2, 3.0, 0.1, 0.5, 0.1, -4.0);
+            let direction = Vector3::new(0.8, -0.36, 0.48);
+            // The limit is approached quadratically in αr, while the error of an approximate
+            // `erfc_x` grows as the bare energy, ∝ r⁻¹, r⁻³, and r⁻⁵ for increasing multipole
+            // order, so that larger separations are used for higher orders if approximate.
+            let separation =
+                |exact, approximate| if ERFC_ERROR > 0.0 { approximate } else { exact };
+            let tolerance = |self_energy: f64, r: f64, bare: f64| {
+                2.0 * (alpha * r).powi(2) * self_energy.abs() + 0.5 * ERFC_ERROR * bare
+            };
+
+            let r = 0.01;
+            let ion = 0.5
+                * (pot.ion_ion_energy(z, z, r) - MultipoleEnergy::ion_ion_energy(&plain, z, z, r));
+            let self_energy = pot.self_energy(&[z], &[], &[]);
+            assert_relative_eq!(
+                self_energy,
+                ion,
+                epsilon = tolerance(self_energy, r, z * z / r)
+            );
+
+            let r = separation(0.02, 0.1);
+            let dipole = 0.5
+                * (pot.dipole_dipole_energy(mu, mu, direction * r)
+                    - plain.dipole_dipole_energy(mu, mu, direction * r));
+            let self_energy = pot.self_energy(&[], &[mu.norm()], &[]);
+            assert_relative_eq!(
+                self_energy,
+                dipole,
+                epsilon = tolerance(self_energy, r, mu.norm_squared() / r.powi(3))
+            );
+
+            let r = separation(0.05, 0.3);
+            let quadrupole = 0.5
+                * (pot.quadrupole_quadrupole_energy(quad, quad, direction * r)
+                    - plain.quadrupole_quadrupole_energy(quad, quad, direction * r));
+            let self_energy = pot.self_energy(&[], &[], &[quad.into()]);
+            assert_relative_eq!(
+                self_energy,
+                quadrupole,
+                epsilon = tolerance(self_energy, r, quad.norm_squared() / r.powi(5))
+            );
+        }
+        check(&RealSpaceEwald::new(10.0, 0.5, None), 0.5, None);
+        check(&RealSpaceEwald::new(10.0, 0.5, Some(3.0)), 0.5, Some(3.0));
+        check(&EwaldTruncated::new(10.0, 0.5), 0.5, None);
+    }
+
+    #[test]
+    fn test_charge_derivative() {
+        use crate::pairwise::*;
+        use crate::{NalgebraVector3, Vector3};
+
+        fn check(pot: &impl MultipoleEnergy) {
+            let positions: Vec<Vector3> = vec![
+                [0.0, 0.0, 0.0].into(),
+                [3.0, 1.0, -2.0].into(),
+                [-2.5, 4.0, 1.0].into(),
+                [1.0, -6.0, 5.0].into(),
+            ];
+            let mut charges = vec![1.0, -0.5, 2.0, -1.5];
+            let energy = |charges: &[f64]| {
+                let mut energy = pot.self_energy(charges, &[], &[]);
+                for i in 0..charges.len() {
+                    for j in (i + 1)..charges.len() {
+                        let r = (NalgebraVector3::from(positions[j])
+                            - NalgebraVector3::from(positions[i]))
+                        .norm();
+                        energy += pot.ion_ion_energy(charges[i], charges[j], r);
+                    }
+                }
+                energy
+            };
+            let h = 1e-6;
+            for i in 0..charges.len() {
+                let derivative = pot.d_energy_d_charge(i, &positions, &charges);
+                charges[i] += h;
+                let forward = energy(&charges);
+                charges[i] -= 2.0 * h;
+                let backward = energy(&charges);
+                charges[i] += h;
+                assert_relative_eq!(derivative, (forward - backward) / (2.0 * h), epsilon = 1e-8);
+            }
+        }
+        check(&Plain::new(20.0, Some(7.0)));
+        check(&RealSpaceEwald::new(20.0, 0.15, Some(7.0)));
+        check(&EwaldTruncated::new(20.0, 0.15));
+        check(&ReactionField::new_shifted(20.0, 80.0, 2.0));
+    }
+
+    #[test]
+    fn test_site_potentials() {
+        use crate::pairwise::*;
+        use crate::Vector3;
+
+        let positions: Vec<Vector3> = vec![
+            [0.0, 0.0, 0.0].into(),
+            [3.0, 1.0, -2.0].into(),
+            [-2.5, 4.0, 1.0].into(),
+        ];
+        let charges = [1.0, -0.5, 2.0];
+        let scheme = RealSpaceEwald::new(20.0, 0.15, None);
+        let potentials = scheme.site_potentials(&positions, &charges, None);
+        let c1 = scheme.self_energy_prefactors().monopole.unwrap();
+        for (i, potential) in potentials.iter().enumerate() {
+            assert_relative_eq!(
+                *potential,
+                scheme.d_energy_d_charge(i, &positions, &charges) - 2.0 * c1 * charges[i] / 20.0,
+                epsilon = 1e-12
+            );
+        }
+
+        // Minimum image convention in a periodic box
+        let plain = Plain::new(5.0, None);
+        let positions: Vec<Vector3> = vec![[0.5, 0.5, 0.5].into(), [9.5, 0.5, 0.5].into()];
+        let potentials = plain.site_potentials(&positions, &[1.0, -2.0], Some([10.0; 3].into()));
+        assert_relative_eq!(potentials[0], -2.0);
+        assert_relative_eq!(potentials[1], 1.0);
+        assert_eq!(
+            plain.site_potentials(&positions, &[1.0, -2.0], None),
+            vec![0.0, 0.0]
+        );
+    }
 }
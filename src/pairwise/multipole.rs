@@ -0,0 +1,221 @@
+// Copyright 2023 Björn Stenqvist and Mikael Lund
+//
+// Converted to Rust with modification from the C++ library "CoulombGalore":
+// https://zenodo.org/doi/10.5281/zenodo.3522058
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Composite point multipole with charge, dipole and quadrupole moments.
+
+use crate::{Matrix3, NalgebraMatrix3, NalgebraVector3, Vector3};
+
+/// Point multipole with a charge and optional dipole and quadrupole moments.
+///
+/// Used with [`MultipoleEnergy::pair_energy`](super::MultipoleEnergy::pair_energy) and
+/// [`MultipoleForce::pair_force`](super::MultipoleForce::pair_force) to sum all cross terms
+/// between two particles in a single call.
+/// Missing moments are skipped.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::pairwise::{Multipole, MultipoleEnergy, Plain};
+/// let ion = Multipole::new(1.0);
+/// let dipole = Multipole::new(0.0).with_dipole([0.0, 0.0, 2.0]);
+/// let plain = Plain::without_cutoff();
+/// let r = [0.0, 0.0, 4.0];
+/// assert_relative_eq!(
+///     plain.pair_energy(&ion, &dipole, r),
+///     plain.ion_dipole_energy(1.0, [0.0, 0.0, 2.0], r)
+/// );
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Multipole {
+    /// Point charge, UNIT: [ input charge ]
+    pub charge: f64,
+    /// Dipole moment, UNIT: [ ( input length ) x ( input charge ) ]
+    pub dipole: Option<Vector3>,
+    /// Quadrupole moment, UNIT: [ ( input length )^2 x ( input charge ) ]
+    pub quadrupole: Option<Matrix3>,
+}
+
+impl Multipole {
+    /// New multipole with a point charge and no higher moments.
+    pub const fn new(charge: f64) -> Self {
+        Self {
+            charge,
+            dipole: None,
+            quadrupole: None,
+        }
+    }
+
+    /// Set the dipole moment.
+    pub fn with_dipole(mut self, dipole: impl Into<Vector3>) -> Self {
+        self.dipole = Some(dipole.into());
+        self
+    }
+
+    /// Set the quadrupole moment.
+    pub fn with_quadrupole(mut self, quadrupole: impl Into<Matrix3>) -> Self {
+        self.quadrupole = Some(quadrupole.into());
+        self
+    }
+
+    /// Multipole moments of a set of point charges about `origin`.
+    ///
+    /// The dipole moment is $\boldsymbol{\mu} = \sum_i q_i \boldsymbol{r}_i$ and the quadrupole
+    /// moment is the second moment, $Q_{ab} = \sum_i q_i r_{i,a} r_{i,b}$, where $\boldsymbol{r}_i$
+    /// are positions relative to `origin`. See [`MomentOrigin`] for common choices of origin.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `positions` and `charges` differ in length.
+    ///
+    /// # Examples
+    /// ~~~
+    /// # use approx::assert_relative_eq;
+    /// use coulomb::pairwise::{MomentOrigin, Multipole};
+    /// let positions = [[1.0, 0.0, 0.0].into(), [-1.0, 0.0, 0.0].into()];
+    /// let charges = [1.0, -1.0];
+    /// let origin = MomentOrigin::Geometric.position(&positions, &charges).unwrap();
+    /// let multipole = Multipole::from_point_charges(&positions, &charges, origin);
+    /// assert_relative_eq!(multipole.charge, 0.0);
+    /// assert_relative_eq!(multipole.dipole.unwrap().x, 2.0);
+    /// ~~~
+    pub fn from_point_charges(
+        positions: &[Vector3],
+        charges: &[f64],
+        origin: impl Into<Vector3>,
+    ) -> Self {
+        assert_eq!(positions.len(), charges.len());
+        let origin: NalgebraVector3 = origin.into().into();
+        let mut dipole = NalgebraVector3::zeros();
+        let mut quadrupole = NalgebraMatrix3::zeros();
+        for (position, charge) in positions.iter().zip(charges) {
+            let r = NalgebraVector3::from(*position) - origin;
+            dipole += r * *charge;
+            quadrupole += r * r.transpose() * *charge;
+        }
+        Self::new(charges.iter().sum())
+            .with_dipole(dipole)
+            .with_quadrupole(quadrupole)
+    }
+
+    /// Charge, if non-zero.
+    pub(crate) fn nonzero_charge(&self) -> Option<f64> {
+        (self.charge != 0.0).then_some(self.charge)
+    }
+}
+
+impl From<f64> for Multipole {
+    fn from(charge: f64) -> Self {
+        Self::new(charge)
+    }
+}
+
+/// Origin for calculating multipole moments of a set of point charges.
+///
+/// The dipole moment of a neutral set of charges is independent of the origin,
+/// but in general higher moments are not.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MomentOrigin<'a> {
+    /// Center of charge, using absolute charges as weights
+    CenterOfCharge,
+    /// Geometric center, _i.e._ the unweighted mean position
+    Geometric,
+    /// Center of mass with given masses
+    CenterOfMass(&'a [f64]),
+    /// Fixed point
+    Point(Vector3),
+}
+
+impl MomentOrigin<'_> {
+    /// Position of the origin for the given point charges.
+    ///
+    /// Returns `None` if the weights sum to zero, _e.g._ for an empty set or for the
+    /// center of charge of uncharged sites.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `positions`, `charges`, or masses differ in length.
+    pub fn position(&self, positions: &[Vector3], charges: &[f64]) -> Option<Vector3> {
+        assert_eq!(positions.len(), charges.len());
+        let weighted_mean = |weights: &mut dyn Iterator<Item = f64>| {
+            let mut sum = NalgebraVector3::zeros();
+            let mut total_weight = 0.0;
+            for (position, weight) in positions.iter().zip(weights) {
+                sum += NalgebraVector3::from(*position) * weight;
+                total_weight += weight;
+            }
+            (total_weight != 0.0).then(|| (sum / total_weight).into())
+        };
+        match self {
+            Self::CenterOfCharge => weighted_mean(&mut charges.iter().map(|q| q.abs())),
+            Self::Geometric => weighted_mean(&mut positions.iter().map(|_| 1.0)),
+            Self::CenterOfMass(masses) => {
+                assert_eq!(positions.len(), masses.len());
+                weighted_mean(&mut masses.iter().copied())
+            }
+            Self::Point(point) => Some(*point),
+        }
+    }
+}
+
+#[test]
+fn test_from_point_charges() {
+    use crate::pairwise::{MultipoleEnergy, Plain};
+    use approx::assert_relative_eq;
+
+    let positions: Vec<Vector3> = vec![
+        [0.1, 0.2, -0.3].into(),
+        [-0.5, 0.4, 0.2].into(),
+        [0.3, -0.6, 0.1].into(),
+    ];
+    let charges = [0.8, -0.5, -0.4];
+    let masses = [16.0, 1.0, 1.0];
+
+    let com = MomentOrigin::CenterOfMass(&masses)
+        .position(&positions, &charges)
+        .unwrap();
+    assert_relative_eq!(com.x, (1.6 - 0.5 + 0.3) / 18.0);
+    let coc = MomentOrigin::CenterOfCharge
+        .position(&positions, &charges)
+        .unwrap();
+    assert_relative_eq!(coc.y, (0.16 + 0.2 - 0.24) / 1.7);
+    let geometric = MomentOrigin::Geometric
+        .position(&positions, &charges)
+        .unwrap();
+    assert_relative_eq!(geometric.z, 0.0, epsilon = 1e-15);
+    assert!(MomentOrigin::CenterOfCharge
+        .position(&positions, &[0.0; 3])
+        .is_none());
+
+    // Far-field energy with a point charge approaches the direct sum
+    let multipole = Multipole::from_point_charges(&positions, &charges, com);
+    assert_relative_eq!(multipole.charge, -0.1);
+    let plain = Plain::without_cutoff();
+    let target = NalgebraVector3::new(24.0, -15.0, 18.0);
+    let exact: f64 = positions
+        .iter()
+        .zip(&charges)
+        .map(|(p, q)| q / (target - NalgebraVector3::from(*p)).norm())
+        .sum();
+    let r = target - NalgebraVector3::from(com);
+    let error = |multipole: &Multipole| {
+        (plain.pair_energy(multipole, &Multipole::new(1.0), r) - exact).abs()
+    };
+    let monopole = Multipole::new(multipole.charge);
+    let dipole = monopole.with_dipole(multipole.dipole.unwrap());
+    assert!(error(&dipole) < 0.1 * error(&monopole));
+    assert!(error(&multipole) < 0.1 * error(&dipole));
+}
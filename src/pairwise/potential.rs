@@ -93,4 +93,45 @@ pub trait MultipolePotential: ShortRangeFunction + Cutoff {
                 f * a + trace * b
             }
     }
+
+    /// Electrostatic potential at every charge from all other charges.
+    ///
+    /// Parameters:
+    /// - `positions`: Positions of the charges, UNIT: [ input length ]
+    /// - `charges`: Point charges, UNIT: [ input charge ]
+    /// - `box_length`: Side lengths of an orthorhombic, periodic box. Distances follow the
+    ///   minimum image convention. If `None`, the system is not periodic.
+    ///
+    /// Returns:
+    /// - Potential at each position, UNIT: [ ( input charge ) / ( input length ) ]
+    ///
+    /// The potential excludes the particle itself and reciprocal-space contributions.
+    /// The energy change of inserting a charge, $z$, at particle $i$ is $z \Phi_i$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `positions` and `charges` differ in length.
+    fn site_potentials(
+        &self,
+        positions: &[Vector3],
+        charges: &[f64],
+        box_length: Option<Vector3>,
+    ) -> Vec<f64> {
+        assert_eq!(positions.len(), charges.len());
+        let box_length: Option<NalgebraVector3> = box_length.map(Into::into);
+        let mut potentials = vec![0.0; positions.len()];
+        for (i, (position_i, charge_i)) in positions.iter().zip(charges).enumerate() {
+            for (j, (position_j, charge_j)) in positions.iter().zip(charges).enumerate().skip(i + 1)
+            {
+                let mut r = NalgebraVector3::from(*position_j) - NalgebraVector3::from(*position_i);
+                if let Some(box_length) = &box_length {
+                    r -= box_length.component_mul(&r.component_div(box_length).map(f64::round));
+                }
+                let distance = r.norm();
+                potentials[i] += self.ion_potential(*charge_j, distance);
+                potentials[j] += self.ion_potential(*charge_i, distance);
+            }
+        }
+        potentials
+    }
 }
@@ -0,0 +1,343 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+use crate::pairwise::*;
+use crate::Cutoff;
+use core::fmt::Display;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Distance-dependent dielectric, $\varepsilon(r) = \varepsilon' r$
+///
+/// The relative permittivity grows linearly with separation, giving the short-range function
+///
+/// $$ S(q) = \frac{1}{\varepsilon(r)} = \frac{1}{\varepsilon' r_c q} $$
+///
+/// where $\varepsilon'$ is the slope in units of inverse length, _e.g._ 4 Å⁻¹ for the common
+/// $\varepsilon(r) = 4r$ heuristic.
+/// Unlike other schemes, the permittivity is part of the interaction and results should _not_ be
+/// divided by the permittivity of the medium.
+/// The charge-charge energy decays as $1/r^2$ and the force as $2/r^3$.
+/// The interaction is truncated at the cutoff.
+/// See Warshel and Levitt, <https://doi.org/10.1016/0022-2836(76)90311-9>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::pairwise::{LinearDielectric, MultipoleEnergy};
+/// let scheme = LinearDielectric::new(20.0, 4.0);
+/// assert_relative_eq!(scheme.ion_ion_energy(1.0, -1.0, 5.0), -1.0 / (4.0 * 25.0));
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct LinearDielectric {
+    /// Cut-off radius
+    cutoff: f64,
+    /// Slope of the permittivity, ε(r) / r, UNIT: [ ( input length )^-1 ]
+    slope: f64,
+}
+
+impl LinearDielectric {
+    /// New scheme with a `cutoff` and permittivity slope, ε(r) / r.
+    pub const fn new(cutoff: f64, slope: f64) -> Self {
+        Self { cutoff, slope }
+    }
+
+    /// Slope of the permittivity, ε(r) / r
+    pub const fn slope(&self) -> f64 {
+        self.slope
+    }
+
+    /// Prefactor, 1 / ε' r_c, of the short-range function
+    fn prefactor(&self) -> f64 {
+        (self.slope * self.cutoff).recip()
+    }
+}
+
+impl Display for LinearDielectric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Linear dielectric: ε(𝑟) = {:.1}𝑟, 𝑟✂ = {:.1} <{}>",
+            self.slope,
+            self.cutoff,
+            Self::url()
+        )
+    }
+}
+
+impl Cutoff for LinearDielectric {
+    fn cutoff(&self) -> f64 {
+        self.cutoff
+    }
+}
+
+impl DebyeLength for LinearDielectric {
+    #[inline]
+    fn kappa(&self) -> Option<f64> {
+        None
+    }
+}
+
+impl ShortRangeFunction for LinearDielectric {
+    fn url() -> &'static str {
+        "https://doi.org/10.1016/0022-2836(76)90311-9"
+    }
+    #[inline]
+    fn short_range_f0(&self, q: f64) -> f64 {
+        self.prefactor() / q
+    }
+    #[inline]
+    fn short_range_f1(&self, q: f64) -> f64 {
+        -self.prefactor() / (q * q)
+    }
+    #[inline]
+    fn short_range_f2(&self, q: f64) -> f64 {
+        2.0 * self.prefactor() / q.powi(3)
+    }
+    #[inline]
+    fn short_range_f3(&self, q: f64) -> f64 {
+        -6.0 * self.prefactor() / q.powi(4)
+    }
+    #[inline]
+    fn short_range_f4(&self, q: f64) -> f64 {
+        24.0 * self.prefactor() / q.powi(5)
+    }
+}
+
+/// Sigmoidal distance-dependent dielectric
+///
+/// The relative permittivity rises from near unity at contact to the bulk value, $\varepsilon_b$,
+/// following Mehler and Solmajer,
+///
+/// $$ \varepsilon(r) = A + \frac{B}{1 + k e^{-\lambda B r}}, \quad B = \varepsilon_b - A $$
+///
+/// with $A = -8.5525$, $k = 7.7839$, and slope $\lambda$ (0.003627 Å⁻¹ in the original work).
+/// The short-range function is $S(q) = 1/\varepsilon(r_c q)$ and, as for [`LinearDielectric`],
+/// results should _not_ be divided by the permittivity of the medium.
+/// The interaction is truncated at the cutoff.
+/// See <https://doi.org/10.1093/protein/4.8.903>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::pairwise::{MultipoleEnergy, SigmoidalDielectric};
+/// let scheme = SigmoidalDielectric::mehler_solmajer(50.0);
+/// assert_relative_eq!(scheme.permittivity(40.0), 78.4, max_relative = 1e-3);
+/// assert_relative_eq!(scheme.ion_ion_energy(1.0, 1.0, 40.0), 1.0 / (40.0 * scheme.permittivity(40.0)));
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct SigmoidalDielectric {
+    /// Cut-off radius
+    cutoff: f64,
+    /// Bulk relative permittivity, εᵦ
+    bulk_permittivity: f64,
+    /// Slope, λ, UNIT: [ ( input length )^-1 ]
+    slope: f64,
+}
+
+impl SigmoidalDielectric {
+    /// Offset, A, of the sigmoidal permittivity
+    const OFFSET: f64 = -8.5525;
+    /// Prefactor, k, of the exponential
+    const K: f64 = 7.7839;
+
+    /// New scheme with a `cutoff`, bulk relative permittivity and slope, λ.
+    pub const fn new(cutoff: f64, bulk_permittivity: f64, slope: f64) -> Self {
+        Self {
+            cutoff,
+            bulk_permittivity,
+            slope,
+        }
+    }
+
+    /// New scheme with the bulk relative permittivity of a medium and slope, λ.
+    pub fn from_medium(cutoff: f64, medium: &crate::Medium, slope: f64) -> Self {
+        Self::new(cutoff, medium.permittivity(), slope)
+    }
+
+    /// Original parametrization for water with εᵦ = 78.4 and λ = 0.003627 Å⁻¹.
+    pub const fn mehler_solmajer(cutoff: f64) -> Self {
+        Self::new(cutoff, 78.4, 0.003627)
+    }
+
+    /// Relative permittivity at `distance`, ε(r)
+    pub fn permittivity(&self, distance: f64) -> f64 {
+        self.permittivity_derivatives(distance)[0]
+    }
+
+    /// Permittivity and its first four derivatives with respect to distance
+    fn permittivity_derivatives(&self, distance: f64) -> [f64; 5] {
+        let b = self.bulk_permittivity - Self::OFFSET;
+        let c = self.slope * b;
+        // y = 1 / (1 + k exp(-cr)) obeys the logistic equation, y' = c y (1 - y)
+        let y = (1.0 + Self::K * (-c * distance).exp()).recip();
+        let y1 = c * y * (1.0 - y);
+        let y2 = c * (1.0 - 2.0 * y) * y1;
+        let y3 = c * ((1.0 - 2.0 * y) * y2 - 2.0 * y1 * y1);
+        let y4 = c * ((1.0 - 2.0 * y) * y3 - 6.0 * y1 * y2);
+        [Self::OFFSET + b * y, b * y1, b * y2, b * y3, b * y4]
+    }
+
+    /// Inverse permittivity, 1/ε(r), and its first four derivatives with respect to q
+    fn short_range_derivatives(&self, q: f64) -> [f64; 5] {
+        let [e0, e1, e2, e3, e4] = self.permittivity_derivatives(q * self.cutoff);
+        let s = e0.recip();
+        let (s2, s3, s4, s5) = (s * s, s.powi(3), s.powi(4), s.powi(5));
+        let rc = self.cutoff;
+        [
+            s,
+            -e1 * s2 * rc,
+            (2.0 * e1 * e1 * s3 - e2 * s2) * rc.powi(2),
+            (-6.0 * e1.powi(3) * s4 + 6.0 * e1 * e2 * s3 - e3 * s2) * rc.powi(3),
+            (24.0 * e1.powi(4) * s5 - 36.0 * e1 * e1 * e2 * s4
+                + (6.0 * e2 * e2 + 8.0 * e1 * e3) * s3
+                - e4 * s2)
+                * rc.powi(4),
+        ]
+    }
+}
+
+impl Display for SigmoidalDielectric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Sigmoidal dielectric: εᵦ = {:.1}, λ = {:.2e}, 𝑟✂ = {:.1} <{}>",
+            self.bulk_permittivity,
+            self.slope,
+            self.cutoff,
+            Self::url()
+        )
+    }
+}
+
+impl Cutoff for SigmoidalDielectric {
+    fn cutoff(&self) -> f64 {
+        self.cutoff
+    }
+}
+
+impl DebyeLength for SigmoidalDielectric {
+    #[inline]
+    fn kappa(&self) -> Option<f64> {
+        None
+    }
+}
+
+impl ShortRangeFunction for SigmoidalDielectric {
+    fn url() -> &'static str {
+        "https://doi.org/10.1093/protein/4.8.903"
+    }
+    fn short_range_f0(&self, q: f64) -> f64 {
+        self.short_range_derivatives(q)[0]
+    }
+    fn short_range_f1(&self, q: f64) -> f64 {
+        self.short_range_derivatives(q)[1]
+    }
+    fn short_range_f2(&self, q: f64) -> f64 {
+        self.short_range_derivatives(q)[2]
+    }
+    fn short_range_f3(&self, q: f64) -> f64 {
+        self.short_range_derivatives(q)[3]
+    }
+    fn short_range_f4(&self, q: f64) -> f64 {
+        self.short_range_derivatives(q)[4]
+    }
+}
+
+#[test]
+fn test_linear_dielectric() {
+    use crate::NalgebraVector3;
+    use approx::assert_relative_eq;
+
+    let scheme = LinearDielectric::new(30.0, 4.0);
+    assert_eq!(
+        scheme.to_string(),
+        "Linear dielectric: ε(𝑟) = 4.0𝑟, 𝑟✂ = 30.0 <https://doi.org/10.1016/0022-2836(76)90311-9>"
+    );
+    let (z1, z2) = (1.5, -2.0);
+    let r = NalgebraVector3::new(2.0, -3.0, 4.0);
+    let distance = r.norm();
+    assert_relative_eq!(
+        MultipoleEnergy::ion_ion_energy(&scheme, z1, z2, distance),
+        z1 * z2 / (4.0 * distance * distance)
+    );
+    assert_relative_eq!(
+        NalgebraVector3::from(scheme.ion_ion_force(z1, z2, r)),
+        r * (2.0 * z1 * z2 / (4.0 * distance.powi(4))),
+        epsilon = 1e-12
+    );
+    assert_eq!(MultipoleEnergy::ion_ion_energy(&scheme, z1, z2, 31.0), 0.0);
+
+    // Charge-dipole energy, μ·∇φ, is consistent with the charge potential
+    let mu = NalgebraVector3::new(0.3, 0.5, -0.2);
+    let h = 1e-6;
+    let numerical: f64 = (0..3)
+        .map(|i| {
+            let dr = NalgebraVector3::ith(i, h);
+            mu[i]
+                * (MultipolePotential::ion_potential(&scheme, z1, (r + dr).norm())
+                    - MultipolePotential::ion_potential(&scheme, z1, (r - dr).norm()))
+                / (2.0 * h)
+        })
+        .sum();
+    assert_relative_eq!(
+        scheme.ion_dipole_energy(z1, mu, r),
+        numerical,
+        max_relative = 1e-8
+    );
+}
+
+#[test]
+fn test_sigmoidal_dielectric() {
+    use approx::assert_relative_eq;
+
+    let scheme = SigmoidalDielectric::mehler_solmajer(30.0);
+    assert_eq!(
+        scheme.to_string(),
+        "Sigmoidal dielectric: εᵦ = 78.4, λ = 3.63e-3, 𝑟✂ = 30.0 <https://doi.org/10.1093/protein/4.8.903>"
+    );
+    // Low permittivity at contact rising to the bulk value
+    assert_relative_eq!(scheme.permittivity(0.0), -8.5525 + 86.9525 / 8.7839);
+    assert!(scheme.permittivity(5.0) > 10.0);
+
+    // Analytic derivatives of the short-range function
+    let h = 1e-5;
+    for q in [0.05, 0.2, 0.5, 0.9] {
+        let s = |q: f64| scheme.short_range_derivatives(q);
+        for n in 1..5 {
+            let numerical = (s(q + h)[n - 1] - s(q - h)[n - 1]) / (2.0 * h);
+            assert_relative_eq!(s(q)[n], numerical, max_relative = 1e-6, epsilon = 1e-8);
+        }
+    }
+
+    // Consistent force
+    let (z1, z2, r) = (1.0, -1.0, crate::NalgebraVector3::new(1.0, 2.0, 2.0));
+    let energy = |d: f64| MultipoleEnergy::ion_ion_energy(&scheme, z1, z2, d);
+    let force = crate::NalgebraVector3::from(scheme.ion_ion_force(z1, z2, r));
+    assert_relative_eq!(
+        force.dot(&r) / 3.0,
+        -(energy(3.0 + h) - energy(3.0 - h)) / (2.0 * h),
+        max_relative = 1e-7
+    );
+}
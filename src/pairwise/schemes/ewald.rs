@@ -15,10 +15,9 @@
 // See the license for the specific language governing permissions and
 // limitations under the license.
 
-#[cfg(test)]
 use crate::debye_length::DebyeLength;
-use crate::math::erfc_x;
-use crate::pairwise::{SelfEnergyPrefactors, ShortRangeFunction};
+use crate::math::{erf_derivatives, erfc_x, erfcx_x, exp_x};
+use crate::pairwise::{Gaussian, SelfEnergyPrefactors, ShortRangeFunction};
 #[cfg(test)]
 use approx::assert_relative_eq;
 #[cfg(feature = "serde")]
@@ -45,6 +44,15 @@ pub struct RealSpaceEwald {
     /// Reduced inverse screening length, 𝜻 = 𝜿 × 𝑟✂︎ (dimensionless)
     #[cfg_attr(feature = "serde", serde(skip))]
     zeta: Option<f64>,
+    /// Reduced inverse width of Gaussian charges, 𝛽 × 𝑟✂︎ (dimensionless)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    smearing: Option<f64>,
+    /// Shift of the screened error function arguments, 𝜻 / 2𝜂 (zero without salt)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    shift: f64,
+    /// Self-energy prefactors, _c1_, _c2_, and _c3_
+    #[cfg_attr(feature = "serde", serde(skip))]
+    self_energy: [f64; 3],
 }
 
 #[cfg(feature = "serde")]
@@ -80,6 +88,9 @@ impl core::fmt::Display for RealSpaceEwald {
         if let Some(zeta) = self.zeta {
             write!(f, ", 𝜻 = {:.1}", zeta)?;
         }
+        if let Some(beta) = self.smearing {
+            write!(f, ", 𝛽𝑟✂ = {:.1}", beta)?;
+        }
         write!(f, " <{}>", Self::url())?;
         Ok(())
     }
@@ -92,13 +103,23 @@ impl RealSpaceEwald {
     ///
     /// The Debye length and cutoff should have the same unit of length.
     pub fn new(cutoff: f64, alpha: f64, debye_length: Option<f64>) -> Self {
-        Self {
+        let mut ewald = Self {
             cutoff,
             alpha,
             debye_length,
             eta: alpha * cutoff,
-            zeta: debye_length.map(|d| cutoff / d),
-        }
+            zeta: None,
+            smearing: None,
+            shift: 0.0,
+            self_energy: [0.0; 3],
+        };
+        ewald.set_zeta(debye_length.map(|d| cutoff / d));
+        ewald
+    }
+    /// Create from medium. The Debye length is calculated from the medium's properties in units
+    /// of angstrom. `cutoff` should be provided in angstrom and `alpha` in inverse angstrom.
+    pub fn from_medium(cutoff: f64, alpha: f64, medium: &crate::Medium) -> Self {
+        Self::new(cutoff, alpha, medium.debye_length())
     }
     /// Construct a salt-free Ewald scheme with given cutoff and alpha.
     pub fn new_without_salt(cutoff: f64, alpha: f64) -> Self {
@@ -114,6 +135,67 @@ impl RealSpaceEwald {
     pub fn alpha(&self) -> f64 {
         self.eta / self.cutoff
     }
+
+    /// Real-space interactions between a pair of Gaussian charges.
+    ///
+    /// The short-range function becomes $S(r) = \text{erf}(\beta r) - \text{erf}(\alpha r)$
+    /// where $\beta$ is the smearing of the `gaussian` pair.
+    /// The reciprocal space and self-energy terms are those of point charges, so the
+    /// same k-space machinery can be used for Gaussian charges.
+    /// The widths must be set for each pair and the smearing is exact only without salt.
+    ///
+    /// # Examples
+    /// ~~~
+    /// # use approx::assert_relative_eq;
+    /// use coulomb::pairwise::{Gaussian, MultipolePotential, RealSpaceEwald};
+    /// let ewald = RealSpaceEwald::new_without_salt(10.0, 0.3);
+    /// let smeared = ewald.with_smearing(&Gaussian::new(0.5, 0.5));
+    /// assert!(smeared.ion_potential(1.0, 1.0) < ewald.ion_potential(1.0, 1.0));
+    /// ~~~
+    pub fn with_smearing(&self, gaussian: &Gaussian) -> Self {
+        Self {
+            smearing: Some(gaussian.beta() * self.cutoff),
+            ..self.clone()
+        }
+    }
+
+    /// Set the reduced inverse screening length and update the constants that depend on it
+    fn set_zeta(&mut self, zeta: Option<f64>) {
+        let eta = self.eta;
+        self.zeta = zeta;
+        self.shift = zeta.map_or(0.0, |zeta| zeta / (2.0 * eta));
+        self.self_energy = if let Some(zeta) = zeta {
+            let exp_shift = f64::exp(-self.shift.powi(2));
+            let erfc_shift = erfc_x(self.shift);
+            [
+                -eta / Self::SQRT_PI * (exp_shift - Self::SQRT_PI * self.shift * erfc_shift),
+                -eta.powi(3) / Self::SQRT_PI * 2.0 / 3.0
+                    * (Self::SQRT_PI * zeta.powi(3) / 4.0 / eta.powi(3) * erfc_shift
+                        + (1.0 - zeta.powi(2) / 2.0 / eta.powi(2)) * exp_shift),
+                -((24.0 * eta.powi(5) - 4.0 * eta.powi(3) * zeta.powi(2)
+                    + 2.0 * eta * zeta.powi(4))
+                    / Self::SQRT_PI
+                    * exp_shift
+                    - zeta.powi(5) * erfc_shift)
+                    / 120.0,
+            ]
+        } else {
+            [
+                -eta / Self::SQRT_PI,
+                -eta.powi(3) / Self::SQRT_PI * 2.0 / 3.0,
+                -eta.powi(5) / Self::SQRT_PI / 5.0,
+            ]
+        };
+    }
+
+    /// Derivatives of the smearing term, −erfc(𝛽q), with respect to q
+    fn smearing(&self, q: f64) -> [f64; 5] {
+        self.smearing.map_or([0.0; 5], |beta| {
+            let mut s = erf_derivatives(beta, q);
+            s[0] -= 1.0;
+            s
+        })
+    }
 }
 
 impl crate::Cutoff for RealSpaceEwald {
@@ -129,11 +211,15 @@ impl crate::DebyeLength for RealSpaceEwald {
         self.zeta.map(|z| z / self.cutoff)
     }
     fn set_debye_length(&mut self, debye_length: Option<f64>) -> crate::Result<()> {
-        self.zeta = debye_length.map(|d| self.cutoff / d);
+        crate::debye_length::check_debye_length(debye_length)?;
+        self.debye_length = debye_length;
+        self.set_zeta(debye_length.map(|d| self.cutoff / d));
         Ok(())
     }
 }
 
+impl crate::SaltSensitive for RealSpaceEwald {}
+
 impl ShortRangeFunction for RealSpaceEwald {
     fn url() -> &'static str {
         "https://doi.org/fcjts8"
@@ -141,75 +227,90 @@ impl ShortRangeFunction for RealSpaceEwald {
 
     #[inline]
     fn short_range_f0(&self, q: f64) -> f64 {
-        if let Some(zeta) = self.zeta {
-            0.5 * (erfc_x(self.eta * q + zeta / (2.0 * self.eta)) * f64::exp(2.0 * zeta * q)
-                + erfc_x(self.eta * q - zeta / (2.0 * self.eta)))
-        } else {
-            erfc_x(self.eta * q)
-        }
+        self.smearing(q)[0]
+            + if self.zeta.is_some() {
+                // erfc(𝜂q + 𝜻/2𝜂) exp(2𝜻q) = erfcx(𝜂q + 𝜻/2𝜂) exp(-(𝜂q - 𝜻/2𝜂)²) stays finite
+                // for large 𝜻 also in single precision
+                let exp_c = exp_x(-(self.eta * q - self.shift).powi(2));
+                0.5 * (erfcx_x(self.eta * q + self.shift) * exp_c
+                    + erfc_x(self.eta * q - self.shift))
+            } else {
+                erfc_x(self.eta * q)
+            }
     }
 
     fn short_range_f1(&self, q: f64) -> f64 {
-        if let Some(zeta) = self.zeta {
-            let exp_c = f64::exp(-(self.eta * q - zeta / (2.0 * self.eta)).powi(2));
-            let erfc_c = erfc_x(self.eta * q + zeta / (2.0 * self.eta));
-            -2.0 * self.eta / Self::SQRT_PI * exp_c + zeta * erfc_c * f64::exp(2.0 * zeta * q)
-        } else {
-            -2.0 * self.eta / Self::SQRT_PI * f64::exp(-self.eta.powi(2) * q.powi(2))
-        }
+        self.smearing(q)[1]
+            + if let Some(zeta) = self.zeta {
+                let exp_c = exp_x(-(self.eta * q - self.shift).powi(2));
+                let erfcx_c = erfcx_x(self.eta * q + self.shift);
+                -2.0 * self.eta / Self::SQRT_PI * exp_c + zeta * erfcx_c * exp_c
+            } else {
+                -2.0 * self.eta / Self::SQRT_PI * exp_x(-self.eta.powi(2) * q.powi(2))
+            }
     }
 
     fn short_range_f2(&self, q: f64) -> f64 {
-        if let Some(zeta) = self.zeta {
-            let exp_c = f64::exp(-(self.eta * q - zeta / (2.0 * self.eta)).powi(2));
-            let erfc_c = erfc_x(self.eta * q + zeta / (2.0 * self.eta));
-            4.0 * self.eta.powi(2) / Self::SQRT_PI * (self.eta * q - zeta / self.eta) * exp_c
-                + 2.0 * zeta.powi(2) * erfc_c * f64::exp(2.0 * zeta * q)
-        } else {
-            4.0 * self.eta.powi(2) / Self::SQRT_PI
-                * (self.eta * q)
-                * f64::exp(-(self.eta * q).powi(2))
-        }
+        self.smearing(q)[2]
+            + if let Some(zeta) = self.zeta {
+                let exp_c = exp_x(-(self.eta * q - self.shift).powi(2));
+                let erfcx_c = erfcx_x(self.eta * q + self.shift);
+                4.0 * self.eta.powi(2) / Self::SQRT_PI * (self.eta * q - 2.0 * self.shift) * exp_c
+                    + 2.0 * zeta.powi(2) * erfcx_c * exp_c
+            } else {
+                4.0 * self.eta.powi(2) / Self::SQRT_PI
+                    * (self.eta * q)
+                    * exp_x(-(self.eta * q).powi(2))
+            }
     }
 
     fn short_range_f3(&self, q: f64) -> f64 {
-        if let Some(zeta) = self.zeta {
-            let exp_c = f64::exp(-(self.eta * q - zeta / (2.0 * self.eta)).powi(2));
-            let erfc_c = erfc_x(self.eta * q + zeta / (2.0 * self.eta));
-            4.0 * self.eta.powi(3) / Self::SQRT_PI
-                * (1.0
-                    - 2.0
-                        * (self.eta * q - zeta / self.eta)
-                        * (self.eta * q - zeta / (2.0 * self.eta))
-                    - zeta.powi(2) / self.eta.powi(2))
-                * exp_c
-                + 4.0 * zeta.powi(3) * erfc_c * f64::exp(2.0 * zeta * q)
-        } else {
-            4.0 * self.eta.powi(3) / Self::SQRT_PI
-                * (1.0 - 2.0 * (self.eta * q).powi(2))
-                * (-(self.eta * q).powi(2)).exp()
-        }
+        self.smearing(q)[3]
+            + if let Some(zeta) = self.zeta {
+                let exp_c = exp_x(-(self.eta * q - self.shift).powi(2));
+                let erfcx_c = erfcx_x(self.eta * q + self.shift);
+                4.0 * self.eta.powi(3) / Self::SQRT_PI
+                    * (1.0
+                        - 2.0 * (self.eta * q - 2.0 * self.shift) * (self.eta * q - self.shift)
+                        - zeta.powi(2) / self.eta.powi(2))
+                    * exp_c
+                    + 4.0 * zeta.powi(3) * erfcx_c * exp_c
+            } else {
+                4.0 * self.eta.powi(3) / Self::SQRT_PI
+                    * (1.0 - 2.0 * (self.eta * q).powi(2))
+                    * exp_x(-(self.eta * q).powi(2))
+            }
+    }
+
+    fn short_range_f4(&self, q: f64) -> f64 {
+        self.smearing(q)[4]
+            + if let Some(zeta) = self.zeta {
+                let c = self.eta * q - self.shift;
+                let exp_c = exp_x(-c.powi(2));
+                let erfcx_c = erfcx_x(self.eta * q + self.shift);
+                (-24.0 * self.eta.powi(4) * c
+                    + 16.0 * self.eta.powi(4) * c.powi(3)
+                    + 4.0 * self.eta.powi(3) * zeta
+                    - 8.0 * self.eta.powi(3) * zeta * c.powi(2)
+                    + 8.0 * self.eta.powi(2) * zeta.powi(2) * c
+                    - 8.0 * self.eta * zeta.powi(3))
+                    / Self::SQRT_PI
+                    * exp_c
+                    + 8.0 * zeta.powi(4) * erfcx_c * exp_c
+            } else {
+                8.0 * self.eta.powi(5) / Self::SQRT_PI
+                    * q
+                    * (2.0 * (self.eta * q).powi(2) - 3.0)
+                    * exp_x(-(self.eta * q).powi(2))
+            }
     }
 
     fn self_energy_prefactors(&self) -> SelfEnergyPrefactors {
-        let (c1, c2) = if let Some(zeta) = self.zeta {
-            let c1 = -self.eta / Self::SQRT_PI
-                * (f64::exp(-zeta.powi(2) / 4.0 / self.eta.powi(2))
-                    - Self::SQRT_PI * zeta / (2.0 * self.eta) * erfc_x(zeta / (2.0 * self.eta)));
-            let c2 = -self.eta.powi(3) / Self::SQRT_PI * 2.0 / 3.0
-                * (Self::SQRT_PI * zeta.powi(3) / 4.0 / self.eta.powi(3)
-                    * erfc_x(zeta / (2.0 * self.eta))
-                    + (1.0 - zeta.powi(2) / 2.0 / self.eta.powi(2))
-                        * f64::exp(-zeta.powi(2) / 4.0 / self.eta.powi(2)));
-            (c1, c2)
-        } else {
-            let c1 = -self.eta / Self::SQRT_PI;
-            let c2 = -self.eta.powi(3) / Self::SQRT_PI * 2.0 / 3.0;
-            (c1, c2)
-        };
+        let [c1, c2, c3] = self.self_energy;
         SelfEnergyPrefactors {
             monopole: Some(c1),
             dipole: Some(c2),
+            quadrupole: Some(c3),
         }
     }
 }
@@ -217,54 +318,205 @@ impl ShortRangeFunction for RealSpaceEwald {
 #[test]
 fn test_ewald() {
     use crate::pairwise::MultipoleEnergy;
+    use crate::test_utils::ERFC_ERROR;
 
     // Test short-ranged function without salt
     let cutoff = 29.0;
     let alpha = 0.1;
     let pot = RealSpaceEwald::new_without_salt(cutoff, alpha);
-    let eps = 1e-8;
+    // `erfc_x` has an absolute error, and `erfc_x` and `exp_x` a relative error on each term,
+    // within `ERFC_ERROR`
+    let eps = 1e-8 + 2.0 * ERFC_ERROR;
+    let max_relative = 2.0 * ERFC_ERROR;
+    let h = 1e-4;
 
     assert_relative_eq!(
-        pot.self_energy(&[2.0], &[0.0]),
+        pot.self_energy(&[2.0], &[0.0], &[]),
         -0.2256758334,
         epsilon = eps
     );
     assert_relative_eq!(
-        pot.self_energy(&[0.0], &[f64::sqrt(2.0)]),
+        pot.self_energy(&[0.0], &[f64::sqrt(2.0)], &[]),
         -0.000752257778,
         epsilon = eps
     );
 
     assert_relative_eq!(pot.alpha(), alpha, epsilon = eps);
-    assert_relative_eq!(pot.short_range_f0(0.5), 0.04030484067840161, epsilon = eps);
-    assert_relative_eq!(pot.short_range_f1(0.5), -0.39971358519150996, epsilon = eps);
-    assert_relative_eq!(pot.short_range_f2(0.5), 3.36159125, epsilon = eps);
-    assert_relative_eq!(pot.short_range_f3(0.5), -21.54779992186245, epsilon = eps);
+    assert_relative_eq!(
+        pot.short_range_f0(0.5),
+        0.04030497436254074,
+        epsilon = eps,
+        max_relative = max_relative
+    );
+    assert_relative_eq!(
+        pot.short_range_f1(0.5),
+        -0.39971358519150996,
+        epsilon = eps,
+        max_relative = max_relative
+    );
+    assert_relative_eq!(
+        pot.short_range_f2(0.5),
+        3.36159125,
+        epsilon = eps,
+        max_relative = max_relative
+    );
+    assert_relative_eq!(
+        pot.short_range_f3(0.5),
+        -21.54779992186245,
+        epsilon = eps,
+        max_relative = max_relative
+    );
+    // Rounding of `short_range_f3` is amplified by one over the step
+    assert_relative_eq!(
+        pot.short_range_f4(0.5),
+        (pot.short_range_f3(0.5 + h) - pot.short_range_f3(0.5 - h)) / (2.0 * h),
+        epsilon = 1e-4 + ERFC_ERROR * pot.short_range_f3(0.5).abs() / h
+    );
 
     // Test short-ranged function with a Debye screening length
     let debye_length = 23.0;
     let pot = RealSpaceEwald::new_with_salt(cutoff, alpha, debye_length);
-    let eps = 1e-7;
+    let eps = 1e-7 + 2.0 * ERFC_ERROR;
 
     assert_relative_eq!(
-        pot.self_energy(&[2.0], &[0.0]),
-        -0.14930129209178544,
+        pot.self_energy(&[2.0], &[0.0], &[]),
+        -0.14930130405462153,
         epsilon = eps
     );
     assert_relative_eq!(
-        pot.self_energy(&[0.0], &[f64::sqrt(2.0)]),
+        pot.self_energy(&[0.0], &[f64::sqrt(2.0)], &[]),
         -0.0006704901976,
         epsilon = eps
     );
 
     assert_relative_eq!(pot.kappa().unwrap(), 1.0 / 23.0, epsilon = eps);
-    assert_relative_eq!(pot.short_range_f0(0.5), 0.07306333588, epsilon = eps);
-    assert_relative_eq!(pot.short_range_f1(0.5), -0.6344413331247332, epsilon = eps);
-    assert_relative_eq!(pot.short_range_f2(0.5), 4.42313324197739, epsilon = eps);
-    assert_relative_eq!(pot.short_range_f3(0.5), -19.859372613319028, epsilon = eps);
+    assert_relative_eq!(
+        pot.short_range_f0(0.5),
+        0.07306333589635242,
+        epsilon = eps,
+        max_relative = max_relative
+    );
+    assert_relative_eq!(
+        pot.short_range_f1(0.5),
+        -0.6344411909409592,
+        epsilon = eps,
+        max_relative = max_relative
+    );
+    assert_relative_eq!(
+        pot.short_range_f2(0.5),
+        4.423133600527777,
+        epsilon = eps,
+        max_relative = max_relative
+    );
+    assert_relative_eq!(
+        pot.short_range_f3(0.5),
+        -19.85937170914849,
+        epsilon = eps,
+        max_relative = max_relative
+    );
+    // Rounding of `short_range_f3` is amplified by one over the step
+    assert_relative_eq!(
+        pot.short_range_f4(0.5),
+        (pot.short_range_f3(0.5 + h) - pot.short_range_f3(0.5 - h)) / (2.0 * h),
+        epsilon = 1e-4 + ERFC_ERROR * pot.short_range_f3(0.5).abs() / h
+    );
 
     assert_eq!(
         pot.to_string(),
         "Real-space Ewald: 𝑟✂ = 29.0, 𝜂 = 2.9, 𝜻 = 1.3 <https://doi.org/fcjts8>"
     );
 }
+
+#[test]
+fn test_ewald_strong_screening() {
+    use crate::pairwise::MultipoleEnergy;
+    use crate::test_utils::ERFC_ERROR;
+    // With 𝜻 = 50, exp(2𝜻q) overflows in single precision for q > 0.89
+    let pot = RealSpaceEwald::new(50.0, 0.1, Some(1.0));
+    // Reference values from differentiating the short-range function with `mpmath`
+    let reference = [
+        0.783249747782942,
+        -2.0939320070353533,
+        -11.667120443060249,
+        41.61400173021167,
+        1415.204613847902,
+    ];
+    let f = [
+        pot.short_range_f0(0.9),
+        pot.short_range_f1(0.9),
+        pot.short_range_f2(0.9),
+        pot.short_range_f3(0.9),
+        pot.short_range_f4(0.9),
+    ];
+    for (f, reference) in f.into_iter().zip(reference) {
+        assert_relative_eq!(f, reference, max_relative = 1e-12 + ERFC_ERROR);
+    }
+    assert_relative_eq!(
+        MultipoleEnergy::ion_ion_energy(&pot, 1.0, 1.0, 45.0),
+        reference[0] * f64::exp(-45.0) / 45.0,
+        max_relative = 1e-12 + ERFC_ERROR
+    );
+    for q in (0..=100).map(|i| f64::from(i) / 100.0) {
+        assert!(pot.short_range_f0(q).is_finite() && pot.short_range_f4(q).is_finite());
+    }
+}
+
+#[test]
+fn test_ewald_smearing() {
+    use crate::pairwise::{MultipoleEnergy, MultipoleForce};
+    use crate::test_utils::ERFC_ERROR;
+    use crate::NalgebraVector3 as Vector3;
+    // Each side has an `erfc_x` or `erf_x` term of magnitude below unity, and a few `exp_x` terms
+    let eps = 1e-10 + 4.0 * ERFC_ERROR;
+    let alpha = 0.4;
+    let gaussian = Gaussian::new(0.7, 1.1);
+    let ewald = RealSpaceEwald::new_without_salt(12.0, alpha).with_smearing(&gaussian);
+    assert_eq!(
+        ewald.to_string(),
+        "Real-space Ewald: 𝑟✂ = 12.0, 𝜂 = 4.8, 𝛽𝑟✂ = 6.5 <https://doi.org/fcjts8>"
+    );
+    // Point charges in reciprocal space interact as Gaussians of combined width 1 / (√2 α)
+    let reciprocal = Gaussian::new(0.5 / alpha, 0.5 / alpha);
+    let (z1, z2) = (1.5, -0.8);
+    let (mu1, mu2) = (Vector3::new(0.3, -1.2, 0.8), Vector3::new(-0.9, 0.4, 1.1));
+    let r = Vector3::new(0.9, -0.4, 1.2);
+    assert_relative_eq!(
+        MultipoleEnergy::ion_ion_energy(&ewald, z1, z2, r.norm())
+            + reciprocal.ion_ion_energy(z1, z2, r.norm()),
+        gaussian.ion_ion_energy(z1, z2, r.norm()),
+        epsilon = eps
+    );
+    assert_relative_eq!(
+        ewald.ion_dipole_energy(z1, mu1, r) + reciprocal.ion_dipole_energy(z1, mu1, r),
+        gaussian.ion_dipole_energy(z1, mu1, r),
+        epsilon = eps
+    );
+    assert_relative_eq!(
+        ewald.dipole_dipole_energy(mu1, mu2, r) + reciprocal.dipole_dipole_energy(mu1, mu2, r),
+        gaussian.dipole_dipole_energy(mu1, mu2, r),
+        epsilon = eps
+    );
+    let force = Vector3::from(ewald.ion_ion_force(z1, z2, r))
+        + Vector3::from(reciprocal.ion_ion_force(z1, z2, r));
+    assert_relative_eq!(
+        force,
+        Vector3::from(gaussian.ion_ion_force(z1, z2, r)),
+        epsilon = eps
+    );
+}
+
+#[test]
+fn test_ewald_from_medium() {
+    use crate::{Medium, Salt};
+    let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+    let pot = RealSpaceEwald::from_medium(29.0, 0.1, &medium);
+    assert_eq!(pot, RealSpaceEwald::new(29.0, 0.1, medium.debye_length()));
+    let mut pot = RealSpaceEwald::from_medium(29.0, 0.1, &Medium::neat_water(298.15));
+    assert_eq!(pot, RealSpaceEwald::new_without_salt(29.0, 0.1));
+    crate::SaltSensitive::update_screening(&mut pot, &medium).unwrap();
+    assert_eq!(pot, RealSpaceEwald::from_medium(29.0, 0.1, &medium));
+    for invalid in [0.0, -10.0, f64::INFINITY, f64::NAN] {
+        assert!(crate::DebyeLength::set_debye_length(&mut pot, Some(invalid)).is_err());
+    }
+    assert_eq!(pot, RealSpaceEwald::from_medium(29.0, 0.1, &medium));
+}
@@ -16,7 +16,7 @@
 // limitations under the license.
 
 use crate::pairwise::{SelfEnergyPrefactors, ShortRangeFunction};
-use crate::{math::erf_x, math::erfc_x, Cutoff};
+use crate::{math::erf_x, math::erfc_x, math::exp_x, Cutoff};
 use core::f64::consts::FRAC_2_SQRT_PI;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize};
@@ -117,9 +117,11 @@ impl ShortRangeFunction for EwaldTruncated {
         let c2 = -2.0 * self.eta.powi(3)
             / (3.0
                 * (erf_x(self.eta) / Self::FRAC_1_SQRT_PI - 2.0 * self.eta * self.exp_minus_eta2));
+        let c3 = -self.eta.powi(5) * Self::FRAC_1_SQRT_PI / 5.0 * self.f0;
         SelfEnergyPrefactors {
             monopole: Some(c1),
             dipole: Some(c2),
+            quadrupole: Some(c3),
         }
     }
     fn short_range_f0(&self, q: f64) -> f64 {
@@ -130,17 +132,25 @@ impl ShortRangeFunction for EwaldTruncated {
     }
     fn short_range_f1(&self, q: f64) -> f64 {
         -self.eta
-            * ((-(self.eta * q).powi(2)).exp() - self.exp_minus_eta2)
+            * (exp_x(-(self.eta * q).powi(2)) - self.exp_minus_eta2)
             * FRAC_2_SQRT_PI
             * self.f0
     }
     fn short_range_f2(&self, q: f64) -> f64 {
-        2.0 * self.eta.powi(3) * q * (-(self.eta * q).powi(2)).exp() * FRAC_2_SQRT_PI * self.f0
+        2.0 * self.eta.powi(3) * q * exp_x(-(self.eta * q).powi(2)) * FRAC_2_SQRT_PI * self.f0
     }
     fn short_range_f3(&self, q: f64) -> f64 {
         -4.0 * ((self.eta * q).powi(2) - 0.5)
             * self.eta.powi(3)
-            * (-(self.eta * q).powi(2)).exp()
+            * exp_x(-(self.eta * q).powi(2))
+            * FRAC_2_SQRT_PI
+            * self.f0
+    }
+    fn short_range_f4(&self, q: f64) -> f64 {
+        8.0 * ((self.eta * q).powi(2) - 1.5)
+            * self.eta.powi(5)
+            * q
+            * exp_x(-(self.eta * q).powi(2))
             * FRAC_2_SQRT_PI
             * self.f0
     }
@@ -160,23 +170,56 @@ impl Display for EwaldTruncated {
 fn test_truncated_ewald() {
     use crate::pairwise::MultipoleEnergy;
 
+    use crate::test_utils::ERFC_ERROR;
     use approx::assert_relative_eq;
     let cutoff = 29.0;
     let alpha = 0.1;
-    let eps = 1e-9;
+    // `erfc_x` has an absolute error, and `erfc_x` and `exp_x` a relative error on each term,
+    // within `ERFC_ERROR`
+    let eps = 1e-9 + 2.0 * ERFC_ERROR;
+    let max_relative = 2.0 * ERFC_ERROR;
+    let h = 1e-4;
     let pot = EwaldTruncated::new(cutoff, alpha);
-    assert_relative_eq!(pot.short_range_f0(0.5), 0.03993019621374575, epsilon = eps);
-    assert_relative_eq!(pot.short_range_f1(0.5), -0.39929238172082965, epsilon = eps);
-    assert_relative_eq!(pot.short_range_f2(0.5), 3.364180431728417, epsilon = eps);
-    assert_relative_eq!(pot.short_range_f3(0.5), -21.56439656737916, epsilon = eps);
     assert_relative_eq!(
-        pot.self_energy(&[2.0], &[0.0]),
-        -0.22579937362074382,
-        epsilon = eps
+        pot.short_range_f0(0.5),
+        0.03993035146677211,
+        epsilon = eps,
+        max_relative = max_relative
+    );
+    assert_relative_eq!(
+        pot.short_range_f1(0.5),
+        -0.39929237279316654,
+        epsilon = eps,
+        max_relative = max_relative
+    );
+    assert_relative_eq!(
+        pot.short_range_f2(0.5),
+        3.364180356509678,
+        epsilon = eps,
+        max_relative = max_relative
+    );
+    assert_relative_eq!(
+        pot.short_range_f3(0.5),
+        -21.564396085227038,
+        epsilon = eps,
+        max_relative = max_relative
+    );
+    // Rounding of `short_range_f3` is amplified by one over the step
+    assert_relative_eq!(
+        pot.short_range_f4(0.5),
+        (pot.short_range_f3(0.5 + h) - pot.short_range_f3(0.5 - h)) / (2.0 * h),
+        epsilon = 1e-4 + ERFC_ERROR * pot.short_range_f3(0.5).abs() / h
+    );
+    assert_relative_eq!(
+        pot.self_energy(&[2.0], &[0.0], &[]),
+        -0.22579936857216082,
+        epsilon = eps,
+        max_relative = max_relative
     );
     assert_relative_eq!(
-        pot.self_energy(&[0.0], &[f64::sqrt(2.0)]),
-        -0.0007528321650,
-        epsilon = eps
+        pot.self_energy(&[0.0], &[f64::sqrt(2.0)], &[]),
+        -0.0007528321648,
+        epsilon = eps,
+        max_relative = max_relative
     );
 }
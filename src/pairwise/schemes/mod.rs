@@ -17,11 +17,14 @@
 
 //! Pairwise interaction schemes implementing the `ShortRangeFunction` trait.
 
+pub(crate) mod distance_dependent;
 pub(crate) mod ewald;
 pub(crate) mod ewald_truncated;
+pub(crate) mod offset_yukawa;
 pub(crate) mod plain;
 pub(crate) mod poisson;
 pub(crate) mod reactionfield;
+pub(crate) mod scheme_kind;
 
 /// Test utilities for pairwise schemes
 #[cfg(test)]
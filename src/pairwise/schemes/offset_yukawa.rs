@@ -0,0 +1,176 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+use crate::{pairwise::ShortRangeFunction, Cutoff, DebyeLength};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Screened Coulomb (Yukawa) scheme where screening starts at a contact distance, $\sigma$.
+///
+/// The charge-charge interaction is
+///
+/// $$ u(r) = \frac{z_1 z_2 e^{-\kappa (r - \sigma)}}{r} $$
+///
+/// which corresponds to the constant short-range function $S(q) = e^{\kappa \sigma}$.
+/// This is commonly used for charged colloids and proteins where the salt cannot penetrate the
+/// particles, and reduces to [`Plain`](crate::pairwise::Plain) for $\sigma = 0$ or without salt.
+/// The interaction is truncated at the cutoff and, unlike the [`Yukawa`](crate::pairwise::Yukawa)
+/// scheme, not shifted.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::pairwise::{MultipoleEnergy, OffsetYukawa};
+/// let scheme = OffsetYukawa::new(100.0, Some(10.0), 20.0);
+/// assert_relative_eq!(scheme.ion_ion_energy(1.0, 1.0, 20.0), 1.0 / 20.0);
+/// assert_relative_eq!(scheme.ion_ion_energy(1.0, 1.0, 30.0), (-1.0f64).exp() / 30.0);
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct OffsetYukawa {
+    /// Cut-off distance
+    cutoff: f64,
+    /// Optional Debye length
+    #[cfg_attr(feature = "serde", serde(alias = "debye", default))]
+    debye_length: Option<f64>,
+    /// Contact distance, σ, where screening starts
+    contact_distance: f64,
+}
+
+impl OffsetYukawa {
+    /// New scheme with a `cutoff`, optional Debye length, and contact distance, σ.
+    pub const fn new(cutoff: f64, debye_length: Option<f64>, contact_distance: f64) -> Self {
+        Self {
+            cutoff,
+            debye_length,
+            contact_distance,
+        }
+    }
+
+    /// Create from medium. The Debye length is calculated from the medium's properties in units
+    /// of angstrom. `cutoff` and `contact_distance` should be provided in angstrom.
+    pub fn from_medium(cutoff: f64, medium: &crate::Medium, contact_distance: f64) -> Self {
+        Self::new(cutoff, medium.debye_length(), contact_distance)
+    }
+
+    /// Contact distance, σ, where screening starts
+    pub const fn contact_distance(&self) -> f64 {
+        self.contact_distance
+    }
+}
+
+impl core::fmt::Display for OffsetYukawa {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Offset Yukawa: 𝑟✂ = {:.1}, σ = {:.1}",
+            self.cutoff, self.contact_distance
+        )?;
+        if let Some(debye_length) = self.debye_length {
+            write!(f, ", λᴰ = {:.1}", debye_length)?;
+        }
+        write!(f, " <{}>", Self::url())
+    }
+}
+
+impl Cutoff for OffsetYukawa {
+    #[inline]
+    fn cutoff(&self) -> f64 {
+        self.cutoff
+    }
+}
+
+impl DebyeLength for OffsetYukawa {
+    #[inline]
+    fn kappa(&self) -> Option<f64> {
+        self.debye_length.map(f64::recip)
+    }
+    fn set_debye_length(&mut self, debye_length: Option<f64>) -> crate::Result<()> {
+        crate::debye_length::check_debye_length(debye_length)?;
+        self.debye_length = debye_length;
+        Ok(())
+    }
+}
+
+impl crate::SaltSensitive for OffsetYukawa {}
+
+impl ShortRangeFunction for OffsetYukawa {
+    fn url() -> &'static str {
+        "https://doi.org/10.11429/ppmsj1919.17.0_48"
+    }
+    #[inline]
+    fn short_range_f0(&self, _q: f64) -> f64 {
+        self.kappa()
+            .map_or(1.0, |kappa| (kappa * self.contact_distance).exp())
+    }
+    #[inline]
+    fn short_range_f1(&self, _q: f64) -> f64 {
+        0.0
+    }
+    #[inline]
+    fn short_range_f2(&self, _q: f64) -> f64 {
+        0.0
+    }
+    #[inline]
+    fn short_range_f3(&self, _q: f64) -> f64 {
+        0.0
+    }
+    #[inline]
+    fn short_range_f4(&self, _q: f64) -> f64 {
+        0.0
+    }
+}
+
+#[test]
+fn test_yukawa() {
+    use crate::pairwise::{MultipoleEnergy, MultipoleForce, MultipolePotential, Plain};
+    use crate::NalgebraVector3;
+    use approx::assert_relative_eq;
+
+    let (debye_length, sigma) = (8.0, 5.0);
+    let mut scheme = OffsetYukawa::new(40.0, Some(debye_length), sigma);
+    assert_eq!(
+        scheme.to_string(),
+        "Offset Yukawa: 𝑟✂ = 40.0, σ = 5.0, λᴰ = 8.0 <https://doi.org/10.11429/ppmsj1919.17.0_48>"
+    );
+    let (z1, z2) = (2.0, -3.0);
+    let r = NalgebraVector3::new(3.0, 4.0, 12.0);
+    let distance = r.norm();
+    let expected = z1 * z2 * (-(distance - sigma) / debye_length).exp() / distance;
+    assert_relative_eq!(scheme.ion_ion_energy(z1, z2, distance), expected);
+    assert_relative_eq!(scheme.ion_potential(z1, distance) * z2, expected);
+    assert_eq!(scheme.ion_ion_energy(z1, z2, 41.0), 0.0);
+
+    // Force is the negative gradient of the energy
+    let h = 1e-6;
+    let force = NalgebraVector3::from(scheme.ion_ion_force(z1, z2, r));
+    let derivative = (scheme.ion_ion_energy(z1, z2, distance + h)
+        - scheme.ion_ion_energy(z1, z2, distance - h))
+        / (2.0 * h);
+    assert_relative_eq!(force, -r / distance * derivative, max_relative = 1e-6);
+
+    // Without screening or contact distance, the scheme is plain Coulomb
+    let plain = Plain::new(40.0, Some(debye_length));
+    assert_relative_eq!(
+        OffsetYukawa::new(40.0, Some(debye_length), 0.0).ion_ion_energy(z1, z2, distance),
+        plain.ion_ion_energy(z1, z2, distance)
+    );
+    scheme.set_debye_length(None).unwrap();
+    assert_relative_eq!(scheme.ion_ion_energy(z1, z2, distance), z1 * z2 / distance);
+}
@@ -132,11 +132,14 @@ impl DebyeLength for Plain {
         self.kappa
     }
     fn set_debye_length(&mut self, debye_length: Option<f64>) -> crate::Result<()> {
+        crate::debye_length::check_debye_length(debye_length)?;
         self.kappa = debye_length.map(f64::recip);
         Ok(())
     }
 }
 
+impl crate::SaltSensitive for Plain {}
+
 impl ShortRangeFunction for Plain {
     fn url() -> &'static str {
         "https://doi.org/msxd"
@@ -158,6 +161,10 @@ impl ShortRangeFunction for Plain {
     fn short_range_f3(&self, _q: f64) -> f64 {
         0.0
     }
+    #[inline]
+    fn short_range_f4(&self, _q: f64) -> f64 {
+        0.0
+    }
 }
 
 #[test]
@@ -195,6 +202,7 @@ fn test_coulomb() {
     assert_eq!(pot.short_range_f1(0.5), 0.0);
     assert_eq!(pot.short_range_f2(0.5), 0.0);
     assert_eq!(pot.short_range_f3(0.5), 0.0);
+    assert_eq!(pot.short_range_f4(0.5), 0.0);
 
     // Test potentials
     assert_eq!(pot.ion_potential(z1, cutoff + 1.0), 0.0);
@@ -337,6 +345,107 @@ fn test_coulomb() {
     assert_relative_eq!(energy, -0.0331091497054298, epsilon = eps);
 }
 
+#[test]
+fn test_quadrupole_quadrupole() {
+    use crate::pairwise::MultipoleEnergy;
+    use crate::{NalgebraMatrix3 as Matrix3, NalgebraVector3 as Vector3};
+    use approx::assert_relative_eq;
+
+    // Linear quadrupoles made from charges (+1, -2, +1) with no net charge or dipole
+    let cluster = |axis: Vector3, d: f64| -> Vec<(f64, Vector3)> {
+        vec![(1.0, axis * d), (-2.0, Vector3::zeros()), (1.0, -axis * d)]
+    };
+    let quadrupole = |charges: &[(f64, Vector3)]| -> Matrix3 {
+        charges.iter().map(|(z, r)| *z * r * r.transpose()).sum()
+    };
+    let d = 0.01;
+    let cluster1 = cluster(Vector3::new(1.0, 2.0, -1.0).normalize(), d);
+    let cluster2 = cluster(Vector3::new(0.0, 1.0, 3.0).normalize(), d);
+    let r = Vector3::new(3.0, -4.0, 2.0);
+
+    // Direct Coulomb sum between the two clusters
+    let exact: f64 = cluster1
+        .iter()
+        .flat_map(|(z1, r1)| {
+            cluster2
+                .iter()
+                .map(move |(z2, r2)| z1 * z2 / (r + r2 - r1).norm())
+        })
+        .sum();
+
+    let pot = Plain::without_cutoff();
+    let energy = pot.quadrupole_quadrupole_energy(quadrupole(&cluster1), quadrupole(&cluster2), r);
+    assert_relative_eq!(energy, exact, max_relative = 1e-4);
+    assert_relative_eq!(energy, -3.1043696615708066e-12, max_relative = 1e-9);
+
+    // Symmetric with respect to exchange of particles
+    let (quad1, quad2) = (quadrupole(&cluster1), quadrupole(&cluster2));
+    assert_relative_eq!(
+        pot.quadrupole_quadrupole_energy(quad1, quad2, r),
+        pot.quadrupole_quadrupole_energy(quad2, quad1, -r),
+        max_relative = 1e-12
+    );
+    let pot = Plain::new(5.0, None);
+    assert_eq!(pot.quadrupole_quadrupole_energy(quad1, quad2, r * 2.0), 0.0);
+}
+
+#[test]
+fn test_dipole_quadrupole() {
+    use crate::pairwise::{MultipoleEnergy, MultipoleForce};
+    use crate::{NalgebraMatrix3 as Matrix3, NalgebraVector3 as Vector3};
+    use approx::assert_relative_eq;
+
+    // Dipole from charges (+1, -1) and a linear quadrupole from charges (+1, -2, +1)
+    let d = 0.01;
+    let axis1 = Vector3::new(1.0, 2.0, -1.0).normalize();
+    let axis2 = Vector3::new(0.0, 1.0, 3.0).normalize();
+    let dipole_charges = [(1.0, axis1 * d), (-1.0, -axis1 * d)];
+    let quad_charges = [
+        (1.0, axis2 * d),
+        (-2.0, Vector3::zeros()),
+        (1.0, -axis2 * d),
+    ];
+    let dipole: Vector3 = dipole_charges.iter().map(|(z, r)| *z * r).sum();
+    let quad: Matrix3 = quad_charges
+        .iter()
+        .map(|(z, r)| *z * r * r.transpose())
+        .sum();
+
+    // Direct Coulomb sum, r = r_Q - r_mu
+    let exact = |r: Vector3| -> f64 {
+        dipole_charges
+            .iter()
+            .flat_map(|(z1, r1)| {
+                quad_charges
+                    .iter()
+                    .map(move |(z2, r2)| z1 * z2 / (r + r2 - r1).norm())
+            })
+            .sum()
+    };
+    let r = Vector3::new(3.0, -4.0, 2.0);
+    let pot = Plain::without_cutoff();
+    let energy = pot.dipole_quadrupole_energy(dipole, quad, r);
+    assert_relative_eq!(energy, exact(r), max_relative = 1e-4);
+
+    // 1/r⁴ scaling in the plain limit
+    assert_relative_eq!(
+        pot.dipole_quadrupole_energy(dipole, quad, 2.0 * r),
+        energy / 16.0,
+        max_relative = 1e-12
+    );
+
+    // Force on the dipole is the negative gradient with respect to its position
+    let h = 1e-5;
+    let force: Vector3 = pot.dipole_quadrupole_force(dipole, quad, -r).into();
+    for a in 0..3 {
+        let dr = Vector3::ith(a, h);
+        let gradient = (pot.dipole_quadrupole_energy(dipole, quad, r - dr)
+            - pot.dipole_quadrupole_energy(dipole, quad, r + dr))
+            / (2.0 * h);
+        assert_relative_eq!(force[a], -gradient, max_relative = 1e-6);
+    }
+}
+
 #[cfg(feature = "uom")]
 #[test]
 fn test_plain_si() {
@@ -87,11 +87,27 @@ impl Screening {
             yukawa_denom: 1.0 / (1.0 - (2.0 * reduced_kappa).exp()),
         }
     }
+
+    /// Screened reduced distance, qᵖ, and its first three derivatives with respect to q
+    #[inline]
+    fn screened_distance(&self, q: f64) -> [f64; 4] {
+        let exp2kq = (2.0 * self.reduced_kappa * q).exp() * self.yukawa_denom;
+        [
+            self.yukawa_denom - exp2kq,
+            -2.0 * self.reduced_kappa * exp2kq,
+            -4.0 * self.reduced_kappa_squared * exp2kq,
+            -8.0 * self.reduced_kappa_squared * self.reduced_kappa * exp2kq,
+        ]
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 /// Generalized Poisson scheme for short-range electrostatic interactions.
+///
+/// `C` and `D` are const generic parameters, so that the polynomial of the short-range function
+/// is unrolled and constant folded for each scheme. They are signed as `D` may be negative.
+/// For orders that are only known at runtime, see [`RuntimePoisson`].
 pub struct Poisson<const C: i32, const D: i32> {
     /// Cutoff radius
     cutoff: f64,
@@ -128,6 +144,248 @@ impl<'de, const C: i32, const D: i32> Deserialize<'de> for Poisson<C, D> {
     }
 }
 
+/// Binomial coefficient which, as [`binomial`], is zero if `k > n`
+#[inline]
+const fn binomial_coefficient(n: i32, k: i32) -> i32 {
+    if k > n {
+        return 0;
+    }
+    let (mut result, mut i) = (1, 0);
+    while i < k {
+        result = result * (n - i) / (i + 1);
+        i += 1;
+    }
+    result
+}
+
+/// Coefficient, (C - c) × binom(D - 1 + c, c), of the polynomial in the short-range function.
+///
+/// As a `const fn`, the coefficients fold to constants when inlined with constant orders.
+#[inline]
+const fn coefficient(order_c: i32, order_d: i32, c: i32) -> i32 {
+    (order_c - c) * binomial_coefficient(order_d - 1 + c, c)
+}
+
+/// Check that the orders, C and D, give a valid Poisson scheme
+fn check_orders(c: i32, d: i32) -> Result<(), &'static str> {
+    if c < 1 {
+        return Err("`C` must be larger than zero");
+    }
+    if d < -1 && d != -c {
+        return Err("If `D` is less than negative one, then it has to equal negative `C`");
+    }
+    if d == 0 && c != 1 {
+        return Err("If `D` is zero, then `C` has to equal one ");
+    }
+    Ok(())
+}
+
+/// Binomial prefactor, which vanishes for unscreened schemes with D = -C
+fn binom_cdc(c: i32, d: i32, screened: bool) -> f64 {
+    if screened || d != -c {
+        f64::from(binomial(c + d, c) * d)
+    } else {
+        0.0
+    }
+}
+
+/// URL to the original article of the scheme with orders C and D
+const fn reference(c: i32, d: i32) -> &'static str {
+    match (c, d) {
+        (1, -1) => "https://doi.org/msxd",             // plain
+        (1, 0) => "https://doi.org/10.1063/1.478738",  // wolf
+        (1, 1) => "https://doi.org/10/fp959p",         // fennell
+        (1, 2) => "https://doi.org/10/csh8bg",         // kale
+        (1, 3) => "https://doi.org/10.1021/ct300961",  // mccann
+        (2, 1) => "https://doi.org/10.1063/1.3582791", // fukuda
+        (2, 2) => "https://doi.org/dbpbts",            // markland
+        (3, 3) => "https://doi.org/10/c5fr",           // stenqvist
+        (4, 3) => "https://doi.org/10.1063/1.3216520", // fanourgakis
+        _ => "https://doi.org/c5fr",                   // generic poisson
+    }
+}
+
+/// Orders, C and D, and polynomial coefficients of a Poisson scheme.
+///
+/// The short-range function and its derivatives are shared by [`Poisson`], where the orders
+/// are const generic parameters and all methods fold to constants when inlined, and
+/// [`RuntimePoisson`], where the orders are given at runtime.
+trait Orders: Copy {
+    /// Number of cancelled derivatives at the origin, C
+    fn c(self) -> i32;
+    /// Number of cancelled derivatives at the cutoff, D
+    fn d(self) -> i32;
+    /// Coefficient, (C - c) × binom(D - 1 + c, c), of the polynomial
+    fn coefficient(self, c: i32) -> f64;
+
+    /// Polynomial, ∑ₖ aₖ qᵖ, and its derivative with respect to qᵖ using Horner's method
+    #[inline]
+    fn polynomial(self, qp: f64) -> (f64, f64) {
+        let (sum, derivative) = (0..self.c())
+            .rev()
+            .fold((0.0, 0.0), |(sum, derivative), c| {
+                (sum * qp + self.coefficient(c), derivative * qp + sum)
+            });
+        (sum / f64::from(self.c()), derivative / f64::from(self.c()))
+    }
+
+    /// First derivative of the short-range function with respect to qᵖ
+    #[inline]
+    fn dsdqp(self, qp: f64) -> f64 {
+        let (sum, derivative) = self.polynomial(qp);
+        let d = self.d();
+        -f64::from(d + 1) * (1.0 - qp).powi(d) * sum + (1.0 - qp).powi(d + 1) * derivative
+    }
+
+    #[inline]
+    fn short_range_f0(self, screening: Option<&Screening>, q: f64) -> f64 {
+        let (c, d) = (self.c(), self.d());
+        // checks on C and D are evaluated at compile time for constant orders
+        if d == -c {
+            return 1.0;
+        }
+        let qp: f64 = screening.map_or(q, |s| s.screened_distance(q)[0]);
+
+        if d == 0 && c == 1 {
+            return 1.0 - qp;
+        }
+        (1.0 - qp).powi(d + 1) * self.polynomial(qp).0
+    }
+
+    #[inline]
+    fn short_range_f1(self, screening: Option<&Screening>, q: f64) -> f64 {
+        let (c, d) = (self.c(), self.d());
+        if d == -c {
+            return 0.0;
+        }
+        if d == 0 && c == 1 {
+            return 0.0;
+        }
+        let (qp, dqpdq) = if let Some(s) = screening {
+            let [qp, dqpdq, ..] = s.screened_distance(q);
+            (qp, dqpdq)
+        } else {
+            (q, 1.0)
+        };
+        self.dsdqp(qp) * dqpdq
+    }
+
+    #[inline]
+    fn short_range_f2(self, screening: Option<&Screening>, binom_cdc: f64, q: f64) -> f64 {
+        let (c, d) = (self.c(), self.d());
+        if d == -c {
+            return 0.0;
+        }
+        if d == 0 && c == 1 {
+            return 0.0;
+        }
+
+        let (qp, dqpdq, d2qpdq2, dsdqp) = if let Some(s) = screening {
+            let [qp, dqpdq, d2qpdq2, _] = s.screened_distance(q);
+            (qp, dqpdq, d2qpdq2, self.dsdqp(qp))
+        } else {
+            (q, 1.0, 0.0, 0.0)
+        };
+        let d2sdqp2 = binom_cdc * (1.0 - qp).powi(d - 1) * qp.powi(c - 1);
+        d2sdqp2 * dqpdq * dqpdq + dsdqp * d2qpdq2
+    }
+
+    #[inline]
+    fn short_range_f3(self, screening: Option<&Screening>, binom_cdc: f64, q: f64) -> f64 {
+        let (c, d) = (self.c(), self.d());
+        if d == -c {
+            return 0.0;
+        }
+        if d == 0 && c == 1 {
+            return 0.0;
+        }
+
+        let (qp, dqpdq, d2qpdq2, d3qpdq3, d2sdqp2, dsdqp) = if let Some(s) = screening {
+            let [qp, dqpdq, d2qpdq2, d3qpdq3] = s.screened_distance(q);
+            let d2sdqp2 = binom_cdc * (1.0 - qp).powi(d - 1) * qp.powi(c - 1);
+            (qp, dqpdq, d2qpdq2, d3qpdq3, d2sdqp2, self.dsdqp(qp))
+        } else {
+            (q, 1.0, 0.0, 0.0, 0.0, 0.0)
+        };
+        let d3sdqp3 = binom_cdc
+            * (1.0 - qp).powi(d - 2)
+            * qp.powi(c - 2)
+            * ((2.0 - c as f64 - d as f64) * qp + c as f64 - 1.0);
+        d3sdqp3 * dqpdq * dqpdq * dqpdq + 3.0 * d2sdqp2 * dqpdq * d2qpdq2 + dsdqp * d3qpdq3
+    }
+
+    fn self_energy_prefactors(self, screening: Option<&Screening>) -> SelfEnergyPrefactors {
+        let (c, d) = (self.c(), self.d());
+        let mut c1: f64 = -0.5 * (c + d) as f64 / c as f64;
+        if let Some(s) = screening {
+            c1 = c1 * -2.0 * s.reduced_kappa * s.yukawa_denom;
+        }
+        SelfEnergyPrefactors {
+            monopole: Some(c1),
+            dipole: None,
+            quadrupole: None,
+        }
+    }
+
+    /// Write the orders, cutoff, Debye length, and reference of the scheme
+    fn describe(
+        self,
+        f: &mut core::fmt::Formatter<'_>,
+        cutoff: f64,
+        debye_length: Option<f64>,
+    ) -> core::fmt::Result {
+        let (c, d) = (self.c(), self.d());
+        write!(f, "Poisson: 𝐶 = {}, 𝐷 = {}, 𝑟✂ = {:.1} Å", c, d, cutoff)?;
+        if let Some(debye_length) = debye_length {
+            write!(f, ", λᴰ = {:.1} Å", debye_length)?;
+        }
+        write!(f, " <{}>", reference(c, d))?;
+        Ok(())
+    }
+}
+
+/// Orders given as const generic parameters
+#[derive(Clone, Copy)]
+struct ConstOrders<const C: i32, const D: i32>;
+
+impl<const C: i32, const D: i32> Orders for ConstOrders<C, D> {
+    #[inline]
+    fn c(self) -> i32 {
+        C
+    }
+    #[inline]
+    fn d(self) -> i32 {
+        D
+    }
+    #[inline]
+    fn coefficient(self, c: i32) -> f64 {
+        f64::from(coefficient(C, D, c))
+    }
+}
+
+/// Orders given at runtime with tabulated coefficients
+#[derive(Clone, Copy)]
+struct RuntimeOrders<'a> {
+    c: i32,
+    d: i32,
+    coefficients: &'a [f64],
+}
+
+impl Orders for RuntimeOrders<'_> {
+    #[inline]
+    fn c(self) -> i32 {
+        self.c
+    }
+    #[inline]
+    fn d(self) -> i32 {
+        self.d
+    }
+    #[inline]
+    fn coefficient(self, c: i32) -> f64 {
+        self.coefficients[c as usize]
+    }
+}
+
 /// Scheme for a vanilla coulomb interaction using the Poisson framework. Same as `Coulomb`.
 pub type _Plain = Poisson<1, -1>;
 
@@ -160,34 +418,28 @@ pub type Fanourgakis = Poisson<4, 3>;
 impl<const C: i32, const D: i32> Poisson<C, D> {
     /// Create a new Poisson scheme with a given cutoff and optional Debye length.
     pub fn new(cutoff: f64, debye_length: Option<f64>) -> Self {
-        if C < 1 {
-            panic!("`C` must be larger than zero");
-        }
-        if D < -1 && D != -C {
-            panic!("If `D` is less than negative one, then it has to equal negative `C`");
-        }
-        if D == 0 && C != 1 {
-            panic!("If `D` is zero, then `C` has to equal one ");
+        if let Err(message) = check_orders(C, D) {
+            panic!("{}", message);
         }
 
         let _has_dipolar_selfenergy = C >= 2;
 
         let screening = debye_length.map(|d| Screening::new(d, cutoff));
 
-        let binom_cdc = if screening.is_some() || D != -C {
-            f64::from(binomial(C + D, C) * D)
-        } else {
-            0.0
-        };
-
         Self {
             cutoff,
             debye_length,
             _has_dipolar_selfenergy,
-            binom_cdc,
+            binom_cdc: binom_cdc(C, D, screening.is_some()),
             screening,
         }
     }
+
+    /// Create from medium. The Debye length is calculated from the medium's properties in units
+    /// of angstrom. `cutoff` should be provided in angstrom.
+    pub fn from_medium(cutoff: f64, medium: &crate::Medium) -> Self {
+        Self::new(cutoff, medium.debye_length())
+    }
 }
 
 impl<const C: i32, const D: i32> crate::Cutoff for Poisson<C, D> {
@@ -202,167 +454,199 @@ impl<const C: i32, const D: i32> crate::DebyeLength for Poisson<C, D> {
         self.screening.as_ref().map(|s| s.kappa)
     }
     fn set_debye_length(&mut self, debye_length: Option<f64>) -> crate::Result<()> {
+        crate::debye_length::check_debye_length(debye_length)?;
+        self.debye_length = debye_length;
         self.screening = debye_length.map(|d| Screening::new(d, self.cutoff));
+        self.binom_cdc = binom_cdc(C, D, self.screening.is_some());
         Ok(())
     }
 }
 
+impl<const C: i32, const D: i32> crate::SaltSensitive for Poisson<C, D> {}
+
 impl<const C: i32, const D: i32> ShortRangeFunction for Poisson<C, D> {
     fn url() -> &'static str {
-        match (C, D) {
-            (1, -1) => "https://doi.org/msxd",             // plain
-            (1, 0) => "https://doi.org/10.1063/1.478738",  // wolf
-            (1, 1) => "https://doi.org/10/fp959p",         // fennell
-            (1, 2) => "https://doi.org/10/csh8bg",         // kale
-            (1, 3) => "https://doi.org/10.1021/ct300961",  // mccann
-            (2, 1) => "https://doi.org/10.1063/1.3582791", // fukuda
-            (2, 2) => "https://doi.org/dbpbts",            // markland
-            (3, 3) => "https://doi.org/10/c5fr",           // stenqvist
-            (4, 3) => "https://doi.org/10.1063/1.3216520", // fanourgakis
-            _ => "https://doi.org/c5fr",                   // generic poisson
-        }
+        reference(C, D)
     }
 
     fn short_range_f0(&self, q: f64) -> f64 {
-        // checks on C and D are evaluated at compile time
-        if D == -C {
-            return 1.0;
-        }
-        let qp: f64 = self.screening.as_ref().map_or(q, |s| {
-            (1.0 - (2.0 * s.reduced_kappa * q).exp()) * s.yukawa_denom
-        });
+        ConstOrders::<C, D>.short_range_f0(self.screening.as_ref(), q)
+    }
+    fn short_range_f1(&self, q: f64) -> f64 {
+        ConstOrders::<C, D>.short_range_f1(self.screening.as_ref(), q)
+    }
+    fn short_range_f2(&self, q: f64) -> f64 {
+        ConstOrders::<C, D>.short_range_f2(self.screening.as_ref(), self.binom_cdc, q)
+    }
+    fn short_range_f3(&self, q: f64) -> f64 {
+        ConstOrders::<C, D>.short_range_f3(self.screening.as_ref(), self.binom_cdc, q)
+    }
+    fn self_energy_prefactors(&self) -> SelfEnergyPrefactors {
+        ConstOrders::<C, D>.self_energy_prefactors(self.screening.as_ref())
+    }
+}
 
-        if D == 0 && C == 1 {
-            return 1.0 - qp;
+impl<const C: i32, const D: i32> core::fmt::Display for Poisson<C, D> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        ConstOrders::<C, D>.describe(f, self.cutoff, self.kappa().map(f64::recip))
+    }
+}
+
+/// Generalized Poisson scheme with orders, C and D, given at runtime.
+///
+/// This is the same short-range function as [`Poisson`], for orders that are only known at
+/// runtime, _e.g._ from a configuration file.
+/// The polynomial coefficients are tabulated on construction, but the series cannot be
+/// unrolled by the compiler so that [`Poisson`] should be preferred when the orders are known at
+/// compile time.
+/// Run `cargo bench --bench schemes -- poisson` to compare the two.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::pairwise::{RuntimePoisson, ShortRangeFunction, Stenqvist};
+/// let (c, d) = (3, 3); // _e.g._ from user input
+/// let scheme = RuntimePoisson::new(c, d, 12.0, None).unwrap();
+/// assert_relative_eq!(scheme.short_range_f0(0.5), Stenqvist::new(12.0, None).short_range_f0(0.5));
+/// assert!(RuntimePoisson::new(0, 3, 12.0, None).is_err());
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct RuntimePoisson {
+    /// Number of cancelled derivatives at the origin, C
+    c: i32,
+    /// Number of cancelled derivatives at the cutoff, D
+    d: i32,
+    /// Cutoff radius
+    cutoff: f64,
+    /// Debye length
+    debye_length: Option<f64>,
+    /// Coefficients, (C - c) × binom(D - 1 + c, c), of the polynomial
+    #[cfg_attr(feature = "serde", serde(skip))]
+    coefficients: Vec<f64>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    binom_cdc: f64,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    screening: Option<Screening>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for RuntimePoisson {
+    fn deserialize<DES>(deserializer: DES) -> Result<Self, DES::Error>
+    where
+        DES: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct RuntimePoissonData {
+            c: i32,
+            d: i32,
+            cutoff: f64,
+            debye_length: Option<f64>,
         }
 
-        // todo: could binomial coeffs be evaluated at compile time? E.g. with recursion.
-        let sum: f64 = (0..C)
-            .map(|c| (binomial(D - 1 + c, c) * (C - c)) as f64 / f64::from(C) * qp.powi(c))
-            .sum();
-        (1.0 - qp).powi(D + 1) * sum
+        let RuntimePoissonData {
+            c,
+            d,
+            cutoff,
+            debye_length,
+        } = RuntimePoissonData::deserialize(deserializer)?;
+        RuntimePoisson::new(c, d, cutoff, debye_length).map_err(serde::de::Error::custom)
     }
-    fn short_range_f1(&self, q: f64) -> f64 {
-        if D == -C {
-            return 0.0;
-        }
-        if D == 0 && C == 1 {
-            return 0.0;
-        }
-        let (qp, dqpdq) = if let Some(s) = &self.screening {
-            let exp2kq = (2.0 * s.reduced_kappa * q).exp();
-            let qp = (1.0 - exp2kq) * s.yukawa_denom;
-            let dqpdq = -2.0 * s.reduced_kappa * exp2kq * s.yukawa_denom;
-            (qp, dqpdq)
-        } else {
-            (q, 1.0)
-        };
-        let mut sum1 = 1.0;
-        let mut sum2 = 0.0;
+}
 
-        for c in 1..C {
-            let factor = (binomial(D - 1 + c, c) * (C - c)) as f64 / C as f64;
-            sum1 += factor * qp.powi(c);
-            sum2 += factor * c as f64 * qp.powi(c - 1);
-        }
-        let dsdqp = -f64::from(D + 1) * (1.0 - qp).powi(D) * sum1 + (1.0 - qp).powi(D + 1) * sum2;
-        dsdqp * dqpdq
+impl RuntimePoisson {
+    /// Create a new Poisson scheme with orders, C and D, a given cutoff and optional Debye length.
+    ///
+    /// # Errors
+    /// Returns an error if the orders do not give a valid scheme, see [`Poisson`].
+    pub fn new(c: i32, d: i32, cutoff: f64, debye_length: Option<f64>) -> crate::Result<Self> {
+        check_orders(c, d).map_err(crate::Error::InvalidPoissonOrders)?;
+        let screening = debye_length.map(|debye_length| Screening::new(debye_length, cutoff));
+        Ok(Self {
+            c,
+            d,
+            cutoff,
+            debye_length,
+            coefficients: (0..c).map(|k| f64::from(coefficient(c, d, k))).collect(),
+            binom_cdc: binom_cdc(c, d, screening.is_some()),
+            screening,
+        })
     }
 
-    fn short_range_f2(&self, q: f64) -> f64 {
-        if D == -C {
-            return 0.0;
-        }
-        if D == 0 && C == 1 {
-            return 0.0;
-        }
+    /// Orders, (C, D), of the scheme
+    pub const fn orders(&self) -> (i32, i32) {
+        (self.c, self.d)
+    }
 
-        let (qp, dqpdq, d2qpdq2, dsdqp) = if let Some(s) = &self.screening {
-            let qp = (1.0 - (2.0 * s.reduced_kappa * q).exp()) * s.yukawa_denom;
-            let dqpdq = -2.0 * s.reduced_kappa * (2.0 * s.reduced_kappa * q).exp() * s.yukawa_denom;
-            let d2qpdq2 =
-                -4.0 * s.reduced_kappa_squared * (2.0 * s.reduced_kappa * q).exp() * s.yukawa_denom;
-            let mut tmp1 = 1.0;
-            let mut tmp2 = 0.0;
-            for c in 1..C {
-                let b = binomial(D - 1 + c, c) as f64 * (C - c) as f64;
-                tmp1 += b / C as f64 * qp.powi(c);
-                tmp2 += b * c as f64 / C as f64 * qp.powi(c - 1);
-            }
-            let dsdqp =
-                -f64::from(D + 1) * (1.0 - qp).powi(D) * tmp1 + (1.0 - qp).powi(D + 1) * tmp2;
-            (qp, dqpdq, d2qpdq2, dsdqp)
-        } else {
-            (q, 1.0, 0.0, 0.0)
-        };
-        let d2sdqp2 = self.binom_cdc * (1.0 - qp).powi(D - 1) * qp.powi(C - 1);
-        d2sdqp2 * dqpdq * dqpdq + dsdqp * d2qpdq2
+    /// URL to the original article describing the scheme
+    pub const fn reference(&self) -> &'static str {
+        reference(self.c, self.d)
     }
 
-    fn short_range_f3(&self, q: f64) -> f64 {
-        if D == -C {
-            return 0.0;
-        }
-        if D == 0 && C == 1 {
-            return 0.0;
+    fn orders_with_coefficients(&self) -> RuntimeOrders<'_> {
+        RuntimeOrders {
+            c: self.c,
+            d: self.d,
+            coefficients: &self.coefficients,
         }
+    }
+}
 
-        let (qp, dqpdq, d2qpdq2, d3qpdq3, d2sdqp2, dsdqp) = if let Some(s) = &self.screening {
-            let qp = (1.0 - (2.0 * s.reduced_kappa * q).exp()) * s.yukawa_denom;
-            let dqpdq = -2.0 * s.reduced_kappa * (2.0 * s.reduced_kappa * q).exp() * s.yukawa_denom;
-            let d2qpdq2 =
-                -4.0 * s.reduced_kappa_squared * (2.0 * s.reduced_kappa * q).exp() * s.yukawa_denom;
-            let d3qpdq3 = -8.0
-                * s.reduced_kappa_squared
-                * s.reduced_kappa
-                * (2.0 * s.reduced_kappa * q).exp()
-                * s.yukawa_denom;
-            let d2sdqp2 = self.binom_cdc * (1.0 - qp).powi(D - 1) * qp.powi(C - 1);
-            let mut tmp1 = 1.0;
-            let mut tmp2 = 0.0;
-            for c in 1..C {
-                tmp1 += (binomial(D - 1 + c, c) * (C - c)) as f64 / C as f64 * qp.powi(c);
-                tmp2 += (binomial(D - 1 + c, c) * (C - c)) as f64 / C as f64
-                    * c as f64
-                    * qp.powi(c - 1);
-            }
-            let dsdqp =
-                -f64::from(D + 1) * (1.0 - qp).powi(D) * tmp1 + (1.0 - qp).powi(D + 1) * tmp2;
-            (qp, dqpdq, d2qpdq2, d3qpdq3, d2sdqp2, dsdqp)
-        } else {
-            (q, 1.0, 0.0, 0.0, 0.0, 0.0)
-        };
-        let d3sdqp3 = self.binom_cdc
-            * (1.0 - qp).powi(D - 2)
-            * qp.powi(C - 2)
-            * ((2.0 - C as f64 - D as f64) * qp + C as f64 - 1.0);
-        d3sdqp3 * dqpdq * dqpdq * dqpdq + 3.0 * d2sdqp2 * dqpdq * d2qpdq2 + dsdqp * d3qpdq3
+impl crate::Cutoff for RuntimePoisson {
+    fn cutoff(&self) -> f64 {
+        self.cutoff
+    }
+}
+
+impl crate::DebyeLength for RuntimePoisson {
+    #[inline]
+    fn kappa(&self) -> Option<f64> {
+        self.screening.as_ref().map(|s| s.kappa)
+    }
+    fn set_debye_length(&mut self, debye_length: Option<f64>) -> crate::Result<()> {
+        crate::debye_length::check_debye_length(debye_length)?;
+        self.debye_length = debye_length;
+        self.screening = debye_length.map(|d| Screening::new(d, self.cutoff));
+        self.binom_cdc = binom_cdc(self.c, self.d, self.screening.is_some());
+        Ok(())
+    }
+}
+
+impl crate::SaltSensitive for RuntimePoisson {}
+
+impl ShortRangeFunction for RuntimePoisson {
+    /// Generalized Poisson scheme, see [`RuntimePoisson::reference`] for the given orders
+    fn url() -> &'static str {
+        "https://doi.org/c5fr"
     }
 
+    fn short_range_f0(&self, q: f64) -> f64 {
+        self.orders_with_coefficients()
+            .short_range_f0(self.screening.as_ref(), q)
+    }
+    fn short_range_f1(&self, q: f64) -> f64 {
+        self.orders_with_coefficients()
+            .short_range_f1(self.screening.as_ref(), q)
+    }
+    fn short_range_f2(&self, q: f64) -> f64 {
+        self.orders_with_coefficients()
+            .short_range_f2(self.screening.as_ref(), self.binom_cdc, q)
+    }
+    fn short_range_f3(&self, q: f64) -> f64 {
+        self.orders_with_coefficients()
+            .short_range_f3(self.screening.as_ref(), self.binom_cdc, q)
+    }
     fn self_energy_prefactors(&self) -> SelfEnergyPrefactors {
-        let mut c1: f64 = -0.5 * (C + D) as f64 / C as f64;
-        if let Some(s) = &self.screening {
-            c1 = c1 * -2.0 * s.reduced_kappa * s.yukawa_denom;
-        }
-        SelfEnergyPrefactors {
-            monopole: Some(c1),
-            dipole: None,
-        }
+        self.orders_with_coefficients()
+            .self_energy_prefactors(self.screening.as_ref())
     }
 }
 
-impl<const C: i32, const D: i32> core::fmt::Display for Poisson<C, D> {
+impl core::fmt::Display for RuntimePoisson {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(
-            f,
-            "Poisson: 𝐶 = {}, 𝐷 = {}, 𝑟✂ = {:.1} Å",
-            C, D, self.cutoff
-        )?;
-        if let Some(debye_length) = self.kappa().map(f64::recip) {
-            write!(f, ", λᴰ = {:.1} Å", debye_length)?;
-        }
-        write!(f, " <{}>", Self::url())?;
-        Ok(())
+        self.orders_with_coefficients()
+            .describe(f, self.cutoff, self.kappa().map(f64::recip))
     }
 }
 
@@ -395,7 +679,7 @@ fn test_poisson() {
 
     let pot = Stenqvist::new(cutoff, Some(23.0));
     approx::assert_relative_eq!(
-        pot.self_energy(&[2.0], &[0.0]),
+        pot.self_energy(&[2.0], &[0.0], &[]),
         -0.03037721287,
         epsilon = eps
     );
@@ -675,3 +959,109 @@ fn test_poisson() {
         eps
     );
 }
+
+#[test]
+fn test_poisson_salt_sensitive() {
+    use crate::{DebyeLength, Medium, Salt, SaltSensitive};
+    // Unscreened schemes with D = -C have a vanishing binomial prefactor
+    let mut pot = _Plain::new(29.0, None);
+    pot.set_debye_length(Some(20.0)).unwrap();
+    assert_eq!(pot, _Plain::new(29.0, Some(20.0)));
+    pot.set_debye_length(None).unwrap();
+    assert_eq!(pot, _Plain::new(29.0, None));
+
+    let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.05);
+    let mut pot = Stenqvist::new(29.0, None);
+    pot.update_screening(&medium).unwrap();
+    assert_eq!(pot, Stenqvist::from_medium(29.0, &medium));
+    for invalid in [0.0, -10.0, f64::INFINITY, f64::NAN] {
+        assert!(pot.set_debye_length(Some(invalid)).is_err());
+    }
+    assert_eq!(pot, Stenqvist::from_medium(29.0, &medium));
+}
+
+#[test]
+fn test_poisson_coefficients() {
+    use approx::assert_relative_eq;
+    for n in -3..14 {
+        for k in 0..8 {
+            assert_eq!(binomial_coefficient(n, k), binomial(n, k));
+        }
+    }
+    // Closed form of the polynomial sum for C = 8, D = 8
+    let q: f64 = 0.3;
+    let sum: f64 = (0..8)
+        .map(|c| f64::from(binomial(7 + c, c) * (8 - c)) / 8.0 * q.powi(c))
+        .sum();
+    let pot = Poisson::<8, 8>::new(10.0, None);
+    assert_relative_eq!(
+        pot.short_range_f0(q),
+        (1.0 - q).powi(9) * sum,
+        epsilon = 1e-14
+    );
+
+    // Derivatives against finite differences, with and without salt
+    for pot in [
+        Poisson::<8, 8>::new(10.0, None),
+        Poisson::new(10.0, Some(7.0)),
+    ] {
+        let h = 1e-5;
+        let diff = |f: &dyn Fn(f64) -> f64| (f(q + h) - f(q - h)) / (2.0 * h);
+        let f0 = |q| pot.short_range_f0(q);
+        let f1 = |q| pot.short_range_f1(q);
+        let f2 = |q| pot.short_range_f2(q);
+        assert_relative_eq!(pot.short_range_f1(q), diff(&f0), max_relative = 1e-7);
+        assert_relative_eq!(pot.short_range_f2(q), diff(&f1), max_relative = 1e-7);
+        assert_relative_eq!(pot.short_range_f3(q), diff(&f2), max_relative = 1e-6);
+    }
+}
+
+#[test]
+fn test_runtime_poisson() {
+    use crate::DebyeLength;
+    use approx::assert_relative_eq;
+
+    fn check<const C: i32, const D: i32>(debye_length: Option<f64>) {
+        let pot = Poisson::<C, D>::new(10.0, debye_length);
+        let runtime = RuntimePoisson::new(C, D, 10.0, debye_length).unwrap();
+        assert_eq!(runtime.orders(), (C, D));
+        assert_eq!(runtime.reference(), Poisson::<C, D>::url());
+        assert_eq!(runtime.to_string(), pot.to_string());
+        for q in [0.1, 0.3, 0.7, 0.9] {
+            assert_relative_eq!(runtime.short_range_f0(q), pot.short_range_f0(q));
+            assert_relative_eq!(runtime.short_range_f1(q), pot.short_range_f1(q));
+            assert_relative_eq!(runtime.short_range_f2(q), pot.short_range_f2(q));
+            assert_relative_eq!(runtime.short_range_f3(q), pot.short_range_f3(q));
+        }
+        assert_eq!(
+            runtime.self_energy_prefactors().monopole,
+            pot.self_energy_prefactors().monopole
+        );
+    }
+    for debye_length in [None, Some(7.0)] {
+        check::<1, -1>(debye_length);
+        check::<1, 0>(debye_length);
+        check::<1, 1>(debye_length);
+        check::<2, 2>(debye_length);
+        check::<3, 3>(debye_length);
+        check::<4, 3>(debye_length);
+        check::<8, 8>(debye_length);
+    }
+
+    let mut runtime = RuntimePoisson::new(3, 3, 10.0, None).unwrap();
+    runtime.set_debye_length(Some(7.0)).unwrap();
+    assert_eq!(runtime, RuntimePoisson::new(3, 3, 10.0, Some(7.0)).unwrap());
+    assert!(matches!(
+        runtime.set_debye_length(Some(-7.0)),
+        Err(crate::Error::InvalidDebyeLength)
+    ));
+    assert!(runtime.set_debye_length(Some(f64::NAN)).is_err());
+    assert_eq!(runtime, RuntimePoisson::new(3, 3, 10.0, Some(7.0)).unwrap());
+
+    for (c, d) in [(0, 3), (2, -3), (2, 0)] {
+        assert!(matches!(
+            RuntimePoisson::new(c, d, 10.0, None),
+            Err(crate::Error::InvalidPoissonOrders(_))
+        ));
+    }
+}
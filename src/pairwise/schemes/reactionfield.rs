@@ -91,6 +91,22 @@ impl ReactionField {
             cutoff,
         }
     }
+    /// Create from medium, which is the surroundings outside the cut-off.
+    ///
+    /// # Arguments
+    /// - `cutoff` - Spherical cut-off distance
+    /// - `medium` - Medium providing the relative permittivity outside the cut-off
+    /// - `dielec_in` - Relative permittivity inside the cut-off i.e. the dispersing medium
+    /// - `shifted` - Shift to zero potential at the cut-off
+    ///
+    pub fn from_medium(
+        cutoff: f64,
+        medium: &crate::Medium,
+        dielec_in: f64,
+        shift_to_zero: bool,
+    ) -> Self {
+        Self::new(cutoff, medium.permittivity(), dielec_in, shift_to_zero)
+    }
     /// Get outside relative dielectric constant
     pub const fn permittivity_out(&self) -> f64 {
         self.dielec_out
@@ -160,6 +176,9 @@ impl ShortRangeFunction for ReactionField {
     fn short_range_f3(&self, _q: f64) -> f64 {
         6.0 * (self.dielec_out - self.dielec_in) / (2.0 * self.dielec_out + self.dielec_in)
     }
+    fn short_range_f4(&self, _q: f64) -> f64 {
+        0.0
+    }
 
     fn self_energy_prefactors(&self) -> SelfEnergyPrefactors {
         let monopole = if self.shift_to_zero {
@@ -171,7 +190,11 @@ impl ShortRangeFunction for ReactionField {
             -(2.0 * self.dielec_out - 2.0 * self.dielec_in)
                 / (2.0 * (2.0 * self.dielec_out + self.dielec_in)),
         );
-        SelfEnergyPrefactors { monopole, dipole }
+        SelfEnergyPrefactors {
+            monopole,
+            dipole,
+            quadrupole: None,
+        }
     }
 }
 
@@ -189,9 +212,9 @@ mod tests {
 
         let pot = ReactionField::new_unshifted(cutoff, dielec_out, dielec_in);
 
-        assert_relative_eq!(pot.self_energy(&[2.0], &[0.0]), 0.0, epsilon = 1e-6);
+        assert_relative_eq!(pot.self_energy(&[2.0], &[0.0], &[]), 0.0, epsilon = 1e-6);
         assert_relative_eq!(
-            pot.self_energy(&[0.0], &[f64::sqrt(2.0)]),
+            pot.self_energy(&[0.0], &[f64::sqrt(2.0)], &[]),
             -0.00004023807698,
             epsilon = 1e-9
         );
@@ -217,4 +240,12 @@ mod tests {
             "Reaction field: εᵢ = 1.0, εₒ = 80.0, 𝑟✂ = 29.0, shifted <https://doi.org/dscmwg>"
         );
     }
+
+    #[test]
+    fn test_reaction_field_from_medium() {
+        let medium = crate::Medium::neat_water(298.15);
+        let pot = ReactionField::from_medium(29.0, &medium, 1.0, true);
+        assert_eq!(pot.permittivity_out(), medium.permittivity());
+        assert_eq!(pot.permittivity_in(), 1.0);
+    }
 }
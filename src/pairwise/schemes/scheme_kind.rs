@@ -0,0 +1,268 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Interaction schemes selected at runtime.
+
+use super::{
+    ewald::RealSpaceEwald,
+    ewald_truncated::EwaldTruncated,
+    plain::Plain,
+    poisson::{
+        Fanourgakis, Kale, Markland, McCann, RuntimePoisson, Stenqvist, UndampedFukuda,
+        UndampedWolf, Yukawa,
+    },
+    reactionfield::ReactionField,
+};
+use crate::pairwise::{SelfEnergyPrefactors, ShortRangeFunction};
+use crate::{Cutoff, DebyeLength};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Interaction scheme selected at runtime, _e.g._ from a configuration file.
+///
+/// The short-range function, cutoff, and Debye length are dispatched to the wrapped scheme so
+/// that all multipole traits, [`MultipoleEnergy`](crate::pairwise::MultipoleEnergy) _etc._, are
+/// available without generic parameters.
+/// Poisson schemes are given by their named (C, D) pairs, see [`Poisson`](crate::pairwise::Poisson),
+/// or by arbitrary orders, see [`RuntimePoisson`].
+/// With `serde`, the variant is selected by its snake case name, _e.g._ `stenqvist: {cutoff: 12.0}`.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::pairwise::{MultipoleEnergy, Plain, SchemeKind, Stenqvist};
+/// let schemes = [
+///     SchemeKind::from(Plain::new(12.0, None)),
+///     SchemeKind::from(Stenqvist::new(12.0, None)),
+/// ];
+/// let energies: Vec<f64> = schemes.iter().map(|s| s.ion_ion_energy(1.0, 1.0, 6.0)).collect();
+/// assert_relative_eq!(energies[0], 1.0 / 6.0);
+/// assert_relative_eq!(energies[1], 0.15625 / 6.0);
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum SchemeKind {
+    /// Plain Coulomb, see [`Plain`]
+    Plain(Plain),
+    /// Real-space Ewald, see [`RealSpaceEwald`]
+    Ewald(RealSpaceEwald),
+    /// Truncated Gaussian Ewald, see [`EwaldTruncated`]
+    EwaldTruncated(EwaldTruncated),
+    /// Reaction field, see [`ReactionField`]
+    ReactionField(ReactionField),
+    /// Undamped Wolf, see [`UndampedWolf`]
+    Wolf(UndampedWolf),
+    /// Levitt/undamped Fennell, see [`Yukawa`]
+    Fennell(Yukawa),
+    /// Kale, see [`Kale`]
+    Kale(Kale),
+    /// McCann, see [`McCann`]
+    #[cfg_attr(feature = "serde", serde(rename = "mccann"))]
+    McCann(McCann),
+    /// Undamped Fukuda, see [`UndampedFukuda`]
+    Fukuda(UndampedFukuda),
+    /// Markland, see [`Markland`]
+    Markland(Markland),
+    /// Stenqvist, see [`Stenqvist`]
+    Stenqvist(Stenqvist),
+    /// Fanourgakis, see [`Fanourgakis`]
+    Fanourgakis(Fanourgakis),
+    /// Poisson with orders given at runtime, see [`RuntimePoisson`]
+    Poisson(RuntimePoisson),
+}
+
+/// Evaluate an expression for the scheme wrapped by a [`SchemeKind`]
+macro_rules! dispatch {
+    ($kind:expr, $scheme:ident => $body:expr) => {
+        match $kind {
+            SchemeKind::Plain($scheme) => $body,
+            SchemeKind::Ewald($scheme) => $body,
+            SchemeKind::EwaldTruncated($scheme) => $body,
+            SchemeKind::ReactionField($scheme) => $body,
+            SchemeKind::Wolf($scheme) => $body,
+            SchemeKind::Fennell($scheme) => $body,
+            SchemeKind::Kale($scheme) => $body,
+            SchemeKind::McCann($scheme) => $body,
+            SchemeKind::Fukuda($scheme) => $body,
+            SchemeKind::Markland($scheme) => $body,
+            SchemeKind::Stenqvist($scheme) => $body,
+            SchemeKind::Fanourgakis($scheme) => $body,
+            SchemeKind::Poisson($scheme) => $body,
+        }
+    };
+}
+
+/// Implement `From` for each scheme wrapped by a [`SchemeKind`]
+macro_rules! impl_from {
+    ($($variant:ident($scheme:ty)),+) => {
+        $(
+            impl From<$scheme> for SchemeKind {
+                fn from(scheme: $scheme) -> Self {
+                    Self::$variant(scheme)
+                }
+            }
+        )+
+    };
+}
+
+impl_from!(
+    Plain(Plain),
+    Ewald(RealSpaceEwald),
+    EwaldTruncated(EwaldTruncated),
+    ReactionField(ReactionField),
+    Wolf(UndampedWolf),
+    Fennell(Yukawa),
+    Kale(Kale),
+    McCann(McCann),
+    Fukuda(UndampedFukuda),
+    Markland(Markland),
+    Stenqvist(Stenqvist),
+    Fanourgakis(Fanourgakis),
+    Poisson(RuntimePoisson)
+);
+
+impl SchemeKind {
+    /// URL to the original article describing the wrapped scheme
+    pub fn reference(&self) -> &'static str {
+        fn url<T: ShortRangeFunction>(_: &T) -> &'static str {
+            T::url()
+        }
+        dispatch!(self, scheme => url(scheme))
+    }
+}
+
+impl core::fmt::Display for SchemeKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        dispatch!(self, scheme => scheme.fmt(f))
+    }
+}
+
+impl Cutoff for SchemeKind {
+    #[inline]
+    fn cutoff(&self) -> f64 {
+        dispatch!(self, scheme => scheme.cutoff())
+    }
+}
+
+impl DebyeLength for SchemeKind {
+    #[inline]
+    fn kappa(&self) -> Option<f64> {
+        dispatch!(self, scheme => scheme.kappa())
+    }
+    fn set_debye_length(&mut self, debye_length: Option<f64>) -> crate::Result<()> {
+        dispatch!(self, scheme => scheme.set_debye_length(debye_length))
+    }
+}
+
+impl crate::SaltSensitive for SchemeKind {}
+
+impl ShortRangeFunction for SchemeKind {
+    /// Library of interaction schemes, see [`SchemeKind::reference`] for the wrapped scheme
+    fn url() -> &'static str {
+        "https://doi.org/10.5281/zenodo.3522058"
+    }
+    #[inline]
+    fn short_range_f0(&self, q: f64) -> f64 {
+        dispatch!(self, scheme => scheme.short_range_f0(q))
+    }
+    #[inline]
+    fn short_range_f1(&self, q: f64) -> f64 {
+        dispatch!(self, scheme => scheme.short_range_f1(q))
+    }
+    #[inline]
+    fn short_range_f2(&self, q: f64) -> f64 {
+        dispatch!(self, scheme => scheme.short_range_f2(q))
+    }
+    #[inline]
+    fn short_range_f3(&self, q: f64) -> f64 {
+        dispatch!(self, scheme => scheme.short_range_f3(q))
+    }
+    #[inline]
+    fn short_range_f4(&self, q: f64) -> f64 {
+        dispatch!(self, scheme => scheme.short_range_f4(q))
+    }
+    fn self_energy_prefactors(&self) -> SelfEnergyPrefactors {
+        dispatch!(self, scheme => scheme.self_energy_prefactors())
+    }
+}
+
+#[test]
+fn test_scheme_kind() {
+    use crate::pairwise::{MultipoleEnergy, MultipoleForce};
+    use crate::{Medium, Salt, SaltSensitive};
+    use approx::assert_relative_eq;
+
+    fn check<T>(scheme: T)
+    where
+        T: MultipoleEnergy + MultipoleForce + Into<SchemeKind> + Clone,
+    {
+        let kind: SchemeKind = scheme.clone().into();
+        let (r, mu) = ([3.0, 1.0, -2.0], [0.5, -1.0, 2.0]);
+        assert_eq!(kind.cutoff(), scheme.cutoff());
+        assert_eq!(kind.kappa(), scheme.kappa());
+        assert_relative_eq!(
+            kind.ion_ion_energy(1.0, -2.0, 4.0),
+            scheme.ion_ion_energy(1.0, -2.0, 4.0)
+        );
+        assert_relative_eq!(
+            kind.dipole_dipole_energy(mu, mu, r),
+            scheme.dipole_dipole_energy(mu, mu, r)
+        );
+        assert_relative_eq!(
+            kind.self_energy(&[1.0], &[1.0], &[]),
+            scheme.self_energy(&[1.0], &[1.0], &[])
+        );
+        let (a, b) = (
+            kind.ion_dipole_force(1.0, mu, r),
+            scheme.ion_dipole_force(1.0, mu, r),
+        );
+        assert_eq!([a.x, a.y, a.z], [b.x, b.y, b.z]);
+    }
+    check(Plain::new(12.0, Some(20.0)));
+    check(RealSpaceEwald::new(12.0, 0.2, Some(20.0)));
+    check(EwaldTruncated::new(12.0, 0.2));
+    check(ReactionField::new(12.0, 80.0, 1.0, true));
+    check(UndampedWolf::new(12.0, None));
+    check(Yukawa::new(12.0, Some(20.0)));
+    check(Kale::new(12.0, None));
+    check(McCann::new(12.0, None));
+    check(UndampedFukuda::new(12.0, None));
+    check(Markland::new(12.0, None));
+    check(Stenqvist::new(12.0, Some(20.0)));
+    check(Fanourgakis::new(12.0, None));
+    check(RuntimePoisson::new(3, 2, 12.0, Some(20.0)).unwrap());
+
+    let mut kind = SchemeKind::from(Stenqvist::new(12.0, None));
+    assert_eq!(kind.reference(), Stenqvist::url());
+    assert_eq!(kind.to_string(), Stenqvist::new(12.0, None).to_string());
+    kind.set_debye_length(Some(20.0)).unwrap();
+    assert_eq!(kind, SchemeKind::from(Stenqvist::new(12.0, Some(20.0))));
+    let mut kind = SchemeKind::from(ReactionField::new(12.0, 80.0, 1.0, true));
+    assert!(kind.set_debye_length(Some(20.0)).is_err());
+
+    // Screening from a medium through the enum
+    let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+    let mut kind = SchemeKind::from(RealSpaceEwald::new_without_salt(12.0, 0.2));
+    kind.update_screening(&medium).unwrap();
+    assert_eq!(
+        kind,
+        SchemeKind::from(RealSpaceEwald::from_medium(12.0, 0.2, &medium))
+    );
+    let mut kind = SchemeKind::from(ReactionField::new(12.0, 80.0, 1.0, true));
+    assert!(kind.update_screening(&medium).is_err());
+}
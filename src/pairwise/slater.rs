@@ -0,0 +1,216 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Interactions between Slater-type charge distributions.
+
+use crate::{NalgebraVector3, Vector3};
+
+/// Interactions between Slater-type (exponentially decaying) charge distributions.
+///
+/// A Slater charge with exponent $\beta$ has the density $\rho(r) = q \beta^3 e^{-\beta r} / 8\pi$
+/// and, at large separation, interacts as a point charge.
+/// At short range the Coulomb interaction is damped,
+///
+/// $$ u(r) = \frac{q_1 q_2}{r} \lambda(r), \quad \lambda(r) = 1 - \sum_i P_i(r) e^{-\beta_i r} $$
+///
+/// where $P_i$ are low order polynomials in $r$, see _e.g._
+/// <https://doi.org/10.1021/acs.jctc.5b00267>.
+/// Closed forms are used for a point charge with a Slater charge, and for two Slater charges with
+/// equal or different exponents.
+/// Exponents within a relative difference of 10⁻³ are treated as equal to avoid numerical
+/// cancellation.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::pairwise::Slater;
+/// let slater = Slater::new(2.0, 2.0);
+/// assert_relative_eq!(slater.ion_ion_energy(1.0, 1.0, 0.0), 5.0 * 2.0 / 16.0);
+/// assert_relative_eq!(slater.ion_ion_energy(1.0, 1.0, 40.0), 1.0 / 40.0);
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Slater {
+    /// Exponentially damped terms as exponents and coefficients of $r^{-1}, 1, r, r^2$
+    terms: [(f64, [f64; 4]); 2],
+}
+
+impl Slater {
+    /// Relative difference below which two exponents are treated as equal
+    const EQUAL_EXPONENTS: f64 = 1e-3;
+
+    /// Interaction between Slater charges with exponents `exponent1` and `exponent2`.
+    pub fn new(exponent1: f64, exponent2: f64) -> Self {
+        let (a, b) = (exponent1, exponent2);
+        if (a - b).abs() < Self::EQUAL_EXPONENTS * a.max(b) {
+            let a = 0.5 * (a + b);
+            let p = [1.0, 11.0 * a / 16.0, 3.0 * a * a / 16.0, a.powi(3) / 48.0];
+            return Self {
+                terms: [(a, p), (a, [0.0; 4])],
+            };
+        }
+        let term = |a: f64, b: f64| {
+            let (a2, b2, b4) = (a * a, b * b, b.powi(4));
+            let d = b2 - a2;
+            (
+                a,
+                [
+                    b4 * (b2 - 3.0 * a2) / d.powi(3),
+                    0.5 * a * b4 / (d * d),
+                    0.0,
+                    0.0,
+                ],
+            )
+        };
+        Self {
+            terms: [term(a, b), term(b, a)],
+        }
+    }
+
+    /// Interaction between a point charge and a Slater charge with `exponent`.
+    pub fn with_point_charge(exponent: f64) -> Self {
+        Self {
+            terms: [
+                (exponent, [1.0, 0.5 * exponent, 0.0, 0.0]),
+                (exponent, [0.0; 4]),
+            ],
+        }
+    }
+
+    /// Sum of the damped terms, $\sum_i P_i(r) e^{-\beta_i r}$, and its derivative with respect to r,
+    /// excluding the singular $r^{-1}$ coefficients which are returned separately.
+    fn damped_terms(&self, distance: f64) -> (f64, f64, f64, f64) {
+        let r = distance;
+        let (mut singular, mut singular_derivative, mut regular, mut regular_derivative) =
+            (0.0, 0.0, 0.0, 0.0);
+        for (beta, [p_1, p0, p1, p2]) in self.terms {
+            let exp = (-beta * r).exp();
+            let polynomial = p0 + p1 * r + p2 * r * r;
+            singular += p_1 * exp;
+            singular_derivative -= beta * p_1 * exp;
+            regular += polynomial * exp;
+            regular_derivative += (p1 + 2.0 * p2 * r - beta * polynomial) * exp;
+        }
+        (singular, singular_derivative, regular, regular_derivative)
+    }
+
+    /// Damping function, $\lambda(r)$, of the Coulomb interaction.
+    pub fn damping(&self, distance: f64) -> f64 {
+        let (singular, _, regular, _) = self.damped_terms(distance);
+        1.0 - singular - regular * distance
+    }
+
+    /// Interaction energy between two Slater charges, UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn ion_ion_energy(&self, charge1: f64, charge2: f64, distance: f64) -> f64 {
+        let energy = if distance == 0.0 {
+            // Finite contact value from the r-derivative of 1 - Σ p₋₁ exp(-βr)
+            let (_, singular_derivative, regular, _) = self.damped_terms(0.0);
+            -singular_derivative - regular
+        } else {
+            self.damping(distance) / distance
+        };
+        charge1 * charge2 * energy
+    }
+
+    /// Force on Slater charge 2 from charge 1, where `r` = r₂ - r₁.
+    ///
+    /// UNIT: [ ( input charge )^2 / ( input length )^2 ]
+    pub fn ion_ion_force(&self, charge1: f64, charge2: f64, r: impl Into<Vector3>) -> Vector3 {
+        let r: NalgebraVector3 = r.into().into();
+        let distance = r.norm();
+        if distance == 0.0 {
+            return NalgebraVector3::zeros().into();
+        }
+        let (singular, singular_derivative, _, regular_derivative) = self.damped_terms(distance);
+        // u = (1 - s(r)) / r - g(r)
+        let dudr = -(1.0 - singular) / (distance * distance)
+            - singular_derivative / distance
+            - regular_derivative;
+        (-charge1 * charge2 * dudr / distance * r).into()
+    }
+
+    /// Self-energy of a Slater charge with `exponent`, $5 \beta q^2 / 32$.
+    ///
+    /// UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn ion_self_energy(charge: f64, exponent: f64) -> f64 {
+        0.5 * Self::new(exponent, exponent).ion_ion_energy(charge, charge, 0.0)
+    }
+}
+
+#[test]
+fn test_slater() {
+    use approx::assert_relative_eq;
+    use core::f64::consts::PI;
+
+    /// Energy between Slater densities from numerical integration over the second density
+    fn integrate(a: f64, b: f64, distance: f64) -> f64 {
+        let potential = |r: f64| match r {
+            0.0 => 0.5 * a,
+            _ => (1.0 - (1.0 + 0.5 * a * r) * (-a * r).exp()) / r,
+        };
+        let (n_r, n_u, r_max) = (2000, 200, 40.0 / b);
+        let (h_r, h_u) = (r_max / n_r as f64, 2.0 / n_u as f64);
+        let simpson = |i: usize, n: usize| match i {
+            _ if i == 0 || i == n => 1.0,
+            _ if i % 2 == 1 => 4.0,
+            _ => 2.0,
+        };
+        let mut sum = 0.0;
+        for i in 1..=n_r {
+            let r = i as f64 * h_r;
+            let density = b.powi(3) / (8.0 * PI) * (-b * r).exp();
+            let mut shell = 0.0;
+            for j in 0..=n_u {
+                let u = -1.0 + j as f64 * h_u;
+                let s = (distance * distance + r * r - 2.0 * distance * r * u).sqrt();
+                shell += simpson(j, n_u) * potential(s);
+            }
+            sum += simpson(i, n_r) * 2.0 * PI * r * r * density * shell * h_u / 3.0;
+        }
+        sum * h_r / 3.0
+    }
+
+    for (a, b) in [(1.2, 1.2), (1.2, 2.1), (2.1, 1.2), (1.5, 1.5005)] {
+        let slater = Slater::new(a, b);
+        for distance in [0.3, 1.4, 3.0] {
+            assert_relative_eq!(
+                slater.ion_ion_energy(1.0, 1.0, distance),
+                integrate(a, b, distance),
+                max_relative = 1e-5
+            );
+        }
+        // Contact limit and force consistency
+        assert_relative_eq!(
+            slater.ion_ion_energy(1.0, 1.0, 0.0),
+            slater.ion_ion_energy(1.0, 1.0, 1e-6),
+            max_relative = 1e-5
+        );
+        let (z1, z2, h) = (1.3, -0.8, 1e-6);
+        let r = NalgebraVector3::new(0.6, -0.2, 0.9);
+        let force = NalgebraVector3::from(slater.ion_ion_force(z1, z2, r));
+        let energy = |r: NalgebraVector3| slater.ion_ion_energy(z1, z2, r.norm());
+        for i in 0..3 {
+            let dr = NalgebraVector3::ith(i, h);
+            let gradient = (energy(r + dr) - energy(r - dr)) / (2.0 * h);
+            assert_relative_eq!(force[i], -gradient, epsilon = 1e-8);
+        }
+    }
+
+    // Point charge with a Slater charge
+    let slater = Slater::with_point_charge(1.7);
+    let r: f64 = 0.8;
+    assert_relative_eq!(slater.damping(r), 1.0 - (1.0 + 0.85 * r) * (-1.7 * r).exp());
+    assert_relative_eq!(slater.ion_ion_energy(1.0, 1.0, 0.0), 0.85);
+    assert_relative_eq!(Slater::ion_self_energy(2.0, 1.6), 5.0 * 1.6 * 4.0 / 32.0);
+}
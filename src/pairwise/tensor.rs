@@ -0,0 +1,199 @@
+// Copyright 2023 Björn Stenqvist and Mikael Lund
+//
+// Converted to Rust with modification from the C++ library "CoulombGalore":
+// https://zenodo.org/doi/10.5281/zenodo.3522058
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Cartesian interaction tensors for higher order multipoles.
+//!
+//! The interaction kernel of a scheme is the radial function
+//!
+//! $$ T(r) = \frac{S(r / r_c) e^{-\kappa r}}{r} $$
+//!
+//! and all multipolar interactions follow from Cartesian derivatives of $T$.
+//! For a radial function these can be written in terms of
+//! $t_n = \left(\frac{1}{r}\frac{d}{dr}\right)^n T(r)$, e.g.
+//! $\partial_a\partial_b T = \delta_{ab} t_1 + r_a r_b t_2$.
+
+use super::ShortRangeFunction;
+use crate::{Cutoff, NalgebraMatrix3, NalgebraVector3};
+use num_integer::binomial;
+
+/// Reduced radial derivatives, $t_n = (r^{-1} d/dr)^n T(r)$ for $n = 0,\dots,4$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RadialDerivatives {
+    /// Distance vector, 𝒓
+    pub r: NalgebraVector3,
+    /// Reduced derivatives, 𝑡₀ … 𝑡₄
+    pub t: [f64; 5],
+}
+
+impl RadialDerivatives {
+    /// Evaluate the reduced derivatives of the interaction kernel of `scheme` at `r`.
+    ///
+    /// Only derivatives up to and including `order` (at most four) are evaluated;
+    /// higher orders are set to zero.
+    /// Returns `None` if the distance is beyond the cutoff.
+    pub fn new<T: ShortRangeFunction + Cutoff + ?Sized>(
+        scheme: &T,
+        r: NalgebraVector3,
+        order: usize,
+    ) -> Option<Self> {
+        debug_assert!(order <= 4);
+        let r2 = r.norm_squared();
+        if r2 >= scheme.cutoff_squared() {
+            return None;
+        }
+        let r1 = r2.sqrt();
+        let cutoff = scheme.cutoff();
+        let q = r1 / cutoff;
+        // Derivatives of S(r / r_c) with respect to r
+        let s: [f64; 5] = core::array::from_fn(|n| match n {
+            _ if n > order => 0.0,
+            0 => scheme.short_range_f0(q),
+            1 => scheme.short_range_f1(q) / cutoff,
+            2 => scheme.short_range_f2(q) / cutoff.powi(2),
+            3 => scheme.short_range_f3(q) / cutoff.powi(3),
+            _ => scheme.short_range_f4(q) / cutoff.powi(4),
+        });
+        Some(Self::from_short_range(
+            r,
+            s,
+            scheme.kappa().unwrap_or(0.0),
+            order,
+        ))
+    }
+
+    /// Reduced derivatives of $T(r) = S(r) e^{-\kappa r} / r$ from the derivatives, $S^{(n)}(r)$,
+    /// of the short-range function with respect to $r$.
+    ///
+    /// Only derivatives up to and including `order` (at most four) are evaluated.
+    pub fn from_short_range(r: NalgebraVector3, s: [f64; 5], kappa: f64, order: usize) -> Self {
+        debug_assert!(order <= 4);
+        let r2 = r.norm_squared();
+        let r1 = r2.sqrt();
+        // Derivatives of exp(-κr) / r with respect to r using the Leibniz rule
+        let exp_kr = (-kappa * r1).exp();
+        let mut factorial = 1.0;
+        let inv_r: [f64; 5] = core::array::from_fn(|n| {
+            if n > 0 {
+                factorial *= n as f64;
+            }
+            (-1.0f64).powi(n as i32) * factorial / r1.powi(n as i32 + 1)
+        });
+        let leibniz = |n: usize, f: &[f64; 5], g: &[f64; 5]| -> f64 {
+            (0..=n)
+                .map(|k| binomial(n, k) as f64 * f[k] * g[n - k])
+                .sum()
+        };
+        let exp: [f64; 5] = core::array::from_fn(|n| (-kappa).powi(n as i32) * exp_kr);
+        let screened: [f64; 5] = core::array::from_fn(|n| leibniz(n, &exp, &inv_r));
+        let d: [f64; 5] = core::array::from_fn(|n| match n <= order {
+            true => leibniz(n, &s, &screened),
+            false => 0.0,
+        });
+
+        let mut t = [0.0; 5];
+        t[0] = d[0];
+        t[1] = d[1] / r1;
+        if order >= 2 {
+            t[2] = (d[2] - t[1]) / r2;
+        }
+        if order >= 3 {
+            t[3] = (d[3] - 3.0 * r1 * t[2]) / (r2 * r1);
+        }
+        if order >= 4 {
+            t[4] = (d[4] - 3.0 * t[2] - 6.0 * r2 * t[3]) / (r2 * r2);
+        }
+        Self { r, t }
+    }
+
+    /// Second-rank interaction tensor, $\partial_a\partial_b T = \delta_{ab} t_1 + r_a r_b t_2$.
+    pub fn rank2(&self) -> NalgebraMatrix3 {
+        NalgebraMatrix3::from_diagonal_element(self.t[1]) + self.r * self.r.transpose() * self.t[2]
+    }
+
+    /// Contraction of a vector with the third-rank interaction tensor,
+    /// $\sum_c v_c \partial_a\partial_b\partial_c T$.
+    pub fn contract_rank3_matrix(&self, v: &NalgebraVector3) -> NalgebraMatrix3 {
+        let r = &self.r;
+        let [_, _, t2, t3, _] = self.t;
+        let vr = v.dot(r);
+        (NalgebraMatrix3::from_diagonal_element(vr) + v * r.transpose() + r * v.transpose()) * t2
+            + r * r.transpose() * (vr * t3)
+    }
+
+    /// Contraction of a vector and a rank-two tensor with the third-rank interaction tensor,
+    /// $\sum_{abc} v_a B_{bc} \partial_a\partial_b\partial_c T$.
+    pub fn contract_rank3(&self, v: &NalgebraVector3, b: &NalgebraMatrix3) -> f64 {
+        let r = &self.r;
+        let [_, _, t2, t3, _] = self.t;
+        let (br, btr) = (b * r, b.transpose() * r);
+        let vr = v.dot(r);
+        t2 * (v.dot(&br) + v.dot(&btr) + vr * b.trace()) + t3 * vr * r.dot(&br)
+    }
+
+    /// Contraction of a vector and a rank-two tensor with the fourth-rank interaction tensor,
+    /// leaving the first index free, $\sum_{bcd} v_b B_{cd} \partial_a\partial_b\partial_c\partial_d T$.
+    pub fn contract_rank4_vector(
+        &self,
+        v: &NalgebraVector3,
+        b: &NalgebraMatrix3,
+    ) -> NalgebraVector3 {
+        let r = &self.r;
+        let [_, _, t2, t3, t4] = self.t;
+        let tr_b = b.trace();
+        let (br, btr) = (b * r, b.transpose() * r);
+        let (vr, rbr) = (v.dot(r), r.dot(&br));
+        (v * tr_b + b * v + b.transpose() * v) * t2
+            + (v * rbr + (br + btr) * vr + r * (v.dot(&br) + v.dot(&btr) + vr * tr_b)) * t3
+            + r * (vr * rbr * t4)
+    }
+
+    /// Contraction of two rank-two tensors with the fourth-rank interaction tensor,
+    /// $\sum_{abcd} A_{ab} B_{cd} \partial_a\partial_b\partial_c\partial_d T$.
+    pub fn contract_rank4(&self, a: &NalgebraMatrix3, b: &NalgebraMatrix3) -> f64 {
+        let r = &self.r;
+        let [_, _, t2, t3, t4] = self.t;
+        let (tr_a, tr_b) = (a.trace(), b.trace());
+        let (ar, atr) = (a * r, a.transpose() * r);
+        let (br, btr) = (b * r, b.transpose() * r);
+        let (rar, rbr) = (r.dot(&ar), r.dot(&br));
+        t2 * (tr_a * tr_b + a.dot(b) + a.dot(&b.transpose()))
+            + t3 * (tr_a * rbr
+                + tr_b * rar
+                + ar.dot(&br)
+                + ar.dot(&btr)
+                + atr.dot(&br)
+                + atr.dot(&btr))
+            + t4 * rar * rbr
+    }
+
+    /// Contraction of a rank-two tensor with the fourth-rank interaction tensor,
+    /// leaving the first two indices free, $\sum_{cd} B_{cd} \partial_a\partial_b\partial_c\partial_d T$.
+    pub fn contract_rank4_matrix(&self, b: &NalgebraMatrix3) -> NalgebraMatrix3 {
+        let r = &self.r;
+        let [_, _, t2, t3, t4] = self.t;
+        let tr_b = b.trace();
+        let s = b * r + b.transpose() * r;
+        let rbr = r.dot(&(b * r));
+        (NalgebraMatrix3::from_diagonal_element(tr_b) + b + b.transpose()) * t2
+            + (NalgebraMatrix3::from_diagonal_element(rbr)
+                + s * r.transpose()
+                + r * s.transpose()
+                + r * r.transpose() * tr_b)
+                * t3
+            + r * r.transpose() * (rbr * t4)
+    }
+}
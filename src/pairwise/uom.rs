@@ -20,12 +20,6 @@
 use crate::pairwise::{MultipoleEnergy, MultipoleField, MultipolePotential};
 use crate::units::*;
 
-unit! {
-    system: uom::si;
-    quantity: uom::si::electric_charge_areal_density; // charge per length^2
-    @valence_per_angstrom_squared: 16.02176634; "e/Å2", "valence_per_angstrom_squared", "valence_per_angstrom_squared";
-}
-
 impl<T: MultipoleEnergy> MultipoleEnergySI for T {}
 impl<T: MultipoleField> MultipoleFieldSI for T {}
 impl<T: MultipolePotential> MultipolePotentialSI for T {}
@@ -0,0 +1,212 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Periodic boundary conditions and the minimum image convention.
+//!
+//! A [`Cell`] describes the periodicity of a system and provides minimum image
+//! distance vectors as well as wrapping of positions into the primary cell.
+//! The primary cell is centered at the origin, _i.e._ an orthorhombic cell spans
+//! $[-L/2, L/2)$ in each direction.
+//!
+//! # Examples
+//! ~~~
+//! # use approx::assert_relative_eq;
+//! use coulomb::pbc::Cell;
+//! let cell = Cell::Cubic(10.0);
+//! let r = cell.distance([0.0, 0.0, -4.5], [0.0, 0.0, 4.5]);
+//! assert_relative_eq!(r.z, -1.0);
+//! assert_relative_eq!(cell.wrap([0.0, 12.0, -6.0]).y, 2.0);
+//! assert_eq!(cell.volume(), Some(1000.0));
+//! ~~~
+
+use crate::{Matrix3, NalgebraMatrix3, NalgebraVector3, Result, Vector3};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Simulation cell with or without periodic boundaries
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Cell {
+    /// Open boundaries without periodicity (default)
+    #[default]
+    Open,
+    /// Cubic cell with side length, UNIT: [ input length ]
+    Cubic(f64),
+    /// Orthorhombic cell with side lengths, UNIT: [ input length ]
+    Orthorhombic(Vector3),
+    /// Triclinic cell spanned by three lattice vectors
+    Triclinic(TriclinicCell),
+}
+
+impl Cell {
+    /// True if the cell is periodic
+    pub const fn is_periodic(&self) -> bool {
+        !matches!(self, Cell::Open)
+    }
+
+    /// Volume of the cell, or `None` if not periodic, UNIT: [ ( input length )^3 ]
+    pub fn volume(&self) -> Option<f64> {
+        match self {
+            Cell::Open => None,
+            Cell::Cubic(length) => Some(length.powi(3)),
+            Cell::Orthorhombic(length) => Some(length.x * length.y * length.z),
+            Cell::Triclinic(cell) => Some(cell.volume()),
+        }
+    }
+
+    /// Lattice vectors as matrix columns, or `None` if not periodic
+    pub fn box_matrix(&self) -> Option<Matrix3> {
+        match self {
+            Cell::Open => None,
+            Cell::Cubic(length) => Some(NalgebraMatrix3::from_diagonal_element(*length).into()),
+            Cell::Orthorhombic(length) => {
+                Some(NalgebraMatrix3::from_diagonal(&NalgebraVector3::from(*length)).into())
+            }
+            Cell::Triclinic(cell) => Some(cell.matrix),
+        }
+    }
+
+    /// Minimum image of a distance vector
+    ///
+    /// For triclinic cells, the image is found by rounding fractional coordinates which
+    /// is exact for distances shorter than half the smallest perpendicular width of the cell.
+    pub fn minimum_image(&self, r: impl Into<Vector3>) -> Vector3 {
+        let r: NalgebraVector3 = r.into().into();
+        let round = |r: NalgebraVector3, length: NalgebraVector3| {
+            r - length.component_mul(&r.component_div(&length).map(f64::round))
+        };
+        match self {
+            Cell::Open => r,
+            Cell::Cubic(length) => round(r, NalgebraVector3::repeat(*length)),
+            Cell::Orthorhombic(length) => round(r, (*length).into()),
+            Cell::Triclinic(cell) => {
+                let fractional = NalgebraMatrix3::from(cell.inverse) * r;
+                NalgebraMatrix3::from(cell.matrix) * fractional.map(|s| s - s.round())
+            }
+        }
+        .into()
+    }
+
+    /// Minimum image distance vector, 𝐛 - 𝐚, from point `a` to point `b`
+    pub fn distance(&self, a: impl Into<Vector3>, b: impl Into<Vector3>) -> Vector3 {
+        let (a, b): (NalgebraVector3, NalgebraVector3) = (a.into().into(), b.into().into());
+        self.minimum_image(b - a)
+    }
+
+    /// Wrap a position into the primary cell centered at the origin
+    pub fn wrap(&self, position: impl Into<Vector3>) -> Vector3 {
+        self.minimum_image(position)
+    }
+}
+
+/// Triclinic cell spanned by three lattice vectors, 𝐚, 𝐛, and 𝐜
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "Matrix3", into = "Matrix3")
+)]
+pub struct TriclinicCell {
+    /// Lattice vectors as columns
+    matrix: Matrix3,
+    /// Inverse of the lattice matrix for fractional coordinates
+    inverse: Matrix3,
+}
+
+impl TriclinicCell {
+    /// New cell from lattice vectors, UNIT: [ input length ]
+    ///
+    /// # Errors
+    /// Returns [`Error::SingularMatrix`](crate::Error::SingularMatrix) if the vectors
+    /// are linearly dependent.
+    pub fn new(
+        a: impl Into<Vector3>,
+        b: impl Into<Vector3>,
+        c: impl Into<Vector3>,
+    ) -> Result<Self> {
+        let matrix: Matrix3 =
+            NalgebraMatrix3::from_columns(&[a.into().into(), b.into().into(), c.into().into()])
+                .into();
+        Self::try_from(matrix)
+    }
+
+    /// Lattice vectors as matrix columns
+    pub const fn matrix(&self) -> Matrix3 {
+        self.matrix
+    }
+
+    /// Volume of the cell, |𝐚 · (𝐛 × 𝐜)|
+    pub fn volume(&self) -> f64 {
+        NalgebraMatrix3::from(self.matrix).determinant().abs()
+    }
+}
+
+impl TryFrom<Matrix3> for TriclinicCell {
+    type Error = crate::Error;
+    fn try_from(matrix: Matrix3) -> Result<Self> {
+        let inverse = NalgebraMatrix3::from(matrix)
+            .try_inverse()
+            .ok_or(crate::Error::SingularMatrix)?;
+        Ok(Self {
+            matrix,
+            inverse: inverse.into(),
+        })
+    }
+}
+
+impl From<TriclinicCell> for Matrix3 {
+    fn from(cell: TriclinicCell) -> Matrix3 {
+        cell.matrix
+    }
+}
+
+#[test]
+fn test_cell() {
+    use approx::assert_relative_eq;
+    let r = [6.0, -7.0, 2.0];
+    assert_eq!(Cell::Open.minimum_image(r), r.into());
+    assert!(!Cell::Open.is_periodic());
+    assert!(Cell::Open.volume().is_none());
+
+    let cubic = Cell::Cubic(10.0);
+    let orthorhombic = Cell::Orthorhombic([10.0, 10.0, 10.0].into());
+    let triclinic = Cell::Triclinic(
+        TriclinicCell::new([10.0, 0.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]).unwrap(),
+    );
+    for cell in [cubic, orthorhombic, triclinic] {
+        assert!(cell.is_periodic());
+        assert_relative_eq!(cell.volume().unwrap(), 1000.0);
+        let image = NalgebraVector3::from(cell.minimum_image(r));
+        assert_relative_eq!(image, NalgebraVector3::new(-4.0, 3.0, 2.0), epsilon = 1e-12);
+        assert_eq!(cell.box_matrix(), cubic.box_matrix());
+    }
+
+    // Skewed cell where the image is a lattice translation away
+    let cell = TriclinicCell::new([10.0, 0.0, 0.0], [5.0, 8.0, 0.0], [0.0, 2.0, 9.0]).unwrap();
+    assert_relative_eq!(cell.volume(), 720.0);
+    let cell = Cell::Triclinic(cell);
+    let image = NalgebraVector3::from(cell.minimum_image([15.5, 9.0, 9.2]));
+    assert_relative_eq!(image, NalgebraVector3::new(0.5, -1.0, 0.2), epsilon = 1e-12);
+    let wrapped = NalgebraVector3::from(cell.wrap([14.0, 0.0, 0.0]));
+    assert_relative_eq!(
+        wrapped,
+        NalgebraVector3::new(4.0, 0.0, 0.0),
+        epsilon = 1e-12
+    );
+
+    assert!(matches!(
+        TriclinicCell::new([1.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 0.0, 1.0]),
+        Err(crate::Error::SingularMatrix)
+    ));
+}
@@ -0,0 +1,224 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Field dependent permittivity due to dielectric saturation.
+
+use super::{Permittivity, RelativePermittivity};
+use crate::math::langevin;
+use crate::{Result, BOLTZMANN_CONSTANT, ELEMENTARY_CHARGE, VACUUM_ELECTRIC_PERMITTIVITY};
+use core::f64::consts::PI;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Dipole moment of one debye, UNIT: [ C m ]
+const DEBYE: f64 = 1e-21 / 299_792_458.0;
+
+/// Convert a field with charges in units of the elementary charge and lengths in angstrom to V/m
+fn to_volt_per_meter(field: f64) -> f64 {
+    field * ELEMENTARY_CHARGE * 1e20 / (4.0 * PI * VACUUM_ELECTRIC_PERMITTIVITY)
+}
+
+/// Booth model for dielectric saturation in strong electric fields, εᵣ(𝐸),
+///
+/// $$ \varepsilon_r(E) = n^2 + (\varepsilon_r(0) - n^2) \frac{3}{\beta E} L(\beta E) \quad \text{with} \quad \beta = \frac{5 \mu (n^2 + 2)}{2 k T} $$
+///
+/// where $n$ is the refractive index, $\mu$ the dipole moment of the solvent molecules,
+/// $L(x) = \coth x - 1/x$ the Langevin function, and $\varepsilon_r(0)$ the zero-field
+/// permittivity from an underlying temperature dependent model.
+/// Saturation becomes important for fields above ~10⁸ V/m, found close to multivalent ions
+/// and electrodes. See Booth, <https://doi.org/10.1063/1.1748233>.
+///
+/// The field inside the dielectric depends on the permittivity itself. For a spherically symmetric
+/// source, the displacement field is unaffected by the dielectric and the local field is found
+/// self-consistently from the _vacuum_ field, 𝐸₀ = ε(𝐸)𝐸, as given by
+/// [`MultipoleField`](crate::pairwise::MultipoleField) without dividing by the relative permittivity.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::Booth;
+/// use coulomb::pairwise::{MultipoleField, Plain};
+/// let booth = Booth::water();
+/// let temperature = 298.15;
+/// assert_relative_eq!(booth.saturation_parameter(temperature), 1.41e-8, epsilon = 1e-10);
+/// assert_relative_eq!(booth.correction_factor(1e8, temperature).unwrap(), 0.89, epsilon = 0.01);
+///
+/// // Saturation 5 Å from a divalent ion using the field from a pairwise scheme
+/// let field = Plain::without_cutoff().ion_field_scalar(2.0, 5.0); // e/Å²
+/// let permittivity = booth.permittivity_from_pairwise_field(field, temperature).unwrap();
+/// assert_relative_eq!(permittivity, 52.0, epsilon = 0.1);
+/// ~~~
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct Booth<T: RelativePermittivity> {
+    /// Zero-field relative permittivity model
+    model: T,
+    /// Refractive index, 𝑛
+    refractive_index: f64,
+    /// Molecular dipole moment, UNIT: [ debye ]
+    dipole_moment: f64,
+}
+
+impl Booth<Permittivity> {
+    /// Water with the NR permittivity model, 𝑛 = 1.33, and µ = 1.85 D
+    pub const fn water() -> Self {
+        Self::new(Permittivity::Water, 1.33, 1.85)
+    }
+}
+
+impl<T: RelativePermittivity> Booth<T> {
+    /// New model from the zero-field permittivity, refractive index, and dipole moment in debye
+    pub const fn new(model: T, refractive_index: f64, dipole_moment: f64) -> Self {
+        Self {
+            model,
+            refractive_index,
+            dipole_moment,
+        }
+    }
+
+    /// Saturation parameter, β = 5µ(𝑛² + 2)/2𝑘𝑇, UNIT: [ m / V ]
+    pub fn saturation_parameter(&self, temperature: f64) -> f64 {
+        5.0 * self.dipole_moment * DEBYE * (self.refractive_index.powi(2) + 2.0)
+            / (2.0 * BOLTZMANN_CONSTANT * temperature)
+    }
+
+    /// Relative permittivity in a local electric field of strength 𝐸 (V/m)
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is out of range for the zero-field model.
+    pub fn permittivity(&self, field: f64, temperature: f64) -> Result<f64> {
+        let n2 = self.refractive_index.powi(2);
+        let x = self.saturation_parameter(temperature) * field.abs();
+        let saturation = if x < 1e-2 {
+            1.0 - x * x / 15.0
+        } else {
+            3.0 * langevin(x) / x
+        };
+        Ok(n2 + (self.model.permittivity(temperature)? - n2) * saturation)
+    }
+
+    /// Ratio, εᵣ(𝐸)/εᵣ(0), to correct a zero-field permittivity in a local field 𝐸 (V/m)
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is out of range for the zero-field model.
+    pub fn correction_factor(&self, field: f64, temperature: f64) -> Result<f64> {
+        Ok(self.permittivity(field, temperature)? / self.model.permittivity(temperature)?)
+    }
+
+    /// Relative permittivity from the vacuum field, 𝐸₀ = εᵣ(𝐸)𝐸 (V/m), of a spherically
+    /// symmetric source by solving for the local field, 𝐸.
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is out of range for the zero-field model.
+    pub fn permittivity_from_vacuum_field(
+        &self,
+        vacuum_field: f64,
+        temperature: f64,
+    ) -> Result<f64> {
+        let vacuum_field = vacuum_field.abs();
+        let static_permittivity = self.model.permittivity(temperature)?;
+        // εᵣ(𝐸)𝐸 increases monotonically with 𝐸 and the local field is bracketed by
+        // the fully screened and the optical limits
+        let (mut low, mut high) = (
+            vacuum_field / static_permittivity,
+            vacuum_field / self.refractive_index.powi(2),
+        );
+        while high - low > f64::EPSILON * high {
+            let field = 0.5 * (low + high);
+            if self.permittivity(field, temperature)? * field < vacuum_field {
+                low = field;
+            } else {
+                high = field;
+            }
+        }
+        self.permittivity(0.5 * (low + high), temperature)
+    }
+
+    /// Relative permittivity from the vacuum field of a spherically symmetric source as given by
+    /// [`MultipoleField`](crate::pairwise::MultipoleField) with charges in units of the
+    /// elementary charge and lengths in angstrom.
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is out of range for the zero-field model.
+    pub fn permittivity_from_pairwise_field(&self, field: f64, temperature: f64) -> Result<f64> {
+        self.permittivity_from_vacuum_field(to_volt_per_meter(field), temperature)
+    }
+
+    /// Relative permittivity at a distance (Å) from an ion with charge in units of the
+    /// elementary charge.
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is out of range for the zero-field model.
+    pub fn permittivity_near_ion(
+        &self,
+        charge: f64,
+        distance: f64,
+        temperature: f64,
+    ) -> Result<f64> {
+        self.permittivity_from_pairwise_field(charge / distance.powi(2), temperature)
+    }
+}
+
+#[test]
+fn test_booth() {
+    use approx::assert_relative_eq;
+    let temperature = 298.15;
+    let booth = Booth::new(super::ConstantPermittivity::new(80.0), 1.5, 2.0);
+    assert_relative_eq!(booth.permittivity(0.0, temperature).unwrap(), 80.0);
+    assert_relative_eq!(
+        booth.correction_factor(-1e6, temperature).unwrap(),
+        1.0,
+        epsilon = 1e-4
+    );
+
+    // Limits and continuity of the series expansion
+    assert_relative_eq!(
+        booth.permittivity(1e13, temperature).unwrap(),
+        2.25,
+        epsilon = 0.01
+    );
+    let beta = booth.saturation_parameter(temperature);
+    assert_relative_eq!(
+        booth.permittivity(0.99e-2 / beta, temperature).unwrap(),
+        booth.permittivity(1.01e-2 / beta, temperature).unwrap(),
+        epsilon = 1e-4
+    );
+    let field = 3.0 / beta;
+    let x: f64 = 3.0;
+    assert_relative_eq!(
+        booth.permittivity(field, temperature).unwrap(),
+        2.25 + (80.0 - 2.25) * 3.0 / x * (1.0 / x.tanh() - 1.0 / x),
+        max_relative = 1e-12
+    );
+
+    // Self-consistent local field near an ion
+    let (charge, distance) = (3.0, 4.0);
+    let permittivity = booth
+        .permittivity_near_ion(charge, distance, temperature)
+        .unwrap();
+    let vacuum_field = to_volt_per_meter(charge / distance.powi(2));
+    assert_relative_eq!(
+        booth
+            .permittivity(vacuum_field / permittivity, temperature)
+            .unwrap(),
+        permittivity,
+        max_relative = 1e-10
+    );
+    assert!(permittivity < 80.0);
+    assert!(Booth::water().permittivity(0.0, 500.0).is_err());
+}
@@ -0,0 +1,150 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Complex permittivity of lossy media.
+
+use super::{ConstantPermittivity, DebyeRelaxation};
+use crate::VACUUM_ELECTRIC_PERMITTIVITY;
+use num_complex::Complex64;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Trait for media with a frequency dependent, complex relative permittivity, ε′ - iε″.
+///
+/// The imaginary part, ε″ ≥ 0, describes dielectric losses with the sign convention of
+/// fields varying as exp(iωt).
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::{ComplexPermittivity, DebyeRelaxation};
+/// let water = DebyeRelaxation::water_25c();
+/// let omega = 2.0 * std::f64::consts::PI * 2.45e9;
+/// assert_relative_eq!(water.loss_tangent(omega), 0.119, epsilon = 1e-3);
+/// // Heating rate of water in a microwave field of 1 kV/m, ≈ 0.15 K/s
+/// let power = water.dissipated_power_density(omega, 1e3);
+/// assert_relative_eq!(power / 4.18e6, 0.15, epsilon = 0.01);
+/// assert_relative_eq!(f64::from(water.to_static()), 78.36);
+/// ~~~
+pub trait ComplexPermittivity {
+    /// Complex relative permittivity, ε′ - iε″, at angular frequency ω (rad/s)
+    fn complex_permittivity(&self, angular_frequency: f64) -> Complex64;
+
+    /// Loss tangent, tan δ = ε″/ε′, at angular frequency ω (rad/s)
+    fn loss_tangent(&self, angular_frequency: f64) -> f64 {
+        let permittivity = self.complex_permittivity(angular_frequency);
+        -permittivity.im / permittivity.re
+    }
+
+    /// Time-averaged power dissipated per volume, ½ωε₀ε″E², UNIT: [ W / m^3 ]
+    ///
+    /// The electric field amplitude, E, is in V/m and the angular frequency, ω, in rad/s.
+    fn dissipated_power_density(&self, angular_frequency: f64, field_amplitude: f64) -> f64 {
+        let loss = -self.complex_permittivity(angular_frequency).im;
+        0.5 * angular_frequency * VACUUM_ELECTRIC_PERMITTIVITY * loss * field_amplitude.powi(2)
+    }
+
+    /// Static, real relative permittivity in the limit ω → 0
+    fn to_static(&self) -> ConstantPermittivity {
+        ConstantPermittivity::new(self.complex_permittivity(0.0).re)
+    }
+}
+
+impl ComplexPermittivity for ConstantPermittivity {
+    fn complex_permittivity(&self, _: f64) -> Complex64 {
+        Complex64::from(f64::from(*self))
+    }
+}
+
+impl ComplexPermittivity for DebyeRelaxation {
+    fn complex_permittivity(&self, angular_frequency: f64) -> Complex64 {
+        self.relaxations().iter().fold(
+            Complex64::from(self.high_frequency_permittivity()),
+            |sum, (strength, time)| sum + strength / Complex64::new(1.0, angular_frequency * time),
+        )
+    }
+}
+
+/// Dielectric with an Ohmic conductivity, σ, adding -iσ/ε₀ω to the complex permittivity.
+///
+/// This describes, _e.g._, salt solutions where ion migration dominates the losses at low
+/// frequencies.
+///
+/// # Examples
+/// ~~~
+/// use coulomb::permittivity::{ComplexPermittivity, Conducting, DebyeRelaxation};
+/// // Water with 0.1 M NaCl with a conductivity of ≈ 1 S/m
+/// let saline = Conducting::new(DebyeRelaxation::water_25c(), 1.0);
+/// let omega = 2.0 * std::f64::consts::PI * 1e8;
+/// assert!(saline.loss_tangent(omega) > DebyeRelaxation::water_25c().loss_tangent(omega));
+/// ~~~
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct Conducting<T: ComplexPermittivity> {
+    /// Dielectric response of the medium
+    dielectric: T,
+    /// Conductivity, UNIT: [ S / m ]
+    conductivity: f64,
+}
+
+impl<T: ComplexPermittivity> Conducting<T> {
+    /// New conducting medium with conductivity in S/m.
+    pub const fn new(dielectric: T, conductivity: f64) -> Self {
+        Self {
+            dielectric,
+            conductivity,
+        }
+    }
+
+    /// Conductivity, UNIT: [ S / m ]
+    pub const fn conductivity(&self) -> f64 {
+        self.conductivity
+    }
+}
+
+impl<T: ComplexPermittivity> ComplexPermittivity for Conducting<T> {
+    fn complex_permittivity(&self, angular_frequency: f64) -> Complex64 {
+        self.dielectric.complex_permittivity(angular_frequency)
+            - Complex64::i() * self.conductivity
+                / (VACUUM_ELECTRIC_PERMITTIVITY * angular_frequency)
+    }
+
+    fn to_static(&self) -> ConstantPermittivity {
+        self.dielectric.to_static()
+    }
+}
+
+#[test]
+fn test_complex_permittivity() {
+    use approx::assert_relative_eq;
+    let constant = ConstantPermittivity::new(4.0);
+    assert_eq!(constant.complex_permittivity(1e9), Complex64::from(4.0));
+    assert_eq!(constant.loss_tangent(1e9), 0.0);
+    assert_eq!(constant.to_static(), constant);
+
+    // Conductivity loss, σ/ε₀ω, on top of the dielectric loss
+    let omega = 1e9;
+    let conducting = Conducting::new(constant, 0.5);
+    let loss = 0.5 / (VACUUM_ELECTRIC_PERMITTIVITY * omega);
+    assert_relative_eq!(conducting.loss_tangent(omega), loss / 4.0);
+    assert_relative_eq!(
+        conducting.dissipated_power_density(omega, 2.0),
+        0.5 * 0.5 * 4.0
+    );
+    assert_eq!(conducting.to_static(), constant);
+}
@@ -0,0 +1,204 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Relative permittivity from fluctuations of the system dipole moment in simulations.
+
+use crate::{bjerrum_length, NalgebraVector3, Result, Vector3};
+use core::f64::consts::PI;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Dielectric boundary condition surrounding the periodic simulation cell
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BoundaryCondition {
+    /// Conducting surroundings, εʀꜰ = ∞, as in Ewald summation without a surface term
+    #[default]
+    Tinfoil,
+    /// Vacuum surroundings, εʀꜰ = 1, as in Ewald summation with a surface term
+    Vacuum,
+    /// Surroundings with a finite relative permittivity, εʀꜰ, _e.g._ a reaction field
+    Dielectric(f64),
+}
+
+impl BoundaryCondition {
+    /// Relative permittivity of the surroundings, εʀꜰ
+    pub const fn permittivity(&self) -> f64 {
+        match self {
+            Self::Tinfoil => f64::INFINITY,
+            Self::Vacuum => 1.0,
+            Self::Dielectric(permittivity) => *permittivity,
+        }
+    }
+}
+
+/// Estimated relative permittivity with statistical error
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DielectricEstimate {
+    /// Relative permittivity from the full time series
+    pub permittivity: f64,
+    /// Standard error from block averaging
+    pub error: f64,
+}
+
+/// Relative permittivity from fluctuations of the total dipole moment, 𝐌, of a periodic system
+///
+/// With conducting (tinfoil) boundaries,
+///
+/// $$ \varepsilon_r = 1 + \frac{\langle \mathbf{M}^2 \rangle - \langle \mathbf{M} \rangle^2}{3 \varepsilon_0 V k T} $$
+///
+/// and for surroundings with permittivity εʀꜰ,
+///
+/// $$ \frac{(\varepsilon_r - 1)(2\varepsilon_{RF} + 1)}{2\varepsilon_{RF} + \varepsilon_r} = \frac{\langle \mathbf{M}^2 \rangle - \langle \mathbf{M} \rangle^2}{3 \varepsilon_0 V k T} $$
+///
+/// which for vacuum boundaries, εʀꜰ = 1, is the Clausius-Mossotti form.
+/// See Neumann, <https://doi.org/10.1080/00268978300102721>.
+/// The dipole moments are in eÅ, the volume in Å³, and the statistical error is estimated
+/// from the spread of estimates over consecutive blocks of the time series.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::{BoundaryCondition, DielectricEstimator};
+/// // Dipole moments from a simulation, eÅ
+/// let dipoles: Vec<_> = (0..1000)
+///     .map(|i| [10.0 * (0.1 * i as f64).sin(), 10.0 * (0.37 * i as f64).cos(), 0.0].into())
+///     .collect();
+/// let estimator = DielectricEstimator::new(30_000.0, 298.15, BoundaryCondition::Tinfoil);
+/// let estimate = estimator.estimate(&dipoles).unwrap();
+/// assert_relative_eq!(estimate.permittivity, 8.8, epsilon = 0.1);
+/// assert!(estimate.error < 0.5);
+/// ~~~
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct DielectricEstimator {
+    /// Volume of the simulation cell, UNIT: [ Å^3 ]
+    volume: f64,
+    /// Temperature, UNIT: [ K ]
+    temperature: f64,
+    /// Boundary condition of the simulation
+    boundary: BoundaryCondition,
+    /// Number of blocks for the error estimate
+    blocks: usize,
+}
+
+impl DielectricEstimator {
+    /// New estimator from the cell volume (Å³), temperature (K), and boundary condition
+    /// using ten blocks for the error estimate.
+    pub const fn new(volume: f64, temperature: f64, boundary: BoundaryCondition) -> Self {
+        Self {
+            volume,
+            temperature,
+            boundary,
+            blocks: 10,
+        }
+    }
+
+    /// Set the number of blocks used for the error estimate
+    pub const fn with_blocks(mut self, blocks: usize) -> Self {
+        self.blocks = blocks;
+        self
+    }
+
+    /// Relative permittivity from the dipole fluctuation, ⟨𝐌²⟩ - ⟨𝐌⟩² (e²Å²)
+    pub fn permittivity(&self, fluctuation: f64) -> f64 {
+        let x = 4.0 * PI / 3.0 * fluctuation * bjerrum_length(self.temperature, 1.0) / self.volume;
+        match self.boundary {
+            BoundaryCondition::Tinfoil => 1.0 + x,
+            _ => {
+                let surrounding = self.boundary.permittivity();
+                (2.0 * surrounding * (1.0 + x) + 1.0) / (2.0 * surrounding + 1.0 - x)
+            }
+        }
+    }
+
+    /// Estimate the relative permittivity from a time series of system dipole moments (eÅ)
+    ///
+    /// # Errors
+    /// Returns an error if there are fewer than two blocks or fewer samples than blocks.
+    pub fn estimate(&self, dipoles: &[Vector3]) -> Result<DielectricEstimate> {
+        if self.blocks < 2 || dipoles.len() < self.blocks {
+            return Err(crate::Error::Unsupported(
+                "dielectric estimate needs at least two blocks with one sample each",
+            ));
+        }
+        let fluctuation = |dipoles: &[Vector3]| {
+            let n = dipoles.len() as f64;
+            let (sum, sum_squared) = dipoles.iter().fold(
+                (NalgebraVector3::zeros(), 0.0),
+                |(sum, sum_squared), dipole| {
+                    let dipole = NalgebraVector3::from(*dipole);
+                    (sum + dipole, sum_squared + dipole.norm_squared())
+                },
+            );
+            sum_squared / n - (sum / n).norm_squared()
+        };
+        let block_size = dipoles.len() / self.blocks;
+        let estimates: Vec<f64> = dipoles
+            .chunks_exact(block_size)
+            .take(self.blocks)
+            .map(|block| self.permittivity(fluctuation(block)))
+            .collect();
+        let n = estimates.len() as f64;
+        let mean = estimates.iter().sum::<f64>() / n;
+        let variance = estimates.iter().map(|e| (e - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        Ok(DielectricEstimate {
+            permittivity: self.permittivity(fluctuation(dipoles)),
+            error: (variance / n).sqrt(),
+        })
+    }
+}
+
+#[test]
+fn test_dielectric_estimator() {
+    use approx::assert_relative_eq;
+    // Alternating dipoles with ⟨𝐌⟩ = 0 and ⟨𝐌²⟩ = 100
+    let dipoles: Vec<Vector3> = (0..100)
+        .map(|i| [if i % 2 == 0 { 10.0 } else { -10.0 }, 0.0, 0.0].into())
+        .collect();
+    let (volume, temperature) = (100_000.0, 300.0);
+    let x = 4.0 * PI / 3.0 * 100.0 * bjerrum_length(temperature, 1.0) / volume;
+
+    let tinfoil = DielectricEstimator::new(volume, temperature, BoundaryCondition::Tinfoil);
+    let estimate = tinfoil.estimate(&dipoles).unwrap();
+    assert_relative_eq!(estimate.permittivity, 1.0 + x);
+    assert_relative_eq!(estimate.error, 0.0, epsilon = 1e-10);
+
+    // Vacuum boundaries follow the Clausius-Mossotti form
+    let vacuum = DielectricEstimator::new(volume, temperature, BoundaryCondition::Vacuum);
+    let y = x / 3.0;
+    let permittivity = vacuum.estimate(&dipoles).unwrap().permittivity;
+    assert_relative_eq!(permittivity, (1.0 + 2.0 * y) / (1.0 - y));
+    let same = DielectricEstimator::new(volume, temperature, BoundaryCondition::Dielectric(1.0));
+    assert_relative_eq!(same.permittivity(100.0), permittivity);
+
+    // Large surrounding permittivity approaches tinfoil
+    let far = DielectricEstimator::new(volume, temperature, BoundaryCondition::Dielectric(1e12));
+    assert_relative_eq!(far.permittivity(100.0), 1.0 + x, max_relative = 1e-9);
+
+    // Constant dipole gives no fluctuation
+    let constant = vec![Vector3::from([1.0, 2.0, 3.0]); 20];
+    assert_relative_eq!(
+        tinfoil.estimate(&constant).unwrap().permittivity,
+        1.0,
+        epsilon = 1e-12
+    );
+    assert!(tinfoil.with_blocks(30).estimate(&constant).is_err());
+    assert!(tinfoil.with_blocks(1).estimate(&constant).is_err());
+}
@@ -0,0 +1,221 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Empirical permittivity with an arbitrary sum of terms.
+
+use super::{EmpiricalPermittivity, RelativePermittivity};
+use crate::Result;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Term in a [`GeneralEmpiricalPermittivity`] model
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EmpiricalTerm {
+    /// Power term, a𝑇ⁿ, with coefficient and exponent. A constant has exponent zero.
+    Power(f64, f64),
+    /// Logarithmic term, c㏑(𝑇), with coefficient
+    Logarithm(f64),
+}
+
+impl EmpiricalTerm {
+    /// Value of the term at a given temperature
+    fn value(&self, temperature: f64) -> f64 {
+        match self {
+            Self::Power(coeff, exponent) => coeff * temperature.powf(*exponent),
+            Self::Logarithm(coeff) => coeff * temperature.ln(),
+        }
+    }
+    /// Temperature derivative of the term
+    fn derivative(&self, temperature: f64) -> f64 {
+        match self {
+            Self::Power(_, exponent) if *exponent == 0.0 => 0.0,
+            Self::Power(coeff, exponent) => coeff * exponent * temperature.powf(exponent - 1.0),
+            Self::Logarithm(coeff) => coeff / temperature,
+        }
+    }
+}
+
+impl Display for EmpiricalTerm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Power(coeff, exponent) if *exponent == 0.0 => write!(f, "{:.2e}", coeff),
+            Self::Power(coeff, exponent) if *exponent == 1.0 => write!(f, "{:.2e}𝑇", coeff),
+            Self::Power(coeff, exponent) if *exponent == 2.0 => write!(f, "{:.2e}𝑇²", coeff),
+            Self::Power(coeff, exponent) if *exponent == -1.0 => write!(f, "{:.2e}/𝑇", coeff),
+            Self::Power(coeff, exponent) => write!(f, "{:.2e}𝑇^{}", coeff, exponent),
+            Self::Logarithm(coeff) => write!(f, "{:.2e}㏑(𝑇)", coeff),
+        }
+    }
+}
+
+/// Empirical model for the relative permittivity, εᵣ(𝑇), as an arbitrary sum of terms
+///
+/// Each term is either a power, a𝑇ⁿ, or a logarithm, c㏑(𝑇), which covers most published fits.
+/// The [`EmpiricalPermittivity`] NR model is a special case and can be converted.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::{EmpiricalTerm, GeneralEmpiricalPermittivity, RelativePermittivity};
+/// // Malmberg and Maryott fit for water, εᵣ = 87.74 - 0.4008𝑡 + 9.398e-4𝑡² - 1.41e-6𝑡³ in °C
+/// let model = GeneralEmpiricalPermittivity::new(
+///     vec![
+///         EmpiricalTerm::Power(87.74, 0.0),
+///         EmpiricalTerm::Power(-0.4008, 1.0),
+///         EmpiricalTerm::Power(9.398e-4, 2.0),
+///         EmpiricalTerm::Power(-1.41e-6, 3.0),
+///     ],
+///     (273.15, 373.15),
+/// )
+/// .with_temperature_offset(-273.15);
+/// assert_relative_eq!(model.permittivity(298.15).unwrap(), 78.29, epsilon = 0.01);
+/// assert!(model.permittivity(400.0).is_err());
+/// ~~~
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct GeneralEmpiricalPermittivity {
+    /// Terms that are summed to give the permittivity
+    terms: Vec<EmpiricalTerm>,
+    /// Closed temperature interval in which the model is valid, UNIT: [ K ]
+    temperature_interval: (f64, f64),
+    /// Offset added to the temperature before evaluating the terms, UNIT: [ K ]
+    #[cfg_attr(feature = "serde", serde(default))]
+    temperature_offset: f64,
+}
+
+impl GeneralEmpiricalPermittivity {
+    /// New model from terms and the closed temperature interval in which it is valid
+    pub const fn new(terms: Vec<EmpiricalTerm>, temperature_interval: (f64, f64)) -> Self {
+        Self {
+            terms,
+            temperature_interval,
+            temperature_offset: 0.0,
+        }
+    }
+
+    /// Evaluate terms at 𝑇 + offset, _e.g._ -273.15 K for fits in degrees Celsius
+    pub const fn with_temperature_offset(mut self, offset: f64) -> Self {
+        self.temperature_offset = offset;
+        self
+    }
+
+    /// Terms of the model
+    pub fn terms(&self) -> &[EmpiricalTerm] {
+        &self.terms
+    }
+
+    /// Shifted temperature or error if out of range
+    fn shifted_temperature(&self, temperature: f64) -> Result<f64> {
+        if temperature < self.temperature_interval.0 || temperature > self.temperature_interval.1 {
+            Err(crate::Error::TemperatureOutOfRange)
+        } else {
+            Ok(temperature + self.temperature_offset)
+        }
+    }
+}
+
+impl RelativePermittivity for GeneralEmpiricalPermittivity {
+    fn permittivity(&self, temperature: f64) -> Result<f64> {
+        let temperature = self.shifted_temperature(temperature)?;
+        Ok(self.terms.iter().map(|term| term.value(temperature)).sum())
+    }
+    fn d_permittivity_d_temperature(&self, temperature: f64) -> Option<f64> {
+        let temperature = self.shifted_temperature(temperature).ok()?;
+        Some(
+            self.terms
+                .iter()
+                .map(|term| term.derivative(temperature))
+                .sum(),
+        )
+    }
+}
+
+impl From<EmpiricalPermittivity> for GeneralEmpiricalPermittivity {
+    fn from(model: EmpiricalPermittivity) -> Self {
+        let [a, b, c, d, e] = model.coeffs;
+        Self::new(
+            vec![
+                EmpiricalTerm::Power(a, 0.0),
+                EmpiricalTerm::Power(b, 1.0),
+                EmpiricalTerm::Power(c, 2.0),
+                EmpiricalTerm::Power(d, -1.0),
+                EmpiricalTerm::Logarithm(e),
+            ],
+            model.temperature_interval,
+        )
+    }
+}
+
+impl Display for GeneralEmpiricalPermittivity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "εᵣ(𝑇) = ")?;
+        for (i, term) in self.terms.iter().enumerate() {
+            if i > 0 {
+                write!(f, " + ")?;
+            }
+            write!(f, "{}", term)?;
+        }
+        if self.temperature_offset != 0.0 {
+            write!(f, "; 𝑇 → 𝑇 + {:.2}", self.temperature_offset)?;
+        }
+        write!(
+            f,
+            "; 𝑇 = [{:.1}, {:.1}]",
+            self.temperature_interval.0, self.temperature_interval.1
+        )
+    }
+}
+
+#[test]
+fn test_general_empirical_permittivity() {
+    use super::WATER;
+    use approx::assert_relative_eq;
+    let general = GeneralEmpiricalPermittivity::from(WATER);
+    for temperature in [273.0, 298.15, 350.0, 403.0] {
+        assert_relative_eq!(
+            general.permittivity(temperature).unwrap(),
+            WATER.permittivity(temperature).unwrap(),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            general.d_permittivity_d_temperature(temperature).unwrap(),
+            WATER.d_permittivity_d_temperature(temperature).unwrap(),
+            max_relative = 1e-10
+        );
+    }
+    assert!(general.permittivity(404.0).is_err());
+    assert_eq!(general.to_string(), WATER.to_string());
+
+    // Fractional exponent and offset
+    let model = GeneralEmpiricalPermittivity::new(
+        vec![
+            EmpiricalTerm::Power(2.0, 0.5),
+            EmpiricalTerm::Power(1.0, 0.0),
+        ],
+        (0.0, 500.0),
+    )
+    .with_temperature_offset(-100.0);
+    assert_relative_eq!(model.permittivity(104.0).unwrap(), 5.0);
+    assert_relative_eq!(model.d_permittivity_d_temperature(104.0).unwrap(), 0.5);
+    assert_eq!(
+        model.to_string(),
+        "εᵣ(𝑇) = 2.00e0𝑇^0.5 + 1.00e0; 𝑇 → 𝑇 + -100.00; 𝑇 = [0.0, 500.0]"
+    );
+}
@@ -0,0 +1,280 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Permittivity of solvent mixtures.
+
+use super::{Permittivity, RelativePermittivity};
+use crate::Result;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rule for combining the permittivities, εᵢ, of components with fractions, φᵢ
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MixingRule {
+    /// Linear (Oster) mixing, ε = ∑ φᵢεᵢ
+    #[default]
+    Linear,
+    /// Looyenga mixing, ε^⅓ = ∑ φᵢεᵢ^⅓, see <https://doi.org/10.1016/0031-8914(65)90045-5>
+    Looyenga,
+    /// Symmetric Bruggeman effective medium, ∑ φᵢ(εᵢ - ε)/(εᵢ + 2ε) = 0,
+    /// see <https://doi.org/10.1002/andp.19354160705>
+    Bruggeman,
+}
+
+impl Display for MixingRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MixingRule::Linear => write!(f, "linear"),
+            MixingRule::Looyenga => write!(f, "Looyenga"),
+            MixingRule::Bruggeman => write!(f, "Bruggeman"),
+        }
+    }
+}
+
+/// Relative permittivity of a mixture of two or more solvents, εᵣ(𝑇)
+///
+/// Each component is a permittivity model with a mole or volume fraction, φᵢ, and the
+/// permittivities are combined using a [`MixingRule`].
+/// Fractions are normalized to unity and the temperature must be valid for all components.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::*;
+/// let components = vec![(Permittivity::Water, 0.8), (Permittivity::Ethanol, 0.2)];
+/// let linear = MixturePermittivity::new(components.clone(), MixingRule::Linear).unwrap();
+/// assert_relative_eq!(linear.permittivity(298.15).unwrap(), 67.55, epsilon = 0.01);
+/// let looyenga = MixturePermittivity::new(components, MixingRule::Looyenga).unwrap();
+/// assert!(looyenga.permittivity(298.15).unwrap() < 67.55);
+/// ~~~
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct MixturePermittivity {
+    /// Permittivity model and fraction of each component
+    components: Vec<(Permittivity, f64)>,
+    /// Rule for combining the component permittivities
+    #[cfg_attr(feature = "serde", serde(default))]
+    rule: MixingRule,
+}
+
+impl MixturePermittivity {
+    /// New mixture from components as (model, fraction) pairs. Fractions are normalized.
+    ///
+    /// # Errors
+    /// Returns an error if a fraction is negative or not finite, or if the fractions sum to zero.
+    pub fn new(mut components: Vec<(Permittivity, f64)>, rule: MixingRule) -> Result<Self> {
+        let sum: f64 = components.iter().map(|(_, fraction)| fraction).sum();
+        if sum <= 0.0 || !sum.is_finite() || components.iter().any(|(_, fraction)| *fraction < 0.0)
+        {
+            return Err(crate::Error::InvalidFraction);
+        }
+        components
+            .iter_mut()
+            .for_each(|(_, fraction)| *fraction /= sum);
+        Ok(Self { components, rule })
+    }
+
+    /// Binary mixture where `fraction` is that of the first component
+    ///
+    /// # Errors
+    /// Returns an error if the fraction is outside [0, 1].
+    pub fn binary(
+        first: Permittivity,
+        second: Permittivity,
+        fraction: f64,
+        rule: MixingRule,
+    ) -> Result<Self> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return Err(crate::Error::InvalidFraction);
+        }
+        Self::new(vec![(first, fraction), (second, 1.0 - fraction)], rule)
+    }
+
+    /// Permittivity model and normalized fraction of each component
+    pub fn components(&self) -> &[(Permittivity, f64)] {
+        &self.components
+    }
+
+    /// Mixing rule
+    pub const fn rule(&self) -> MixingRule {
+        self.rule
+    }
+
+    /// Permittivities and fractions of all components at a given temperature
+    fn component_permittivities(&self, temperature: f64) -> Result<Vec<(f64, f64)>> {
+        self.components
+            .iter()
+            .map(|(model, fraction)| Ok((model.permittivity(temperature)?, *fraction)))
+            .collect()
+    }
+}
+
+/// Solve the Bruggeman equation by bisection between the smallest and largest permittivity
+fn bruggeman(components: &[(f64, f64)]) -> f64 {
+    let residual = |epsilon: f64| -> f64 {
+        components
+            .iter()
+            .map(|(e, fraction)| fraction * (e - epsilon) / (e + 2.0 * epsilon))
+            .sum()
+    };
+    let (mut low, mut high) = components
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), (e, _)| {
+            (low.min(*e), high.max(*e))
+        });
+    // The residual decreases monotonically with ε
+    while high - low > f64::EPSILON * high {
+        let middle = 0.5 * (low + high);
+        if residual(middle) > 0.0 {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    0.5 * (low + high)
+}
+
+impl RelativePermittivity for MixturePermittivity {
+    fn permittivity(&self, temperature: f64) -> Result<f64> {
+        let components = self.component_permittivities(temperature)?;
+        let permittivity = match self.rule {
+            MixingRule::Linear => components.iter().map(|(e, fraction)| fraction * e).sum(),
+            MixingRule::Looyenga => components
+                .iter()
+                .map(|(e, fraction)| fraction * e.cbrt())
+                .sum::<f64>()
+                .powi(3),
+            MixingRule::Bruggeman => bruggeman(&components),
+        };
+        Ok(permittivity)
+    }
+    fn d_permittivity_d_temperature(&self, temperature: f64) -> Option<f64> {
+        let components = self.component_permittivities(temperature).ok()?;
+        let derivatives = self
+            .components
+            .iter()
+            .map(|(model, _)| model.d_permittivity_d_temperature(temperature))
+            .collect::<Option<Vec<f64>>>()?;
+        let terms = components.iter().zip(derivatives);
+        let derivative = match self.rule {
+            MixingRule::Linear => terms.map(|((_, fraction), de)| fraction * de).sum(),
+            MixingRule::Looyenga => {
+                let permittivity = self.permittivity(temperature).ok()?;
+                permittivity.cbrt().powi(2)
+                    * terms
+                        .map(|((e, fraction), de)| fraction * de / e.cbrt().powi(2))
+                        .sum::<f64>()
+            }
+            // Implicit differentiation of the Bruggeman equation
+            MixingRule::Bruggeman => {
+                let epsilon = bruggeman(&components);
+                let numerator: f64 = terms
+                    .map(|((e, fraction), de)| {
+                        fraction * epsilon * de / (e + 2.0 * epsilon).powi(2)
+                    })
+                    .sum();
+                let denominator: f64 = components
+                    .iter()
+                    .map(|(e, fraction)| fraction * e / (e + 2.0 * epsilon).powi(2))
+                    .sum();
+                numerator / denominator
+            }
+        };
+        Some(derivative)
+    }
+}
+
+impl Display for MixturePermittivity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} mixture:", self.rule)?;
+        for (i, (model, fraction)) in self.components.iter().enumerate() {
+            let separator = if i == 0 { "" } else { " +" };
+            write!(f, "{} {:.2} × ({})", separator, fraction, model)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_mixture_permittivity() {
+    use approx::assert_relative_eq;
+    let (temperature, h) = (298.15, 1e-4);
+    let (water, oil) = (Permittivity::Water, Permittivity::Fixed(2.0));
+    for rule in [
+        MixingRule::Linear,
+        MixingRule::Looyenga,
+        MixingRule::Bruggeman,
+    ] {
+        // Pure components and bounds
+        let pure = MixturePermittivity::binary(water.clone(), oil.clone(), 1.0, rule).unwrap();
+        assert_relative_eq!(
+            pure.permittivity(temperature).unwrap(),
+            water.permittivity(temperature).unwrap(),
+            max_relative = 1e-12
+        );
+        let mixture = MixturePermittivity::binary(water.clone(), oil.clone(), 0.5, rule).unwrap();
+        let permittivity = mixture.permittivity(temperature).unwrap();
+        assert!(permittivity > 2.0 && permittivity < 78.4);
+
+        // Analytic derivative
+        let derivative = (mixture.permittivity(temperature + h).unwrap()
+            - mixture.permittivity(temperature - h).unwrap())
+            / (2.0 * h);
+        assert_relative_eq!(
+            mixture.d_permittivity_d_temperature(temperature).unwrap(),
+            derivative,
+            max_relative = 1e-6
+        );
+    }
+
+    // Bruggeman has a closed form for two components
+    let mixture = MixturePermittivity::binary(
+        oil.clone(),
+        Permittivity::Fixed(8.0),
+        0.25,
+        MixingRule::Bruggeman,
+    )
+    .unwrap();
+    let b: f64 = 0.25 * (2.0 * 2.0 - 8.0) + 0.75 * (2.0 * 8.0 - 2.0);
+    let expected = (b + (b * b + 8.0 * 2.0 * 8.0).sqrt()) / 4.0;
+    assert_relative_eq!(
+        mixture.permittivity(temperature).unwrap(),
+        expected,
+        max_relative = 1e-12
+    );
+
+    // Fractions are normalized
+    let mixture = MixturePermittivity::new(
+        vec![(oil.clone(), 3.0), (water.clone(), 1.0)],
+        MixingRule::Linear,
+    )
+    .unwrap();
+    assert_eq!(mixture.components()[0].1, 0.75);
+    assert!(mixture.permittivity(500.0).is_err());
+    assert!(
+        MixturePermittivity::new(vec![(oil.clone(), -1.0), (water, 2.0)], MixingRule::Linear)
+            .is_err()
+    );
+    assert!(MixturePermittivity::binary(oil.clone(), oil, 1.5, MixingRule::Linear).is_err());
+    assert_eq!(
+        mixture.to_string(),
+        "linear mixture: 0.75 × (2) + 0.25 × (εᵣ(𝑇) = -1.66e3 + -8.85e-1𝑇 + 3.63e-4𝑇² + 6.48e4/𝑇 + 3.08e2㏑(𝑇); 𝑇 = [273.0, 403.0])"
+    );
+}
@@ -0,0 +1,970 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Relative permittivity models
+
+use crate::Result;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod booth;
+mod complex;
+mod fluctuation;
+mod general;
+mod mixture;
+mod molecular;
+mod pressure;
+mod relaxation;
+mod tabulated;
+#[cfg(feature = "uom")]
+mod uom;
+mod water;
+pub use booth::Booth;
+pub use complex::{ComplexPermittivity, Conducting};
+pub use fluctuation::{BoundaryCondition, DielectricEstimate, DielectricEstimator};
+pub use general::{EmpiricalTerm, GeneralEmpiricalPermittivity};
+pub use mixture::{MixingRule, MixturePermittivity};
+pub use molecular::{
+    clausius_mossotti, clausius_mossotti_polarizability, kirkwood_frohlich, kirkwood_g_factor,
+    onsager,
+};
+pub use pressure::BradleyPitzer;
+pub use relaxation::DebyeRelaxation;
+pub use tabulated::TabulatedPermittivity;
+#[cfg(feature = "uom")]
+pub use uom::RelativePermittivitySI;
+pub use water::{IceIh, WaterIapws, WaterMalmbergMaryott, WaterSupercooled};
+
+/// Trait for objects that has a relative permittivity
+///
+/// Fallible methods return the crate [`Error`](crate::Error) so that errors can be matched on.
+pub trait RelativePermittivity {
+    /// Relative permittivity or error if temperature is out of range.
+    ///
+    /// # Errors
+    /// Returns [`Error::TemperatureOutOfRange`](crate::Error::TemperatureOutOfRange) if the
+    /// temperature is outside the range of the model.
+    fn permittivity(&self, temperature: f64) -> Result<f64>;
+
+    /// Test if temperature is within range
+    fn temperature_is_ok(&self, temperature: f64) -> bool {
+        self.permittivity(temperature).is_ok()
+    }
+    /// Convert to constant permittivity for a given temperature
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is out of range, see [`RelativePermittivity::permittivity`].
+    fn to_const_permittivity(&self, temperature: f64) -> Result<ConstantPermittivity> {
+        Ok(ConstantPermittivity::new(self.permittivity(temperature)?))
+    }
+    /// Temperature derivative, dεᵣ/d𝑇, or `None` if unavailable or if temperature is out of range.
+    fn d_permittivity_d_temperature(&self, temperature: f64) -> Option<f64> {
+        let _ = temperature;
+        None
+    }
+    /// Logarithmic temperature derivative, dln(εᵣ)/dln(𝑇) = 𝑇/εᵣ dεᵣ/d𝑇, or `None` if unavailable.
+    ///
+    /// For water at room temperature, this is approximately -1.38.
+    fn dlog_permittivity_dlog_temperature(&self, temperature: f64) -> Option<f64> {
+        let derivative = self.d_permittivity_d_temperature(temperature)?;
+        Some(temperature * derivative / self.permittivity(temperature).ok()?)
+    }
+    /// Tries to set the permittivity to a constant value
+    ///
+    /// # Errors
+    /// Returns [`Error::Unsupported`](crate::Error::Unsupported) if the model cannot be set to a constant.
+    fn set_permittivity(&mut self, permittivity: f64) -> Result<()> {
+        let _ = permittivity;
+        Err(crate::Error::Unsupported("setting the permittivity"))
+    }
+}
+
+/// Enum for all available permittivity models
+///
+/// With serde, models can be deserialized from the tagged enum form, _e.g._ `{"Fixed": 2.0}`,
+/// from a name, _e.g._ `"water"`, see [`Permittivity::from_name`], or from a number for a
+/// constant permittivity. Serialization always uses the tagged form.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields, remote = "Self")
+)]
+pub enum Permittivity {
+    /// Custom constant permittivity, independent of temperature
+    Fixed(f64),
+    /// Custom empirical permittivity model with temperature dependence
+    Empirical(EmpiricalPermittivity),
+    /// Custom empirical permittivity model with an arbitrary sum of terms
+    GeneralEmpirical(GeneralEmpiricalPermittivity),
+    /// Custom permittivity interpolated from tabulated (𝑇, εᵣ) data
+    Tabulated(TabulatedPermittivity),
+    /// Mixture of solvents combined with a mixing rule
+    Mixture(MixturePermittivity),
+    /// Relative permittivity of water using the NR model for temperature dependence
+    Water,
+    /// Relative permittivity of water using the Bradley-Pitzer model for temperature and pressure dependence
+    BradleyPitzer(BradleyPitzer),
+    /// Relative permittivity of water using the Malmberg-Maryott model for temperature dependence
+    WaterMalmbergMaryott,
+    /// Relative permittivity of water using the IAPWS model at saturated liquid density
+    WaterIapws,
+    /// Relative permittivity of water extrapolated to supercooled temperatures
+    WaterSupercooled,
+    /// Relative permittivity of ice Ih
+    Ice,
+    /// Relative permittivity of heavy water, D₂O, with empirical temperature dependence
+    HeavyWater,
+    /// Relative permittivity of ethanol using the NR model for temperature dependence
+    Ethanol,
+    /// Relative permittivity of methanol using the NR model for temperature dependence
+    Methanol,
+    /// Relative permittivity of dimethyl sulfoxide (DMSO) with empirical temperature dependence
+    Dmso,
+    /// Relative permittivity of acetonitrile with empirical temperature dependence
+    Acetonitrile,
+    /// Relative permittivity of acetone with empirical temperature dependence
+    Acetone,
+    /// Relative permittivity of glycerol with empirical temperature dependence
+    Glycerol,
+    /// Relative permittivity of 1-propanol with empirical temperature dependence
+    Propanol,
+    /// Relative permittivity of 2-propanol with empirical temperature dependence
+    Isopropanol,
+    /// Relative permittivity of formamide with empirical temperature dependence
+    Formamide,
+    /// Relative permittivity of tetrahydrofuran (THF) with empirical temperature dependence
+    Tetrahydrofuran,
+    /// Perfect conductor with infinite permittivity, εᵣ = ∞
+    Metal,
+    /// Relative permittivity of free space, εᵣ = 1.0
+    Vacuum,
+    /// Relative permittivity of water at 25 degree Celcius, εᵣ = 78.4
+    Water25,
+    /// Effective relative permittivity of a protein interior, εᵣ = 4.0
+    ProteinInterior,
+    /// Relative permittivity of the hydrocarbon core of a lipid bilayer, εᵣ = 2.0
+    LipidBilayerCore,
+    /// Relative permittivity of the ionic liquid [BMIM][BF₄] at 25 degree Celcius, εᵣ = 11.7
+    BmimBf4,
+    /// Relative permittivity of the ionic liquid [BMIM][PF₆] at 25 degree Celcius, εᵣ = 11.4
+    BmimPf6,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Permittivity {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        Permittivity::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Permittivity {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        deserializer.deserialize_any(PermittivityVisitor)
+    }
+}
+
+/// Visitor accepting the tagged enum form, a model name, or a number
+#[cfg(feature = "serde")]
+struct PermittivityVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for PermittivityVisitor {
+    type Value = Permittivity;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "a permittivity model, a model name, or a number")
+    }
+    fn visit_f64<E: serde::de::Error>(self, value: f64) -> core::result::Result<Self::Value, E> {
+        Ok(Permittivity::Fixed(value))
+    }
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> core::result::Result<Self::Value, E> {
+        self.visit_f64(value as f64)
+    }
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> core::result::Result<Self::Value, E> {
+        self.visit_f64(value as f64)
+    }
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> core::result::Result<Self::Value, E> {
+        // Unit variant names first to keep serialization round-trip stable
+        Permittivity::deserialize(serde::de::value::StrDeserializer::<E>::new(value))
+            .or_else(|_| value.parse().map_err(E::custom))
+    }
+    fn visit_map<A: serde::de::MapAccess<'de>>(
+        self,
+        map: A,
+    ) -> core::result::Result<Self::Value, A::Error> {
+        Permittivity::deserialize(serde::de::value::MapAccessDeserializer::new(map))
+    }
+    fn visit_enum<A: serde::de::EnumAccess<'de>>(
+        self,
+        data: A,
+    ) -> core::result::Result<Self::Value, A::Error> {
+        Permittivity::deserialize(serde::de::value::EnumAccessDeserializer::new(data))
+    }
+}
+
+impl Permittivity {
+    /// All named models that can be looked up with [`Permittivity::from_name`]
+    pub const NAMED: [Permittivity; 23] = [
+        Permittivity::Water,
+        Permittivity::WaterMalmbergMaryott,
+        Permittivity::WaterIapws,
+        Permittivity::WaterSupercooled,
+        Permittivity::Ice,
+        Permittivity::Water25,
+        Permittivity::HeavyWater,
+        Permittivity::Ethanol,
+        Permittivity::Methanol,
+        Permittivity::Dmso,
+        Permittivity::Acetonitrile,
+        Permittivity::Acetone,
+        Permittivity::Glycerol,
+        Permittivity::Propanol,
+        Permittivity::Isopropanol,
+        Permittivity::Formamide,
+        Permittivity::Tetrahydrofuran,
+        Permittivity::ProteinInterior,
+        Permittivity::LipidBilayerCore,
+        Permittivity::BmimBf4,
+        Permittivity::BmimPf6,
+        Permittivity::Metal,
+        Permittivity::Vacuum,
+    ];
+
+    /// Names of a model; empty for custom models
+    fn names(&self) -> &'static [&'static str] {
+        match self {
+            Permittivity::Water => &["water", "h2o"],
+            Permittivity::Water25 => &["water25"],
+            Permittivity::WaterMalmbergMaryott => &["water-malmberg-maryott", "malmberg-maryott"],
+            Permittivity::WaterIapws => &["water-iapws", "iapws"],
+            Permittivity::WaterSupercooled => &["water-supercooled", "supercooled water"],
+            Permittivity::Ice => &["ice", "ice ih"],
+            Permittivity::HeavyWater => &["heavy water", "d2o", "deuterium oxide"],
+            Permittivity::Ethanol => &["ethanol", "etoh"],
+            Permittivity::Methanol => &["methanol", "meoh"],
+            Permittivity::Dmso => &["dmso", "dimethyl sulfoxide", "dimethylsulfoxide"],
+            Permittivity::Acetonitrile => &["acetonitrile", "mecn", "acn"],
+            Permittivity::Acetone => &["acetone", "propanone"],
+            Permittivity::Glycerol => &["glycerol", "glycerin", "glycerine"],
+            Permittivity::Propanol => &["1-propanol", "propanol", "n-propanol", "propan-1-ol"],
+            Permittivity::Isopropanol => &[
+                "2-propanol",
+                "isopropanol",
+                "propan-2-ol",
+                "isopropyl alcohol",
+                "ipa",
+            ],
+            Permittivity::Formamide => &["formamide"],
+            Permittivity::Tetrahydrofuran => &["tetrahydrofuran", "thf"],
+            Permittivity::ProteinInterior => &["protein interior", "protein"],
+            Permittivity::LipidBilayerCore => &["lipid bilayer core", "lipid", "hydrocarbon"],
+            Permittivity::BmimBf4 => &["[bmim][bf4]", "bmim-bf4", "bmimbf4"],
+            Permittivity::BmimPf6 => &["[bmim][pf6]", "bmim-pf6", "bmimpf6"],
+            Permittivity::Metal => &["metal"],
+            Permittivity::Vacuum => &["vacuum"],
+            Permittivity::Fixed(_)
+            | Permittivity::Empirical(_)
+            | Permittivity::GeneralEmpirical(_)
+            | Permittivity::Tabulated(_)
+            | Permittivity::Mixture(_)
+            | Permittivity::BradleyPitzer(_) => &[],
+        }
+    }
+
+    /// Look up a named model from its name or an alias, e.g. `"acetonitrile"` or `"MeCN"`,
+    /// ignoring case and surrounding whitespace.
+    ///
+    /// See [`Permittivity::NAMED`] for the available models.
+    ///
+    /// # Errors
+    /// Returns [`Error::UnknownSolvent`](crate::Error::UnknownSolvent) if the name is unknown.
+    ///
+    /// # Examples
+    /// ~~~
+    /// use coulomb::permittivity::Permittivity;
+    /// assert_eq!(Permittivity::from_name("Acetonitrile").unwrap(), Permittivity::Acetonitrile);
+    /// assert_eq!(Permittivity::from_name(" THF ").unwrap(), Permittivity::Tetrahydrofuran);
+    /// assert!(Permittivity::from_name("unobtainium").is_err());
+    /// // Named models or constant values can also be parsed
+    /// assert_eq!("isopropanol".parse::<Permittivity>().unwrap(), Permittivity::Isopropanol);
+    /// assert_eq!("2.5".parse::<Permittivity>().unwrap(), Permittivity::Fixed(2.5));
+    /// ~~~
+    pub fn from_name(name: &str) -> Result<Self> {
+        let name = name.trim();
+        Self::NAMED
+            .into_iter()
+            .find(|model| {
+                model
+                    .names()
+                    .iter()
+                    .any(|alias| alias.eq_ignore_ascii_case(name))
+            })
+            .ok_or_else(|| crate::Error::UnknownSolvent(name.to_string()))
+    }
+}
+
+impl FromStr for Permittivity {
+    type Err = crate::Error;
+    /// Named model, see [`Permittivity::from_name`], or else a constant permittivity
+    fn from_str(name: &str) -> Result<Self> {
+        Self::from_name(name).or_else(|err| name.trim().parse().map(Self::Fixed).map_err(|_| err))
+    }
+}
+
+impl RelativePermittivity for Permittivity {
+    fn permittivity(&self, temperature: f64) -> Result<f64> {
+        match self {
+            Permittivity::Fixed(d) => Ok(*d),
+            Permittivity::Empirical(d) => d.permittivity(temperature),
+            Permittivity::GeneralEmpirical(d) => d.permittivity(temperature),
+            Permittivity::Tabulated(d) => d.permittivity(temperature),
+            Permittivity::Mixture(d) => d.permittivity(temperature),
+            Permittivity::Water => WATER.permittivity(temperature),
+            Permittivity::BradleyPitzer(d) => d.permittivity(temperature),
+            Permittivity::WaterMalmbergMaryott => WaterMalmbergMaryott.permittivity(temperature),
+            Permittivity::WaterIapws => WaterIapws.permittivity(temperature),
+            Permittivity::WaterSupercooled => WaterSupercooled.permittivity(temperature),
+            Permittivity::Ice => IceIh.permittivity(temperature),
+            Permittivity::HeavyWater => HEAVY_WATER.permittivity(temperature),
+            Permittivity::Ethanol => ETHANOL.permittivity(temperature),
+            Permittivity::Methanol => METHANOL.permittivity(temperature),
+            Permittivity::Dmso => DMSO.permittivity(temperature),
+            Permittivity::Acetonitrile => ACETONITRILE.permittivity(temperature),
+            Permittivity::Acetone => ACETONE.permittivity(temperature),
+            Permittivity::Glycerol => GLYCEROL.permittivity(temperature),
+            Permittivity::Propanol => PROPANOL.permittivity(temperature),
+            Permittivity::Isopropanol => ISOPROPANOL.permittivity(temperature),
+            Permittivity::Formamide => FORMAMIDE.permittivity(temperature),
+            Permittivity::Tetrahydrofuran => TETRAHYDROFURAN.permittivity(temperature),
+            Permittivity::Metal => METAL.permittivity(temperature),
+            Permittivity::Vacuum => VACUUM.permittivity(temperature),
+            Permittivity::Water25 => WATER_25C.permittivity(temperature),
+            Permittivity::ProteinInterior => PROTEIN_INTERIOR.permittivity(temperature),
+            Permittivity::LipidBilayerCore => LIPID_BILAYER_CORE.permittivity(temperature),
+            Permittivity::BmimBf4 => BMIM_BF4.permittivity(temperature),
+            Permittivity::BmimPf6 => BMIM_PF6.permittivity(temperature),
+        }
+    }
+    fn d_permittivity_d_temperature(&self, temperature: f64) -> Option<f64> {
+        match self {
+            Permittivity::Fixed(_)
+            | Permittivity::Metal
+            | Permittivity::Vacuum
+            | Permittivity::Water25
+            | Permittivity::ProteinInterior
+            | Permittivity::LipidBilayerCore
+            | Permittivity::BmimBf4
+            | Permittivity::BmimPf6 => Some(0.0),
+            Permittivity::Empirical(d) => d.d_permittivity_d_temperature(temperature),
+            Permittivity::GeneralEmpirical(d) => d.d_permittivity_d_temperature(temperature),
+            Permittivity::Tabulated(d) => d.d_permittivity_d_temperature(temperature),
+            Permittivity::Mixture(d) => d.d_permittivity_d_temperature(temperature),
+            Permittivity::Water => WATER.d_permittivity_d_temperature(temperature),
+            Permittivity::BradleyPitzer(d) => d.d_permittivity_d_temperature(temperature),
+            Permittivity::WaterMalmbergMaryott => {
+                WaterMalmbergMaryott.d_permittivity_d_temperature(temperature)
+            }
+            Permittivity::WaterIapws => WaterIapws.d_permittivity_d_temperature(temperature),
+            Permittivity::WaterSupercooled => {
+                WaterSupercooled.d_permittivity_d_temperature(temperature)
+            }
+            Permittivity::Ice => IceIh.d_permittivity_d_temperature(temperature),
+            Permittivity::HeavyWater => HEAVY_WATER.d_permittivity_d_temperature(temperature),
+            Permittivity::Ethanol => ETHANOL.d_permittivity_d_temperature(temperature),
+            Permittivity::Methanol => METHANOL.d_permittivity_d_temperature(temperature),
+            Permittivity::Dmso => DMSO.d_permittivity_d_temperature(temperature),
+            Permittivity::Acetonitrile => ACETONITRILE.d_permittivity_d_temperature(temperature),
+            Permittivity::Acetone => ACETONE.d_permittivity_d_temperature(temperature),
+            Permittivity::Glycerol => GLYCEROL.d_permittivity_d_temperature(temperature),
+            Permittivity::Propanol => PROPANOL.d_permittivity_d_temperature(temperature),
+            Permittivity::Isopropanol => ISOPROPANOL.d_permittivity_d_temperature(temperature),
+            Permittivity::Formamide => FORMAMIDE.d_permittivity_d_temperature(temperature),
+            Permittivity::Tetrahydrofuran => {
+                TETRAHYDROFURAN.d_permittivity_d_temperature(temperature)
+            }
+        }
+    }
+}
+
+impl From<Permittivity> for Box<dyn RelativePermittivity> {
+    fn from(model: Permittivity) -> Box<dyn RelativePermittivity> {
+        match model {
+            Permittivity::Fixed(d) => Box::new(ConstantPermittivity::from(d)),
+            Permittivity::Empirical(d) => Box::new(d),
+            Permittivity::GeneralEmpirical(d) => Box::new(d),
+            Permittivity::Tabulated(d) => Box::new(d),
+            Permittivity::Mixture(d) => Box::new(d),
+            Permittivity::Water => Box::new(WATER),
+            Permittivity::BradleyPitzer(d) => Box::new(d),
+            Permittivity::WaterMalmbergMaryott => Box::new(WaterMalmbergMaryott),
+            Permittivity::WaterIapws => Box::new(WaterIapws),
+            Permittivity::WaterSupercooled => Box::new(WaterSupercooled),
+            Permittivity::Ice => Box::new(IceIh),
+            Permittivity::HeavyWater => Box::new(HEAVY_WATER),
+            Permittivity::Ethanol => Box::new(ETHANOL),
+            Permittivity::Methanol => Box::new(METHANOL),
+            Permittivity::Dmso => Box::new(DMSO),
+            Permittivity::Acetonitrile => Box::new(ACETONITRILE),
+            Permittivity::Acetone => Box::new(ACETONE),
+            Permittivity::Glycerol => Box::new(GLYCEROL),
+            Permittivity::Propanol => Box::new(PROPANOL),
+            Permittivity::Isopropanol => Box::new(ISOPROPANOL),
+            Permittivity::Formamide => Box::new(FORMAMIDE),
+            Permittivity::Tetrahydrofuran => Box::new(TETRAHYDROFURAN),
+            Permittivity::Metal => Box::new(METAL),
+            Permittivity::Vacuum => Box::new(VACUUM),
+            Permittivity::Water25 => Box::new(WATER_25C),
+            Permittivity::ProteinInterior => Box::new(PROTEIN_INTERIOR),
+            Permittivity::LipidBilayerCore => Box::new(LIPID_BILAYER_CORE),
+            Permittivity::BmimBf4 => Box::new(BMIM_BF4),
+            Permittivity::BmimPf6 => Box::new(BMIM_PF6),
+        }
+    }
+}
+
+impl Display for Permittivity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Permittivity::Fixed(d) => write!(f, "{}", d),
+            Permittivity::Empirical(d) => write!(f, "{}", d),
+            Permittivity::GeneralEmpirical(d) => write!(f, "{}", d),
+            Permittivity::Tabulated(d) => write!(f, "{}", d),
+            Permittivity::Mixture(d) => write!(f, "{}", d),
+            Permittivity::Water => write!(f, "{}", WATER),
+            Permittivity::BradleyPitzer(d) => write!(f, "{}", d),
+            Permittivity::WaterMalmbergMaryott => write!(f, "{}", WaterMalmbergMaryott),
+            Permittivity::WaterIapws => write!(f, "{}", WaterIapws),
+            Permittivity::WaterSupercooled => write!(f, "{}", WaterSupercooled),
+            Permittivity::Ice => write!(f, "{}", IceIh),
+            Permittivity::HeavyWater => write!(f, "{}", HEAVY_WATER),
+            Permittivity::Ethanol => write!(f, "{}", ETHANOL),
+            Permittivity::Methanol => write!(f, "{}", METHANOL),
+            Permittivity::Dmso => write!(f, "{}", DMSO),
+            Permittivity::Acetonitrile => write!(f, "{}", ACETONITRILE),
+            Permittivity::Acetone => write!(f, "{}", ACETONE),
+            Permittivity::Glycerol => write!(f, "{}", GLYCEROL),
+            Permittivity::Propanol => write!(f, "{}", PROPANOL),
+            Permittivity::Isopropanol => write!(f, "{}", ISOPROPANOL),
+            Permittivity::Formamide => write!(f, "{}", FORMAMIDE),
+            Permittivity::Tetrahydrofuran => write!(f, "{}", TETRAHYDROFURAN),
+            Permittivity::Metal => write!(f, "{}", METAL),
+            Permittivity::Vacuum => write!(f, "{}", VACUUM),
+            Permittivity::Water25 => write!(f, "{}", WATER_25C),
+            Permittivity::ProteinInterior => write!(f, "{}", PROTEIN_INTERIOR),
+            Permittivity::LipidBilayerCore => write!(f, "{}", LIPID_BILAYER_CORE),
+            Permittivity::BmimBf4 => write!(f, "{}", BMIM_BF4),
+            Permittivity::BmimPf6 => write!(f, "{}", BMIM_PF6),
+        }
+    }
+}
+
+/// Perfect conductor with infinite permittivity, εᵣ = ∞
+pub const METAL: ConstantPermittivity = ConstantPermittivity::new(f64::INFINITY);
+
+/// Relative permittivity of water at 25 degree Celcius, εᵣ = 78.4
+pub const WATER_25C: ConstantPermittivity = ConstantPermittivity::new(78.4);
+
+/// Effective relative permittivity of a protein interior, εᵣ = 4.0
+///
+/// Values between 2 and 4 are commonly used in continuum electrostatics, see
+/// Gilson and Honig, _Biopolymers_ 25, 2097 (1986).
+pub const PROTEIN_INTERIOR: ConstantPermittivity = ConstantPermittivity::new(4.0);
+
+/// Relative permittivity of the hydrocarbon core of a lipid bilayer, εᵣ = 2.0
+pub const LIPID_BILAYER_CORE: ConstantPermittivity = ConstantPermittivity::new(2.0);
+
+/// Relative permittivity of 1-butyl-3-methylimidazolium tetrafluoroborate at 25 degree Celcius,
+/// εᵣ = 11.7, from dielectric spectroscopy by Weingärtner, _Z. Phys. Chem._ 220, 1395 (2006).
+pub const BMIM_BF4: ConstantPermittivity = ConstantPermittivity::new(11.7);
+
+/// Relative permittivity of 1-butyl-3-methylimidazolium hexafluorophosphate at 25 degree Celcius,
+/// εᵣ = 11.4, from dielectric spectroscopy by Weingärtner, _Z. Phys. Chem._ 220, 1395 (2006).
+pub const BMIM_PF6: ConstantPermittivity = ConstantPermittivity::new(11.4);
+
+/// Relative permittivity of free space, εᵣ = 1.0
+pub const VACUUM: ConstantPermittivity = ConstantPermittivity::new(1.0);
+
+/// Relative permittivity of water, εᵣ(𝑇)
+///
+/// See <https://doi.org/ggddkk>.
+pub const WATER: EmpiricalPermittivity = EmpiricalPermittivity::new(
+    &[-1664.4988, -0.884533, 0.0003635, 64839.1736, 308.3394],
+    (273.0, 403.0),
+);
+/// Relative permittivity of heavy water, D₂O, εᵣ(𝑇)
+///
+/// The [`WATER`] model scaled by 0.9962 to give εᵣ = 78.06 at 25 °C, reflecting the near constant
+/// ratio between D₂O and H₂O, see Vidulich, Evans, and Kay, _J. Phys. Chem._ 71, 656 (1967).
+/// Valid between the melting and boiling points of D₂O.
+pub const HEAVY_WATER: EmpiricalPermittivity = EmpiricalPermittivity::new(
+    &[-1658.2183, -0.88119548, 0.00036212844, 64594.522, 307.17597],
+    (277.0, 374.0),
+);
+/// Relative permittivity of methanol, εᵣ(𝑇)
+///
+/// See <https://doi.org/ggddkk>.
+pub const METHANOL: EmpiricalPermittivity = EmpiricalPermittivity::new(
+    &[-1750.3069, -0.99026, 0.0004666, 51360.2652, 327.3124],
+    (176.0, 318.0),
+);
+/// Relative permittivity of ethanol, εᵣ(𝑇)
+///
+/// See <https://doi.org/ggddkk>.
+pub const ETHANOL: EmpiricalPermittivity = EmpiricalPermittivity::new(
+    &[-1522.2782, -1.00508, 0.0005211, 38733.9481, 293.1133],
+    (288.0, 328.0),
+);
+/// Relative permittivity of dimethyl sulfoxide (DMSO), εᵣ(𝑇)
+///
+/// Fitted as εᵣ = 𝑎 + 𝑑/𝑇 to εᵣ = 46.45 and dεᵣ/d𝑇 = -0.110 K⁻¹ at 25 °C.
+pub const DMSO: EmpiricalPermittivity =
+    EmpiricalPermittivity::new(&[13.6535, 0.0, 0.0, 9778.28, 0.0], (292.0, 343.0));
+/// Relative permittivity of acetonitrile, εᵣ(𝑇)
+///
+/// Fitted as εᵣ = 𝑎 + 𝑑/𝑇 to εᵣ = 35.94 and dεᵣ/d𝑇 = -0.160 K⁻¹ at 25 °C.
+pub const ACETONITRILE: EmpiricalPermittivity =
+    EmpiricalPermittivity::new(&[-11.7640, 0.0, 0.0, 14222.95, 0.0], (273.0, 343.0));
+/// Relative permittivity of acetone, εᵣ(𝑇)
+///
+/// Fitted as εᵣ = 𝑎 + 𝑑/𝑇 to εᵣ = 20.56 and dεᵣ/d𝑇 = -0.094 K⁻¹ at 25 °C.
+pub const ACETONE: EmpiricalPermittivity =
+    EmpiricalPermittivity::new(&[-7.4661, 0.0, 0.0, 8355.98, 0.0], (273.0, 323.0));
+/// Relative permittivity of glycerol, εᵣ(𝑇)
+///
+/// Fitted as εᵣ = 𝑎 + 𝑑/𝑇 to εᵣ = 42.5 and dεᵣ/d𝑇 = -0.180 K⁻¹ at 25 °C.
+pub const GLYCEROL: EmpiricalPermittivity =
+    EmpiricalPermittivity::new(&[-11.1670, 0.0, 0.0, 16000.82, 0.0], (273.0, 343.0));
+/// Relative permittivity of 1-propanol, εᵣ(𝑇)
+///
+/// Fitted as εᵣ = 𝑎 + 𝑑/𝑇 to εᵣ = 20.45 and dεᵣ/d𝑇 = -0.135 K⁻¹ at 25 °C.
+pub const PROPANOL: EmpiricalPermittivity =
+    EmpiricalPermittivity::new(&[-19.8003, 0.0, 0.0, 12000.61, 0.0], (273.0, 343.0));
+/// Relative permittivity of 2-propanol, εᵣ(𝑇)
+///
+/// Fitted as εᵣ = 𝑎 + 𝑑/𝑇 to εᵣ = 19.92 and dεᵣ/d𝑇 = -0.146 K⁻¹ at 25 °C.
+pub const ISOPROPANOL: EmpiricalPermittivity =
+    EmpiricalPermittivity::new(&[-23.6099, 0.0, 0.0, 12978.44, 0.0], (273.0, 343.0));
+/// Relative permittivity of formamide, εᵣ(𝑇)
+///
+/// Fitted as εᵣ = 𝑎 + 𝑑/𝑇 to εᵣ = 109.5 and dεᵣ/d𝑇 = -0.500 K⁻¹ at 25 °C.
+pub const FORMAMIDE: EmpiricalPermittivity =
+    EmpiricalPermittivity::new(&[-39.5750, 0.0, 0.0, 44446.71, 0.0], (276.0, 343.0));
+/// Relative permittivity of tetrahydrofuran (THF), εᵣ(𝑇)
+///
+/// Fitted as εᵣ = 𝑎 + 𝑑/𝑇 to εᵣ = 7.58 and dεᵣ/d𝑇 = -0.025 K⁻¹ at 25 °C.
+pub const TETRAHYDROFURAN: EmpiricalPermittivity =
+    EmpiricalPermittivity::new(&[0.1263, 0.0, 0.0, 2222.34, 0.0], (273.0, 333.0));
+
+/// Temperature independent relative permittivity, εᵣ = constant
+///
+/// # Example
+/// ~~~
+/// use coulomb::permittivity::*;
+/// let dielec = ConstantPermittivity::new(2.0);
+/// assert_eq!(dielec.permittivity(298.15).unwrap(), 2.0);
+/// assert!(dielec.temperature_is_ok(f64::INFINITY));
+///
+/// assert_eq!(VACUUM.permittivity(298.15).unwrap(), 1.0);
+/// assert_eq!(METAL.to_string(), "εᵣ = ∞");
+/// ~~~
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConstantPermittivity {
+    #[cfg_attr(feature = "serde", serde(rename = "epsr", alias = "εᵣ"))]
+    permittivity: f64,
+}
+
+impl ConstantPermittivity {
+    /// New constant permittivity
+    pub const fn new(permittivity: f64) -> Self {
+        Self { permittivity }
+    }
+}
+
+impl From<ConstantPermittivity> for f64 {
+    fn from(d: ConstantPermittivity) -> f64 {
+        d.permittivity
+    }
+}
+
+impl From<f64> for ConstantPermittivity {
+    fn from(d: f64) -> ConstantPermittivity {
+        ConstantPermittivity::new(d)
+    }
+}
+
+impl RelativePermittivity for ConstantPermittivity {
+    fn permittivity(&self, _: f64) -> Result<f64> {
+        Ok(self.permittivity)
+    }
+    fn d_permittivity_d_temperature(&self, _: f64) -> Option<f64> {
+        Some(0.0)
+    }
+    fn set_permittivity(&mut self, permittivity: f64) -> Result<()> {
+        self.permittivity = permittivity;
+        Ok(())
+    }
+}
+
+impl Display for ConstantPermittivity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "εᵣ = {}",
+            match self.permittivity.is_infinite() {
+                true => "∞".to_string(),
+                false => format!("{:.2}", self.permittivity),
+            }
+        )
+    }
+}
+
+/// Empirical model for the temperature dependent relative permittivity, εᵣ(𝑇),
+///
+/// For more information, see
+/// [Neau and Raspo](https://doi.org/10.1016/j.fluid.2019.112371).
+///
+/// # Example
+/// ~~~
+/// use coulomb::permittivity::*;
+/// assert_eq!(WATER.permittivity(298.15).unwrap(), 78.35565171480539);
+/// assert_eq!(METHANOL.permittivity(298.15).unwrap(), 33.081980713895064);
+/// assert_eq!(ETHANOL.permittivity(298.15).unwrap(), 24.33523434183735);
+/// ~~~
+///
+/// We can also pretty print the model:
+/// ~~~
+/// # use coulomb::permittivity::*;
+/// assert_eq!(WATER.to_string(),
+///            "εᵣ(𝑇) = -1.66e3 + -8.85e-1𝑇 + 3.63e-4𝑇² + 6.48e4/𝑇 + 3.08e2㏑(𝑇); 𝑇 = [273.0, 403.0]");
+/// ~~~
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EmpiricalPermittivity {
+    /// Coefficients for the model
+    coeffs: [f64; 5],
+    /// Closed temperature interval in which the model is valid
+    temperature_interval: (f64, f64),
+    /// Policy for temperatures outside the interval
+    #[cfg_attr(feature = "serde", serde(default))]
+    extrapolation: ExtrapolationPolicy,
+}
+
+/// Policy for evaluating an empirical model outside its temperature interval
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::*;
+/// assert!(WATER.permittivity(250.0).is_err());
+/// let clamped = WATER.with_extrapolation(ExtrapolationPolicy::ClampToBoundary);
+/// assert_eq!(clamped.permittivity(250.0).unwrap(), WATER.permittivity(273.0).unwrap());
+/// let linear = WATER.with_extrapolation(ExtrapolationPolicy::LinearExtrapolate);
+/// assert_relative_eq!(linear.permittivity(263.0).unwrap(), 92.5, epsilon = 0.1);
+/// ~~~
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExtrapolationPolicy {
+    /// Return [`Error::TemperatureOutOfRange`](crate::Error::TemperatureOutOfRange)
+    #[default]
+    Error,
+    /// Evaluate the model at the nearest boundary of the temperature interval
+    ClampToBoundary,
+    /// Extrapolate linearly from the nearest boundary and log a warning
+    LinearExtrapolate,
+}
+
+impl EmpiricalPermittivity {
+    /// Creates a new instance of the NR model
+    pub const fn new(coeffs: &[f64; 5], temperature_interval: (f64, f64)) -> EmpiricalPermittivity {
+        EmpiricalPermittivity {
+            coeffs: *coeffs,
+            temperature_interval,
+            extrapolation: ExtrapolationPolicy::Error,
+        }
+    }
+
+    /// Set the policy for temperatures outside the interval; default is to fail.
+    pub const fn with_extrapolation(mut self, policy: ExtrapolationPolicy) -> Self {
+        self.extrapolation = policy;
+        self
+    }
+
+    /// Policy for temperatures outside the interval
+    pub const fn extrapolation(&self) -> ExtrapolationPolicy {
+        self.extrapolation
+    }
+
+    /// Nearest boundary of the temperature interval, or `None` if inside
+    fn nearest_boundary(&self, temperature: f64) -> Option<f64> {
+        let (min, max) = self.temperature_interval;
+        if temperature < min {
+            Some(min)
+        } else if temperature > max {
+            Some(max)
+        } else {
+            None
+        }
+    }
+
+    fn evaluate(&self, temperature: f64) -> f64 {
+        self.coeffs[0]
+            + self.coeffs[1] * temperature
+            + self.coeffs[2] * temperature.powi(2)
+            + self.coeffs[3] / temperature
+            + self.coeffs[4] * temperature.ln()
+    }
+
+    fn derivative(&self, temperature: f64) -> f64 {
+        self.coeffs[1] + 2.0 * self.coeffs[2] * temperature - self.coeffs[3] / temperature.powi(2)
+            + self.coeffs[4] / temperature
+    }
+}
+
+impl RelativePermittivity for EmpiricalPermittivity {
+    fn permittivity(&self, temperature: f64) -> Result<f64> {
+        let Some(boundary) = self.nearest_boundary(temperature) else {
+            return Ok(self.evaluate(temperature));
+        };
+        match self.extrapolation {
+            ExtrapolationPolicy::Error => Err(crate::Error::TemperatureOutOfRange),
+            ExtrapolationPolicy::ClampToBoundary => Ok(self.evaluate(boundary)),
+            ExtrapolationPolicy::LinearExtrapolate => {
+                log::warn!(
+                    "extrapolating permittivity to {:.2} K outside [{:.1}, {:.1}] K",
+                    temperature,
+                    self.temperature_interval.0,
+                    self.temperature_interval.1
+                );
+                Ok(self.evaluate(boundary) + self.derivative(boundary) * (temperature - boundary))
+            }
+        }
+    }
+    fn d_permittivity_d_temperature(&self, temperature: f64) -> Option<f64> {
+        let Some(boundary) = self.nearest_boundary(temperature) else {
+            return Some(self.derivative(temperature));
+        };
+        match self.extrapolation {
+            ExtrapolationPolicy::Error => None,
+            ExtrapolationPolicy::ClampToBoundary => Some(0.0),
+            ExtrapolationPolicy::LinearExtrapolate => Some(self.derivative(boundary)),
+        }
+    }
+}
+
+impl Display for EmpiricalPermittivity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "εᵣ(𝑇) = {:.2e} + {:.2e}𝑇 + {:.2e}𝑇² + {:.2e}/𝑇 + {:.2e}㏑(𝑇); 𝑇 = [{:.1}, {:.1}]",
+            self.coeffs[0],
+            self.coeffs[1],
+            self.coeffs[2],
+            self.coeffs[3],
+            self.coeffs[4],
+            self.temperature_interval.0,
+            self.temperature_interval.1
+        )
+    }
+}
+
+#[test]
+fn test_extrapolation_policy() {
+    use approx::assert_relative_eq;
+    let (min, max) = (273.0, 403.0);
+    assert_eq!(WATER.extrapolation(), ExtrapolationPolicy::Error);
+    assert!(matches!(
+        WATER.permittivity(max + 1.0),
+        Err(crate::Error::TemperatureOutOfRange)
+    ));
+    assert!(WATER.d_permittivity_d_temperature(max + 1.0).is_none());
+
+    let clamped = WATER.with_extrapolation(ExtrapolationPolicy::ClampToBoundary);
+    assert_eq!(
+        clamped.permittivity(298.15).unwrap(),
+        WATER.permittivity(298.15).unwrap()
+    );
+    assert_eq!(
+        clamped.permittivity(500.0).unwrap(),
+        WATER.permittivity(max).unwrap()
+    );
+    assert_eq!(
+        clamped.permittivity(200.0).unwrap(),
+        WATER.permittivity(min).unwrap()
+    );
+    assert_eq!(clamped.d_permittivity_d_temperature(500.0), Some(0.0));
+    assert!(clamped.temperature_is_ok(500.0));
+
+    let linear = WATER.with_extrapolation(ExtrapolationPolicy::LinearExtrapolate);
+    let slope = WATER.d_permittivity_d_temperature(max).unwrap();
+    assert_relative_eq!(
+        linear.permittivity(max + 10.0).unwrap(),
+        WATER.permittivity(max).unwrap() + 10.0 * slope
+    );
+    assert_eq!(linear.d_permittivity_d_temperature(max + 10.0), Some(slope));
+    assert_eq!(
+        linear.d_permittivity_d_temperature(298.15),
+        WATER.d_permittivity_d_temperature(298.15)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_permittivity_serde() {
+    use serde::de::value::{
+        Error, F64Deserializer, MapDeserializer, StrDeserializer, U64Deserializer,
+    };
+    let from_str = |s| <Permittivity as Deserialize>::deserialize(StrDeserializer::<Error>::new(s));
+    assert_eq!(from_str("Water").unwrap(), Permittivity::Water);
+    assert_eq!(
+        from_str("WaterMalmbergMaryott").unwrap(),
+        Permittivity::WaterMalmbergMaryott
+    );
+    assert_eq!(from_str("metal").unwrap(), Permittivity::Metal);
+    assert_eq!(from_str("THF").unwrap(), Permittivity::Tetrahydrofuran);
+    assert_eq!(from_str("2.5").unwrap(), Permittivity::Fixed(2.5));
+    assert!(from_str("unobtainium").is_err());
+    assert_eq!(
+        <Permittivity as Deserialize>::deserialize(F64Deserializer::<Error>::new(4.0)).unwrap(),
+        Permittivity::Fixed(4.0)
+    );
+    assert_eq!(
+        <Permittivity as Deserialize>::deserialize(U64Deserializer::<Error>::new(80)).unwrap(),
+        Permittivity::Fixed(80.0)
+    );
+    // Tagged form; the trait impl is used since the derived functions are inherent
+    let map = MapDeserializer::<_, Error>::new([("Fixed", 2.0)].into_iter());
+    assert_eq!(
+        <Permittivity as Deserialize>::deserialize(map).unwrap(),
+        Permittivity::Fixed(2.0)
+    );
+}
+
+#[test]
+fn test_permittivity_enum() {
+    let temperature = 298.15;
+    for model in [
+        Permittivity::Fixed(2.0),
+        Permittivity::Empirical(WATER),
+        Permittivity::GeneralEmpirical(WATER.into()),
+        Permittivity::Mixture(
+            MixturePermittivity::binary(
+                Permittivity::Water,
+                Permittivity::Methanol,
+                0.5,
+                MixingRule::Bruggeman,
+            )
+            .unwrap(),
+        ),
+        Permittivity::Tabulated(
+            TabulatedPermittivity::new(vec![(280.0, 85.0), (300.0, 77.0), (320.0, 70.0)]).unwrap(),
+        ),
+        Permittivity::Water,
+        Permittivity::WaterMalmbergMaryott,
+        Permittivity::WaterIapws,
+        Permittivity::WaterSupercooled,
+        Permittivity::HeavyWater,
+        Permittivity::BradleyPitzer(BradleyPitzer::new(1000.0)),
+        Permittivity::Ethanol,
+        Permittivity::Methanol,
+        Permittivity::Dmso,
+        Permittivity::Acetonitrile,
+        Permittivity::Acetone,
+        Permittivity::Glycerol,
+        Permittivity::Propanol,
+        Permittivity::Isopropanol,
+        Permittivity::Formamide,
+        Permittivity::Tetrahydrofuran,
+        Permittivity::Metal,
+        Permittivity::Vacuum,
+        Permittivity::Water25,
+        Permittivity::ProteinInterior,
+        Permittivity::LipidBilayerCore,
+        Permittivity::BmimBf4,
+        Permittivity::BmimPf6,
+    ] {
+        let boxed = Box::<dyn RelativePermittivity>::from(model.clone());
+        assert_eq!(
+            model.permittivity(temperature).unwrap(),
+            boxed.permittivity(temperature).unwrap()
+        );
+    }
+    assert!(matches!(
+        Permittivity::Water.permittivity(200.0),
+        Err(crate::Error::TemperatureOutOfRange)
+    ));
+    assert!(matches!(
+        WATER.to_const_permittivity(200.0),
+        Err(crate::Error::TemperatureOutOfRange)
+    ));
+    assert!(matches!(
+        WATER.clone().set_permittivity(2.0),
+        Err(crate::Error::Unsupported(_))
+    ));
+    assert!(Permittivity::Water
+        .d_permittivity_d_temperature(200.0)
+        .is_none());
+
+    // Analytic temperature derivative
+    let h = 1e-4;
+    let derivative = (WATER.permittivity(temperature + h).unwrap()
+        - WATER.permittivity(temperature - h).unwrap())
+        / (2.0 * h);
+    approx::assert_relative_eq!(
+        Permittivity::Water
+            .d_permittivity_d_temperature(temperature)
+            .unwrap(),
+        derivative,
+        max_relative = 1e-8
+    );
+    assert_eq!(VACUUM.d_permittivity_d_temperature(temperature), Some(0.0));
+
+    // Solvent library at 25 °C
+    for (model, expected) in [
+        (HEAVY_WATER, 78.06),
+        (DMSO, 46.45),
+        (ACETONITRILE, 35.94),
+        (TETRAHYDROFURAN, 7.58),
+    ] {
+        approx::assert_relative_eq!(
+            model.permittivity(temperature).unwrap(),
+            expected,
+            epsilon = 1e-3
+        );
+    }
+    assert!(Permittivity::Acetone.permittivity(340.0).is_err());
+
+    // Every named model can be found by each of its names
+    for model in Permittivity::NAMED {
+        assert!(!model.names().is_empty());
+        for name in model.names() {
+            assert_eq!(Permittivity::from_name(name).unwrap(), model);
+            assert_eq!(name.to_uppercase().parse::<Permittivity>().unwrap(), model);
+        }
+    }
+    assert!(matches!(
+        "80 M".parse::<Permittivity>(),
+        Err(crate::Error::UnknownSolvent(_))
+    ));
+}
@@ -0,0 +1,156 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Bulk permittivity from molecular dipole moments and polarizabilities.
+//!
+//! Lengths are in angstrom and charges in units of the elementary charge, as in the
+//! [`pairwise`](crate::pairwise) and [`polarization`](crate::polarization) modules, so that
+//! number densities are in Å⁻³, polarizability volumes, α/4πε₀, in Å³, and dipole moments in eÅ
+//! (1 D ≈ 0.2082 eÅ).
+
+use crate::bjerrum_length;
+use core::f64::consts::PI;
+
+/// Squared dipole moment over thermal energy, µ²/4πε₀𝑘𝑇, UNIT: [ Å^3 ]
+fn dipole_volume(dipole_moment: f64, temperature: f64) -> f64 {
+    dipole_moment.powi(2) * bjerrum_length(temperature, 1.0)
+}
+
+/// Relative permittivity of non-polar molecules from the Clausius-Mossotti relation,
+///
+/// $$ \frac{\varepsilon_r - 1}{\varepsilon_r + 2} = \frac{4\pi}{3} \rho \alpha $$
+///
+/// where ρ is the number density and α the polarizability volume.
+/// For polarizable molecules this is the high-frequency permittivity, ε∞ = 𝑛², with 𝑛 the
+/// refractive index. Returns infinity at and beyond the polarization catastrophe, 4πρα/3 ≥ 1.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::{clausius_mossotti, clausius_mossotti_polarizability};
+/// let density = 0.0334; // water, Å⁻³
+/// let permittivity = clausius_mossotti(density, 1.47);
+/// assert_relative_eq!(permittivity, 1.78, epsilon = 0.01);
+/// assert_relative_eq!(clausius_mossotti_polarizability(density, permittivity), 1.47);
+/// ~~~
+pub fn clausius_mossotti(number_density: f64, polarizability: f64) -> f64 {
+    let y = 4.0 * PI / 3.0 * number_density * polarizability;
+    if y >= 1.0 {
+        f64::INFINITY
+    } else {
+        (1.0 + 2.0 * y) / (1.0 - y)
+    }
+}
+
+/// Polarizability volume (Å³) from the relative permittivity using the Clausius-Mossotti relation,
+/// see [`clausius_mossotti`].
+pub fn clausius_mossotti_polarizability(number_density: f64, permittivity: f64) -> f64 {
+    3.0 / (4.0 * PI * number_density) * (permittivity - 1.0) / (permittivity + 2.0)
+}
+
+/// Relative permittivity of polar molecules from the Kirkwood-Fröhlich equation,
+///
+/// $$ \frac{(\varepsilon_r - \varepsilon_\infty)(2\varepsilon_r + \varepsilon_\infty)}{\varepsilon_r (\varepsilon_\infty + 2)^2} = \frac{4 \pi \rho g \mu^2}{9 \cdot 4\pi\varepsilon_0 k T} $$
+///
+/// where ρ is the number density, µ the molecular dipole moment, and ε∞ the high-frequency
+/// permittivity from the polarizability, see [`clausius_mossotti`].
+/// The Kirkwood g-factor describes orientational correlations between neighbouring dipoles;
+/// with 𝑔 = 1 this is the Onsager equation.
+/// See Fröhlich, _Theory of Dielectrics_ (1958).
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::{kirkwood_frohlich, kirkwood_g_factor, onsager};
+/// let (density, dipole_moment, temperature) = (0.0334, 0.385, 298.15); // water, µ = 1.85 D
+/// let high_frequency = 1.78;
+/// let permittivity = onsager(density, dipole_moment, high_frequency, temperature);
+/// assert_relative_eq!(permittivity, 28.6, epsilon = 0.1);
+/// // The experimental permittivity corresponds to strong orientational correlations
+/// let g = kirkwood_g_factor(density, dipole_moment, 78.4, high_frequency, temperature);
+/// assert_relative_eq!(g, 2.7, epsilon = 0.1);
+/// assert_relative_eq!(kirkwood_frohlich(density, dipole_moment, g, high_frequency, temperature), 78.4);
+/// ~~~
+pub fn kirkwood_frohlich(
+    number_density: f64,
+    dipole_moment: f64,
+    g_factor: f64,
+    high_frequency_permittivity: f64,
+    temperature: f64,
+) -> f64 {
+    let n2 = high_frequency_permittivity;
+    let y = 4.0 * PI * number_density * g_factor * dipole_volume(dipole_moment, temperature) / 9.0
+        * (n2 + 2.0).powi(2);
+    // Positive root of 2ε² - (𝑛² + 𝑦)ε - 𝑛⁴ = 0
+    let b = n2 + y;
+    (b + (b * b + 8.0 * n2 * n2).sqrt()) / 4.0
+}
+
+/// Relative permittivity of polar molecules from the Onsager equation, see [`kirkwood_frohlich`].
+pub fn onsager(
+    number_density: f64,
+    dipole_moment: f64,
+    high_frequency_permittivity: f64,
+    temperature: f64,
+) -> f64 {
+    kirkwood_frohlich(
+        number_density,
+        dipole_moment,
+        1.0,
+        high_frequency_permittivity,
+        temperature,
+    )
+}
+
+/// Kirkwood g-factor from the static and high-frequency permittivities, see [`kirkwood_frohlich`].
+pub fn kirkwood_g_factor(
+    number_density: f64,
+    dipole_moment: f64,
+    permittivity: f64,
+    high_frequency_permittivity: f64,
+    temperature: f64,
+) -> f64 {
+    let n2 = high_frequency_permittivity;
+    9.0 * (permittivity - n2) * (2.0 * permittivity + n2)
+        / (4.0
+            * PI
+            * number_density
+            * dipole_volume(dipole_moment, temperature)
+            * permittivity
+            * (n2 + 2.0).powi(2))
+}
+
+#[test]
+fn test_molecular_permittivity() {
+    use approx::assert_relative_eq;
+    let (density, temperature) = (0.03, 300.0);
+    assert_eq!(clausius_mossotti(density, 0.0), 1.0);
+    assert_eq!(clausius_mossotti(1.0, 1.0), f64::INFINITY);
+
+    // Kirkwood-Fröhlich reduces to ε∞ without dipoles and is inverted by the g-factor
+    assert_relative_eq!(kirkwood_frohlich(density, 0.0, 1.0, 2.0, temperature), 2.0);
+    let permittivity = kirkwood_frohlich(density, 0.4, 2.5, 2.0, temperature);
+    let y = 4.0 * PI * density * 2.5 * 0.16 * bjerrum_length(temperature, 1.0) / 9.0;
+    assert_relative_eq!(
+        (permittivity - 2.0) * (2.0 * permittivity + 2.0) / (permittivity * 16.0),
+        y,
+        max_relative = 1e-12
+    );
+    assert_relative_eq!(
+        kirkwood_g_factor(density, 0.4, permittivity, 2.0, temperature),
+        2.5,
+        max_relative = 1e-12
+    );
+    assert!(onsager(density, 0.4, 2.0, temperature) < permittivity);
+}
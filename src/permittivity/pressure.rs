@@ -0,0 +1,181 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Pressure dependent permittivity of water.
+
+use super::RelativePermittivity;
+use crate::Result;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Coefficients, U₁ to U₉, of the Bradley-Pitzer model
+const U: [f64; 9] = [
+    3.4279e2, -5.0866e-3, 9.4690e-7, -2.0525, 3.1159e3, -1.8289e2, -8.0325e3, 4.2142e6, 2.1417,
+];
+
+/// Relative permittivity of water as a function of temperature and pressure, εᵣ(𝑇, 𝑝),
+///
+/// $$ \varepsilon_r = \varepsilon_{1000} + C \ln \left ( \frac{B + p}{B + 1000} \right ) $$
+///
+/// where $\varepsilon_{1000} = U_1 \exp(U_2 T + U_3 T^2)$ is the permittivity at 1000 bar,
+/// $C = U_4 + U_5 / (U_6 + T)$, and $B = U_7 + U_8 / T + U_9 T$.
+/// The model is valid for liquid water between 0 and 350 °C and pressures up to 5 kbar.
+/// See Bradley and Pitzer, <https://doi.org/10.1021/j100481a009>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::{BradleyPitzer, RelativePermittivity};
+/// let ambient = BradleyPitzer::new(1.0);
+/// assert_relative_eq!(ambient.permittivity(298.15).unwrap(), 78.38, epsilon = 0.01);
+/// let hydrothermal = BradleyPitzer::new(1000.0);
+/// assert_relative_eq!(hydrothermal.permittivity(573.15).unwrap(), 25.35, epsilon = 0.01);
+/// assert!(hydrothermal.permittivity(700.0).is_err());
+/// ~~~
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct BradleyPitzer {
+    /// Pressure, UNIT: [ bar ]
+    pressure: f64,
+}
+
+impl BradleyPitzer {
+    /// Closed temperature interval in which the model is valid, UNIT: [ K ]
+    pub const TEMPERATURE_INTERVAL: (f64, f64) = (273.15, 623.15);
+    /// Closed pressure interval in which the model is valid, UNIT: [ bar ]
+    pub const PRESSURE_INTERVAL: (f64, f64) = (0.0, 5000.0);
+
+    /// New model at a given pressure in bar
+    pub const fn new(pressure: f64) -> Self {
+        Self { pressure }
+    }
+
+    /// Pressure, UNIT: [ bar ]
+    pub const fn pressure(&self) -> f64 {
+        self.pressure
+    }
+
+    /// Set pressure in bar
+    pub fn set_pressure(&mut self, pressure: f64) {
+        self.pressure = pressure;
+    }
+
+    /// Check that temperature and pressure are within the range of the model
+    fn check_range(&self, temperature: f64) -> Result<()> {
+        let (t_min, t_max) = Self::TEMPERATURE_INTERVAL;
+        let (p_min, p_max) = Self::PRESSURE_INTERVAL;
+        if !(t_min..=t_max).contains(&temperature) {
+            Err(crate::Error::TemperatureOutOfRange)
+        } else if !(p_min..=p_max).contains(&self.pressure) {
+            Err(crate::Error::PressureOutOfRange)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Pressure derivative, dεᵣ/d𝑝, UNIT: [ 1 / bar ]
+    ///
+    /// # Errors
+    /// Returns an error if temperature or pressure is out of range.
+    pub fn d_permittivity_d_pressure(&self, temperature: f64) -> Result<f64> {
+        self.check_range(temperature)?;
+        let c = U[3] + U[4] / (U[5] + temperature);
+        let b = U[6] + U[7] / temperature + U[8] * temperature;
+        Ok(c / (b + self.pressure))
+    }
+}
+
+impl RelativePermittivity for BradleyPitzer {
+    fn permittivity(&self, temperature: f64) -> Result<f64> {
+        self.check_range(temperature)?;
+        let permittivity_1000 = U[0] * f64::exp(U[1] * temperature + U[2] * temperature.powi(2));
+        let c = U[3] + U[4] / (U[5] + temperature);
+        let b = U[6] + U[7] / temperature + U[8] * temperature;
+        Ok(permittivity_1000 + c * f64::ln((b + self.pressure) / (b + 1000.0)))
+    }
+    fn d_permittivity_d_temperature(&self, temperature: f64) -> Option<f64> {
+        self.check_range(temperature).ok()?;
+        let permittivity_1000 = U[0] * f64::exp(U[1] * temperature + U[2] * temperature.powi(2));
+        let c = U[3] + U[4] / (U[5] + temperature);
+        let b = U[6] + U[7] / temperature + U[8] * temperature;
+        let dc = -U[4] / (U[5] + temperature).powi(2);
+        let db = -U[7] / temperature.powi(2) + U[8];
+        Some(
+            permittivity_1000 * (U[1] + 2.0 * U[2] * temperature)
+                + dc * f64::ln((b + self.pressure) / (b + 1000.0))
+                + c * (db / (b + self.pressure) - db / (b + 1000.0)),
+        )
+    }
+}
+
+impl Display for BradleyPitzer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "εᵣ(𝑇, 𝑝 = {:.1} bar); Bradley-Pitzer water",
+            self.pressure
+        )
+    }
+}
+
+#[test]
+fn test_bradley_pitzer() {
+    use approx::assert_relative_eq;
+    // Agrees with the NR model at ambient pressure
+    let model = BradleyPitzer::new(1.01325);
+    for temperature in [280.0, 298.15, 350.0] {
+        assert_relative_eq!(
+            model.permittivity(temperature).unwrap(),
+            super::WATER.permittivity(temperature).unwrap(),
+            max_relative = 0.005
+        );
+    }
+    assert_relative_eq!(model.permittivity(373.15).unwrap(), 55.46, epsilon = 0.01);
+
+    // Analytic derivatives
+    let (temperature, h) = (400.0, 1e-4);
+    let derivative = (model.permittivity(temperature + h).unwrap()
+        - model.permittivity(temperature - h).unwrap())
+        / (2.0 * h);
+    assert_relative_eq!(
+        model.d_permittivity_d_temperature(temperature).unwrap(),
+        derivative,
+        max_relative = 1e-6
+    );
+    let mut compressed = BradleyPitzer::new(2000.0 + h);
+    let upper = compressed.permittivity(temperature).unwrap();
+    compressed.set_pressure(2000.0 - h);
+    let derivative = (upper - compressed.permittivity(temperature).unwrap()) / (2.0 * h);
+    compressed.set_pressure(2000.0);
+    assert_relative_eq!(
+        compressed.d_permittivity_d_pressure(temperature).unwrap(),
+        derivative,
+        max_relative = 1e-6
+    );
+    assert!(compressed.d_permittivity_d_pressure(temperature).unwrap() > 0.0);
+
+    assert!(matches!(
+        BradleyPitzer::new(6000.0).permittivity(298.15),
+        Err(crate::Error::PressureOutOfRange)
+    ));
+    assert_eq!(
+        compressed.to_string(),
+        "εᵣ(𝑇, 𝑝 = 2000.0 bar); Bradley-Pitzer water"
+    );
+}
@@ -0,0 +1,152 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Frequency dependent permittivity from Debye relaxation.
+
+use super::RelativePermittivity;
+use crate::Result;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Frequency dependent relative permittivity with one or more Debye relaxations,
+///
+/// $$ \varepsilon_r(\omega) = \varepsilon_\infty + \sum_j \frac{\Delta\varepsilon_j}{1 + i \omega \tau_j} = \varepsilon' - i \varepsilon'' $$
+///
+/// where $\varepsilon_\infty$ is the high-frequency permittivity, $\Delta\varepsilon_j$ the
+/// strength and $\tau_j$ the relaxation time of each process, and $\omega$ the angular frequency.
+/// The static permittivity is $\varepsilon_s = \varepsilon_\infty + \sum_j \Delta\varepsilon_j$
+/// which is returned by [`RelativePermittivity::permittivity`] for any temperature.
+/// The complex permittivity is available through [`ComplexPermittivity`](super::ComplexPermittivity).
+/// See Debye, _Polar Molecules_ (1929).
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::{ComplexPermittivity, DebyeRelaxation, RelativePermittivity};
+/// let water = DebyeRelaxation::water_25c();
+/// assert_relative_eq!(water.permittivity(298.15).unwrap(), 78.36);
+/// // Microwave oven frequency, 2.45 GHz
+/// let omega = 2.0 * std::f64::consts::PI * 2.45e9;
+/// let epsilon = water.complex_permittivity(omega);
+/// assert_relative_eq!(epsilon.re, 77.2, epsilon = 0.1);
+/// assert_relative_eq!(-epsilon.im, 9.2, epsilon = 0.1);
+/// ~~~
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct DebyeRelaxation {
+    /// High-frequency relative permittivity, ε∞
+    high_frequency_permittivity: f64,
+    /// Strength, Δεⱼ, and relaxation time, τⱼ, of each process, UNIT: [ s ]
+    relaxations: Vec<(f64, f64)>,
+}
+
+impl DebyeRelaxation {
+    /// New model with high-frequency permittivity, ε∞, and relaxation processes as
+    /// (Δεⱼ, τⱼ) pairs with relaxation times in seconds.
+    pub const fn new(high_frequency_permittivity: f64, relaxations: Vec<(f64, f64)>) -> Self {
+        Self {
+            high_frequency_permittivity,
+            relaxations,
+        }
+    }
+
+    /// Single relaxation between the static, εₛ, and high-frequency, ε∞, permittivities with
+    /// relaxation time in seconds.
+    pub fn single(
+        static_permittivity: f64,
+        high_frequency_permittivity: f64,
+        relaxation_time: f64,
+    ) -> Self {
+        Self::new(
+            high_frequency_permittivity,
+            vec![(
+                static_permittivity - high_frequency_permittivity,
+                relaxation_time,
+            )],
+        )
+    }
+
+    /// Water at 25 °C with εₛ = 78.36, ε∞ = 5.2, and τ = 8.27 ps.
+    ///
+    /// See Kaatze, <https://doi.org/10.1021/je00058a001>.
+    pub fn water_25c() -> Self {
+        Self::single(78.36, 5.2, 8.27e-12)
+    }
+
+    /// Static relative permittivity, εₛ = ε∞ + ∑Δεⱼ
+    pub fn static_permittivity(&self) -> f64 {
+        self.high_frequency_permittivity
+            + self
+                .relaxations
+                .iter()
+                .map(|(strength, _)| strength)
+                .sum::<f64>()
+    }
+
+    /// High-frequency relative permittivity, ε∞
+    pub const fn high_frequency_permittivity(&self) -> f64 {
+        self.high_frequency_permittivity
+    }
+
+    /// Strength, Δεⱼ, and relaxation time, τⱼ (s), of each process
+    pub fn relaxations(&self) -> &[(f64, f64)] {
+        &self.relaxations
+    }
+}
+
+impl RelativePermittivity for DebyeRelaxation {
+    fn permittivity(&self, _: f64) -> Result<f64> {
+        Ok(self.static_permittivity())
+    }
+}
+
+impl Display for DebyeRelaxation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "εᵣ(ω) = {:.2}", self.high_frequency_permittivity)?;
+        for (strength, time) in &self.relaxations {
+            write!(f, " + {:.2}/(1 + iω·{:.2e} s)", strength, time)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_debye_relaxation() {
+    use super::ComplexPermittivity;
+    use approx::assert_relative_eq;
+    use num_complex::Complex64;
+    let model = DebyeRelaxation::new(2.0, vec![(70.0, 1e-11), (6.0, 1e-12)]);
+    assert_eq!(model.static_permittivity(), 78.0);
+    assert_eq!(model.complex_permittivity(0.0), Complex64::from(78.0));
+    assert_relative_eq!(model.complex_permittivity(1e20).re, 2.0, epsilon = 1e-6);
+
+    // Loss peak of Δε/2 at ωτ = 1
+    let single = DebyeRelaxation::single(80.0, 5.0, 1e-11);
+    let epsilon = single.complex_permittivity(1e11);
+    assert_relative_eq!(epsilon.re, 5.0 + 75.0 / 2.0);
+    assert_relative_eq!(epsilon.im, -75.0 / 2.0);
+    let h = 1e-3;
+    let loss = |omega: f64| -single.complex_permittivity(omega).im;
+    assert!(loss(1e11) > loss(1e11 * (1.0 + h)) && loss(1e11) > loss(1e11 * (1.0 - h)));
+
+    assert_eq!(
+        single.to_string(),
+        "εᵣ(ω) = 5.00 + 75.00/(1 + iω·1.00e-11 s)"
+    );
+}
@@ -0,0 +1,235 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Permittivity interpolated from tabulated data.
+
+use super::RelativePermittivity;
+use crate::Result;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Relative permittivity, εᵣ(𝑇), interpolated from tabulated (𝑇, εᵣ) data
+///
+/// The interpolation is a monotone, piecewise cubic Hermite spline where slopes are chosen
+/// to avoid overshooting between data points, see
+/// Fritsch and Carlson, <https://doi.org/10.1137/0717021>.
+/// Temperatures outside the tabulated range are rejected.
+/// With serde, the model is (de)serialized as a list of (𝑇, εᵣ) pairs.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::{RelativePermittivity, TabulatedPermittivity};
+/// let data = vec![(273.15, 87.9), (298.15, 78.4), (323.15, 69.9), (348.15, 62.3)];
+/// let model = TabulatedPermittivity::new(data).unwrap();
+/// assert_relative_eq!(model.permittivity(298.15).unwrap(), 78.4);
+/// assert!(model.permittivity(310.0).unwrap() < 78.4);
+/// assert!(model.permittivity(373.15).is_err());
+/// ~~~
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "Vec<(f64, f64)>", into = "Vec<(f64, f64)>")
+)]
+pub struct TabulatedPermittivity {
+    /// Strictly increasing temperatures, UNIT: [ K ]
+    temperatures: Vec<f64>,
+    /// Relative permittivities at each temperature
+    permittivities: Vec<f64>,
+    /// Slopes, dεᵣ/d𝑇, at each temperature, UNIT: [ 1 / K ]
+    slopes: Vec<f64>,
+}
+
+impl TabulatedPermittivity {
+    /// New model from (𝑇, εᵣ) pairs in any order.
+    ///
+    /// # Errors
+    /// Returns an error if there are fewer than two points, if temperatures are
+    /// duplicated, or if any value is not finite.
+    pub fn new(mut data: Vec<(f64, f64)>) -> Result<Self> {
+        if data.len() < 2 {
+            return Err(crate::Error::Spline(
+                "at least two data points are required",
+            ));
+        }
+        if data.iter().any(|(t, e)| !t.is_finite() || !e.is_finite()) {
+            return Err(crate::Error::Spline("data must be finite"));
+        }
+        data.sort_by(|a, b| a.0.total_cmp(&b.0));
+        if data.windows(2).any(|w| w[0].0 == w[1].0) {
+            return Err(crate::Error::Spline("temperatures must be unique"));
+        }
+        let (temperatures, permittivities): (Vec<f64>, Vec<f64>) = data.into_iter().unzip();
+        let slopes = monotone_slopes(&temperatures, &permittivities);
+        Ok(Self {
+            temperatures,
+            permittivities,
+            slopes,
+        })
+    }
+
+    /// Closed temperature interval covered by the data, UNIT: [ K ]
+    pub fn temperature_interval(&self) -> (f64, f64) {
+        (
+            self.temperatures[0],
+            self.temperatures[self.temperatures.len() - 1],
+        )
+    }
+
+    /// Index of the interval containing the temperature, or error if out of range
+    fn interval(&self, temperature: f64) -> Result<usize> {
+        let (min, max) = self.temperature_interval();
+        if !(min..=max).contains(&temperature) {
+            return Err(crate::Error::TemperatureOutOfRange);
+        }
+        let i = self.temperatures.partition_point(|t| *t <= temperature);
+        Ok(i.clamp(1, self.temperatures.len() - 1) - 1)
+    }
+}
+
+/// Fritsch-Carlson slopes preserving monotonicity of the data
+fn monotone_slopes(x: &[f64], y: &[f64]) -> Vec<f64> {
+    let h: Vec<f64> = x.windows(2).map(|w| w[1] - w[0]).collect();
+    let delta: Vec<f64> = y
+        .windows(2)
+        .zip(&h)
+        .map(|(w, h)| (w[1] - w[0]) / h)
+        .collect();
+    let n = x.len();
+    if n == 2 {
+        return vec![delta[0]; 2];
+    }
+    // Three-point estimate at the end points, limited to preserve shape
+    let end_slope = |h0: f64, h1: f64, d0: f64, d1: f64| {
+        let slope = ((2.0 * h0 + h1) * d0 - h0 * d1) / (h0 + h1);
+        if slope.signum() != d0.signum() {
+            0.0
+        } else if d0.signum() != d1.signum() && slope.abs() > 3.0 * d0.abs() {
+            3.0 * d0
+        } else {
+            slope
+        }
+    };
+    let mut slopes = vec![0.0; n];
+    slopes[0] = end_slope(h[0], h[1], delta[0], delta[1]);
+    slopes[n - 1] = end_slope(h[n - 2], h[n - 3], delta[n - 2], delta[n - 3]);
+    for k in 1..n - 1 {
+        if delta[k - 1] * delta[k] > 0.0 {
+            let w1 = 2.0 * h[k] + h[k - 1];
+            let w2 = h[k] + 2.0 * h[k - 1];
+            slopes[k] = (w1 + w2) / (w1 / delta[k - 1] + w2 / delta[k]);
+        }
+    }
+    slopes
+}
+
+impl RelativePermittivity for TabulatedPermittivity {
+    fn permittivity(&self, temperature: f64) -> Result<f64> {
+        let i = self.interval(temperature)?;
+        let h = self.temperatures[i + 1] - self.temperatures[i];
+        let t = (temperature - self.temperatures[i]) / h;
+        Ok(
+            (2.0 * t.powi(3) - 3.0 * t.powi(2) + 1.0) * self.permittivities[i]
+                + (t.powi(3) - 2.0 * t.powi(2) + t) * h * self.slopes[i]
+                + (-2.0 * t.powi(3) + 3.0 * t.powi(2)) * self.permittivities[i + 1]
+                + (t.powi(3) - t.powi(2)) * h * self.slopes[i + 1],
+        )
+    }
+    fn d_permittivity_d_temperature(&self, temperature: f64) -> Option<f64> {
+        let i = self.interval(temperature).ok()?;
+        let h = self.temperatures[i + 1] - self.temperatures[i];
+        let t = (temperature - self.temperatures[i]) / h;
+        Some(
+            (6.0 * t.powi(2) - 6.0 * t) * (self.permittivities[i] - self.permittivities[i + 1]) / h
+                + (3.0 * t.powi(2) - 4.0 * t + 1.0) * self.slopes[i]
+                + (3.0 * t.powi(2) - 2.0 * t) * self.slopes[i + 1],
+        )
+    }
+}
+
+impl TryFrom<Vec<(f64, f64)>> for TabulatedPermittivity {
+    type Error = crate::Error;
+    fn try_from(data: Vec<(f64, f64)>) -> Result<Self> {
+        Self::new(data)
+    }
+}
+
+impl From<TabulatedPermittivity> for Vec<(f64, f64)> {
+    fn from(model: TabulatedPermittivity) -> Self {
+        model
+            .temperatures
+            .into_iter()
+            .zip(model.permittivities)
+            .collect()
+    }
+}
+
+impl Display for TabulatedPermittivity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (min, max) = self.temperature_interval();
+        write!(
+            f,
+            "εᵣ(𝑇) tabulated at {} points; 𝑇 = [{:.1}, {:.1}]",
+            self.temperatures.len(),
+            min,
+            max
+        )
+    }
+}
+
+#[test]
+fn test_tabulated_permittivity() {
+    use approx::assert_relative_eq;
+    // Reproduces tabulated points and is monotone in between
+    let data = vec![(350.0, 62.0), (300.0, 78.0), (310.0, 74.0), (320.0, 72.0)];
+    let model = TabulatedPermittivity::new(data.clone()).unwrap();
+    for (temperature, permittivity) in &data {
+        assert_relative_eq!(model.permittivity(*temperature).unwrap(), permittivity);
+    }
+    let values: Vec<f64> = (0..=500)
+        .map(|i| model.permittivity(300.0 + 0.1 * i as f64).unwrap())
+        .collect();
+    assert!(values.windows(2).all(|w| w[1] <= w[0]));
+    assert_eq!(model.temperature_interval(), (300.0, 350.0));
+    assert!(matches!(
+        model.permittivity(299.0),
+        Err(crate::Error::TemperatureOutOfRange)
+    ));
+    assert!(model.d_permittivity_d_temperature(351.0).is_none());
+
+    // Analytic derivative
+    let (temperature, h) = (314.0, 1e-5);
+    let derivative = (model.permittivity(temperature + h).unwrap()
+        - model.permittivity(temperature - h).unwrap())
+        / (2.0 * h);
+    assert_relative_eq!(
+        model.d_permittivity_d_temperature(temperature).unwrap(),
+        derivative,
+        max_relative = 1e-6
+    );
+
+    // Two points give linear interpolation
+    let linear = TabulatedPermittivity::new(vec![(300.0, 80.0), (320.0, 70.0)]).unwrap();
+    assert_relative_eq!(linear.permittivity(305.0).unwrap(), 77.5);
+
+    assert!(TabulatedPermittivity::new(vec![(300.0, 80.0)]).is_err());
+    assert!(TabulatedPermittivity::new(vec![(300.0, 80.0), (300.0, 70.0)]).is_err());
+    assert!(TabulatedPermittivity::new(vec![(300.0, 80.0), (f64::NAN, 70.0)]).is_err());
+    assert_eq!(
+        model.to_string(),
+        "εᵣ(𝑇) tabulated at 4 points; 𝑇 = [300.0, 350.0]"
+    );
+}
@@ -0,0 +1,88 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Physical units for the `permittivity` module using the `uom` crate.
+
+use super::{ConstantPermittivity, RelativePermittivity};
+use crate::units::{kelvin, per_kelvin, TemperatureCoefficient, ThermodynamicTemperature};
+use crate::Result;
+
+impl<T: RelativePermittivity + ?Sized> RelativePermittivitySI for T {}
+
+/// Relative permittivity with compile time units for the temperature.
+///
+/// # Examples
+/// ~~~
+/// use coulomb::permittivity::{RelativePermittivity, RelativePermittivitySI, WATER};
+/// use coulomb::units::{degree_celsius, kelvin, ThermodynamicTemperature};
+/// let room = ThermodynamicTemperature::new::<degree_celsius>(25.0);
+/// assert_eq!(room.get::<kelvin>(), 298.15);
+/// assert_eq!(WATER.permittivity_at(room).unwrap(), WATER.permittivity(298.15).unwrap());
+/// ~~~
+pub trait RelativePermittivitySI: RelativePermittivity {
+    /// Relative permittivity or error if temperature is out of range
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is out of range, see [`RelativePermittivity::permittivity`].
+    fn permittivity_at(&self, temperature: ThermodynamicTemperature) -> Result<f64> {
+        self.permittivity(temperature.get::<kelvin>())
+    }
+    /// Test if temperature is within range
+    fn temperature_is_ok_at(&self, temperature: ThermodynamicTemperature) -> bool {
+        self.temperature_is_ok(temperature.get::<kelvin>())
+    }
+    /// Convert to constant permittivity for a given temperature
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is out of range, see [`RelativePermittivity::permittivity`].
+    fn to_const_permittivity_at(
+        &self,
+        temperature: ThermodynamicTemperature,
+    ) -> Result<ConstantPermittivity> {
+        self.to_const_permittivity(temperature.get::<kelvin>())
+    }
+    /// Temperature derivative, dεᵣ/d𝑇, or `None` if unavailable or if temperature is out of range
+    fn d_permittivity_d_temperature_at(
+        &self,
+        temperature: ThermodynamicTemperature,
+    ) -> Option<TemperatureCoefficient> {
+        self.d_permittivity_d_temperature(temperature.get::<kelvin>())
+            .map(TemperatureCoefficient::new::<per_kelvin>)
+    }
+}
+
+#[test]
+fn test_permittivity_si() {
+    use super::{Permittivity, WATER};
+    use crate::units::degree_celsius;
+    use approx::assert_relative_eq;
+    let temperature = ThermodynamicTemperature::new::<degree_celsius>(25.0);
+    assert_relative_eq!(
+        WATER.permittivity_at(temperature).unwrap(),
+        WATER.permittivity(298.15).unwrap()
+    );
+    assert_relative_eq!(
+        Permittivity::Water
+            .d_permittivity_d_temperature_at(temperature)
+            .unwrap()
+            .get::<per_kelvin>(),
+        WATER.d_permittivity_d_temperature(298.15).unwrap()
+    );
+    // 25 K is not 25 °C
+    let cold = ThermodynamicTemperature::new::<kelvin>(25.0);
+    assert!(!WATER.temperature_is_ok_at(cold));
+    assert!(WATER.to_const_permittivity_at(cold).is_err());
+    let boxed: Box<dyn RelativePermittivity> = Permittivity::Vacuum.into();
+    assert_eq!(boxed.permittivity_at(temperature).unwrap(), 1.0);
+}
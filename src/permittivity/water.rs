@@ -0,0 +1,397 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Alternative formulations of the static permittivity of water and ice.
+
+use super::RelativePermittivity;
+use crate::Result;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Relative permittivity of water from Malmberg and Maryott, εᵣ(𝑇)
+///
+/// $$ \varepsilon_r = 87.740 - 0.40008 t + 9.398 \cdot 10^{-4} t^2 - 1.410 \cdot 10^{-6} t^3 $$
+///
+/// where 𝑡 is the temperature in degrees Celsius, valid from 0 to 100 °C.
+/// See <https://doi.org/10.6028/jres.056.001>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::{RelativePermittivity, WaterMalmbergMaryott};
+/// assert_relative_eq!(WaterMalmbergMaryott.permittivity(298.15).unwrap(), 78.30, epsilon = 0.01);
+/// ~~~
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WaterMalmbergMaryott;
+
+impl WaterMalmbergMaryott {
+    /// Polynomial coefficients in degrees Celsius
+    const COEFFS: [f64; 4] = [87.740, -0.40008, 9.398e-4, -1.410e-6];
+    /// Closed temperature interval in which the model is valid, UNIT: [ K ]
+    pub const TEMPERATURE_INTERVAL: (f64, f64) = (273.15, 373.15);
+
+    /// Temperature in degrees Celsius or error if out of range
+    fn celsius(temperature: f64) -> Result<f64> {
+        let (min, max) = Self::TEMPERATURE_INTERVAL;
+        if !(min..=max).contains(&temperature) {
+            return Err(crate::Error::TemperatureOutOfRange);
+        }
+        Ok(temperature - 273.15)
+    }
+}
+
+impl RelativePermittivity for WaterMalmbergMaryott {
+    fn permittivity(&self, temperature: f64) -> Result<f64> {
+        let t = Self::celsius(temperature)?;
+        let [a, b, c, d] = Self::COEFFS;
+        Ok(a + b * t + c * t.powi(2) + d * t.powi(3))
+    }
+    fn d_permittivity_d_temperature(&self, temperature: f64) -> Option<f64> {
+        let t = Self::celsius(temperature).ok()?;
+        let [_, b, c, d] = Self::COEFFS;
+        Some(b + 2.0 * c * t + 3.0 * d * t.powi(2))
+    }
+}
+
+impl Display for WaterMalmbergMaryott {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "εᵣ(𝑇); Malmberg-Maryott water")
+    }
+}
+
+/// Critical temperature of water, UNIT: [ K ]
+const CRITICAL_TEMPERATURE: f64 = 647.096;
+/// Critical density of water, UNIT: [ kg / m^3 ]
+const CRITICAL_DENSITY: f64 = 322.0;
+
+/// Relative permittivity of water from the IAPWS formulation by Fernández et al., εᵣ(𝑇, ρ)
+///
+/// The permittivity is a function of temperature and density, evaluated from the
+/// Harris-Alder g-factor fitted to experimental data from 238 to 873 K and up to 1200 MPa.
+/// As a [`RelativePermittivity`], the density of saturated liquid water is used
+/// which is close to that at atmospheric pressure below the boiling point.
+/// See IAPWS R8-97 and Fernández et al., <https://doi.org/10.1063/1.555997>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::{RelativePermittivity, WaterIapws};
+/// assert_relative_eq!(WaterIapws.permittivity(298.15).unwrap(), 78.40, epsilon = 0.01);
+/// // Compressed, supercritical water
+/// assert_relative_eq!(WaterIapws::permittivity_at_density(873.15, 600.0).unwrap(), 9.53, epsilon = 0.01);
+/// ~~~
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WaterIapws;
+
+impl WaterIapws {
+    /// Coefficients, 𝑁ₖ, and exponents, 𝑖ₖ and 𝑗ₖ, of the g-factor
+    const G_FACTOR: [(f64, i32, f64); 11] = [
+        (0.978224486826, 1, 0.25),
+        (-0.957771379375, 1, 1.0),
+        (0.237511794148, 1, 2.5),
+        (0.714692244396, 2, 1.5),
+        (-0.298217036956, 3, 1.5),
+        (-0.108863472196, 3, 2.5),
+        (0.949327488264e-1, 4, 2.0),
+        (-0.980469816509e-2, 5, 2.0),
+        (0.165167634970e-4, 6, 5.0),
+        (0.937359795772e-4, 7, 0.5),
+        (-0.123179218720e-9, 10, 10.0),
+    ];
+    /// Closed temperature interval of the saturated liquid in which the model is used, UNIT: [ K ]
+    pub const TEMPERATURE_INTERVAL: (f64, f64) = (273.16, 623.15);
+
+    /// Relative permittivity at a given temperature (K) and density (kg/m³)
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is outside 238–1273 K or if the density is not positive.
+    pub fn permittivity_at_density(temperature: f64, density: f64) -> Result<f64> {
+        // Constants used in the formulation
+        const AVOGADRO: f64 = 6.0221367e23;
+        const BOLTZMANN: f64 = 1.380658e-23;
+        const VACUUM_PERMITTIVITY: f64 = 8.854187817e-12;
+        const DIPOLE_MOMENT: f64 = 6.138e-30;
+        const POLARIZABILITY: f64 = 1.636e-40;
+        const MOLAR_MASS: f64 = 0.018015268;
+        if !(238.0..=1273.0).contains(&temperature) {
+            return Err(crate::Error::TemperatureOutOfRange);
+        }
+        if density <= 0.0 || !density.is_finite() {
+            return Err(crate::Error::Unsupported("non-positive density"));
+        }
+        let delta = density / CRITICAL_DENSITY;
+        let tau = CRITICAL_TEMPERATURE / temperature;
+        let g = 1.0
+            + Self::G_FACTOR
+                .iter()
+                .map(|(n, i, j)| n * delta.powi(*i) * tau.powf(*j))
+                .sum::<f64>()
+            + 0.196096504426e-2 * delta * (temperature / 228.0 - 1.0).powf(-1.2);
+        let a = AVOGADRO * DIPOLE_MOMENT.powi(2) * density * g
+            / (MOLAR_MASS * VACUUM_PERMITTIVITY * BOLTZMANN * temperature);
+        let b = AVOGADRO * POLARIZABILITY * density / (3.0 * MOLAR_MASS * VACUUM_PERMITTIVITY);
+        let root = (9.0 + 2.0 * a + 18.0 * b + a * a + 10.0 * a * b + 9.0 * b * b).sqrt();
+        Ok((1.0 + a + 5.0 * b + root) / (4.0 - 4.0 * b))
+    }
+
+    /// Density of saturated liquid water, UNIT: [ kg / m^3 ]
+    ///
+    /// See Wagner and Pruss, <https://doi.org/10.1063/1.555926>.
+    pub fn saturated_liquid_density(temperature: f64) -> f64 {
+        const TERMS: [(f64, f64); 6] = [
+            (1.99274064, 1.0 / 3.0),
+            (1.09965342, 2.0 / 3.0),
+            (-0.510839303, 5.0 / 3.0),
+            (-1.75493479, 16.0 / 3.0),
+            (-45.5170352, 43.0 / 3.0),
+            (-6.74694450e5, 110.0 / 3.0),
+        ];
+        let tau = 1.0 - temperature / CRITICAL_TEMPERATURE;
+        CRITICAL_DENSITY
+            * (1.0
+                + TERMS
+                    .iter()
+                    .map(|(b, exponent)| b * tau.powf(*exponent))
+                    .sum::<f64>())
+    }
+}
+
+impl RelativePermittivity for WaterIapws {
+    fn permittivity(&self, temperature: f64) -> Result<f64> {
+        let (min, max) = Self::TEMPERATURE_INTERVAL;
+        if !(min..=max).contains(&temperature) {
+            return Err(crate::Error::TemperatureOutOfRange);
+        }
+        Self::permittivity_at_density(temperature, Self::saturated_liquid_density(temperature))
+    }
+    /// Numerical derivative along the saturation curve
+    fn d_permittivity_d_temperature(&self, temperature: f64) -> Option<f64> {
+        const STEP: f64 = 1e-3;
+        self.permittivity(temperature).ok()?;
+        let (min, max) = Self::TEMPERATURE_INTERVAL;
+        let (low, high) = ((temperature - STEP).max(min), (temperature + STEP).min(max));
+        Some((self.permittivity(high).ok()? - self.permittivity(low).ok()?) / (high - low))
+    }
+}
+
+impl Display for WaterIapws {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "εᵣ(𝑇); IAPWS water at saturated liquid density")
+    }
+}
+
+/// Relative permittivity of ice Ih, εᵣ(𝑇)
+///
+/// Curie-Weiss form, εᵣ = ε∞ + 𝐴/(𝑇 - 𝑇₀), with ε∞ = 3.1, 𝐴 = 20715 K, and 𝑇₀ = 38 K, valid from
+/// 200 K to the melting point.
+/// See Auty and Cole, _J. Chem. Phys._ 20, 1309 (1952), and Johari and Whalley,
+/// _J. Chem. Phys._ 75, 1333 (1981).
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::{IceIh, RelativePermittivity};
+/// assert_relative_eq!(IceIh.permittivity(273.15).unwrap(), 91.2, epsilon = 0.1);
+/// assert!(IceIh.permittivity(250.0).unwrap() > IceIh.permittivity(270.0).unwrap());
+/// ~~~
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IceIh;
+
+impl IceIh {
+    /// High-frequency permittivity, ε∞
+    const HIGH_FREQUENCY_PERMITTIVITY: f64 = 3.1;
+    /// Curie constant, 𝐴, UNIT: [ K ]
+    const CURIE_CONSTANT: f64 = 20715.0;
+    /// Curie-Weiss temperature, 𝑇₀, UNIT: [ K ]
+    const CURIE_TEMPERATURE: f64 = 38.0;
+    /// Closed temperature interval in which the model is valid, UNIT: [ K ]
+    pub const TEMPERATURE_INTERVAL: (f64, f64) = (200.0, 273.15);
+
+    /// Temperature relative to the Curie-Weiss temperature or error if out of range
+    fn reduced_temperature(temperature: f64) -> Result<f64> {
+        let (min, max) = Self::TEMPERATURE_INTERVAL;
+        if !(min..=max).contains(&temperature) {
+            return Err(crate::Error::TemperatureOutOfRange);
+        }
+        Ok(temperature - Self::CURIE_TEMPERATURE)
+    }
+}
+
+impl RelativePermittivity for IceIh {
+    fn permittivity(&self, temperature: f64) -> Result<f64> {
+        let t = Self::reduced_temperature(temperature)?;
+        Ok(Self::HIGH_FREQUENCY_PERMITTIVITY + Self::CURIE_CONSTANT / t)
+    }
+    fn d_permittivity_d_temperature(&self, temperature: f64) -> Option<f64> {
+        let t = Self::reduced_temperature(temperature).ok()?;
+        Some(-Self::CURIE_CONSTANT / t.powi(2))
+    }
+}
+
+impl Display for IceIh {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "εᵣ(𝑇) = {:.1} + {:.0}/(𝑇 - {:.0}); ice Ih",
+            Self::HIGH_FREQUENCY_PERMITTIVITY,
+            Self::CURIE_CONSTANT,
+            Self::CURIE_TEMPERATURE
+        )
+    }
+}
+
+/// Relative permittivity of liquid water including the supercooled region, εᵣ(𝑇)
+///
+/// Above the melting point, this is the [`WATER`](super::WATER) model. Below, down to 238 K,
+/// ln εᵣ is extrapolated linearly so that the permittivity and its derivative are continuous
+/// at 273.15 K, consistent with the smooth behaviour measured by Hodge and Angell,
+/// _J. Chem. Phys._ 68, 1363 (1978).
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::permittivity::{RelativePermittivity, WaterSupercooled, WATER};
+/// assert_eq!(WaterSupercooled.permittivity(298.15).unwrap(), WATER.permittivity(298.15).unwrap());
+/// assert_relative_eq!(WaterSupercooled.permittivity(253.15).unwrap(), 97.1, epsilon = 0.1);
+/// ~~~
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WaterSupercooled;
+
+impl WaterSupercooled {
+    /// Melting point below which the permittivity is extrapolated, UNIT: [ K ]
+    const MELTING_POINT: f64 = 273.15;
+    /// Closed temperature interval in which the model is valid, UNIT: [ K ]
+    pub const TEMPERATURE_INTERVAL: (f64, f64) = (238.0, 403.0);
+
+    /// Permittivity and logarithmic slope, dln(εᵣ)/d𝑇, at the melting point
+    fn melting_point() -> (f64, f64) {
+        let permittivity = super::WATER.permittivity(Self::MELTING_POINT).unwrap();
+        let derivative = super::WATER
+            .d_permittivity_d_temperature(Self::MELTING_POINT)
+            .unwrap();
+        (permittivity, derivative / permittivity)
+    }
+}
+
+impl RelativePermittivity for WaterSupercooled {
+    fn permittivity(&self, temperature: f64) -> Result<f64> {
+        if temperature >= Self::MELTING_POINT {
+            return super::WATER.permittivity(temperature);
+        }
+        if temperature < Self::TEMPERATURE_INTERVAL.0 {
+            return Err(crate::Error::TemperatureOutOfRange);
+        }
+        let (permittivity, slope) = Self::melting_point();
+        Ok(permittivity * f64::exp(slope * (temperature - Self::MELTING_POINT)))
+    }
+    fn d_permittivity_d_temperature(&self, temperature: f64) -> Option<f64> {
+        if temperature >= Self::MELTING_POINT {
+            return super::WATER.d_permittivity_d_temperature(temperature);
+        }
+        let (_, slope) = Self::melting_point();
+        Some(slope * self.permittivity(temperature).ok()?)
+    }
+}
+
+impl Display for WaterSupercooled {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "εᵣ(𝑇); water extrapolated to supercooled temperatures")
+    }
+}
+
+#[test]
+fn test_low_temperature_water() {
+    use approx::assert_relative_eq;
+    // Continuous value and slope at the melting point
+    let h = 1e-6;
+    let melting = WaterSupercooled::MELTING_POINT;
+    assert_relative_eq!(
+        WaterSupercooled.permittivity(melting - h).unwrap(),
+        WaterSupercooled.permittivity(melting + h).unwrap(),
+        max_relative = 1e-7
+    );
+    assert_relative_eq!(
+        WaterSupercooled
+            .d_permittivity_d_temperature(melting - h)
+            .unwrap(),
+        WaterSupercooled
+            .d_permittivity_d_temperature(melting + h)
+            .unwrap(),
+        max_relative = 1e-5
+    );
+    assert!(WaterSupercooled.permittivity(237.0).is_err());
+    assert!(super::WATER.permittivity(250.0).is_err());
+
+    // Ice
+    let (temperature, h) = (250.0, 1e-4);
+    let derivative = (IceIh.permittivity(temperature + h).unwrap()
+        - IceIh.permittivity(temperature - h).unwrap())
+        / (2.0 * h);
+    assert_relative_eq!(
+        IceIh.d_permittivity_d_temperature(temperature).unwrap(),
+        derivative,
+        max_relative = 1e-6
+    );
+    assert!(IceIh.permittivity(280.0).is_err());
+    assert_eq!(IceIh.to_string(), "εᵣ(𝑇) = 3.1 + 20715/(𝑇 - 38); ice Ih");
+}
+
+#[test]
+fn test_water_formulations() {
+    use approx::assert_relative_eq;
+    // All formulations agree at ambient conditions
+    for temperature in [274.0, 298.15, 323.15, 373.15] {
+        let reference = super::WATER.permittivity(temperature).unwrap();
+        for permittivity in [
+            WaterMalmbergMaryott.permittivity(temperature).unwrap(),
+            WaterIapws.permittivity(temperature).unwrap(),
+        ] {
+            assert_relative_eq!(permittivity, reference, max_relative = 0.005);
+        }
+    }
+    assert_relative_eq!(
+        WaterIapws::saturated_liquid_density(373.15),
+        958.35,
+        epsilon = 0.01
+    );
+    assert_relative_eq!(
+        WaterIapws.permittivity(373.15).unwrap(),
+        55.53,
+        epsilon = 0.01
+    );
+
+    // Temperature derivatives
+    let (temperature, h) = (310.0, 1e-4);
+    for model in [
+        &WaterMalmbergMaryott as &dyn RelativePermittivity,
+        &WaterIapws,
+    ] {
+        let derivative = (model.permittivity(temperature + h).unwrap()
+            - model.permittivity(temperature - h).unwrap())
+            / (2.0 * h);
+        assert_relative_eq!(
+            model.d_permittivity_d_temperature(temperature).unwrap(),
+            derivative,
+            max_relative = 1e-5
+        );
+    }
+    assert!(WaterMalmbergMaryott.permittivity(380.0).is_err());
+    assert!(WaterIapws.permittivity(650.0).is_err());
+    assert!(WaterIapws::permittivity_at_density(298.15, 0.0).is_err());
+}
@@ -0,0 +1,203 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Ionic atmosphere around a central ion.
+
+use super::CellModel;
+use crate::{DebyeLength, Medium, Result};
+use core::f64::consts::PI;
+
+/// Ionic atmosphere around a central ion of valency $z$ and radius $a$.
+///
+/// In Debye-Hückel theory, the reduced potential outside the ion is
+///
+/// $$ \phi(r) = \frac{z \lambda_B e^{\kappa a}}{1 + \kappa a} \frac{e^{-\kappa r}}{r} $$
+///
+/// with the charge density, $\rho(r) = -\kappa^2 \phi(r) / 4 \pi \lambda_B$, and the charge within $r$,
+///
+/// $$ Q(r) = -z \left ( 1 - \frac{1 + \kappa r}{1 + \kappa a} e^{-\kappa (r - a)} \right ) $$
+///
+/// which neutralizes the ion as $r \to \infty$.
+/// The nonlinear Poisson-Boltzmann profile for a 1:1 salt is available through
+/// [`IonicAtmosphere::nonlinear_profile`].
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::{poisson_boltzmann::IonicAtmosphere, Medium, Salt};
+/// let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+/// let atmosphere = IonicAtmosphere::from_medium(1.0, 2.0, &medium).unwrap();
+/// assert_relative_eq!(atmosphere.integrated_charge(1e3), -1.0);
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IonicAtmosphere {
+    /// Valency of the central ion
+    pub valency: f64,
+    /// Distance of closest approach, UNIT: [ angstrom ]
+    pub radius: f64,
+    /// Bjerrum length, UNIT: [ angstrom ]
+    pub bjerrum_length: f64,
+    /// Inverse Debye length, UNIT: [ angstrom^-1 ]
+    pub kappa: f64,
+}
+
+/// Radial profile of the ionic atmosphere.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RadialProfile {
+    /// Distance from the central ion, UNIT: [ angstrom ]
+    pub radii: Vec<f64>,
+    /// Reduced potential
+    pub potential: Vec<f64>,
+    /// Charge density, UNIT: [ e / angstrom^3 ]
+    pub charge_density: Vec<f64>,
+    /// Charge of the atmosphere within each radius, UNIT: [ e ]
+    pub integrated_charge: Vec<f64>,
+}
+
+impl IonicAtmosphere {
+    /// Number of Debye lengths beyond the ion included in the nonlinear profile
+    const RANGE: f64 = 20.0;
+
+    /// New atmosphere with lengths in angstrom.
+    pub const fn new(valency: f64, radius: f64, bjerrum_length: f64, kappa: f64) -> Self {
+        Self {
+            valency,
+            radius,
+            bjerrum_length,
+            kappa,
+        }
+    }
+
+    /// New atmosphere with the Bjerrum and Debye lengths of a medium.
+    ///
+    /// # Errors
+    /// Returns an error if the medium contains no salt.
+    pub fn from_medium(valency: f64, radius: f64, medium: &Medium) -> Result<Self> {
+        let kappa = medium
+            .kappa()
+            .ok_or(crate::Error::Unsupported("ionic atmosphere without salt"))?;
+        Ok(Self::new(valency, radius, medium.bjerrum_length(), kappa))
+    }
+
+    /// Reduced Debye-Hückel potential at distance `r` ≥ a from the ion center
+    pub fn potential(&self, r: f64) -> f64 {
+        self.valency * self.bjerrum_length * (-self.kappa * (r - self.radius)).exp()
+            / ((1.0 + self.kappa * self.radius) * r)
+    }
+
+    /// Debye-Hückel charge density at distance `r` ≥ a, UNIT: [ e / angstrom^3 ]
+    pub fn charge_density(&self, r: f64) -> f64 {
+        -self.kappa * self.kappa * self.potential(r) / (4.0 * PI * self.bjerrum_length)
+    }
+
+    /// Debye-Hückel charge of the atmosphere within distance `r` ≥ a, UNIT: [ e ]
+    pub fn integrated_charge(&self, r: f64) -> f64 {
+        let ka = self.kappa * self.radius;
+        -self.valency
+            * (1.0 - (1.0 + self.kappa * r) / (1.0 + ka) * (-self.kappa * (r - self.radius)).exp())
+    }
+
+    /// Debye-Hückel profile at the given distances from the ion center
+    pub fn profile(&self, radii: &[f64]) -> RadialProfile {
+        RadialProfile {
+            radii: radii.to_vec(),
+            potential: radii.iter().map(|r| self.potential(*r)).collect(),
+            charge_density: radii.iter().map(|r| self.charge_density(*r)).collect(),
+            integrated_charge: radii.iter().map(|r| self.integrated_charge(*r)).collect(),
+        }
+    }
+
+    /// Nonlinear Poisson-Boltzmann profile in a 1:1 salt, where $\rho(r) = -\kappa^2 \sinh \phi(r) / 4 \pi \lambda_B$.
+    ///
+    /// The profile extends 20 Debye lengths beyond the ion, which is treated as the center of a
+    /// large [`CellModel`].
+    ///
+    /// # Errors
+    /// Returns an error if the Poisson-Boltzmann equation cannot be solved.
+    pub fn nonlinear_profile(&self) -> Result<RadialProfile> {
+        let cell = CellModel::new(
+            self.radius,
+            self.radius + Self::RANGE / self.kappa,
+            self.bjerrum_length,
+            self.kappa,
+        );
+        let (radii, potential): (Vec<f64>, Vec<f64>) =
+            cell.potential_profile(self.valency)?.into_iter().unzip();
+        let charge_density: Vec<f64> = potential
+            .iter()
+            .map(|phi| -self.kappa * self.kappa * phi.sinh() / (4.0 * PI * self.bjerrum_length))
+            .collect();
+        // Trapezoidal integration of 4πr²ρ(r)
+        let shell = |i: usize| 4.0 * PI * radii[i] * radii[i] * charge_density[i];
+        let integrated_charge = core::iter::once(0.0)
+            .chain((1..radii.len()).scan(0.0, |charge, i| {
+                *charge += 0.5 * (shell(i - 1) + shell(i)) * (radii[i] - radii[i - 1]);
+                Some(*charge)
+            }))
+            .collect();
+        Ok(RadialProfile {
+            radii,
+            potential,
+            charge_density,
+            integrated_charge,
+        })
+    }
+}
+
+#[test]
+fn test_ionic_atmosphere() {
+    use approx::assert_relative_eq;
+    let medium = Medium::salt_water(298.15, crate::Salt::SodiumChloride, 0.05);
+    let atmosphere = IonicAtmosphere::from_medium(-2.0, 3.0, &medium).unwrap();
+    assert!(IonicAtmosphere::from_medium(1.0, 3.0, &Medium::neat_water(298.15)).is_err());
+
+    // Gauss' law at contact, φ'(a) = -zλ𝐵/a²
+    let (a, h) = (3.0, 1e-6);
+    assert_relative_eq!(
+        (atmosphere.potential(a + h) - atmosphere.potential(a)) / h,
+        2.0 * atmosphere.bjerrum_length / (a * a),
+        max_relative = 1e-5
+    );
+    // Integrated charge from the density
+    let (r, dr) = (12.0, 1e-5);
+    assert_relative_eq!(
+        (atmosphere.integrated_charge(r + dr) - atmosphere.integrated_charge(r - dr)) / (2.0 * dr),
+        4.0 * PI * r * r * atmosphere.charge_density(r),
+        max_relative = 1e-6
+    );
+    assert_eq!(atmosphere.integrated_charge(a), 0.0);
+
+    // Nonlinear profile approaches Debye-Hückel for weak coupling and neutralizes the ion
+    let weak = IonicAtmosphere::new(0.01, 3.0, 7.0, 0.1);
+    let profile = weak.nonlinear_profile().unwrap();
+    assert_relative_eq!(
+        profile.potential[0],
+        weak.potential(3.0),
+        max_relative = 1e-4
+    );
+    assert_relative_eq!(
+        *profile.integrated_charge.last().unwrap(),
+        -0.01,
+        max_relative = 1e-4
+    );
+    let strong = IonicAtmosphere::new(-3.0, 3.0, 7.0, 0.1);
+    let profile = strong.nonlinear_profile().unwrap();
+    assert!(profile.potential[0] > -strong.potential(3.0).abs());
+    assert_relative_eq!(
+        *profile.integrated_charge.last().unwrap(),
+        3.0,
+        max_relative = 1e-3
+    );
+}
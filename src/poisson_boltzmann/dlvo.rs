@@ -0,0 +1,136 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! DLVO interaction between charged colloidal spheres.
+
+use super::geometry::Electrolyte;
+use crate::{Medium, Result};
+
+/// DLVO interaction between two charged spheres in an electrolyte.
+///
+/// The interaction is the sum of a screened-Coulomb repulsion,
+///
+/// $$ \beta u_{el}(R) = Z_1 Z_2 \lambda_B \frac{e^{\kappa a_1}}{1 + \kappa a_1} \frac{e^{\kappa a_2}}{1 + \kappa a_2} \frac{e^{-\kappa R}}{R} $$
+///
+/// where the prefactors account for the exclusion of salt from the sphere interiors,
+/// and the van der Waals attraction between two homogeneous spheres,
+///
+/// $$ \beta u_{vdW}(R) = -\frac{A}{6} \left [ \frac{2 a_1 a_2}{R^2 - (a_1 + a_2)^2} + \frac{2 a_1 a_2}{R^2 - (a_1 - a_2)^2} + \ln \frac{R^2 - (a_1 + a_2)^2}{R^2 - (a_1 - a_2)^2} \right ] $$
+///
+/// where $A$ is the Hamaker constant in units of $k_B T$ and $R$ the center-to-center distance.
+/// See Verwey and Overbeek, _Theory of the Stability of Lyophobic Colloids_ (1948), and
+/// Hamaker, <https://doi.org/10.1016/S0031-8914(37)80203-7>.
+///
+/// # Examples
+/// ~~~
+/// use coulomb::{poisson_boltzmann::Dlvo, Medium, Salt};
+/// let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.01);
+/// let dlvo = Dlvo::from_medium(&medium, 2.0).unwrap();
+/// // Repulsive barrier at intermediate separations; attractive near contact
+/// assert!(dlvo.energy(50.0, 100.0, 50.0, 100.0, 230.0) > 0.0);
+/// assert!(dlvo.energy(50.0, 100.0, 50.0, 100.0, 200.5) < 0.0);
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dlvo {
+    /// Electrolyte screening the electrostatic repulsion
+    pub electrolyte: Electrolyte,
+    /// Hamaker constant, UNIT: [ kT ]
+    pub hamaker_constant: f64,
+}
+
+impl Dlvo {
+    /// New DLVO interaction with a Hamaker constant in units of kT.
+    pub const fn new(electrolyte: Electrolyte, hamaker_constant: f64) -> Self {
+        Self {
+            electrolyte,
+            hamaker_constant,
+        }
+    }
+
+    /// New DLVO interaction with the Bjerrum and Debye lengths of a medium.
+    ///
+    /// # Errors
+    /// Returns an error if the medium contains no salt.
+    pub fn from_medium(medium: &Medium, hamaker_constant: f64) -> Result<Self> {
+        Ok(Self::new(
+            Electrolyte::from_medium(medium)?,
+            hamaker_constant,
+        ))
+    }
+
+    /// Screened-Coulomb repulsion at center-to-center `distance`, UNIT: [ kT ]
+    pub fn electrostatic_energy(
+        &self,
+        charge1: f64,
+        radius1: f64,
+        charge2: f64,
+        radius2: f64,
+        distance: f64,
+    ) -> f64 {
+        self.electrolyte
+            .sphere_sphere_energy(charge1, radius1, charge2, radius2, distance)
+    }
+
+    /// Van der Waals attraction at center-to-center `distance` > a₁ + a₂, UNIT: [ kT ]
+    pub fn van_der_waals_energy(&self, radius1: f64, radius2: f64, distance: f64) -> f64 {
+        let r2 = distance * distance;
+        let outer = r2 - (radius1 + radius2).powi(2);
+        let inner = r2 - (radius1 - radius2).powi(2);
+        let product = 2.0 * radius1 * radius2;
+        -self.hamaker_constant / 6.0 * (product / outer + product / inner + (outer / inner).ln())
+    }
+
+    /// Total DLVO interaction at center-to-center `distance`, UNIT: [ kT ]
+    pub fn energy(
+        &self,
+        charge1: f64,
+        radius1: f64,
+        charge2: f64,
+        radius2: f64,
+        distance: f64,
+    ) -> f64 {
+        self.electrostatic_energy(charge1, radius1, charge2, radius2, distance)
+            + self.van_der_waals_energy(radius1, radius2, distance)
+    }
+}
+
+#[test]
+fn test_dlvo() {
+    use approx::assert_relative_eq;
+    let dlvo = Dlvo::new(Electrolyte::new(7.0, 0.05), 3.0);
+    let (a1, a2) = (20.0, 35.0);
+
+    // Far apart, the spheres attract as point particles, -16A a₁³a₂³ / 9R⁶
+    let r: f64 = 1e4;
+    assert_relative_eq!(
+        dlvo.van_der_waals_energy(a1, a2, r),
+        -16.0 * 3.0 * a1.powi(3) * a2.powi(3) / (9.0 * r.powi(6)),
+        max_relative = 1e-4
+    );
+    // Near contact, the Derjaguin approximation gives -A a₁a₂ / 6h(a₁ + a₂)
+    let h = 1e-3;
+    assert_relative_eq!(
+        dlvo.van_der_waals_energy(a1, a2, a1 + a2 + h),
+        -3.0 * a1 * a2 / (6.0 * h * (a1 + a2)),
+        max_relative = 1e-3
+    );
+    assert_relative_eq!(
+        dlvo.energy(10.0, a1, -5.0, a2, 80.0),
+        dlvo.electrostatic_energy(10.0, a1, -5.0, a2, 80.0)
+            + dlvo.van_der_waals_energy(a1, a2, 80.0)
+    );
+    assert!(dlvo.electrostatic_energy(10.0, a1, -5.0, a2, 80.0) < 0.0);
+    assert!(Dlvo::from_medium(&Medium::neat_water(298.15), 1.0).is_err());
+}
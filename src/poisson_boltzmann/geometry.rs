@@ -0,0 +1,308 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Linearized Poisson-Boltzmann solutions for spheres, cylinders, and planes.
+//!
+//! In the linearized Poisson-Boltzmann (Debye-Hückel) equation, $\nabla^2 \phi = \kappa^2 \phi$,
+//! the reduced potential outside charged bodies in an electrolyte has closed forms for simple
+//! geometries.
+//! Energies are in units of $k_B T$ and per unit length or area for cylinders and planes.
+//!
+//! | Geometry  | Charge                        | Potential outside the body, $\phi$                            |
+//! |-----------|-------------------------------|---------------------------------------------------------------|
+//! | Sphere    | $Z$                           | $Z \lambda_B e^{-\kappa (r - a)} / (1 + \kappa a) r$           |
+//! | Cylinder  | $\xi$ per length              | $2 \lambda_B \xi K_0(\kappa r) / \kappa a K_1(\kappa a)$       |
+//! | Plane     | $\sigma$ per area             | $4 \pi \lambda_B \sigma e^{-\kappa x} / \kappa$                |
+//!
+//! where $a$ is the radius and $K_\nu$ are modified Bessel functions of the second kind.
+//!
+//! # Examples
+//! ~~~
+//! # use approx::assert_relative_eq;
+//! use coulomb::poisson_boltzmann::geometry::Electrolyte;
+//! let electrolyte = Electrolyte::new(7.0, 0.1);
+//! let energy = electrolyte.sphere_sphere_energy(10.0, 20.0, 10.0, 20.0, 50.0);
+//! assert!(energy > 0.0);
+//! ~~~
+
+use crate::math::bessel_k;
+use crate::{DebyeLength, Medium, Result};
+use core::f64::consts::PI;
+
+/// Boundary condition at surfaces of interacting bodies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Boundary {
+    /// Surface potentials are fixed
+    #[default]
+    ConstantPotential,
+    /// Surface charges are fixed
+    ConstantCharge,
+}
+
+/// Electrolyte described by Bjerrum and Debye lengths.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Electrolyte {
+    /// Bjerrum length, UNIT: [ angstrom ]
+    pub bjerrum_length: f64,
+    /// Inverse Debye length, UNIT: [ angstrom^-1 ]
+    pub kappa: f64,
+}
+
+impl Electrolyte {
+    /// New electrolyte with lengths in angstrom.
+    pub const fn new(bjerrum_length: f64, kappa: f64) -> Self {
+        Self {
+            bjerrum_length,
+            kappa,
+        }
+    }
+
+    /// New electrolyte with the Bjerrum and Debye lengths of a medium.
+    ///
+    /// # Errors
+    /// Returns an error if the medium contains no salt.
+    pub fn from_medium(medium: &Medium) -> Result<Self> {
+        let kappa = medium
+            .kappa()
+            .ok_or(crate::Error::Unsupported("electrolyte without salt"))?;
+        Ok(Self::new(medium.bjerrum_length(), kappa))
+    }
+
+    /// Excluded-volume factor, e^{κa}/(1 + κa), of a sphere
+    fn sphere_factor(&self, radius: f64) -> f64 {
+        (self.kappa * radius).exp() / (1.0 + self.kappa * radius)
+    }
+
+    /// Reduced potential at distance `r` ≥ a from the center of a sphere with charge number `charge`
+    pub fn sphere_potential(&self, charge: f64, radius: f64, r: f64) -> f64 {
+        charge * self.bjerrum_length * self.sphere_factor(radius) * (-self.kappa * r).exp() / r
+    }
+
+    /// Reduced surface potential of a sphere with charge number `charge`
+    pub fn sphere_surface_potential(&self, charge: f64, radius: f64) -> f64 {
+        self.sphere_potential(charge, radius, radius)
+    }
+
+    /// Interaction energy of two spheres at center-to-center `distance`, UNIT: [ kT ]
+    ///
+    /// Each sphere is a point charge, scaled by its excluded-volume factor, in the potential of the
+    /// other,
+    ///
+    /// $$ \beta u(R) = Z_1 Z_2 \lambda_B \frac{e^{\kappa a_1}}{1 + \kappa a_1} \frac{e^{\kappa a_2}}{1 + \kappa a_2} \frac{e^{-\kappa R}}{R} $$
+    ///
+    /// which neglects the exclusion of each sphere's ionic atmosphere by the other sphere and is
+    /// accurate for separations beyond a few Debye lengths.
+    pub fn sphere_sphere_energy(
+        &self,
+        charge1: f64,
+        radius1: f64,
+        charge2: f64,
+        radius2: f64,
+        distance: f64,
+    ) -> f64 {
+        charge1 * self.sphere_factor(radius1) * self.sphere_potential(charge2, radius2, distance)
+    }
+
+    /// Interaction energy of two spheres with reduced surface potentials at surface-to-surface
+    /// `separation` in the Derjaguin approximation, UNIT: [ kT ]
+    ///
+    /// With $a = a_1 a_2 / (a_1 + a_2)$ and $t = e^{-\kappa h}$, the Hogg-Healy-Fuerstenau
+    /// expression is
+    ///
+    /// $$ \beta u(h) = \frac{a}{4 \lambda_B} \left [ 2 \phi_1 \phi_2 \ln \frac{1 + t}{1 - t} \pm (\phi_1^2 + \phi_2^2) \ln (1 - t^2) \right ] $$
+    ///
+    /// with the upper sign for constant potential and the lower for constant charge, where the
+    /// potentials are those of the isolated spheres.
+    /// Valid for $\kappa a \gg 1$ and $h \ll a$.
+    /// See Hogg _et al._, <https://doi.org/10.1039/TF9666201638>.
+    pub fn sphere_sphere_energy_derjaguin(
+        &self,
+        potential1: f64,
+        radius1: f64,
+        potential2: f64,
+        radius2: f64,
+        separation: f64,
+        boundary: Boundary,
+    ) -> f64 {
+        let radius = radius1 * radius2 / (radius1 + radius2);
+        let t = (-self.kappa * separation).exp();
+        let sign = match boundary {
+            Boundary::ConstantPotential => 1.0,
+            Boundary::ConstantCharge => -1.0,
+        };
+        radius / (4.0 * self.bjerrum_length)
+            * (2.0 * potential1 * potential2 * ((1.0 + t) / (1.0 - t)).ln()
+                + sign * (potential1.powi(2) + potential2.powi(2)) * (1.0 - t * t).ln())
+    }
+
+    /// Reduced potential at distance `r` ≥ a from the axis of a cylinder with `line_charge`, UNIT: [ e / angstrom ]
+    pub fn cylinder_potential(&self, line_charge: f64, radius: f64, r: f64) -> f64 {
+        2.0 * self.bjerrum_length * line_charge * bessel_k(0.0, self.kappa * r)
+            / (self.kappa * radius * bessel_k(1.0, self.kappa * radius))
+    }
+
+    /// Interaction energy per length of two parallel cylinders at axis-to-axis `distance`,
+    /// UNIT: [ kT / angstrom ]
+    ///
+    /// Given by $\beta u(R) = 2 \lambda_B \xi_1 \xi_2 K_0(\kappa R) / \kappa a_1 K_1(\kappa a_1) \kappa a_2 K_1(\kappa a_2)$
+    /// in the same approximation as [`Electrolyte::sphere_sphere_energy`].
+    pub fn cylinder_cylinder_energy(
+        &self,
+        line_charge1: f64,
+        radius1: f64,
+        line_charge2: f64,
+        radius2: f64,
+        distance: f64,
+    ) -> f64 {
+        let ka = self.kappa * radius1;
+        line_charge1 * self.cylinder_potential(line_charge2, radius2, distance)
+            / (ka * bessel_k(1.0, ka))
+    }
+
+    /// Reduced potential at distance `x` from a plane with `charge_density`, UNIT: [ e / angstrom^2 ]
+    pub fn plane_potential(&self, charge_density: f64, x: f64) -> f64 {
+        4.0 * PI * self.bjerrum_length * charge_density * (-self.kappa * x).exp() / self.kappa
+    }
+
+    /// Interaction energy per area of two planes with reduced surface potentials at `separation`,
+    /// UNIT: [ kT / angstrom^2 ]
+    ///
+    /// $$ \beta w(h) = \frac{\kappa}{8 \pi \lambda_B} \left [ 2 \phi_1 \phi_2 \operatorname{csch} \kappa h \mp (\phi_1^2 + \phi_2^2) (\coth \kappa h - 1) \right ] $$
+    ///
+    /// with the upper sign for constant potential and the lower for constant charge, where the
+    /// potentials are those of the isolated planes.
+    pub fn plane_plane_energy(
+        &self,
+        potential1: f64,
+        potential2: f64,
+        separation: f64,
+        boundary: Boundary,
+    ) -> f64 {
+        let kh = self.kappa * separation;
+        let sign = match boundary {
+            Boundary::ConstantPotential => -1.0,
+            Boundary::ConstantCharge => 1.0,
+        };
+        self.kappa / (8.0 * PI * self.bjerrum_length)
+            * (2.0 * potential1 * potential2 / kh.sinh()
+                + sign * (potential1.powi(2) + potential2.powi(2)) * (kh.tanh().recip() - 1.0))
+    }
+}
+
+#[test]
+fn test_geometry() {
+    use approx::assert_relative_eq;
+    let electrolyte = Electrolyte::new(7.0, 0.1);
+    let bjerrum_length = electrolyte.bjerrum_length;
+    let h = 1e-6;
+
+    // Gauss' law at the surface of each body
+    let (a, charge) = (10.0, -4.0);
+    let slope = |f: &dyn Fn(f64) -> f64, x: f64| (f(x + h) - f(x - h)) / (2.0 * h);
+    assert_relative_eq!(
+        -slope(&|r| electrolyte.sphere_potential(charge, a, r), a),
+        charge * bjerrum_length / (a * a),
+        max_relative = 1e-6
+    );
+    assert_relative_eq!(
+        -slope(&|r| electrolyte.cylinder_potential(charge, a, r), a),
+        2.0 * charge * bjerrum_length / a,
+        max_relative = 1e-6
+    );
+    assert_relative_eq!(
+        -slope(&|x| electrolyte.plane_potential(charge, x), 0.0),
+        4.0 * PI * charge * bjerrum_length,
+        max_relative = 1e-6
+    );
+
+    // Screened Coulomb between spheres reduces to Coulomb's law without salt
+    let coulomb = Electrolyte::new(bjerrum_length, 1e-9);
+    assert_relative_eq!(
+        coulomb.sphere_sphere_energy(2.0, 5.0, -3.0, 8.0, 20.0),
+        -6.0 * bjerrum_length / 20.0,
+        max_relative = 1e-6
+    );
+    assert_relative_eq!(
+        electrolyte.sphere_sphere_energy(2.0, 5.0, -3.0, 8.0, 20.0),
+        electrolyte.sphere_sphere_energy(-3.0, 8.0, 2.0, 5.0, 20.0)
+    );
+
+    // Derjaguin approaches superposition at large κa and weak overlap
+    let (a, charge, separation) = (500.0, 500.0, 40.0);
+    let phi = electrolyte.sphere_surface_potential(charge, a);
+    let derjaguin = electrolyte.sphere_sphere_energy_derjaguin(
+        phi,
+        a,
+        phi,
+        a,
+        separation,
+        Boundary::ConstantPotential,
+    );
+    assert_relative_eq!(
+        derjaguin,
+        electrolyte.sphere_sphere_energy(charge, a, charge, a, 2.0 * a + separation),
+        max_relative = 0.05
+    );
+    let constant_charge = electrolyte.sphere_sphere_energy_derjaguin(
+        phi,
+        a,
+        phi,
+        a,
+        separation,
+        Boundary::ConstantCharge,
+    );
+    assert!(constant_charge > derjaguin);
+
+    // Derjaguin sphere energy is the integral of the plane energy
+    let separation = 5.0;
+    let plane = |x: f64| electrolyte.plane_plane_energy(1.0, -0.5, x, Boundary::ConstantCharge);
+    let sphere = |x: f64| {
+        electrolyte.sphere_sphere_energy_derjaguin(
+            1.0,
+            30.0,
+            -0.5,
+            60.0,
+            x,
+            Boundary::ConstantCharge,
+        )
+    };
+    assert_relative_eq!(
+        -slope(&sphere, separation),
+        2.0 * PI * 20.0 * plane(separation),
+        max_relative = 1e-6
+    );
+    assert_relative_eq!(
+        electrolyte.plane_plane_energy(1.0, 1.0, 200.0, Boundary::ConstantPotential),
+        electrolyte.kappa / (2.0 * PI * bjerrum_length) * (-20.0f64).exp(),
+        max_relative = 1e-6
+    );
+
+    // Parallel cylinders without salt interact logarithmically, u(R₁) - u(R₂) = 2λ𝐵ξ₁ξ₂ ln(R₂/R₁)
+    let weak = Electrolyte::new(bjerrum_length, 1e-7);
+    assert_relative_eq!(
+        weak.cylinder_cylinder_energy(0.5, 3.0, 0.2, 4.0, 10.0)
+            - weak.cylinder_cylinder_energy(0.5, 3.0, 0.2, 4.0, 20.0),
+        2.0 * bjerrum_length * 0.1 * 2.0f64.ln(),
+        max_relative = 1e-5
+    );
+
+    let medium = Medium::salt_water(298.15, crate::Salt::SodiumChloride, 0.1);
+    assert_eq!(
+        Electrolyte::from_medium(&medium).unwrap().kappa,
+        medium.kappa().unwrap()
+    );
+    assert!(Electrolyte::from_medium(&Medium::neat_water(298.15)).is_err());
+}
@@ -0,0 +1,210 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Gouy-Chapman theory for a charged plane.
+
+use crate::{
+    DebyeLength, Medium, Result, AVOGADRO_CONSTANT, BOLTZMANN_CONSTANT, ELEMENTARY_CHARGE,
+    LITER_PER_ANGSTROM3,
+};
+use core::f64::consts::PI;
+
+/// Diffuse double layer at a charged plane in a symmetric z:z electrolyte.
+///
+/// The nonlinear Poisson-Boltzmann equation has the analytic solution
+///
+/// $$ \tanh \frac{z \phi(x)}{4} = \tanh \frac{z \phi_0}{4} e^{-\kappa x} $$
+///
+/// where $\phi_0$ is the reduced surface potential and $x$ the distance from the plane.
+/// Cations and anions have concentrations $c_0 e^{\mp z \phi(x)}$ where $c_0$ is the bulk salt
+/// concentration.
+/// The surface charge density follows from Grahame's equation,
+/// $\sigma = \kappa \sinh(z \phi_0 / 2) / 2 \pi z \lambda_B$,
+/// and the differential capacitance of the diffuse layer is
+/// $C = \varepsilon_0 \varepsilon_r \kappa \cosh(z \phi_0 / 2)$.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::{poisson_boltzmann::GouyChapman, Medium, Salt};
+/// let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+/// let plane = GouyChapman::from_medium(2.0, &medium).unwrap();
+/// assert_relative_eq!(plane.potential(0.0), 2.0);
+/// assert!(plane.potential(10.0) < 2.0);
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GouyChapman {
+    /// Reduced surface potential, φ₀ = eψ₀/k𝐵T
+    pub surface_potential: f64,
+    /// Valency, z, of the symmetric salt
+    pub valency: f64,
+    /// Bjerrum length, UNIT: [ angstrom ]
+    pub bjerrum_length: f64,
+    /// Inverse Debye length, UNIT: [ angstrom^-1 ]
+    pub kappa: f64,
+}
+
+impl GouyChapman {
+    /// New double layer with given reduced surface potential and lengths in angstrom.
+    pub const fn new(
+        surface_potential: f64,
+        valency: f64,
+        bjerrum_length: f64,
+        kappa: f64,
+    ) -> Self {
+        Self {
+            surface_potential,
+            valency,
+            bjerrum_length,
+            kappa,
+        }
+    }
+
+    /// New double layer from a surface charge density, UNIT: [ e / angstrom^2 ]
+    pub fn from_surface_charge(
+        charge_density: f64,
+        valency: f64,
+        bjerrum_length: f64,
+        kappa: f64,
+    ) -> Self {
+        let surface_potential =
+            2.0 / valency * (2.0 * PI * valency * bjerrum_length * charge_density / kappa).asinh();
+        Self::new(surface_potential, valency, bjerrum_length, kappa)
+    }
+
+    /// New double layer in a medium with a symmetric salt.
+    ///
+    /// # Errors
+    /// Returns an error if the medium contains no salt or if the salt is asymmetric.
+    pub fn from_medium(surface_potential: f64, medium: &Medium) -> Result<Self> {
+        let valency = symmetric_valency(medium)?;
+        let kappa = medium
+            .kappa()
+            .ok_or(crate::Error::Unsupported("double layer without salt"))?;
+        Ok(Self::new(
+            surface_potential,
+            valency,
+            medium.bjerrum_length(),
+            kappa,
+        ))
+    }
+
+    /// Reduced potential at distance `x` from the plane
+    pub fn potential(&self, x: f64) -> f64 {
+        let z = self.valency;
+        4.0 / z * ((z * self.surface_potential / 4.0).tanh() * (-self.kappa * x).exp()).atanh()
+    }
+
+    /// Electric field, -dφ/dx, at distance `x` from the plane, UNIT: [ angstrom^-1 ]
+    pub fn field(&self, x: f64) -> f64 {
+        2.0 * self.kappa / self.valency * (0.5 * self.valency * self.potential(x)).sinh()
+    }
+
+    /// Bulk salt concentration, c₀, UNIT: [ mol / l ]
+    pub fn bulk_concentration(&self) -> f64 {
+        self.kappa * self.kappa
+            / (8.0
+                * PI
+                * self.bjerrum_length
+                * self.valency
+                * self.valency
+                * AVOGADRO_CONSTANT
+                * LITER_PER_ANGSTROM3)
+    }
+
+    /// Cation and anion concentrations at distance `x` from the plane, UNIT: [ mol / l ]
+    pub fn concentrations(&self, x: f64) -> (f64, f64) {
+        let boltzmann = (-self.valency * self.potential(x)).exp();
+        let bulk = self.bulk_concentration();
+        (bulk * boltzmann, bulk / boltzmann)
+    }
+
+    /// Surface charge density from Grahame's equation, UNIT: [ e / angstrom^2 ]
+    pub fn surface_charge(&self) -> f64 {
+        self.kappa * (0.5 * self.valency * self.surface_potential).sinh()
+            / (2.0 * PI * self.valency * self.bjerrum_length)
+    }
+
+    /// Differential capacitance of the diffuse layer at `temperature` (K), UNIT: [ F / m^2 ]
+    pub fn capacitance(&self, temperature: f64) -> f64 {
+        // ε₀εᵣ = e² / 4πλ𝐵k𝐵T
+        let permittivity = ELEMENTARY_CHARGE * ELEMENTARY_CHARGE
+            / (4.0 * PI * self.bjerrum_length * 1e-10 * BOLTZMANN_CONSTANT * temperature);
+        permittivity * self.kappa * 1e10 * (0.5 * self.valency * self.surface_potential).cosh()
+    }
+}
+
+/// Valency, z, of a symmetric z:z salt, or mixture of such salts, in a medium
+pub(crate) fn symmetric_valency(medium: &Medium) -> Result<f64> {
+    match medium.ions().as_slice() {
+        [(z1, _), (z2, _)] if *z1 == -*z2 => Ok(z1.abs() as f64),
+        [] => Err(crate::Error::Unsupported("double layer without salt")),
+        _ => Err(crate::Error::Unsupported("asymmetric salt")),
+    }
+}
+
+#[test]
+fn test_gouy_chapman() {
+    use approx::assert_relative_eq;
+    let medium = Medium::salt_water(298.15, crate::Salt::CalciumSulfate, 0.02);
+    let plane = GouyChapman::from_medium(-3.0, &medium).unwrap();
+    assert_eq!(plane.valency, 2.0);
+    assert_relative_eq!(plane.bulk_concentration(), 0.02, max_relative = 1e-10);
+
+    // Poisson-Boltzmann: φ'' = -4πλ𝐵 ρ with ρ = z N_A (c₊ - c₋)
+    let (x, h) = (4.0, 1e-3);
+    let second_derivative =
+        (plane.potential(x + h) - 2.0 * plane.potential(x) + plane.potential(x - h)) / (h * h);
+    let (cation, anion) = plane.concentrations(x);
+    let rho = 2.0 * (cation - anion) * AVOGADRO_CONSTANT * LITER_PER_ANGSTROM3;
+    assert_relative_eq!(
+        second_derivative,
+        -4.0 * PI * plane.bjerrum_length * rho,
+        max_relative = 1e-5
+    );
+    assert_relative_eq!(
+        plane.field(x),
+        -(plane.potential(x + h) - plane.potential(x - h)) / (2.0 * h),
+        max_relative = 1e-6
+    );
+    // Gauss' law at the surface
+    assert_relative_eq!(
+        plane.field(0.0),
+        4.0 * PI * plane.bjerrum_length * plane.surface_charge(),
+        max_relative = 1e-12
+    );
+    // Capacitance is dσ/dψ₀
+    let sigma =
+        |phi| GouyChapman::new(phi, 2.0, plane.bjerrum_length, plane.kappa).surface_charge();
+    let volt_per_unit = BOLTZMANN_CONSTANT * 298.15 / ELEMENTARY_CHARGE;
+    let coulomb_per_m2 = ELEMENTARY_CHARGE * 1e20;
+    assert_relative_eq!(
+        plane.capacitance(298.15),
+        (sigma(-3.0 + h) - sigma(-3.0 - h)) / (2.0 * h) * coulomb_per_m2 / volt_per_unit,
+        max_relative = 1e-4
+    );
+    // Round trip through the surface charge
+    let other = GouyChapman::from_surface_charge(
+        plane.surface_charge(),
+        2.0,
+        plane.bjerrum_length,
+        plane.kappa,
+    );
+    assert_relative_eq!(other.surface_potential, -3.0, max_relative = 1e-12);
+
+    let asymmetric = Medium::salt_water(298.15, crate::Salt::CalciumChloride, 0.1);
+    assert!(GouyChapman::from_medium(1.0, &asymmetric).is_err());
+    assert!(GouyChapman::from_medium(1.0, &Medium::neat_water(298.15)).is_err());
+}
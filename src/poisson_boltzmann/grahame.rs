@@ -0,0 +1,170 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Grahame equation relating surface charge and surface potential.
+
+use crate::units::{
+    valence_per_angstrom_squared, volt, ElectricChargeArealDensity, ElectricPotential,
+};
+use crate::{
+    Medium, Result, Temperature, AVOGADRO_CONSTANT, BOLTZMANN_CONSTANT, ELEMENTARY_CHARGE,
+    LITER_PER_ANGSTROM3,
+};
+use core::f64::consts::PI;
+
+/// Number of bisection steps when solving for the surface potential
+const BISECTIONS: usize = 200;
+
+/// Valencies and number densities (angstrom^-3) of the ions in a medium
+fn ions(medium: &Medium) -> Result<Vec<(f64, f64)>> {
+    let ions = medium.ions();
+    if ions.is_empty() {
+        return Err(crate::Error::MissingSalt);
+    }
+    Ok(ions
+        .into_iter()
+        .map(|(z, c)| (z as f64, c * AVOGADRO_CONSTANT * LITER_PER_ANGSTROM3))
+        .collect())
+}
+
+/// Surface charge density, UNIT: [ e / angstrom^2 ], at reduced surface potential φ₀
+fn reduced_charge_density(surface_potential: f64, ions: &[(f64, f64)], bjerrum_length: f64) -> f64 {
+    let sum: f64 = ions
+        .iter()
+        .map(|(z, n)| n * ((-z * surface_potential).exp() - 1.0))
+        .sum();
+    (sum / (2.0 * PI * bjerrum_length))
+        .sqrt()
+        .copysign(surface_potential)
+}
+
+/// Volt per unit of reduced potential, k𝐵T/e
+fn thermal_voltage(medium: &Medium) -> f64 {
+    BOLTZMANN_CONSTANT * medium.temperature() / ELEMENTARY_CHARGE
+}
+
+/// Surface charge density of a plane from its surface potential using the Grahame equation.
+///
+/// For a planar diffuse double layer in an electrolyte with bulk ion concentrations $c_i$ and
+/// valencies $z_i$,
+///
+/// $$ \sigma^2 = 2 \varepsilon_0 \varepsilon_r k_B T \sum_i c_i \left ( e^{-z_i e \psi_0 / k_B T} - 1 \right ) $$
+///
+/// where $\sigma$ has the same sign as the surface potential, $\psi_0$.
+/// This holds for arbitrary, including asymmetric, electrolytes and reduces to
+/// $\sigma = \sqrt{8 \varepsilon_0 \varepsilon_r k_B T c} \sinh(z e \psi_0 / 2 k_B T)$ for a symmetric salt.
+/// See Grahame, <https://doi.org/10.1021/cr60130a002>.
+///
+/// # Errors
+/// Returns an error if the medium contains no salt.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::{poisson_boltzmann::*, units::*, Medium, Salt};
+/// let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+/// let potential = ElectricPotential::new::<volt>(0.05);
+/// let sigma = surface_charge_from_potential(potential, &medium).unwrap();
+/// let back = surface_potential_from_charge(sigma, &medium).unwrap();
+/// assert_relative_eq!(back.get::<volt>(), 0.05, max_relative = 1e-10);
+/// ~~~
+pub fn surface_charge_from_potential(
+    surface_potential: ElectricPotential,
+    medium: &Medium,
+) -> Result<ElectricChargeArealDensity> {
+    let phi = surface_potential.get::<volt>() / thermal_voltage(medium);
+    let sigma = reduced_charge_density(phi, &ions(medium)?, medium.bjerrum_length());
+    Ok(ElectricChargeArealDensity::new::<
+        valence_per_angstrom_squared,
+    >(sigma))
+}
+
+/// Surface potential of a plane from its surface charge density by inverting the Grahame equation.
+///
+/// See [`surface_charge_from_potential`]. The equation is solved numerically for asymmetric salts.
+///
+/// # Errors
+/// Returns an error if the medium contains no salt or if the potential cannot be bracketed.
+pub fn surface_potential_from_charge(
+    charge_density: ElectricChargeArealDensity,
+    medium: &Medium,
+) -> Result<ElectricPotential> {
+    let ions = ions(medium)?;
+    let bjerrum_length = medium.bjerrum_length();
+    let target = charge_density.get::<valence_per_angstrom_squared>();
+    let sigma = |phi: f64| reduced_charge_density(phi, &ions, bjerrum_length);
+    // σ(φ₀) increases monotonically; expand a bracket around zero and bisect
+    let (mut low, mut high) = (-1.0, 1.0);
+    while sigma(low) > target {
+        low *= 2.0;
+        if low < -1e3 {
+            return Err(crate::Error::NotConverged(0));
+        }
+    }
+    while sigma(high) < target {
+        high *= 2.0;
+        if high > 1e3 {
+            return Err(crate::Error::NotConverged(0));
+        }
+    }
+    for _ in 0..BISECTIONS {
+        let middle = 0.5 * (low + high);
+        match sigma(middle) > target {
+            true => high = middle,
+            false => low = middle,
+        }
+    }
+    Ok(ElectricPotential::new::<volt>(
+        0.5 * (low + high) * thermal_voltage(medium),
+    ))
+}
+
+#[test]
+fn test_grahame() {
+    use crate::units::coulomb_per_square_meter;
+    use crate::Salt;
+    use approx::assert_relative_eq;
+    let voltage = |v| ElectricPotential::new::<volt>(v);
+
+    // Symmetric salt agrees with Gouy-Chapman
+    let medium = Medium::salt_water(298.15, Salt::CalciumSulfate, 0.01);
+    let plane = super::GouyChapman::from_medium(1.7, &medium).unwrap();
+    let sigma = surface_charge_from_potential(voltage(1.7 * thermal_voltage(&medium)), &medium)
+        .unwrap()
+        .get::<coulomb_per_square_meter>();
+    assert_relative_eq!(
+        sigma,
+        plane.surface_charge() * ELEMENTARY_CHARGE * 1e20,
+        max_relative = 1e-10
+    );
+
+    // Asymmetric salt is not odd in the potential; divalent counterions screen more
+    let medium = Medium::salt_water(298.15, Salt::CalciumChloride, 0.01);
+    let positive = surface_charge_from_potential(voltage(0.1), &medium).unwrap();
+    let negative = surface_charge_from_potential(voltage(-0.1), &medium).unwrap();
+    assert!(negative.get::<coulomb_per_square_meter>() < 0.0);
+    assert!(
+        -negative.get::<coulomb_per_square_meter>() > positive.get::<coulomb_per_square_meter>()
+    );
+    for sigma in [positive, negative] {
+        let potential = surface_potential_from_charge(sigma, &medium).unwrap();
+        let again = surface_charge_from_potential(potential, &medium).unwrap();
+        assert_relative_eq!(
+            again.get::<coulomb_per_square_meter>(),
+            sigma.get::<coulomb_per_square_meter>(),
+            max_relative = 1e-10
+        );
+    }
+    assert!(surface_charge_from_potential(voltage(0.1), &Medium::neat_water(298.15)).is_err());
+}
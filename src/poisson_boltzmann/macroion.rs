@@ -0,0 +1,112 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Potential of mean force between macroions at the Debye-Hückel level.
+
+use super::geometry::Electrolyte;
+use crate::{DebyeLength, Medium};
+
+/// Spherical macroion with a net charge and, optionally, titrating groups.
+///
+/// The charge capacitance, $C = \langle Z^2 \rangle - \langle Z \rangle^2$, measures charge
+/// fluctuations due to titration and is zero for a fixed charge.
+/// In an external reduced potential, $\phi$, a regulating macroion lowers its free energy by
+/// $-C \phi^2 / 2$.
+/// See Lund and Jönsson, <https://doi.org/10.1021/bi047630o>.
+///
+/// # Examples
+/// ~~~
+/// use coulomb::{poisson_boltzmann::Macroion, Medium, Salt};
+/// let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.02);
+/// let charged = Macroion::new(-10.0, 15.0);
+/// let neutral = Macroion::new(0.0, 15.0).with_capacitance(3.0);
+/// // Charge regulation makes a neutral protein attract a charged one
+/// assert!(neutral.pmf(&charged, 40.0, &medium) < 0.0);
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Macroion {
+    /// Average charge number, ⟨Z⟩
+    pub charge: f64,
+    /// Radius, UNIT: [ angstrom ]
+    pub radius: f64,
+    /// Charge capacitance, ⟨Z²⟩ - ⟨Z⟩²
+    pub capacitance: f64,
+}
+
+impl Macroion {
+    /// New macroion with fixed charge and radius in angstrom.
+    pub const fn new(charge: f64, radius: f64) -> Self {
+        Self {
+            charge,
+            radius,
+            capacitance: 0.0,
+        }
+    }
+
+    /// Regulate the charge with a given capacitance, ⟨Z²⟩ - ⟨Z⟩².
+    pub const fn with_capacitance(self, capacitance: f64) -> Self {
+        Self {
+            capacitance,
+            ..self
+        }
+    }
+
+    /// Potential of mean force to another macroion at center-to-center `distance` (angstrom),
+    /// UNIT: [ kT ]
+    ///
+    /// With $\phi(R) = \lambda_B \frac{e^{\kappa a_1}}{1 + \kappa a_1} \frac{e^{\kappa a_2}}{1 + \kappa a_2} \frac{e^{-\kappa R}}{R}$,
+    /// the screened interaction per unit charges including the exclusion of salt from both
+    /// macroions, the potential of mean force is
+    ///
+    /// $$ \beta w(R) = Z_1 Z_2 \phi(R) - \frac{1}{2} \left ( C_1 Z_2^2 + C_2 Z_1^2 \right ) \phi(R)^2 $$
+    ///
+    /// where the last term, due to charge regulation, vanishes for fixed charges.
+    /// Without salt, the interaction is unscreened.
+    pub fn pmf(&self, other: &Self, distance: f64, medium: &Medium) -> f64 {
+        let electrolyte = Electrolyte::new(medium.bjerrum_length(), medium.kappa().unwrap_or(0.0));
+        let phi = electrolyte.sphere_sphere_energy(1.0, self.radius, 1.0, other.radius, distance);
+        self.charge * other.charge * phi
+            - 0.5
+                * (self.capacitance * other.charge.powi(2)
+                    + other.capacitance * self.charge.powi(2))
+                * phi.powi(2)
+    }
+}
+
+#[test]
+fn test_macroion_pmf() {
+    use crate::Salt;
+    use approx::assert_relative_eq;
+    let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.05);
+    let electrolyte = Electrolyte::from_medium(&medium).unwrap();
+    let (first, second) = (Macroion::new(8.0, 20.0), Macroion::new(-3.0, 10.0));
+    assert_relative_eq!(
+        first.pmf(&second, 45.0, &medium),
+        electrolyte.sphere_sphere_energy(8.0, 20.0, -3.0, 10.0, 45.0)
+    );
+    assert_relative_eq!(
+        first.pmf(&second, 45.0, &medium),
+        second.pmf(&first, 45.0, &medium)
+    );
+
+    // Charge regulation, -C₂Z₁²φ²/2, in salt-free solution
+    let water = Medium::neat_water(298.15);
+    let regulating = Macroion::new(0.0, 10.0).with_capacitance(2.0);
+    let lb = water.bjerrum_length();
+    assert_relative_eq!(
+        regulating.pmf(&first, 50.0, &water),
+        -0.5 * 2.0 * 64.0 * (lb / 50.0).powi(2)
+    );
+}
@@ -0,0 +1,35 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Poisson-Boltzmann theory for charged bodies in electrolyte solutions.
+//!
+//! Potentials are given in reduced units, $\phi = e \psi / k_B T$, and lengths in angstrom.
+
+mod atmosphere;
+mod dlvo;
+pub mod geometry;
+mod gouy_chapman;
+#[cfg(feature = "uom")]
+mod grahame;
+mod macroion;
+mod renormalization;
+mod solver;
+pub use atmosphere::{IonicAtmosphere, RadialProfile};
+pub use dlvo::Dlvo;
+pub use gouy_chapman::GouyChapman;
+#[cfg(feature = "uom")]
+pub use grahame::{surface_charge_from_potential, surface_potential_from_charge};
+pub use macroion::Macroion;
+pub use renormalization::{saturated_charge, CellModel, EffectiveCharge};
+pub use solver::{PoissonBoltzmannSolution, PoissonBoltzmannSolver, Surface, Symmetry};
@@ -0,0 +1,234 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Charge renormalization of spherical colloids.
+
+use crate::{DebyeLength, Medium, Result};
+
+/// Spherical Poisson-Boltzmann cell model in equilibrium with a 1:1 salt reservoir.
+///
+/// A colloid of radius $a$ and charge number $Z$ sits at the center of a spherical cell of
+/// radius $R$, where the reduced potential obeys
+///
+/// $$ \phi'' + \frac{2}{r} \phi' = \kappa^2 \sinh \phi $$
+///
+/// with $\phi'(a) = -Z \lambda_B / a^2$ and $\phi'(R) = 0$, and $\kappa$ is the inverse Debye
+/// length of the reservoir.
+/// The effective charge follows Alexander's prescription, where the equation is linearized
+/// around the potential at the cell boundary, $\phi_R$, giving the effective inverse Debye length
+/// $\kappa_{eff}^2 = \kappa^2 \cosh \phi_R$, and
+///
+/// $$ Z_{eff} \lambda_B = \frac{\tanh \phi_R}{\kappa_{eff}} \left [ (\kappa_{eff}^2 a R - 1) \sinh(\kappa_{eff} (R - a)) + \kappa_{eff} (R - a) \cosh(\kappa_{eff} (R - a)) \right ] $$
+///
+/// The nonlinear equation is solved by shooting from the cell boundary.
+/// See Alexander _et al._, <https://doi.org/10.1063/1.446897>, and
+/// Trizac _et al._, <https://doi.org/10.1021/la027056m>.
+///
+/// # Examples
+/// ~~~
+/// use coulomb::poisson_boltzmann::CellModel;
+/// let cell = CellModel::new(100.0, 300.0, 7.0, 0.05);
+/// let effective = cell.renormalize(5000.0).unwrap();
+/// assert!(effective.charge < 5000.0);
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CellModel {
+    /// Colloid radius, UNIT: [ angstrom ]
+    pub radius: f64,
+    /// Cell radius, UNIT: [ angstrom ]
+    pub cell_radius: f64,
+    /// Bjerrum length, UNIT: [ angstrom ]
+    pub bjerrum_length: f64,
+    /// Inverse Debye length of the salt reservoir, UNIT: [ angstrom^-1 ]
+    pub kappa: f64,
+}
+
+/// Renormalized charge and screening from [`CellModel::renormalize`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EffectiveCharge {
+    /// Effective charge number
+    pub charge: f64,
+    /// Effective Debye length, UNIT: [ angstrom ]
+    pub debye_length: f64,
+    /// Reduced potential at the cell boundary
+    pub boundary_potential: f64,
+}
+
+impl CellModel {
+    /// Number of integration steps from the cell boundary to the colloid surface
+    const STEPS: usize = 2000;
+    /// Number of bisection steps for the boundary potential
+    const BISECTIONS: usize = 200;
+
+    /// New cell model with radii and lengths in angstrom.
+    pub const fn new(radius: f64, cell_radius: f64, bjerrum_length: f64, kappa: f64) -> Self {
+        Self {
+            radius,
+            cell_radius,
+            bjerrum_length,
+            kappa,
+        }
+    }
+
+    /// New cell model with Bjerrum and Debye lengths of a medium.
+    ///
+    /// # Errors
+    /// Returns an error if the medium contains no salt.
+    pub fn from_medium(radius: f64, cell_radius: f64, medium: &Medium) -> Result<Self> {
+        let kappa = medium
+            .kappa()
+            .ok_or(crate::Error::Unsupported("cell model without salt"))?;
+        Ok(Self::new(
+            radius,
+            cell_radius,
+            medium.bjerrum_length(),
+            kappa,
+        ))
+    }
+
+    /// Integrate inwards from the cell boundary with potential φ_R, returning (r, φ, φ')
+    fn integrate(&self, boundary_potential: f64) -> Vec<(f64, f64, f64)> {
+        let kappa_squared = self.kappa * self.kappa;
+        let derivative =
+            |r: f64, (phi, dphi): (f64, f64)| (dphi, kappa_squared * phi.sinh() - 2.0 * dphi / r);
+        let h = (self.radius - self.cell_radius) / Self::STEPS as f64;
+        let mut state = (boundary_potential, 0.0);
+        let mut profile = Vec::with_capacity(Self::STEPS + 1);
+        profile.push((self.cell_radius, state.0, state.1));
+        for i in 0..Self::STEPS {
+            let r = self.cell_radius + i as f64 * h;
+            let k1 = derivative(r, state);
+            let k2 = derivative(
+                r + 0.5 * h,
+                (state.0 + 0.5 * h * k1.0, state.1 + 0.5 * h * k1.1),
+            );
+            let k3 = derivative(
+                r + 0.5 * h,
+                (state.0 + 0.5 * h * k2.0, state.1 + 0.5 * h * k2.1),
+            );
+            let k4 = derivative(r + h, (state.0 + h * k3.0, state.1 + h * k3.1));
+            state.0 += h / 6.0 * (k1.0 + 2.0 * k2.0 + 2.0 * k3.0 + k4.0);
+            state.1 += h / 6.0 * (k1.1 + 2.0 * k2.1 + 2.0 * k3.1 + k4.1);
+            profile.push((r + h, state.0, state.1));
+        }
+        profile
+    }
+
+    /// Charge number at the surface implied by the boundary potential, φ_R
+    fn surface_charge(&self, boundary_potential: f64) -> f64 {
+        let (_, _, dphi) = *self.integrate(boundary_potential).last().unwrap();
+        -dphi * self.radius * self.radius / self.bjerrum_length
+    }
+
+    /// Reduced potential at the cell boundary, φ_R, for a colloid with charge number `charge`
+    fn boundary_potential(&self, charge: f64) -> Result<f64> {
+        // Solve for |Z| and restore the sign at the end
+        let target = charge.abs();
+        let exceeds = |phi: f64| {
+            let implied = self.surface_charge(phi);
+            !implied.is_finite() || implied > target
+        };
+        let (mut low, mut high) = (0.0, 1.0);
+        while !exceeds(high) {
+            low = high;
+            high *= 2.0;
+            if high > 1e3 {
+                return Err(crate::Error::NotConverged(0));
+            }
+        }
+        for _ in 0..Self::BISECTIONS {
+            let middle = 0.5 * (low + high);
+            match exceeds(middle) {
+                true => high = middle,
+                false => low = middle,
+            }
+        }
+        Ok((0.5 * (low + high)).copysign(charge))
+    }
+
+    /// Reduced potential, (r, φ), from the colloid surface to the cell boundary.
+    ///
+    /// # Errors
+    /// Returns an error if the boundary potential cannot be bracketed.
+    pub fn potential_profile(&self, charge: f64) -> Result<Vec<(f64, f64)>> {
+        let sign = charge.signum();
+        let phi = self.boundary_potential(charge)?.abs();
+        Ok(self
+            .integrate(phi)
+            .iter()
+            .rev()
+            .map(|(r, phi, _)| (*r, sign * phi))
+            .collect())
+    }
+
+    /// Effective charge and Debye length of a colloid with bare charge number `charge`.
+    ///
+    /// # Errors
+    /// Returns an error if the boundary potential cannot be bracketed.
+    pub fn renormalize(&self, charge: f64) -> Result<EffectiveCharge> {
+        let phi = self.boundary_potential(charge)?.abs();
+        let kappa = self.kappa * phi.cosh().sqrt();
+        let span = self.cell_radius - self.radius;
+        let effective = phi.tanh() / kappa
+            * ((kappa * kappa * self.radius * self.cell_radius - 1.0) * (kappa * span).sinh()
+                + kappa * span * (kappa * span).cosh())
+            / self.bjerrum_length;
+        Ok(EffectiveCharge {
+            charge: effective.copysign(charge),
+            debye_length: kappa.recip(),
+            boundary_potential: phi.copysign(charge),
+        })
+    }
+}
+
+/// Saturated effective charge number of a sphere in a salt solution, $Z_{sat} \lambda_B / a = 4 \kappa a + 6$.
+///
+/// This is the far-field prescription of Trizac _et al._ for an isolated sphere of
+/// radius $a$ in the limit of high bare charge, valid for $\kappa a \gtrsim 1$.
+/// See <https://doi.org/10.1103/PhysRevLett.89.248301>.
+pub fn saturated_charge(radius: f64, bjerrum_length: f64, kappa: f64) -> f64 {
+    radius / bjerrum_length * (4.0 * kappa * radius + 6.0)
+}
+
+#[test]
+fn test_charge_renormalization() {
+    use approx::assert_relative_eq;
+    let cell = CellModel::new(50.0, 200.0, 7.0, 0.02);
+
+    // Weakly charged colloids are not renormalized
+    let effective = cell.renormalize(-2.0).unwrap();
+    assert_relative_eq!(effective.charge, -2.0, max_relative = 1e-3);
+    assert!(effective.boundary_potential < 0.0);
+    assert_relative_eq!(effective.debye_length, 50.0, max_relative = 1e-4);
+
+    // Effective charge saturates at high bare charge
+    let charges = [100.0, 1000.0, 10000.0, 100000.0].map(|z| cell.renormalize(z).unwrap().charge);
+    assert!(charges.windows(2).all(|w| w[0] < w[1]));
+    assert!(charges[3] < 1000.0);
+    assert_relative_eq!(charges[2], charges[3], max_relative = 0.02);
+
+    // Compare with the far-field saturation for a dilute cell
+    let cell = CellModel::new(50.0, 2000.0, 7.0, 0.05);
+    assert_relative_eq!(
+        cell.renormalize(1e5).unwrap().charge,
+        saturated_charge(50.0, 7.0, 0.05),
+        max_relative = 0.05
+    );
+
+    let medium = Medium::salt_water(298.15, crate::Salt::SodiumChloride, 0.01);
+    let cell = CellModel::from_medium(50.0, 200.0, &medium).unwrap();
+    assert_eq!(cell.kappa, medium.kappa().unwrap());
+    assert!(CellModel::from_medium(50.0, 200.0, &Medium::neat_water(298.15)).is_err());
+}
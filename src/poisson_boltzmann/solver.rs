@@ -0,0 +1,457 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! One-dimensional nonlinear Poisson-Boltzmann solver.
+
+use crate::{
+    Medium, Result, AVOGADRO_CONSTANT, BOLTZMANN_CONSTANT, ELEMENTARY_CHARGE, LITER_PER_ANGSTROM3,
+};
+use core::f64::consts::PI;
+
+/// Symmetry of a one-dimensional Poisson-Boltzmann problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Symmetry {
+    /// Charged plane; energies per area
+    #[default]
+    Planar,
+    /// Charged cylinder; energies per length
+    Cylindrical,
+    /// Charged sphere
+    Spherical,
+}
+
+impl Symmetry {
+    /// Exponent, d, of the radial measure, r^d
+    const fn dimension(&self) -> i32 {
+        match self {
+            Self::Planar => 0,
+            Self::Cylindrical => 1,
+            Self::Spherical => 2,
+        }
+    }
+
+    /// Angular factor of the measure, 1, 2π, or 4π
+    const fn angular_factor(&self) -> f64 {
+        match self {
+            Self::Planar => 1.0,
+            Self::Cylindrical => 2.0 * PI,
+            Self::Spherical => 4.0 * PI,
+        }
+    }
+}
+
+/// Boundary condition at the charged surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Surface {
+    /// Fixed reduced surface potential
+    Potential(f64),
+    /// Fixed surface charge density, UNIT: [ e / angstrom^2 ]
+    ChargeDensity(f64),
+}
+
+/// Nonlinear Poisson-Boltzmann equation with planar, cylindrical, or spherical symmetry.
+///
+/// The reduced potential between a charged surface at $r_0$ and an outer boundary at $r_1$ obeys
+///
+/// $$ \frac{1}{r^d} \frac{d}{dr} \left ( r^d \frac{d \phi}{dr} \right ) = -4 \pi \lambda_B \sum_i z_i n_i e^{-z_i \phi} $$
+///
+/// where $d = 0, 1, 2$ for planar, cylindrical, and spherical symmetry, and $n_i$ are the number
+/// densities of ions in a bulk reservoir.
+/// The surface has either fixed potential or fixed charge density, $\sigma = -\phi'(r_0) / 4 \pi \lambda_B$,
+/// and the field vanishes at the outer boundary, as in a cell model.
+/// The equation is discretized with finite volumes on a grid refined towards the surface, which
+/// conserves charge exactly, and solved by damped Newton iteration.
+///
+/// The grand potential relative to the bulk,
+///
+/// $$ \beta \Omega = \int \left [ -\frac{\phi'^2}{8 \pi \lambda_B} - \sum_i n_i \left ( e^{-z_i \phi} - 1 \right ) \right ] dV + \sigma \phi_0 A $$
+///
+/// is reported for fixed surface charge, while the last term is omitted for fixed surface
+/// potential.
+/// These satisfy $\partial \beta \Omega / \partial \sigma = A \phi_0$ and
+/// $\partial \beta \Omega / \partial \phi_0 = -A \sigma$, respectively, where $A$ is the surface area.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::poisson_boltzmann::{GouyChapman, PoissonBoltzmannSolver, Surface, Symmetry};
+/// use coulomb::{Medium, Salt};
+/// let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+/// let solver = PoissonBoltzmannSolver::from_medium(Symmetry::Planar, &medium).unwrap();
+/// let solution = solver.solve(0.0, 200.0, Surface::Potential(4.0)).unwrap();
+/// let analytic = GouyChapman::from_medium(4.0, &medium).unwrap();
+/// assert_relative_eq!(solution.surface_charge, analytic.surface_charge(), max_relative = 1e-3);
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoissonBoltzmannSolver {
+    /// Symmetry of the problem
+    pub symmetry: Symmetry,
+    /// Bjerrum length, UNIT: [ angstrom ]
+    pub bjerrum_length: f64,
+    /// Valency and bulk molarity (mol/l) of each ion species
+    pub ions: Vec<(f64, f64)>,
+    /// Number of grid intervals
+    pub intervals: usize,
+    /// Convergence criterion on the largest potential update
+    pub tolerance: f64,
+    /// Maximum number of Newton iterations
+    pub max_iterations: usize,
+}
+
+/// Solution of the Poisson-Boltzmann equation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoissonBoltzmannSolution {
+    /// Grid points, UNIT: [ angstrom ]
+    pub radii: Vec<f64>,
+    /// Reduced potential at each grid point
+    pub potential: Vec<f64>,
+    /// Reduced surface potential
+    pub surface_potential: f64,
+    /// Surface charge density, UNIT: [ e / angstrom^2 ]
+    pub surface_charge: f64,
+    /// Grand potential relative to the bulk, UNIT: [ kT ] per area, per length, or per sphere
+    pub free_energy: f64,
+    /// Number of Newton iterations used
+    pub iterations: usize,
+}
+
+impl PoissonBoltzmannSolver {
+    /// Largest potential update in a single Newton step
+    const MAX_STEP: f64 = 2.0;
+    /// Exponential grading of the grid towards the surface
+    const GRADING: f64 = 4.0;
+
+    /// New solver for ions with given valencies and bulk molarities (mol/l).
+    pub fn new(symmetry: Symmetry, bjerrum_length: f64, ions: Vec<(f64, f64)>) -> Self {
+        Self {
+            symmetry,
+            bjerrum_length,
+            ions,
+            intervals: 1000,
+            tolerance: 1e-10,
+            max_iterations: 100,
+        }
+    }
+
+    /// New solver with the Bjerrum length and salt, or salt mixture, of a medium.
+    ///
+    /// # Errors
+    /// Returns an error if the medium contains no salt.
+    pub fn from_medium(symmetry: Symmetry, medium: &Medium) -> Result<Self> {
+        let ions: Vec<_> = medium
+            .ions()
+            .into_iter()
+            .map(|(z, c)| (z as f64, c))
+            .collect();
+        if ions.is_empty() {
+            return Err(crate::Error::MissingSalt);
+        }
+        Ok(Self::new(symmetry, medium.bjerrum_length(), ions))
+    }
+
+    /// Number densities of the ions, UNIT: [ angstrom^-3 ]
+    fn densities(&self) -> Vec<(f64, f64)> {
+        self.ions
+            .iter()
+            .map(|(z, c)| (*z, c * AVOGADRO_CONSTANT * LITER_PER_ANGSTROM3))
+            .collect()
+    }
+
+    /// Solve between the surface at `inner` and the outer boundary at `outer` (angstrom).
+    ///
+    /// # Errors
+    /// Returns an error if Newton iteration does not converge.
+    pub fn solve(
+        &self,
+        inner: f64,
+        outer: f64,
+        surface: Surface,
+    ) -> Result<PoissonBoltzmannSolution> {
+        let n = self.intervals;
+        let d = self.symmetry.dimension();
+        // Grid is exponentially refined towards the surface where the potential varies rapidly
+        let grading = Self::GRADING;
+        let radii: Vec<f64> = (0..=n)
+            .map(|i| {
+                let s = i as f64 / n as f64;
+                inner + (outer - inner) * (grading * s).exp_m1() / grading.exp_m1()
+            })
+            .collect();
+        let area = |r: f64| r.powi(d);
+        // Control volumes of the nodes, ∫ r^d dr, bounded by the midpoints
+        let midpoint = |i: usize| 0.5 * (radii[i] + radii[i + 1]);
+        let volumes: Vec<f64> = (0..=n)
+            .map(|i| {
+                let low = if i == 0 { inner } else { midpoint(i - 1) };
+                let high = if i == n { outer } else { midpoint(i) };
+                (high.powi(d + 1) - low.powi(d + 1)) / (d + 1) as f64
+            })
+            .collect();
+        // Face areas divided by node spacing, between node i and i + 1
+        let faces: Vec<f64> = (0..n)
+            .map(|i| area(midpoint(i)) / (radii[i + 1] - radii[i]))
+            .collect();
+        let densities = self.densities();
+        let prefactor = 4.0 * PI * self.bjerrum_length;
+        // Charge density and its derivative
+        let rho = |phi: f64| -> (f64, f64) {
+            densities.iter().fold((0.0, 0.0), |(rho, drho), (z, n)| {
+                let boltzmann = n * (-z * phi).exp();
+                (rho + z * boltzmann, drho - z * z * boltzmann)
+            })
+        };
+
+        let mut phi = vec![0.0; n + 1];
+        if let Surface::Potential(potential) = surface {
+            phi[0] = potential;
+        }
+        let mut iterations = 0;
+        loop {
+            if iterations == self.max_iterations {
+                return Err(crate::Error::NotConverged(self.max_iterations));
+            }
+            iterations += 1;
+            // Residual, F, and tridiagonal Jacobian (lower, diagonal, upper)
+            let mut residual = vec![0.0; n + 1];
+            let mut lower = vec![0.0; n + 1];
+            let mut diagonal = vec![0.0; n + 1];
+            let mut upper = vec![0.0; n + 1];
+            for i in 0..=n {
+                let (charge, d_charge) = rho(phi[i]);
+                residual[i] = prefactor * charge * volumes[i];
+                diagonal[i] = prefactor * d_charge * volumes[i];
+                if i > 0 {
+                    residual[i] -= faces[i - 1] * (phi[i] - phi[i - 1]);
+                    diagonal[i] -= faces[i - 1];
+                    lower[i] = faces[i - 1];
+                }
+                if i < n {
+                    residual[i] += faces[i] * (phi[i + 1] - phi[i]);
+                    diagonal[i] -= faces[i];
+                    upper[i] = faces[i];
+                }
+            }
+            match surface {
+                Surface::Potential(_) => {
+                    residual[0] = 0.0;
+                    diagonal[0] = 1.0;
+                    upper[0] = 0.0;
+                }
+                Surface::ChargeDensity(sigma) => {
+                    residual[0] += area(inner) * prefactor * sigma;
+                }
+            }
+            let mut step = solve_tridiagonal(&lower, &diagonal, &upper, &residual);
+            let largest = step.iter().fold(0.0f64, |max, x| max.max(x.abs()));
+            if largest > Self::MAX_STEP {
+                step.iter_mut().for_each(|x| *x *= Self::MAX_STEP / largest);
+            }
+            phi.iter_mut().zip(&step).for_each(|(phi, dx)| *phi -= dx);
+            if largest < self.tolerance {
+                break;
+            }
+        }
+
+        // Surface charge from the charge balance of the first control volume
+        let surface_charge = match surface {
+            Surface::ChargeDensity(sigma) => sigma,
+            Surface::Potential(_) => {
+                -(faces[0] * (phi[1] - phi[0]) + prefactor * rho(phi[0]).0 * volumes[0])
+                    / (prefactor * area(inner))
+            }
+        };
+        let field_energy: f64 = (0..n)
+            .map(|i| faces[i] * (phi[i + 1] - phi[i]).powi(2))
+            .sum::<f64>()
+            / (2.0 * prefactor);
+        let ion_energy: f64 = (0..=n)
+            .map(|i| {
+                volumes[i]
+                    * densities
+                        .iter()
+                        .map(|(z, n)| n * ((-z * phi[i]).exp() - 1.0))
+                        .sum::<f64>()
+            })
+            .sum();
+        let mut free_energy = -field_energy - ion_energy;
+        if let Surface::ChargeDensity(sigma) = surface {
+            free_energy += sigma * phi[0] * area(inner);
+        }
+        Ok(PoissonBoltzmannSolution {
+            radii,
+            surface_potential: phi[0],
+            potential: phi,
+            surface_charge,
+            free_energy: free_energy * self.symmetry.angular_factor(),
+            iterations,
+        })
+    }
+
+    /// Differential capacitance, dσ/dψ₀, at a reduced surface potential and `temperature` (K), UNIT: [ F / m^2 ]
+    ///
+    /// # Errors
+    /// Returns an error if Newton iteration does not converge.
+    pub fn capacitance(
+        &self,
+        inner: f64,
+        outer: f64,
+        surface_potential: f64,
+        temperature: f64,
+    ) -> Result<f64> {
+        const STEP: f64 = 1e-4;
+        let charge = |phi| -> Result<f64> {
+            Ok(self
+                .solve(inner, outer, Surface::Potential(phi))?
+                .surface_charge)
+        };
+        let derivative =
+            (charge(surface_potential + STEP)? - charge(surface_potential - STEP)?) / (2.0 * STEP);
+        let volt_per_unit = BOLTZMANN_CONSTANT * temperature / ELEMENTARY_CHARGE;
+        Ok(derivative * ELEMENTARY_CHARGE * 1e20 / volt_per_unit)
+    }
+}
+
+/// Solve a tridiagonal system with the Thomas algorithm
+fn solve_tridiagonal(lower: &[f64], diagonal: &[f64], upper: &[f64], rhs: &[f64]) -> Vec<f64> {
+    let n = diagonal.len();
+    let mut c = vec![0.0; n];
+    let mut x = vec![0.0; n];
+    c[0] = upper[0] / diagonal[0];
+    x[0] = rhs[0] / diagonal[0];
+    for i in 1..n {
+        let denominator = diagonal[i] - lower[i] * c[i - 1];
+        c[i] = upper[i] / denominator;
+        x[i] = (rhs[i] - lower[i] * x[i - 1]) / denominator;
+    }
+    for i in (0..n - 1).rev() {
+        x[i] -= c[i] * x[i + 1];
+    }
+    x
+}
+
+#[test]
+fn test_poisson_boltzmann_solver() {
+    use super::{geometry::Electrolyte, CellModel, GouyChapman};
+    use crate::{DebyeLength, Salt};
+    use approx::assert_relative_eq;
+
+    // Planar double layer compared with Gouy-Chapman
+    let medium = Medium::salt_water(298.15, Salt::CalciumSulfate, 0.05);
+    let solver = PoissonBoltzmannSolver::from_medium(Symmetry::Planar, &medium).unwrap();
+    let analytic = GouyChapman::from_medium(-2.5, &medium).unwrap();
+    let length = 20.0 / analytic.kappa;
+    let solution = solver.solve(0.0, length, Surface::Potential(-2.5)).unwrap();
+    assert_relative_eq!(
+        solution.surface_charge,
+        analytic.surface_charge(),
+        max_relative = 1e-4
+    );
+    let middle = solution.radii.len() / 10;
+    assert_relative_eq!(
+        solution.potential[middle],
+        analytic.potential(solution.radii[middle]),
+        max_relative = 1e-4
+    );
+    let solution = solver
+        .solve(
+            0.0,
+            length,
+            Surface::ChargeDensity(analytic.surface_charge()),
+        )
+        .unwrap();
+    assert_relative_eq!(solution.surface_potential, -2.5, max_relative = 1e-4);
+    assert_relative_eq!(
+        solver.capacitance(0.0, length, -2.5, 298.15).unwrap(),
+        analytic.capacitance(298.15),
+        max_relative = 1e-3
+    );
+
+    // Free energies are consistent with the boundary conditions
+    let medium = Medium::salt_water(298.15, Salt::CalciumChloride, 0.02);
+    for symmetry in [Symmetry::Planar, Symmetry::Cylindrical, Symmetry::Spherical] {
+        let solver = PoissonBoltzmannSolver::from_medium(symmetry, &medium).unwrap();
+        let (inner, outer, dx): (f64, f64, f64) = (10.0, 120.0, 1e-6);
+        let area = symmetry.angular_factor() * inner.powi(symmetry.dimension());
+        let energy = |surface| solver.solve(inner, outer, surface).unwrap().free_energy;
+        let sigma = 0.01;
+        let solution = solver
+            .solve(inner, outer, Surface::ChargeDensity(sigma))
+            .unwrap();
+        assert_relative_eq!(
+            (energy(Surface::ChargeDensity(sigma + dx))
+                - energy(Surface::ChargeDensity(sigma - dx)))
+                / (2.0 * dx),
+            area * solution.surface_potential,
+            max_relative = 1e-5
+        );
+        let phi = -1.5;
+        let solution = solver.solve(inner, outer, Surface::Potential(phi)).unwrap();
+        assert_relative_eq!(
+            (energy(Surface::Potential(phi + dx)) - energy(Surface::Potential(phi - dx)))
+                / (2.0 * dx),
+            -area * solution.surface_charge,
+            max_relative = 1e-5
+        );
+    }
+
+    // Weakly charged cylinder follows linearized theory
+    let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+    let solver = PoissonBoltzmannSolver::from_medium(Symmetry::Cylindrical, &medium).unwrap();
+    let electrolyte = Electrolyte::from_medium(&medium).unwrap();
+    let (radius, sigma) = (10.0, 1e-5);
+    let solution = solver
+        .solve(
+            radius,
+            radius + 25.0 / electrolyte.kappa,
+            Surface::ChargeDensity(sigma),
+        )
+        .unwrap();
+    let line_charge = 2.0 * PI * radius * sigma;
+    assert_relative_eq!(
+        solution.surface_potential,
+        electrolyte.cylinder_potential(line_charge, radius, radius),
+        max_relative = 1e-4
+    );
+
+    // Spherical cell agrees with the shooting method of the cell model
+    let cell = CellModel::new(
+        30.0,
+        100.0,
+        medium.bjerrum_length(),
+        medium.kappa().unwrap(),
+    );
+    let charge = 200.0;
+    let profile = cell.potential_profile(charge).unwrap();
+    let solver = PoissonBoltzmannSolver::from_medium(Symmetry::Spherical, &medium).unwrap();
+    let solution = solver
+        .solve(
+            30.0,
+            100.0,
+            Surface::ChargeDensity(charge / (4.0 * PI * 900.0)),
+        )
+        .unwrap();
+    assert_relative_eq!(
+        solution.surface_potential,
+        profile[0].1,
+        max_relative = 1e-4
+    );
+    assert_relative_eq!(
+        *solution.potential.last().unwrap(),
+        profile.last().unwrap().1,
+        max_relative = 1e-3
+    );
+}
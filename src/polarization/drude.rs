@@ -0,0 +1,312 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Drude oscillators.
+//!
+//! A Drude oscillator is a charge, $q_D$, attached to its core by a harmonic spring with
+//! force constant $k$, giving an isotropic polarizability $\alpha = q_D^2 / k$.
+//! The core carries the remaining charge of the site and does not interact
+//! electrostatically with its own Drude charge.
+//! Dipoles of different Drude sites, _i.e._ the Drude charge and its opposite at the core,
+//! interact with Thole screening as in the CHARMM Drude force field,
+//! <https://doi.org/10.1021/ct200812a>.
+
+use super::Solver;
+use crate::pairwise::{MultipoleField, MultipolePotential};
+use crate::{NalgebraVector3, Vector3};
+
+/// Drude charge on a spring.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Drude {
+    /// Drude charge, UNIT: [ input charge ]
+    pub charge: f64,
+    /// Harmonic force constant, UNIT: [ ( input charge )^2 / ( input length )^3 ]
+    pub force_constant: f64,
+    /// Dimensionless Thole screening parameter
+    pub thole: f64,
+}
+
+impl Drude {
+    /// Isotropic polarizability, $\alpha = q_D^2 / k$, UNIT: [ ( input length )^3 ]
+    pub fn polarizability(&self) -> f64 {
+        self.charge * self.charge / self.force_constant
+    }
+
+    /// Harmonic spring energy, $k d^2 / 2$, for a displacement, $d$, of the Drude charge from its core.
+    pub fn spring_energy(&self, displacement: impl Into<Vector3>) -> f64 {
+        let displacement: NalgebraVector3 = displacement.into().into();
+        0.5 * self.force_constant * displacement.norm_squared()
+    }
+}
+
+/// Site with a core charge and an optional Drude oscillator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrudeSite {
+    /// Position of the core
+    pub position: Vector3,
+    /// Core charge, excluding the Drude charge, UNIT: [ input charge ]
+    pub charge: f64,
+    /// Drude oscillator attached to the core
+    pub drude: Option<Drude>,
+}
+
+/// Thole screening function between two Drude dipoles.
+///
+/// $$ S(r) = 1 - \left(1 + \frac{a r}{2 w}\right) e^{-a r / w} $$
+///
+/// where $a$ is the sum of the Thole parameters and $w = (\alpha_1 \alpha_2)^{1/6}$.
+pub fn thole_screening(distance: f64, drude1: &Drude, drude2: &Drude) -> f64 {
+    screening(distance, drude1, drude2).0
+}
+
+/// Thole screening and its derivative with respect to distance
+fn screening(distance: f64, drude1: &Drude, drude2: &Drude) -> (f64, f64) {
+    let a = (drude1.thole + drude2.thole)
+        / (drude1.polarizability() * drude2.polarizability()).powf(1.0 / 6.0);
+    let x = a * distance;
+    let exp = (-x).exp();
+    (1.0 - (1.0 + 0.5 * x) * exp, 0.5 * a * (1.0 + x) * exp)
+}
+
+/// Point charges of a configuration, each tagged with its site index and whether it is a Drude charge
+fn charges(
+    sites: &[DrudeSite],
+    drude_positions: &[Vector3],
+) -> Vec<(usize, bool, f64, NalgebraVector3)> {
+    let mut charges = Vec::with_capacity(2 * sites.len());
+    for (i, site) in sites.iter().enumerate() {
+        charges.push((i, false, site.charge, site.position.into()));
+        if let Some(drude) = &site.drude {
+            charges.push((i, true, drude.charge, drude_positions[i].into()));
+        }
+    }
+    charges
+}
+
+/// Thole screened charge of a charge in a Drude dipole, i.e. the Drude charge or its opposite on the core
+fn dipole_charge(site: &DrudeSite, is_drude: bool) -> Option<(Drude, f64)> {
+    site.drude.map(|drude| {
+        (
+            drude,
+            if is_drude {
+                drude.charge
+            } else {
+                -drude.charge
+            },
+        )
+    })
+}
+
+/// Total electrostatic and spring energy of Drude sites for given Drude positions.
+///
+/// All charges interact through `scheme`, except a core with its own Drude charge.
+/// Interactions between Drude dipoles are Thole screened.
+/// Sites without a Drude oscillator can be given any Drude position.
+///
+/// UNIT: [ ( input charge )^2 / ( input length ) ]
+///
+/// # Panics
+///
+/// Panics if `sites` and `drude_positions` differ in length.
+pub fn drude_energy<T: MultipoleField + MultipolePotential>(
+    scheme: &T,
+    sites: &[DrudeSite],
+    drude_positions: &[Vector3],
+) -> f64 {
+    assert_eq!(sites.len(), drude_positions.len());
+    let charges = charges(sites, drude_positions);
+    let mut energy: f64 = sites
+        .iter()
+        .zip(drude_positions)
+        .filter_map(|(site, position)| {
+            let d = NalgebraVector3::from(*position) - NalgebraVector3::from(site.position);
+            site.drude.map(|drude| drude.spring_energy(d))
+        })
+        .sum();
+    for (n, (i, drude_i, q_i, r_i)) in charges.iter().enumerate() {
+        for (j, drude_j, q_j, r_j) in charges.iter().skip(n + 1) {
+            if i == j {
+                continue;
+            }
+            let distance = (r_j - r_i).norm();
+            energy += q_j * scheme.ion_potential(*q_i, distance);
+            if let (Some((d_i, p_i)), Some((d_j, p_j))) = (
+                dipole_charge(&sites[*i], *drude_i),
+                dipole_charge(&sites[*j], *drude_j),
+            ) {
+                energy -= (1.0 - thole_screening(distance, &d_i, &d_j)) * p_i * p_j / distance;
+            }
+        }
+    }
+    energy
+}
+
+/// Electrostatic force on the Drude charge of site `i`
+fn drude_force<T: MultipoleField + MultipolePotential>(
+    scheme: &T,
+    sites: &[DrudeSite],
+    charges: &[(usize, bool, f64, NalgebraVector3)],
+    i: usize,
+    position: NalgebraVector3,
+) -> NalgebraVector3 {
+    let drude = sites[i].drude.expect("site has a Drude oscillator");
+    let mut force = NalgebraVector3::zeros();
+    for (j, drude_j, q_j, r_j) in charges.iter().filter(|c| c.0 != i) {
+        let r = position - r_j;
+        force += NalgebraVector3::from(scheme.ion_field(*q_j, r)) * drude.charge;
+        if let Some((d_j, p_j)) = dipole_charge(&sites[*j], *drude_j) {
+            // Correction energy, -(1 - S) q q' / r, and its negative gradient
+            let distance = r.norm();
+            let (s, ds) = screening(distance, &drude, &d_j);
+            let dudr = drude.charge * p_j * (ds / distance + (1.0 - s) / (distance * distance));
+            force -= r / distance * dudr;
+        }
+    }
+    force
+}
+
+/// Self-consistent Drude positions for a frozen configuration of cores.
+///
+/// Each Drude charge is placed where the spring force balances the electrostatic force,
+/// $\boldsymbol{d}_i = \boldsymbol{F}_i / k_i$, which minimizes [`drude_energy`].
+/// The iteration stops when the largest change in a Drude displacement is below the
+/// tolerance of `solver`.
+/// Returns the Drude positions with the core position for sites without a Drude oscillator.
+pub fn drude_positions<T: MultipoleField + MultipolePotential>(
+    scheme: &T,
+    sites: &[DrudeSite],
+    solver: &Solver,
+) -> crate::Result<Vec<Vector3>> {
+    let mut positions: Vec<Vector3> = sites.iter().map(|site| site.position).collect();
+    for _ in 0..solver.max_iterations {
+        let charges = charges(sites, &positions);
+        let mut max_change: f64 = 0.0;
+        let mut new_positions = positions.clone();
+        for (i, site) in sites.iter().enumerate() {
+            if let Some(drude) = &site.drude {
+                let position = NalgebraVector3::from(positions[i]);
+                let force = drude_force(scheme, sites, &charges, i, position);
+                let new_position =
+                    NalgebraVector3::from(site.position) + force / drude.force_constant;
+                let change = (new_position - position).norm();
+                if change.is_nan() || change > max_change {
+                    max_change = change;
+                }
+                new_positions[i] = new_position.into();
+            }
+        }
+        positions = new_positions;
+        if max_change < solver.tolerance {
+            return Ok(positions);
+        }
+    }
+    Err(crate::Error::NotConverged(solver.max_iterations))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pairwise::Plain;
+    use approx::assert_relative_eq;
+
+    fn sites() -> Vec<DrudeSite> {
+        let drude = |charge: f64, alpha: f64| Drude {
+            charge,
+            force_constant: charge * charge / alpha,
+            thole: 1.3,
+        };
+        vec![
+            DrudeSite {
+                position: [0.0, 0.0, 0.0].into(),
+                charge: 1.0,
+                drude: None,
+            },
+            DrudeSite {
+                position: [3.0, 0.5, -0.2].into(),
+                charge: 1.5,
+                drude: Some(drude(-2.0, 1.2)),
+            },
+            DrudeSite {
+                position: [1.6, 2.2, 0.4].into(),
+                charge: 1.7,
+                drude: Some(drude(-1.8, 0.8)),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_drude() {
+        let drude = Drude {
+            charge: -2.0,
+            force_constant: 4.0,
+            thole: 1.3,
+        };
+        assert_relative_eq!(drude.polarizability(), 1.0);
+        assert_relative_eq!(drude.spring_energy([0.0, 0.1, 0.2]), 0.1);
+        assert_relative_eq!(thole_screening(50.0, &drude, &drude), 1.0);
+        assert!(thole_screening(1.0, &drude, &drude) < 1.0);
+    }
+
+    #[test]
+    fn test_drude_positions() {
+        let sites = sites();
+        let scheme = Plain::without_cutoff();
+        let positions = drude_positions(&scheme, &sites, &Solver::default()).unwrap();
+        assert_eq!(positions[0], sites[0].position);
+
+        // Relaxed Drude positions minimize the energy
+        let h = 1e-5;
+        for i in 1..sites.len() {
+            for a in 0..3 {
+                let energy = |dx: f64| {
+                    let mut positions = positions.clone();
+                    let mut r = NalgebraVector3::from(positions[i]);
+                    r[a] += dx;
+                    positions[i] = r.into();
+                    drude_energy(&scheme, &sites, &positions)
+                };
+                let gradient = (energy(h) - energy(-h)) / (2.0 * h);
+                assert_relative_eq!(gradient, 0.0, epsilon = 1e-7);
+            }
+        }
+    }
+
+    #[test]
+    fn test_induced_dipole_limit() {
+        // A stiff Drude oscillator becomes a point polarizability
+        let alpha = 0.5;
+        let charge = -100.0;
+        let sites = [
+            DrudeSite {
+                position: [0.0, 0.0, 0.0].into(),
+                charge: 1.0,
+                drude: None,
+            },
+            DrudeSite {
+                position: [0.0, 0.0, 5.0].into(),
+                charge: -charge,
+                drude: Some(Drude {
+                    charge,
+                    force_constant: charge * charge / alpha,
+                    thole: 1.3,
+                }),
+            },
+        ];
+        let scheme = Plain::without_cutoff();
+        let positions = drude_positions(&scheme, &sites, &Solver::default()).unwrap();
+        let dipole = charge * (positions[1].z - sites[1].position.z);
+        assert_relative_eq!(dipole, alpha / 25.0, max_relative = 1e-4);
+    }
+}
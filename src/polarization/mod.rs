@@ -0,0 +1,491 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Self-consistent induced dipoles.
+//!
+//! Sites with isotropic polarizabilities, $\alpha_i$, acquire induced dipoles,
+//!
+//! $$ \boldsymbol{\mu}_i = \alpha_i \left( \boldsymbol{E}^0_i + \sum_{j \ne i} \boldsymbol{E}(\boldsymbol{\mu}_j, \boldsymbol{r}_i - \boldsymbol{r}_j) \right) $$
+//!
+//! where $\boldsymbol{E}^0_i$ is the field from the permanent charges of all other sites.
+//! All fields are evaluated with a pairwise interaction scheme
+//! so that cutoffs, screening and splitting functions are respected.
+//! At convergence, the polarization energy is
+//!
+//! $$ U_{pol} = -\frac{1}{2} \sum_i \boldsymbol{\mu}_i \cdot \boldsymbol{E}^0_i $$
+//!
+//! To avoid a polarization catastrophe at close contact, charge-dipole and dipole-dipole
+//! interactions can be damped at short range with [`Thole`].
+//! Alternatively, polarization can be described by [`Drude`] oscillators.
+//!
+//! Polarizabilities have units of [ ( input length )^3 ] and energies, as in the
+//! [`pairwise`](crate::pairwise) module, units of [ ( input charge )^2 / ( input length ) ].
+//!
+//! # Examples
+//! ~~~
+//! # use approx::assert_relative_eq;
+//! use coulomb::pairwise::Plain;
+//! use coulomb::polarization::Solver;
+//! let positions = [[0.0, 0.0, 0.0].into(), [0.0, 0.0, 4.0].into()];
+//! let (charges, polarizabilities) = ([1.0, 0.0], [0.0, 2.0]);
+//! let result = Solver::default()
+//!     .solve(&Plain::without_cutoff(), &positions, &charges, &polarizabilities)
+//!     .unwrap();
+//! assert_relative_eq!(result.dipoles[1].z, 2.0 / 16.0, epsilon = 1e-10);
+//! assert_relative_eq!(result.energy, -0.5 * 2.0 / 256.0, epsilon = 1e-10);
+//! ~~~
+
+use crate::pairwise::{tensor::RadialDerivatives, Multipole, MultipoleForce};
+use crate::{NalgebraVector3, Vector3};
+
+mod drude;
+mod thole;
+pub use drude::{drude_energy, drude_positions, thole_screening, Drude, DrudeSite};
+pub use thole::Thole;
+
+/// Method for solving the induced dipoles.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Method {
+    /// Self-consistent field iteration where new dipoles are mixed with the previous
+    /// ones, μ ← (1 - w) μ + w μ', with mixing parameter _w_.
+    SelfConsistent {
+        /// Mixing parameter, 0 < w ≤ 1
+        mixing: f64,
+    },
+    /// Preconditioned conjugate gradient (default).
+    #[default]
+    ConjugateGradient,
+}
+
+/// Solver for self-consistent induced dipoles.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Solver {
+    /// Solution method
+    pub method: Method,
+    /// Convergence criterion on the largest change in (or residual of) an induced dipole,
+    /// UNIT: [ ( input length ) x ( input charge ) ]
+    pub tolerance: f64,
+    /// Maximum number of iterations
+    pub max_iterations: usize,
+    /// Optional short-range damping of charge-dipole and dipole-dipole interactions
+    pub damping: Option<Thole>,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self {
+            method: Method::default(),
+            tolerance: 1e-10,
+            max_iterations: 1000,
+            damping: None,
+        }
+    }
+}
+
+/// Converged induced dipoles and derived properties.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polarization {
+    /// Induced dipole moment of each site
+    pub dipoles: Vec<Vector3>,
+    /// Polarization energy, UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub energy: f64,
+    /// Force on each site due to polarization, UNIT: [ ( input charge )^2 / ( input length )^2 ]
+    ///
+    /// These exclude interactions between permanent charges.
+    pub forces: Vec<Vector3>,
+    /// Number of iterations used
+    pub iterations: usize,
+}
+
+/// Sites with charges and polarizabilities
+struct Sites<'a, T: MultipoleForce> {
+    scheme: &'a T,
+    positions: Vec<NalgebraVector3>,
+    charges: &'a [f64],
+    polarizabilities: &'a [f64],
+    damping: Option<Thole>,
+}
+
+impl<T: MultipoleForce> Sites<'_, T> {
+    fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Pairs of distinct sites, (i, j), with the first site polarizable
+    fn polarizable_pairs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.len())
+            .filter(|i| self.polarizabilities[*i] != 0.0)
+            .flat_map(move |i| {
+                (0..self.len())
+                    .filter(move |j| *j != i)
+                    .map(move |j| (i, j))
+            })
+    }
+
+    /// Damping correction to the interaction tensors between sites `i` and `j`, evaluated at r = rᵢ - rⱼ
+    fn damping_correction(&self, i: usize, j: usize) -> Option<RadialDerivatives> {
+        let r = self.positions[i] - self.positions[j];
+        if r.norm_squared() >= self.scheme.cutoff_squared() {
+            return None;
+        }
+        self.damping?
+            .correction(r, self.polarizabilities[i], self.polarizabilities[j])
+    }
+
+    /// Field from the permanent charges on each polarizable site
+    fn permanent_field(&self) -> Vec<NalgebraVector3> {
+        let mut field = vec![NalgebraVector3::zeros(); self.len()];
+        for (i, j) in self.polarizable_pairs() {
+            if self.charges[j] != 0.0 {
+                let r = self.positions[i] - self.positions[j];
+                field[i] += NalgebraVector3::from(self.scheme.ion_field(self.charges[j], r));
+                if let Some(t) = self.damping_correction(i, j) {
+                    field[i] -= r * (self.charges[j] * t.t[1]);
+                }
+            }
+        }
+        field
+    }
+
+    /// Field from dipoles on each polarizable site
+    fn dipole_field(&self, dipoles: &[NalgebraVector3]) -> Vec<NalgebraVector3> {
+        let mut field = vec![NalgebraVector3::zeros(); self.len()];
+        for (i, j) in self.polarizable_pairs() {
+            if self.polarizabilities[j] != 0.0 {
+                let r = self.positions[i] - self.positions[j];
+                field[i] += NalgebraVector3::from(self.scheme.dipole_field(dipoles[j], r));
+                if let Some(t) = self.damping_correction(i, j) {
+                    field[i] += t.rank2() * dipoles[j];
+                }
+            }
+        }
+        field
+    }
+
+    /// Apply α to a per-site field
+    fn polarize(&self, field: &[NalgebraVector3]) -> Vec<NalgebraVector3> {
+        field
+            .iter()
+            .zip(self.polarizabilities)
+            .map(|(e, alpha)| e * *alpha)
+            .collect()
+    }
+
+    /// Residual, E⁰ + T μ - μ / α, for polarizable sites
+    fn residual(
+        &self,
+        permanent_field: &[NalgebraVector3],
+        dipoles: &[NalgebraVector3],
+    ) -> Vec<NalgebraVector3> {
+        let dipole_field = self.dipole_field(dipoles);
+        (0..self.len())
+            .map(|i| match self.polarizabilities[i] {
+                0.0 => NalgebraVector3::zeros(),
+                alpha => permanent_field[i] + dipole_field[i] - dipoles[i] / alpha,
+            })
+            .collect()
+    }
+
+    /// Forces on all sites from the converged dipoles, excluding charge-charge interactions
+    fn forces(&self, dipoles: &[NalgebraVector3]) -> Vec<Vector3> {
+        let mut forces = vec![NalgebraVector3::zeros(); self.len()];
+        let multipoles: Vec<_> = self
+            .charges
+            .iter()
+            .zip(dipoles)
+            .map(|(charge, dipole)| Multipole::new(*charge).with_dipole(*dipole))
+            .collect();
+        for i in 0..self.len() {
+            for j in (i + 1)..self.len() {
+                let r = self.positions[j] - self.positions[i];
+                let force = NalgebraVector3::from(self.scheme.pair_force(
+                    &multipoles[i],
+                    &multipoles[j],
+                    r,
+                )) - NalgebraVector3::from(self.scheme.ion_ion_force(
+                    self.charges[i],
+                    self.charges[j],
+                    r,
+                ));
+                forces[j] += force;
+                forces[i] -= force;
+                if let Some(t) = self.damping_correction(i, j) {
+                    // Force on site i from the damping of charge-dipole and dipole-dipole terms
+                    let (mu_i, mu_j) = (&dipoles[i], &dipoles[j]);
+                    let hessian = t.rank2();
+                    let force = hessian * mu_j * self.charges[i] - hessian * mu_i * self.charges[j]
+                        + t.contract_rank3_matrix(mu_i) * mu_j;
+                    forces[i] += force;
+                    forces[j] -= force;
+                }
+            }
+        }
+        forces.into_iter().map(Into::into).collect()
+    }
+}
+
+/// Sum of dot products between two sets of vectors
+fn dot(a: &[NalgebraVector3], b: &[NalgebraVector3]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a.dot(b)).sum()
+}
+
+/// Largest norm in a set of vectors; NaN if any norm is NaN
+fn max_norm(a: &[NalgebraVector3]) -> f64 {
+    a.iter()
+        .map(|v| v.norm())
+        .fold(0.0, |max, x| if x > max || x.is_nan() { x } else { max })
+}
+
+impl Solver {
+    /// Solve for the induced dipoles of sites with given positions, charges, and polarizabilities.
+    ///
+    /// Sites with zero polarizability are not polarized, but contribute with their charges.
+    /// Returns [`Error::NotConverged`](crate::Error::NotConverged) if the solution is not
+    /// converged within the maximum number of iterations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `positions`, `charges`, and `polarizabilities` differ in length.
+    pub fn solve<T: MultipoleForce>(
+        &self,
+        scheme: &T,
+        positions: &[Vector3],
+        charges: &[f64],
+        polarizabilities: &[f64],
+    ) -> crate::Result<Polarization> {
+        assert_eq!(positions.len(), charges.len());
+        assert_eq!(positions.len(), polarizabilities.len());
+        let sites = Sites {
+            scheme,
+            positions: positions
+                .iter()
+                .copied()
+                .map(NalgebraVector3::from)
+                .collect(),
+            charges,
+            polarizabilities,
+            damping: self.damping,
+        };
+        let permanent_field = sites.permanent_field();
+        let (dipoles, iterations) = match self.method {
+            Method::SelfConsistent { mixing } => {
+                self.self_consistent(&sites, &permanent_field, mixing)?
+            }
+            Method::ConjugateGradient => self.conjugate_gradient(&sites, &permanent_field)?,
+        };
+        Ok(Polarization {
+            energy: -0.5 * dot(&dipoles, &permanent_field),
+            forces: sites.forces(&dipoles),
+            dipoles: dipoles.into_iter().map(Into::into).collect(),
+            iterations,
+        })
+    }
+
+    fn self_consistent<T: MultipoleForce>(
+        &self,
+        sites: &Sites<T>,
+        permanent_field: &[NalgebraVector3],
+        mixing: f64,
+    ) -> crate::Result<(Vec<NalgebraVector3>, usize)> {
+        let mut dipoles = sites.polarize(permanent_field);
+        for iteration in 1..=self.max_iterations {
+            let field: Vec<_> = sites
+                .dipole_field(&dipoles)
+                .iter()
+                .zip(permanent_field)
+                .map(|(a, b)| a + b)
+                .collect();
+            let change: Vec<_> = sites
+                .polarize(&field)
+                .iter()
+                .zip(&dipoles)
+                .map(|(new, old)| (new - old) * mixing)
+                .collect();
+            dipoles.iter_mut().zip(&change).for_each(|(mu, d)| *mu += d);
+            if max_norm(&change) < self.tolerance {
+                return Ok((dipoles, iteration));
+            }
+        }
+        Err(crate::Error::NotConverged(self.max_iterations))
+    }
+
+    /// Solves (1/α - T) μ = E⁰ with α as preconditioner
+    fn conjugate_gradient<T: MultipoleForce>(
+        &self,
+        sites: &Sites<T>,
+        permanent_field: &[NalgebraVector3],
+    ) -> crate::Result<(Vec<NalgebraVector3>, usize)> {
+        let mut dipoles = sites.polarize(permanent_field);
+        let mut residual = sites.residual(permanent_field, &dipoles);
+        let mut preconditioned = sites.polarize(&residual);
+        let mut direction = preconditioned.clone();
+        let mut rz = dot(&residual, &preconditioned);
+        for iteration in 1..=self.max_iterations {
+            if max_norm(&preconditioned) < self.tolerance {
+                return Ok((dipoles, iteration));
+            }
+            // A p = p / α - T p, obtained from the residual with zero permanent field
+            let zero_field = vec![NalgebraVector3::zeros(); sites.len()];
+            let a_direction: Vec<_> = sites
+                .residual(&zero_field, &direction)
+                .into_iter()
+                .map(|r| -r)
+                .collect();
+            let step = rz / dot(&direction, &a_direction);
+            for i in 0..sites.len() {
+                dipoles[i] += direction[i] * step;
+                residual[i] -= a_direction[i] * step;
+            }
+            preconditioned = sites.polarize(&residual);
+            let rz_new = dot(&residual, &preconditioned);
+            let beta = rz_new / rz;
+            rz = rz_new;
+            for i in 0..sites.len() {
+                direction[i] = preconditioned[i] + direction[i] * beta;
+            }
+        }
+        Err(crate::Error::NotConverged(self.max_iterations))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pairwise::{Plain, RealSpaceEwald};
+    use crate::test_utils::{erfc_step as step, ERFC_ERROR};
+    use approx::assert_relative_eq;
+
+    fn system() -> (Vec<Vector3>, Vec<f64>, Vec<f64>) {
+        let positions = vec![
+            [0.0, 0.0, 0.0].into(),
+            [3.0, 0.5, -0.2].into(),
+            [-1.0, 3.2, 0.4].into(),
+            [0.5, -0.8, 3.5].into(),
+        ];
+        let charges = vec![1.0, -0.5, 0.0, 0.3];
+        let polarizabilities = vec![0.0, 1.2, 0.8, 1.5];
+        (positions, charges, polarizabilities)
+    }
+
+    #[test]
+    fn test_methods_agree() {
+        let (positions, charges, polarizabilities) = system();
+        let scheme = Plain::without_cutoff();
+        let cg = Solver::default()
+            .solve(&scheme, &positions, &charges, &polarizabilities)
+            .unwrap();
+        let scf = Solver {
+            method: Method::SelfConsistent { mixing: 0.8 },
+            ..Default::default()
+        }
+        .solve(&scheme, &positions, &charges, &polarizabilities)
+        .unwrap();
+        assert!(cg.iterations < scf.iterations);
+        assert_relative_eq!(cg.energy, scf.energy, epsilon = 1e-10);
+        for (a, b) in cg.dipoles.iter().zip(&scf.dipoles) {
+            let (a, b) = (NalgebraVector3::from(*a), NalgebraVector3::from(*b));
+            assert_relative_eq!(a, b, epsilon = 1e-9);
+        }
+        // Non-polarizable sites have no induced dipole
+        assert_relative_eq!(NalgebraVector3::from(cg.dipoles[0]).norm(), 0.0);
+
+        let error = Solver {
+            max_iterations: 2,
+            ..Default::default()
+        }
+        .solve(&scheme, &positions, &charges, &polarizabilities);
+        assert!(matches!(error, Err(crate::Error::NotConverged(2))));
+    }
+
+    #[test]
+    fn test_forces() {
+        let (positions, charges, polarizabilities) = system();
+        let scheme = RealSpaceEwald::new(12.0, 0.2, None);
+        let solver = Solver::default();
+        let result = solver
+            .solve(&scheme, &positions, &charges, &polarizabilities)
+            .unwrap();
+        // An approximate `erfc_x` adds an error of up to the bound times the bare energy over
+        // the step to the difference quotient
+        let h = step(1e-5, 1e-2);
+        let mut bare = 0.0;
+        for i in 0..positions.len() {
+            for j in 0..i {
+                let r = NalgebraVector3::from(positions[i]) - NalgebraVector3::from(positions[j]);
+                bare += (charges[i] * charges[j]).abs() / r.norm();
+            }
+        }
+        for i in 0..positions.len() {
+            for a in 0..3 {
+                let energy = |dx: f64| {
+                    let mut positions = positions.clone();
+                    let mut r = NalgebraVector3::from(positions[i]);
+                    r[a] += dx;
+                    positions[i] = r.into();
+                    solver
+                        .solve(&scheme, &positions, &charges, &polarizabilities)
+                        .unwrap()
+                        .energy
+                };
+                let force = NalgebraVector3::from(result.forces[i])[a];
+                let expected = -(energy(h) - energy(-h)) / (2.0 * h);
+                assert_relative_eq!(force, expected, epsilon = 1e-8 + ERFC_ERROR * bare / h);
+            }
+        }
+    }
+
+    #[test]
+    fn test_thole_damping() {
+        let (mut positions, charges, mut polarizabilities) = system();
+        // Close contact between two polarizable sites leads to a polarization catastrophe
+        positions[2] = [2.4, 0.9, -0.4].into();
+        polarizabilities[2] = 2.0;
+        let scheme = Plain::without_cutoff();
+        let undamped = Solver {
+            method: Method::SelfConsistent { mixing: 1.0 },
+            ..Default::default()
+        };
+        assert!(undamped
+            .solve(&scheme, &positions, &charges, &polarizabilities)
+            .is_err());
+
+        for damping in [Thole::linear(), Thole::exponential()] {
+            let solver = Solver {
+                damping: Some(damping),
+                ..Default::default()
+            };
+            let result = solver
+                .solve(&scheme, &positions, &charges, &polarizabilities)
+                .unwrap();
+            let h = 1e-5;
+            for i in 0..positions.len() {
+                for a in 0..3 {
+                    let energy = |dx: f64| {
+                        let mut positions = positions.clone();
+                        let mut r = NalgebraVector3::from(positions[i]);
+                        r[a] += dx;
+                        positions[i] = r.into();
+                        solver
+                            .solve(&scheme, &positions, &charges, &polarizabilities)
+                            .unwrap()
+                            .energy
+                    };
+                    let force = NalgebraVector3::from(result.forces[i])[a];
+                    let expected = -(energy(h) - energy(-h)) / (2.0 * h);
+                    assert_relative_eq!(force, expected, epsilon = 1e-8);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Thole damping of short-range induction.
+
+use crate::pairwise::tensor::RadialDerivatives;
+use crate::NalgebraVector3;
+
+/// Thole damping of charge-dipole and dipole-dipole interactions at short range.
+///
+/// Point polarizabilities at close contact lead to the _polarization catastrophe_
+/// where induced dipoles diverge.
+/// Thole's model smears the interacting sites over a distance
+/// $s = (\alpha_i \alpha_j)^{1/6}$ which scales the bare interaction tensors of rank
+/// one, two and three by factors $\lambda_3$, $\lambda_5$, and $\lambda_7$ of the reduced
+/// distance $u = r / s$.
+/// The damping is applied as a short-range correction to the interaction scheme.
+///
+/// - Linear: <https://doi.org/10.1016/0301-0104(81)85176-2>
+/// - Exponential: <https://doi.org/10.1021/jp027815+>
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Thole {
+    /// Linearly decaying smeared charge with width parameter _a_
+    Linear {
+        /// Dimensionless width parameter
+        a: f64,
+    },
+    /// Exponentially decaying smeared charge with damping parameter _a_
+    Exponential {
+        /// Dimensionless damping parameter
+        a: f64,
+    },
+}
+
+impl Thole {
+    /// Linear damping with the parameter _a_ = 1.662 from van Duijnen and Swart.
+    pub const fn linear() -> Self {
+        Self::Linear { a: 1.662 }
+    }
+
+    /// Exponential damping with the parameter _a_ = 0.39 used in the AMOEBA force field.
+    pub const fn exponential() -> Self {
+        Self::Exponential { a: 0.39 }
+    }
+
+    /// Damping factors, $[\lambda_3, \lambda_5, \lambda_7]$, at `distance` between sites with
+    /// polarizabilities `alpha1` and `alpha2`.
+    ///
+    /// All factors approach unity at large separation, or if either polarizability is zero.
+    pub fn damping_factors(&self, distance: f64, alpha1: f64, alpha2: f64) -> [f64; 3] {
+        let width = (alpha1 * alpha2).powf(1.0 / 6.0);
+        if width <= 0.0 {
+            return [1.0; 3];
+        }
+        let u = distance / width;
+        match *self {
+            Self::Linear { a } => {
+                let v = u / a;
+                if v >= 1.0 {
+                    return [1.0; 3];
+                }
+                let v3 = v.powi(3);
+                [4.0 * v3 - 3.0 * v3 * v, v3 * v, 0.2 * v3 * v]
+            }
+            Self::Exponential { a } => {
+                let x = a * u.powi(3);
+                let exp = (-x).exp();
+                [
+                    1.0 - exp,
+                    1.0 - (1.0 + x) * exp,
+                    1.0 - (1.0 + x + 0.6 * x * x) * exp,
+                ]
+            }
+        }
+    }
+
+    /// Difference between damped and bare reduced radial derivatives, $t_n$, of $1/r$.
+    ///
+    /// Returns `None` if there is no damping.
+    pub(crate) fn correction(
+        &self,
+        r: NalgebraVector3,
+        alpha1: f64,
+        alpha2: f64,
+    ) -> Option<RadialDerivatives> {
+        let r2 = r.norm_squared();
+        let r1 = r2.sqrt();
+        let [l3, l5, l7] = self.damping_factors(r1, alpha1, alpha2);
+        if l3 == 1.0 && l5 == 1.0 && l7 == 1.0 {
+            return None;
+        }
+        let r3 = r2 * r1;
+        let t = [
+            0.0,
+            (1.0 - l3) / r3,
+            -3.0 * (1.0 - l5) / (r3 * r2),
+            15.0 * (1.0 - l7) / (r3 * r2 * r2),
+            0.0,
+        ];
+        Some(RadialDerivatives { r, t })
+    }
+}
+
+#[test]
+fn test_thole() {
+    use approx::assert_relative_eq;
+    let (alpha1, alpha2) = (1.2, 0.8);
+    for thole in [Thole::linear(), Thole::exponential()] {
+        // Consistency between factors, λ₅ = λ₃ - r λ₃' / 3 and λ₇ = λ₅ - r λ₅' / 5
+        let (r, h) = (1.1, 1e-6);
+        let [l3, l5, l7] = thole.damping_factors(r, alpha1, alpha2);
+        let [l3_p, l5_p, _] = thole.damping_factors(r + h, alpha1, alpha2);
+        let [l3_m, l5_m, _] = thole.damping_factors(r - h, alpha1, alpha2);
+        assert_relative_eq!(l5, l3 - r * (l3_p - l3_m) / (2.0 * h) / 3.0, epsilon = 1e-8);
+        assert_relative_eq!(l7, l5 - r * (l5_p - l5_m) / (2.0 * h) / 5.0, epsilon = 1e-8);
+        assert!(l3 < 1.0 && l5 < 1.0 && l7 < 1.0);
+        // No damping at long range or without polarizability
+        let far = thole.damping_factors(20.0, alpha1, alpha2);
+        far.iter().for_each(|l| assert_relative_eq!(*l, 1.0));
+        assert_eq!(thole.damping_factors(r, 0.0, alpha2), [1.0; 3]);
+    }
+    let v: f64 = 0.5 / 1.662;
+    let [l3, l5, l7] = Thole::linear().damping_factors(0.5, 1.0, 1.0);
+    assert_relative_eq!(l3, 4.0 * v.powi(3) - 3.0 * v.powi(4));
+    assert_relative_eq!(l5, v.powi(4));
+    assert_relative_eq!(l7, 0.2 * v.powi(4));
+}
@@ -0,0 +1,808 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Reciprocal-space sum of Ewald summation.
+
+use super::{BoundaryPermittivity, ReciprocalEnergy, ReciprocalForce};
+use crate::pbc::Cell;
+use crate::system::ParticleSystem;
+use crate::{Matrix3, NalgebraMatrix3, NalgebraVector3, Vector3};
+use core::f64::consts::PI;
+use num_complex::Complex64;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Reciprocal-space energy and forces of point charges and dipoles in a periodic cell.
+///
+/// The energy is
+///
+/// $$ U = \frac{2\pi}{V} \sum_{\mathbf{k}} \frac{e^{-(k^2 + \kappa^2) / 4\alpha^2}}{k^2 + \kappa^2} |Q(\mathbf{k})|^2 $$
+///
+/// with the structure factor
+/// $Q(\mathbf{k}) = \sum_j (q_j + i \boldsymbol{\mu}_j \cdot \mathbf{k}) e^{i \mathbf{k} \cdot \mathbf{r}_j}$.
+/// The wave vectors are $\mathbf{k} = 2\pi \mathbf{H}^{-T} \mathbf{n}$ where $\mathbf{H}$ holds
+/// the lattice vectors and $\mathbf{n}$ are integer vectors with
+/// $0 < |\mathbf{n}| \le n_{max}$.
+/// With salt screening, $\kappa > 0$, the $\mathbf{k} = 0$ term is finite and included.
+///
+/// This complements [`RealSpaceEwald`](crate::pairwise::RealSpaceEwald) with the same damping
+/// parameter, α, and Debye length, which also provides the self-energy.
+/// Energies and forces are given through the [`ReciprocalEnergy`] and [`ReciprocalForce`] traits.
+/// Salt-free systems with a net charge are neutralized by a uniform background,
+/// see [`ReciprocalEnergy::background_energy`].
+///
+/// Since $Q(-\mathbf{k}) = \overline{Q(\mathbf{k})}$, only one of each $\pm\mathbf{k}$ pair is
+/// evaluated, and the phase factors $e^{i \mathbf{k} \cdot \mathbf{r}_j}$ are combined from
+/// powers of the phase factors along each reciprocal lattice vector.
+/// For Monte Carlo simulations, a [`StructureFactorCache`] gives the energy change of
+/// single particle moves without recomputing the structure factors.
+///
+/// With the `rayon` feature, the sums over wave vectors and particles run in parallel.
+/// Partial results are collected in order and reduced serially, so that results are
+/// identical to single-threaded evaluation regardless of the number of threads.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::{pairwise::RealSpaceEwald, reciprocal::EwaldReciprocal, system::ParticleSystem};
+/// // Rock salt lattice with unit spacing, U = -1.747565 per ion pair
+/// let (mut positions, mut charges) = (Vec::new(), Vec::new());
+/// for (x, y, z) in (0..8).map(|n| (n & 1, (n >> 1) & 1, n >> 2)) {
+///     positions.push([x as f64, y as f64, z as f64].into());
+///     charges.push(if (x + y + z) % 2 == 0 { 1.0 } else { -1.0 });
+/// }
+/// let ewald = EwaldReciprocal::new(4.0, 14, None);
+/// let system = ParticleSystem::new(positions, charges)
+///     .with_box_length([2.0; 3])
+///     .with_reciprocal(ewald);
+/// let energy = system.total_energy(&RealSpaceEwald::new(1.0, 4.0, None));
+/// assert_relative_eq!(energy / 4.0, -1.747565, epsilon = 1e-6);
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct EwaldReciprocal {
+    /// Damping parameter, UNIT: [ ( input length )^-1 ]
+    alpha: f64,
+    /// Largest length of the integer wave vectors, 𝑛ₘₐₓ
+    max_wave_number: u32,
+    /// Debye length, UNIT: [ input length ]
+    debye_length: Option<f64>,
+    /// Permittivity at the boundary
+    #[cfg_attr(feature = "serde", serde(default))]
+    surface_permittivity: BoundaryPermittivity,
+}
+
+impl EwaldReciprocal {
+    /// New reciprocal-space sum with damping parameter, α, largest integer wave number, and
+    /// optional Debye length.
+    ///
+    /// Tinfoil boundary conditions are used by default.
+    pub const fn new(alpha: f64, max_wave_number: u32, debye_length: Option<f64>) -> Self {
+        Self {
+            alpha,
+            max_wave_number,
+            debye_length,
+            surface_permittivity: BoundaryPermittivity::Tinfoil,
+        }
+    }
+
+    /// Set the permittivity at the boundary
+    pub const fn with_surface_permittivity(
+        mut self,
+        surface_permittivity: BoundaryPermittivity,
+    ) -> Self {
+        self.surface_permittivity = surface_permittivity;
+        self
+    }
+
+    /// Damping parameter, α, UNIT: [ ( input length )^-1 ]
+    pub const fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Largest length of the integer wave vectors, 𝑛ₘₐₓ
+    pub const fn max_wave_number(&self) -> u32 {
+        self.max_wave_number
+    }
+
+    /// Permittivity at the boundary
+    pub const fn surface_permittivity(&self) -> BoundaryPermittivity {
+        self.surface_permittivity
+    }
+
+    /// Reciprocal lattice vectors, 2π𝐇⁻ᵀ, as columns, and the volume of the cell
+    fn reciprocal_lattice(cell: &Cell) -> (NalgebraMatrix3, f64) {
+        let (matrix, volume) = cell
+            .box_matrix()
+            .zip(cell.volume())
+            .expect("reciprocal space requires a periodic cell");
+        let reciprocal = 2.0
+            * PI
+            * NalgebraMatrix3::from(matrix)
+                .try_inverse()
+                .expect("lattice vectors must be linearly independent")
+                .transpose();
+        (reciprocal, volume)
+    }
+
+    /// Integer vectors, 𝐧, with |𝐧| ≤ 𝑛ₘₐₓ, including 𝐧 = 0 only with salt.
+    /// If `half` is true, only one of each ±𝐧 pair is included.
+    fn integer_vectors(&self, half: bool) -> Vec<[i32; 3]> {
+        let salt = self.debye_length.is_some();
+        let n_max = self.max_wave_number as i32;
+        let range = || -n_max..=n_max;
+        let mut vectors = Vec::new();
+        for nx in range() {
+            for ny in range() {
+                for nz in range() {
+                    let n_squared = nx * nx + ny * ny + nz * nz;
+                    let positive = nx > 0 || (nx == 0 && (ny > 0 || (ny == 0 && nz > 0)));
+                    if n_squared > n_max * n_max
+                        || (n_squared == 0 && !salt)
+                        || (half && n_squared > 0 && !positive)
+                    {
+                        continue;
+                    }
+                    vectors.push([nx, ny, nz]);
+                }
+            }
+        }
+        vectors
+    }
+
+    /// Energy prefactor, 2π exp(-(𝑘² + κ²)/4α²) / 𝑉(𝑘² + κ²)
+    fn prefactor(&self, k: &NalgebraVector3, volume: f64) -> f64 {
+        let kappa_squared = crate::DebyeLength::kappa(self).map_or(0.0, |kappa| kappa * kappa);
+        let k_squared = k.norm_squared() + kappa_squared;
+        2.0 * PI / volume * f64::exp(-k_squared / (4.0 * self.alpha.powi(2))) / k_squared
+    }
+
+    /// Wave vectors in one half of reciprocal space with prefactors doubled for ±𝐤 pairs.
+    ///
+    /// For real charges and dipoles, 𝑄(-𝐤) is the complex conjugate of 𝑄(𝐤) so that both
+    /// wave vectors contribute equally to energies, forces and virials.
+    fn waves(&self, cell: &Cell) -> (NalgebraMatrix3, Vec<Wave>) {
+        let (reciprocal, volume) = Self::reciprocal_lattice(cell);
+        let waves = self
+            .integer_vectors(true)
+            .into_iter()
+            .map(|n| {
+                let k = reciprocal * NalgebraVector3::from(n.map(f64::from));
+                let pairs = if n == [0; 3] { 1.0 } else { 2.0 };
+                Wave {
+                    n,
+                    k,
+                    prefactor: pairs * self.prefactor(&k, volume),
+                }
+            })
+            .collect();
+        (reciprocal, waves)
+    }
+
+    /// Phase tables of all particles and the structure factors, 𝑄(𝐤), of each wave
+    fn structure_factors(
+        &self,
+        system: &ParticleSystem,
+        reciprocal: &NalgebraMatrix3,
+        waves: &[Wave],
+    ) -> (Vec<PhaseTable>, Vec<Complex64>) {
+        let tables = map_indices(system.len(), |index| {
+            PhaseTable::new(reciprocal, self.max_wave_number, system.positions()[index])
+        });
+        let structure_factors = map_indices(waves.len(), |i| {
+            tables
+                .iter()
+                .enumerate()
+                .map(|(index, table)| waves[i].amplitude(system, index, table.phase(&waves[i].n)))
+                .sum()
+        });
+        (tables, structure_factors)
+    }
+
+    /// Structure factors of a system for incremental energy updates, _e.g._ in Monte Carlo
+    /// simulations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not periodic.
+    pub fn structure_factor_cache(&self, system: &ParticleSystem) -> StructureFactorCache {
+        let (reciprocal, waves) = self.waves(system.cell());
+        let (_, structure_factors) = self.structure_factors(system, &reciprocal, &waves);
+        StructureFactorCache {
+            reciprocal,
+            max_wave_number: self.max_wave_number,
+            waves,
+            structure_factors,
+            surface_prefactor: super::surface_prefactor(self.surface_permittivity, system.cell()),
+            dipole_moment: system.dipole_moment().into(),
+        }
+    }
+
+    /// Reciprocal-space virial tensor, UNIT: [ ( input charge )^2 / ( input length ) ]
+    ///
+    /// This is minus the derivative of the energy with respect to a homogeneous strain, 𝜀,
+    /// of the cell and positions, with elements
+    ///
+    /// $$ W_{ij} = \sum_{\mathbf{k}} U(\mathbf{k}) \left[\delta_{ij} - 2\left(\frac{1}{k^2 + \kappa^2} + \frac{1}{4\alpha^2}\right) k_i k_j\right] $$
+    ///
+    /// where $U(\mathbf{k})$ is the energy of each wave vector.
+    /// Dipole moments keep their orientation, adding an asymmetric term.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not periodic.
+    pub fn virial(&self, system: &ParticleSystem) -> Matrix3 {
+        let kappa_squared = crate::DebyeLength::kappa(self).map_or(0.0, |kappa| kappa * kappa);
+        let (reciprocal, waves) = self.waves(system.cell());
+        let (tables, structure_factors) = self.structure_factors(system, &reciprocal, &waves);
+        let virials = map_indices(waves.len(), |i| {
+            let (wave, structure_factor) = (&waves[i], structure_factors[i]);
+            let k = wave.k;
+            let energy = wave.prefactor * structure_factor.norm_sqr();
+            let factor =
+                2.0 * (1.0 / (k.norm_squared() + kappa_squared) + 0.25 / self.alpha.powi(2));
+            let mut virial = energy * (NalgebraMatrix3::identity() - factor * k * k.transpose());
+            if let Some(dipoles) = system.dipoles() {
+                let moment: NalgebraVector3 = dipoles
+                    .iter()
+                    .zip(&tables)
+                    .map(|(mu, table)| {
+                        (structure_factor.conj() * table.phase(&wave.n)).im
+                            * NalgebraVector3::from(*mu)
+                    })
+                    .sum();
+                virial -= 2.0 * wave.prefactor * k * moment.transpose();
+            }
+            virial
+        });
+        let virial: NalgebraMatrix3 = virials.into_iter().sum();
+        virial.into()
+    }
+
+    /// Derivative of the energy with respect to the volume, ∂𝑈/∂𝑉 = -tr(𝐖)/3𝑉, where 𝐖 is
+    /// the [virial](EwaldReciprocal::virial).
+    ///
+    /// Returns the derivative, UNIT: [ ( input charge )^2 / ( input length )^4 ]
+    ///
+    /// The cell and positions are scaled isotropically, keeping fractional coordinates fixed
+    /// as in barostat volume moves.
+    /// This includes the explicit volume dependence of the wave vectors and prefactors that
+    /// is missed by a virial computed from forces alone.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not periodic.
+    pub fn volume_derivative(&self, system: &ParticleSystem) -> f64 {
+        let volume = system
+            .cell()
+            .volume()
+            .expect("reciprocal space requires a periodic cell");
+        -NalgebraMatrix3::from(self.virial(system)).trace() / (3.0 * volume)
+    }
+
+    /// Derivative of the energy with respect to the lattice vectors, ∂𝑈/∂𝐇 = -𝐖𝐇⁻ᵀ, where
+    /// 𝐇 holds the lattice vectors as columns and 𝐖 is the [virial](EwaldReciprocal::virial).
+    ///
+    /// Returns the derivative, UNIT: [ ( input charge )^2 / ( input length )^2 ]
+    ///
+    /// Fractional coordinates are kept fixed, as needed for anisotropic barostats.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not periodic.
+    pub fn box_derivative(&self, system: &ParticleSystem) -> Matrix3 {
+        let inverse = system
+            .cell()
+            .box_matrix()
+            .and_then(|matrix| NalgebraMatrix3::from(matrix).try_inverse())
+            .expect("reciprocal space requires a periodic cell");
+        (-NalgebraMatrix3::from(self.virial(system)) * inverse.transpose()).into()
+    }
+
+    /// Surface virial tensor, UNIT: [ ( input charge )^2 / ( input length ) ]
+    ///
+    /// This is 𝑈ₛ𝐈 + ∑𝐅ᵢ ⊗ 𝐫ᵢ where 𝑈ₛ is the surface energy and 𝐅ᵢ the surface forces.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not periodic.
+    pub fn surface_virial(&self, system: &ParticleSystem) -> Matrix3 {
+        let virial = self
+            .surface_forces(system)
+            .into_iter()
+            .zip(system.positions())
+            .fold(
+                NalgebraMatrix3::identity() * self.surface_energy(system),
+                |sum, (force, position)| {
+                    sum + NalgebraVector3::from(force)
+                        * NalgebraVector3::from(*position).transpose()
+                },
+            );
+        virial.into()
+    }
+}
+
+impl super::ReciprocalState for EwaldReciprocal {
+    /// Wave vectors, 𝐤, and their energy prefactors, 2π exp(-(𝑘² + κ²)/4α²) / 𝑉(𝑘² + κ²)
+    fn k_vectors(&self, cell: &Cell) -> Vec<(Vector3, f64)> {
+        let (reciprocal, volume) = Self::reciprocal_lattice(cell);
+        self.integer_vectors(false)
+            .into_iter()
+            .map(|n| {
+                let k = reciprocal * NalgebraVector3::from(n.map(f64::from));
+                (k.into(), self.prefactor(&k, volume))
+            })
+            .collect()
+    }
+    fn recip_cutoff(&self) -> u32 {
+        self.max_wave_number
+    }
+    fn surface_permittivity(&self) -> BoundaryPermittivity {
+        self.surface_permittivity
+    }
+    fn alpha(&self) -> f64 {
+        self.alpha
+    }
+}
+
+impl ReciprocalEnergy for EwaldReciprocal {
+    fn reciprocal_energy(&self, system: &ParticleSystem) -> f64 {
+        self.structure_factor_cache(system).energy()
+    }
+
+    /// For repeated moves, [`StructureFactorCache::delta_energy_for_move`] avoids
+    /// recomputing the structure factors.
+    fn delta_energy_for_move(
+        &self,
+        system: &ParticleSystem,
+        index: usize,
+        new_position: impl Into<Vector3>,
+    ) -> f64 {
+        self.structure_factor_cache(system)
+            .delta_energy_for_move(system, index, new_position)
+    }
+
+    /// Energy of a uniform, neutralizing background, -π𝑄² / 2𝑉α², for salt-free systems with
+    /// a net charge, 𝑄.
+    ///
+    /// With salt screening, no background is needed and the energy is zero.
+    fn background_energy(&self, system: &ParticleSystem) -> f64 {
+        let volume = system
+            .cell()
+            .volume()
+            .expect("reciprocal space requires a periodic cell");
+        if self.debye_length.is_some() {
+            return 0.0;
+        }
+        -PI * system.net_charge().powi(2) / (2.0 * volume * self.alpha.powi(2))
+    }
+}
+
+impl ReciprocalForce for EwaldReciprocal {
+    fn reciprocal_forces(&self, system: &ParticleSystem) -> Vec<Vector3> {
+        let (reciprocal, waves) = self.waves(system.cell());
+        let (tables, structure_factors) = self.structure_factors(system, &reciprocal, &waves);
+        map_indices(system.len(), |index| {
+            let force: NalgebraVector3 = waves
+                .iter()
+                .zip(&structure_factors)
+                .map(|(wave, structure_factor)| {
+                    let amplitude = wave.amplitude(system, index, tables[index].phase(&wave.n));
+                    2.0 * wave.prefactor * (structure_factor.conj() * amplitude).im * wave.k
+                })
+                .sum();
+            force.into()
+        })
+    }
+}
+
+/// Wave vector in one half of reciprocal space
+#[derive(Debug, Clone, PartialEq)]
+struct Wave {
+    /// Integer indices, 𝐧, of the wave vector
+    n: [i32; 3],
+    /// Wave vector, 𝐤
+    k: NalgebraVector3,
+    /// Energy prefactor, doubled to include -𝐤 unless 𝐤 = 0
+    prefactor: f64,
+}
+
+impl Wave {
+    /// Structure factor amplitude, (𝑞 + 𝑖𝛍·𝐤) exp(𝑖𝐤·𝐫), of a particle with phase factor exp(𝑖𝐤·𝐫)
+    fn amplitude(&self, system: &ParticleSystem, index: usize, phase: Complex64) -> Complex64 {
+        let mu_k = system.dipoles().map_or(0.0, |dipoles| {
+            NalgebraVector3::from(dipoles[index]).dot(&self.k)
+        });
+        Complex64::new(system.charges()[index], mu_k) * phase
+    }
+}
+
+/// Phase factors, exp(𝑖𝑛𝐛ₐ·𝐫) for 0 ≤ 𝑛 ≤ 𝑛ₘₐₓ, of a position along each reciprocal lattice
+/// vector, 𝐛ₐ.
+///
+/// The factors are found by repeated complex multiplication and combined into
+/// exp(𝑖𝐤·𝐫) for any wave vector without evaluating trigonometric functions.
+#[derive(Debug, Clone, PartialEq)]
+struct PhaseTable([Vec<Complex64>; 3]);
+
+impl PhaseTable {
+    fn new(reciprocal: &NalgebraMatrix3, max_wave_number: u32, position: Vector3) -> Self {
+        let position = NalgebraVector3::from(position);
+        Self(core::array::from_fn(|axis| {
+            let step = Complex64::cis(reciprocal.column(axis).dot(&position));
+            core::iter::successors(Some(Complex64::new(1.0, 0.0)), |phase| Some(phase * step))
+                .take(max_wave_number as usize + 1)
+                .collect()
+        }))
+    }
+
+    /// Phase factor, exp(𝑖𝐤·𝐫), of the wave vector with integer indices 𝐧
+    fn phase(&self, n: &[i32; 3]) -> Complex64 {
+        n.iter()
+            .zip(&self.0)
+            .map(|(n, phases)| {
+                let phase = phases[n.unsigned_abs() as usize];
+                if *n < 0 {
+                    phase.conj()
+                } else {
+                    phase
+                }
+            })
+            .product()
+    }
+}
+
+/// Structure factors of a system in one half of reciprocal space.
+///
+/// Created by [`EwaldReciprocal::structure_factor_cache`], the cache gives the energy change
+/// of moving a single particle in 𝒪(𝐾) rather than 𝒪(𝑁𝐾) operations for 𝑁 particles and 𝐾
+/// wave vectors.
+/// Accepted moves must be applied to the cache with
+/// [`StructureFactorCache::update_for_move`] _before_ updating the system.
+/// The cache is invalidated by changes to the cell, charges, or dipoles.
+/// A [`ParticleSystem`] with a reciprocal-space sum keeps and updates its own cache, see
+/// [`ParticleSystem::with_reciprocal`].
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::reciprocal::{EwaldReciprocal, ReciprocalEnergy};
+/// use coulomb::system::ParticleSystem;
+/// let mut system = ParticleSystem::new(
+///     vec![[1.0, 2.0, 3.0].into(), [4.0, 1.0, 2.0].into()],
+///     vec![1.0, -1.0],
+/// )
+/// .with_box_length([6.0; 3]);
+/// let ewald = EwaldReciprocal::new(0.6, 6, None);
+/// let mut cache = ewald.structure_factor_cache(&system);
+/// let new_position = [2.0, 2.0, 3.0];
+/// let delta = cache.delta_energy_for_move(&system, 0, new_position);
+/// assert_relative_eq!(delta, ewald.delta_energy_for_move(&system, 0, new_position));
+///
+/// // Accept the move
+/// cache.update_for_move(&system, 0, new_position);
+/// system.set_position(0, new_position);
+/// assert_relative_eq!(cache.energy(), ewald.reciprocal_energy(&system), epsilon = 1e-12);
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructureFactorCache {
+    /// Reciprocal lattice vectors as columns
+    reciprocal: NalgebraMatrix3,
+    /// Largest length of the integer wave vectors, 𝑛ₘₐₓ
+    max_wave_number: u32,
+    /// Wave vectors in one half of reciprocal space
+    waves: Vec<Wave>,
+    /// Structure factor, 𝑄(𝐤), of each wave vector
+    structure_factors: Vec<Complex64>,
+    /// Prefactor of the surface energy, 2π / (2ε + 1)𝑉
+    surface_prefactor: f64,
+    /// Dipole moment, 𝐌, of the cell
+    dipole_moment: NalgebraVector3,
+}
+
+impl StructureFactorCache {
+    /// Structure factors, 𝑄(𝐤), for one of each ±𝐤 pair
+    pub fn structure_factors(&self) -> &[Complex64] {
+        &self.structure_factors
+    }
+
+    /// Reciprocal-space energy, UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn energy(&self) -> f64 {
+        self.waves
+            .iter()
+            .zip(&self.structure_factors)
+            .map(|(wave, structure_factor)| wave.prefactor * structure_factor.norm_sqr())
+            .sum()
+    }
+
+    /// Change in the structure factors when moving a particle to a new position
+    fn move_changes<'a>(
+        &'a self,
+        system: &'a ParticleSystem,
+        index: usize,
+        new_position: Vector3,
+    ) -> impl Iterator<Item = Complex64> + 'a {
+        let old = PhaseTable::new(
+            &self.reciprocal,
+            self.max_wave_number,
+            system.positions()[index],
+        );
+        let new = PhaseTable::new(&self.reciprocal, self.max_wave_number, new_position);
+        self.waves
+            .iter()
+            .map(move |wave| wave.amplitude(system, index, new.phase(&wave.n) - old.phase(&wave.n)))
+    }
+
+    /// Change in reciprocal-space energy when moving a particle to a new position.
+    ///
+    /// Returns the energy change, UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn delta_energy_for_move(
+        &self,
+        system: &ParticleSystem,
+        index: usize,
+        new_position: impl Into<Vector3>,
+    ) -> f64 {
+        self.move_changes(system, index, new_position.into())
+            .zip(&self.waves)
+            .zip(&self.structure_factors)
+            .map(|((change, wave), old)| {
+                wave.prefactor * ((old + change).norm_sqr() - old.norm_sqr())
+            })
+            .sum()
+    }
+
+    /// Update the structure factors for an accepted move of a particle to a new position.
+    ///
+    /// The system must still hold the old position.
+    pub fn update_for_move(
+        &mut self,
+        system: &ParticleSystem,
+        index: usize,
+        new_position: impl Into<Vector3>,
+    ) {
+        let new_position = new_position.into();
+        let changes: Vec<Complex64> = self.move_changes(system, index, new_position).collect();
+        for (structure_factor, change) in self.structure_factors.iter_mut().zip(changes) {
+            *structure_factor += change;
+        }
+        self.dipole_moment += Self::dipole_change(system, index, new_position);
+    }
+
+    /// Change in the dipole moment of the cell when moving a particle to a new position
+    fn dipole_change(
+        system: &ParticleSystem,
+        index: usize,
+        new_position: Vector3,
+    ) -> NalgebraVector3 {
+        system.charges()[index]
+            * (NalgebraVector3::from(new_position)
+                - NalgebraVector3::from(system.positions()[index]))
+    }
+
+    /// Surface energy, 2π|𝐌|² / (2ε + 1)𝑉, from the dipole moment, 𝐌, of the cell.
+    ///
+    /// Returns the energy, UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn surface_energy(&self) -> f64 {
+        self.surface_prefactor * self.dipole_moment.norm_squared()
+    }
+
+    /// Change in surface energy when moving a particle to a new position.
+    ///
+    /// Returns the energy change, UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn delta_surface_energy_for_move(
+        &self,
+        system: &ParticleSystem,
+        index: usize,
+        new_position: impl Into<Vector3>,
+    ) -> f64 {
+        let new_moment =
+            self.dipole_moment + Self::dipole_change(system, index, new_position.into());
+        self.surface_prefactor * (new_moment.norm_squared() - self.dipole_moment.norm_squared())
+    }
+}
+
+/// Map each index in `0..n`, in parallel with the `rayon` feature, keeping the order of the results
+fn map_indices<T: Send>(n: usize, f: impl Fn(usize) -> T + Sync + Send) -> Vec<T> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        (0..n).into_par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        (0..n).map(f).collect()
+    }
+}
+
+impl crate::DebyeLength for EwaldReciprocal {
+    fn kappa(&self) -> Option<f64> {
+        self.debye_length.map(f64::recip)
+    }
+    fn set_debye_length(&mut self, debye_length: Option<f64>) -> crate::Result<()> {
+        crate::debye_length::check_debye_length(debye_length)?;
+        self.debye_length = debye_length;
+        Ok(())
+    }
+}
+
+impl crate::SaltSensitive for EwaldReciprocal {}
+
+#[test]
+fn test_ewald_reciprocal() {
+    use super::ReciprocalState;
+    use crate::pairwise::RealSpaceEwald;
+    use crate::DebyeLength;
+    use approx::assert_relative_eq;
+    let mut random = crate::test_utils::Random::new(7);
+    let positions = random.positions(6, [0.0; 3], [10.0, 12.0, 11.0]);
+    let charges = vec![1.0, -1.0, 0.5, 1.0, -0.5, 1.0];
+    let dipoles = random.positions(6, [-0.5; 3], [0.5; 3]);
+    let system = ParticleSystem::new(positions, charges).with_box_length([10.0, 12.0, 11.0]);
+
+    // The total energy of a net-charged system is independent of the splitting
+    let energy = |system: &ParticleSystem, alpha: f64, debye_length: Option<f64>| {
+        let system = system
+            .clone()
+            .with_reciprocal(EwaldReciprocal::new(alpha, 16, debye_length));
+        system.total_energy(&RealSpaceEwald::new(5.0, alpha, debye_length))
+    };
+    for debye_length in [None, Some(4.0)] {
+        assert_relative_eq!(
+            energy(&system, 0.8, debye_length),
+            energy(&system, 0.9, debye_length),
+            epsilon = 1e-6
+        );
+    }
+    let dipolar = system.clone().with_dipoles(dipoles);
+    assert_relative_eq!(
+        energy(&dipolar, 0.8, None),
+        energy(&dipolar, 0.9, None),
+        epsilon = 1e-6
+    );
+
+    // Forces and move energies are consistent with the energy
+    let ewald =
+        EwaldReciprocal::new(0.5, 6, None).with_surface_permittivity(BoundaryPermittivity::Vacuum);
+    let h = 1e-6;
+    let forces = ewald.reciprocal_forces(&dipolar);
+    let surface_forces = ewald.surface_forces(&dipolar);
+    for index in [0, 4] {
+        let mut moved = dipolar.clone();
+        let mut position = dipolar.positions()[index];
+        position.y += h;
+        moved.set_position(index, position);
+        let delta = ewald.delta_energy_for_move(&dipolar, index, position);
+        assert_relative_eq!(
+            delta,
+            ewald.reciprocal_energy(&moved) - ewald.reciprocal_energy(&dipolar),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(-delta / h, forces[index].y, epsilon = 1e-5);
+        let delta = ewald.surface_energy(&moved) - ewald.surface_energy(&dipolar);
+        assert_relative_eq!(-delta / h, surface_forces[index].y, epsilon = 1e-5);
+    }
+
+    // Sum over all wave vectors without ±𝐤 symmetry or phase tables
+    let structure_factor = |k: NalgebraVector3| -> Complex64 {
+        (0..dipolar.len())
+            .map(|i| {
+                let mu_k = NalgebraVector3::from(dipolar.dipoles().unwrap()[i]).dot(&k);
+                let phase = k.dot(&NalgebraVector3::from(dipolar.positions()[i]));
+                Complex64::new(dipolar.charges()[i], mu_k) * Complex64::cis(phase)
+            })
+            .sum()
+    };
+    let full: f64 = ewald
+        .k_vectors(dipolar.cell())
+        .into_iter()
+        .map(|(k, prefactor)| prefactor * structure_factor(k.into()).norm_sqr())
+        .sum();
+    assert_relative_eq!(ewald.reciprocal_energy(&dipolar), full, epsilon = 1e-12);
+
+    // Cached structure factors follow a sequence of accepted moves
+    let mut moved = dipolar.clone();
+    let mut cache = ewald.structure_factor_cache(&moved);
+    for (index, position) in [
+        (1, [1.0, 2.0, 3.0]),
+        (3, [9.5, 0.5, 10.0]),
+        (1, [4.0, 11.0, 0.2]),
+    ] {
+        let delta = cache.delta_energy_for_move(&moved, index, position);
+        assert_relative_eq!(
+            delta,
+            ewald.delta_energy_for_move(&moved, index, position),
+            epsilon = 1e-12
+        );
+        let old_energy = cache.energy();
+        cache.update_for_move(&moved, index, position);
+        moved.set_position(index, position);
+        assert_relative_eq!(cache.energy() - old_energy, delta, epsilon = 1e-12);
+    }
+    assert_relative_eq!(
+        cache.energy(),
+        ewald.reciprocal_energy(&moved),
+        epsilon = 1e-12
+    );
+    assert_eq!(
+        EwaldReciprocal::new(0.5, 6, None).surface_energy(&dipolar),
+        0.0
+    );
+    assert_eq!(
+        EwaldReciprocal::new(0.5, 6, Some(4.0)).background_energy(&dipolar),
+        0.0
+    );
+
+    // Invalid Debye lengths are rejected and leave the screening unchanged
+    let mut screened = EwaldReciprocal::new(0.5, 6, None);
+    screened.set_debye_length(Some(4.0)).unwrap();
+    for invalid in [0.0, -4.0, f64::INFINITY, f64::NAN] {
+        assert!(matches!(
+            screened.set_debye_length(Some(invalid)),
+            Err(crate::Error::InvalidDebyeLength)
+        ));
+    }
+    assert_eq!(screened.debye_length(), Some(4.0));
+}
+
+#[test]
+fn test_volume_derivatives() {
+    use crate::pbc::TriclinicCell;
+    use approx::assert_relative_eq;
+    let matrix = NalgebraMatrix3::new(9.0, 1.0, 0.5, 0.0, 10.0, -1.0, 0.0, 0.0, 8.0);
+    let fractional = [
+        [0.1, 0.2, 0.3],
+        [0.6, 0.4, 0.8],
+        [0.3, 0.9, 0.5],
+        [0.8, 0.7, 0.1],
+    ];
+    let system = |matrix: NalgebraMatrix3| {
+        let positions = fractional
+            .iter()
+            .map(|s| (matrix * NalgebraVector3::from(*s)).into())
+            .collect();
+        let lattice: Matrix3 = matrix.into();
+        let cell = Cell::Triclinic(TriclinicCell::try_from(lattice).unwrap());
+        ParticleSystem::new(positions, vec![1.0, -1.0, 0.5, 1.0])
+            .with_dipoles(vec![[0.2, 0.0, 0.1].into(); 4])
+            .with_cell(cell)
+    };
+    let h = 1e-6;
+    for debye_length in [None, Some(5.0)] {
+        let ewald = EwaldReciprocal::new(0.7, 7, debye_length);
+        let derivative = NalgebraMatrix3::from(ewald.box_derivative(&system(matrix)));
+        for i in 0..3 {
+            for j in 0..3 {
+                let mut dh = NalgebraMatrix3::zeros();
+                dh[(i, j)] = h;
+                let numeric = (ewald.reciprocal_energy(&system(matrix + dh))
+                    - ewald.reciprocal_energy(&system(matrix - dh)))
+                    / (2.0 * h);
+                assert_relative_eq!(derivative[(i, j)], numeric, epsilon = 1e-7);
+            }
+        }
+        let volume = matrix.determinant();
+        let scale = |s: f64| system(matrix * (1.0 + s).cbrt());
+        let numeric = (ewald.reciprocal_energy(&scale(h)) - ewald.reciprocal_energy(&scale(-h)))
+            / (2.0 * h * volume);
+        assert_relative_eq!(
+            ewald.volume_derivative(&system(matrix)),
+            numeric,
+            epsilon = 1e-8
+        );
+    }
+}
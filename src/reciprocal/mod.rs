@@ -17,12 +17,20 @@
 
 //! Reciprocal space in Ewald summation schemes.
 
-use crate::Cutoff;
+use crate::pbc::Cell;
+use crate::system::ParticleSystem;
+use crate::{DebyeLength, NalgebraVector3, Vector3};
 use core::f64::consts::PI;
 use core::iter::{zip, IntoIterator};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod ewald;
+pub use ewald::{EwaldReciprocal, StructureFactorCache};
 
 /// Relative permittivity at the boundary
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BoundaryPermittivity {
     /// Tinfoil or metallic boundary conditions, i.e. infinite permittivity (default)
     #[default]
@@ -50,33 +58,21 @@ impl From<BoundaryPermittivity> for f64 {
 /// - General, <https://doi.org/10.1063/1.481216>
 /// - Update optimization <https://doi.org/10.1063/1.481216>, Eq. 24
 /// - Isotropic periodic boundary conditions, <https://doi.org/10/css8>
-pub trait ReciprocalState: Cutoff {
-    /// 3D vector type used for positions and k-vectors.
-    type Vector3;
-
-    /// Reciprocal space vectors, q
-    fn k_vectors(&self) -> &'static [Self::Vector3];
-    /// Reciprocal space cutoff
+pub trait ReciprocalState: DebyeLength {
+    /// Reciprocal space vectors, 𝐤, of a periodic cell and their energy prefactors
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not periodic.
+    fn k_vectors(&self, cell: &Cell) -> Vec<(Vector3, f64)>;
+    /// Reciprocal space cutoff as the largest length of the integer wave vectors
     fn recip_cutoff(&self) -> u32;
     /// Relative permittivity if the surrounding medium
     ///
-    /// If `None`, tinfoil boundary conditions are assumed, e.g. infinite permittivity.
+    /// If `Tinfoil`, infinite permittivity is assumed.
     fn surface_permittivity(&self) -> BoundaryPermittivity;
-    /// Inverse Debye screening length
-    fn kappa(&self) -> Option<f64>;
     /// Damping parameter for the Ewald splitting.
     fn alpha(&self) -> f64;
-    /// Side lengths of the simulation box.
-    fn box_length(&self) -> Vec<f64>;
-    /// Volume of the simulation box
-    fn volume(&self) -> f64;
-
-    /// Recalculate all k-vectors
-    fn recalc_k_vectors(
-        &mut self,
-        positions: impl IntoIterator<Item = Self::Vector3>,
-        charges: impl IntoIterator<Item = f64>,
-    );
 }
 
 /// Calculate the dipole moment of a system with respect to the geometric center
@@ -93,21 +89,84 @@ fn _dipole_moment(
     sum.into()
 }
 
+/// Prefactor of the surface energy, 2π / (2ε + 1)𝑉, vanishing for tinfoil boundaries
+pub(crate) fn surface_prefactor(surface_permittivity: BoundaryPermittivity, cell: &Cell) -> f64 {
+    let volume = cell
+        .volume()
+        .expect("reciprocal space requires a periodic cell");
+    2.0 * PI / ((2.0 * f64::from(surface_permittivity) + 1.0) * volume)
+}
+
 /// Reciprocal-space energy contribution.
+///
+/// All energies are in UNIT: [ ( input charge )^2 / ( input length ) ]
 pub trait ReciprocalEnergy: ReciprocalState {
     /// Calculate the reciprocal-space energy.
-    fn reciprocal_energy() -> f64;
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not periodic.
+    fn reciprocal_energy(&self, system: &ParticleSystem) -> f64;
 
-    /// Surface energy due to the dipole moment of the system, Eₛ = 2π / (2ε + 1) * μ² / V
-    /// @todo Unit?
-    fn surface_energy(&self, system_dipole_moment: f64) -> f64 {
-        2.0 * PI / (2.0 * f64::from(self.surface_permittivity()) + 1.0) / self.volume()
-            * system_dipole_moment.powi(2)
+    /// Change in reciprocal-space energy when moving a particle to a new position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not periodic.
+    fn delta_energy_for_move(
+        &self,
+        system: &ParticleSystem,
+        index: usize,
+        new_position: impl Into<Vector3>,
+    ) -> f64;
+
+    /// Surface energy due to the dipole moment of the system, Eₛ = 2π / (2ε + 1) * |𝐌|² / V
+    ///
+    /// The dipole moment depends on the positions as given, so molecules should not be
+    /// wrapped across the cell boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not periodic.
+    fn surface_energy(&self, system: &ParticleSystem) -> f64 {
+        surface_prefactor(self.surface_permittivity(), system.cell())
+            * NalgebraVector3::from(system.dipole_moment()).norm_squared()
     }
+
+    /// Energy of a uniform, neutralizing background for systems with a net charge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not periodic.
+    fn background_energy(&self, system: &ParticleSystem) -> f64;
 }
 
 /// Reciprocal-space force contribution.
-pub trait ReciprocalForce: ReciprocalState {}
+///
+/// All forces are in UNIT: [ ( input charge )^2 / ( input length )^2 ]
+pub trait ReciprocalForce: ReciprocalState {
+    /// Reciprocal-space force on each particle
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not periodic.
+    fn reciprocal_forces(&self, system: &ParticleSystem) -> Vec<Vector3>;
+
+    /// Surface force, -4π𝑞ᵢ𝐌 / (2ε + 1)𝑉, on each particle
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not periodic.
+    fn surface_forces(&self, system: &ParticleSystem) -> Vec<Vector3> {
+        let prefactor = surface_prefactor(self.surface_permittivity(), system.cell());
+        let dipole_moment = NalgebraVector3::from(system.dipole_moment());
+        system
+            .charges()
+            .iter()
+            .map(|charge| (-2.0 * prefactor * charge * dipole_moment).into())
+            .collect()
+    }
+}
 
 /// Reciprocal-space electric field contribution.
 pub trait ReciprocalField: ReciprocalState {}
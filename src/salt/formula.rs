@@ -0,0 +1,153 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Parsing of chemical formulas into ions and stoichiometry.
+
+use crate::Error;
+
+/// Common ions and their valencies. Elements with several common oxidation states are omitted.
+const IONS: [(&str, isize); 49] = [
+    // Cations
+    ("H", 1),
+    ("Li", 1),
+    ("Na", 1),
+    ("K", 1),
+    ("Rb", 1),
+    ("Cs", 1),
+    ("Ag", 1),
+    ("NH4", 1),
+    ("Mg", 2),
+    ("Ca", 2),
+    ("Sr", 2),
+    ("Ba", 2),
+    ("Mn", 2),
+    ("Co", 2),
+    ("Ni", 2),
+    ("Cu", 2),
+    ("Zn", 2),
+    ("Cd", 2),
+    ("Pb", 2),
+    ("Al", 3),
+    ("Cr", 3),
+    ("Y", 3),
+    ("La", 3),
+    ("Ce", 3),
+    ("Gd", 3),
+    // Anions
+    ("F", -1),
+    ("Cl", -1),
+    ("Br", -1),
+    ("I", -1),
+    ("OH", -1),
+    ("NO2", -1),
+    ("NO3", -1),
+    ("ClO3", -1),
+    ("ClO4", -1),
+    ("HCO3", -1),
+    ("HSO4", -1),
+    ("H2PO4", -1),
+    ("SCN", -1),
+    ("BF4", -1),
+    ("PF6", -1),
+    ("CH3COO", -1),
+    ("SO3", -2),
+    ("SO4", -2),
+    ("S2O3", -2),
+    ("CO3", -2),
+    ("C2O4", -2),
+    ("CrO4", -2),
+    ("HPO4", -2),
+    ("PO4", -3),
+];
+
+/// Replace unicode subscript digits, e.g. in "CaCl₂", with ASCII digits
+pub(super) fn normalize(formula: &str) -> String {
+    formula
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '₀'..='₉' => char::from(b'0' + (c as u32 - '₀' as u32) as u8),
+            _ => c,
+        })
+        .collect()
+}
+
+/// Longest ion at the start of `text` with its symbol length and valency
+fn match_ion(text: &str) -> Option<(usize, isize)> {
+    IONS.iter()
+        .filter(|(symbol, _)| text.starts_with(symbol))
+        .max_by_key(|(symbol, _)| symbol.len())
+        .map(|(symbol, valency)| (symbol.len(), *valency))
+}
+
+/// Leading count of `text`, defaulting to one, and the number of digits consumed
+fn match_count(text: &str) -> Result<(usize, usize), Error> {
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    match digits {
+        0 => Ok((1, 0)),
+        _ => text[..digits]
+            .parse()
+            .ok()
+            .filter(|count| *count > 0)
+            .map(|count| (count, digits))
+            .ok_or_else(|| Error::InvalidFormula(text.to_string())),
+    }
+}
+
+/// Parse a formula such as "KAl(SO4)2" into the valency and count of each ion
+///
+/// Ions are matched greedily from left to right and a parenthesized group must contain a
+/// single ion.
+pub(super) fn parse(formula: &str) -> Result<Vec<(isize, usize)>, Error> {
+    let formula = normalize(formula);
+    let invalid = || Error::InvalidFormula(formula.clone());
+    let mut ions = Vec::new();
+    let mut rest = formula.as_str();
+    while !rest.is_empty() {
+        let (length, valency) = match rest.strip_prefix('(') {
+            Some(inner) => {
+                let (length, valency) = match_ion(inner).ok_or_else(invalid)?;
+                if !inner[length..].starts_with(')') {
+                    return Err(invalid());
+                }
+                (length + 2, valency)
+            }
+            None => match_ion(rest).ok_or_else(invalid)?,
+        };
+        rest = &rest[length..];
+        let (count, digits) = match_count(rest)?;
+        rest = &rest[digits..];
+        ions.push((valency, count));
+    }
+    if ions.is_empty() {
+        return Err(invalid());
+    }
+    Ok(ions)
+}
+
+#[test]
+fn test_parse_formula() {
+    assert_eq!(parse("NaCl").unwrap(), [(1, 1), (-1, 1)]);
+    assert_eq!(parse("MgCl2").unwrap(), [(2, 1), (-1, 2)]);
+    assert_eq!(parse("CaCl₂").unwrap(), [(2, 1), (-1, 2)]);
+    assert_eq!(parse(" Na2SO4 ").unwrap(), [(1, 2), (-2, 1)]);
+    assert_eq!(parse("KAl(SO4)2").unwrap(), [(1, 1), (3, 1), (-2, 2)]);
+    assert_eq!(parse("Ca3(PO4)2").unwrap(), [(2, 3), (-3, 2)]);
+    assert_eq!(parse("NaH2PO4").unwrap(), [(1, 1), (-1, 1)]);
+    assert_eq!(parse("NH4NO3").unwrap(), [(1, 1), (-1, 1)]);
+    assert_eq!(parse("CH3COONa").unwrap(), [(-1, 1), (1, 1)]);
+    for invalid in ["", "Xx", "Na(Cl", "(SO4", "NaCl0", "(NaCl)2"] {
+        assert!(matches!(parse(invalid), Err(Error::InvalidFormula(_))));
+    }
+}
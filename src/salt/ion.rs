@@ -0,0 +1,103 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Ions with valency, size, and an optional label.
+
+use core::fmt::{Display, Formatter};
+
+/// Ion with a valency, radius, and optional label.
+///
+/// # Examples
+/// ~~~
+/// use coulomb::Ion;
+/// let sulfate = Ion::new(-2, 2.0).with_label("SO₄");
+/// assert_eq!(sulfate.diameter(), 4.0);
+/// assert_eq!(sulfate.to_string(), "SO₄²⁻");
+/// assert_eq!(Ion::new(1, 2.0).to_string(), "⁺");
+/// ~~~
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ion {
+    /// Valency, z
+    pub valency: isize,
+    /// Radius, UNIT: [ angstrom ]
+    pub radius: f64,
+    /// Label, e.g. "Na" or "SO₄"
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub label: Option<String>,
+}
+
+impl Ion {
+    /// New unlabeled ion with valency and radius in angstrom.
+    pub const fn new(valency: isize, radius: f64) -> Self {
+        Self {
+            valency,
+            radius,
+            label: None,
+        }
+    }
+
+    /// Set the label of the ion.
+    pub fn with_label(self, label: impl Into<String>) -> Self {
+        Self {
+            label: Some(label.into()),
+            ..self
+        }
+    }
+
+    /// Diameter, UNIT: [ angstrom ]
+    pub fn diameter(&self) -> f64 {
+        2.0 * self.radius
+    }
+}
+
+/// Superscript of a signed integer, e.g. "²⁻", omitting unit magnitudes
+fn superscript_charge(valency: isize) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    let magnitude = match valency.unsigned_abs() {
+        0 | 1 => String::new(),
+        n => n
+            .to_string()
+            .bytes()
+            .map(|digit| DIGITS[(digit - b'0') as usize])
+            .collect(),
+    };
+    let sign = match valency.signum() {
+        1 => "⁺",
+        -1 => "⁻",
+        _ => "",
+    };
+    magnitude + sign
+}
+
+impl Display for Ion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            self.label.as_deref().unwrap_or_default(),
+            superscript_charge(self.valency)
+        )
+    }
+}
+
+#[test]
+fn test_ion() {
+    assert_eq!(Ion::new(3, 1.0).with_label("La").to_string(), "La³⁺");
+    assert_eq!(Ion::new(-12, 1.0).to_string(), "¹²⁻");
+    assert_eq!(Ion::new(0, 1.0).with_label("X").to_string(), "X");
+}
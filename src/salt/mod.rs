@@ -0,0 +1,505 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+mod formula;
+mod ion;
+
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+pub use ion::Ion;
+use num_integer::gcd;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// # Common salts and valencies
+///
+/// Stores the valencies ions in a salt and is used to calculate
+/// the ionic strength of arbitrary salt types.
+/// Below are some common salts and their valencies:
+///
+/// Salt      | `valencies`
+/// --------- | -----------
+/// NaCl      | `[1, -1]`
+/// CaCl₂     | `[2, -1]`
+/// KAl(SO₄)₂ | `[1, 3, -2]`
+///
+/// Named salts can be looked up by formula or name, see [`Salt::from_name`].
+///
+/// # Examples
+/// ~~~
+/// use coulomb::Salt;
+/// let molarity = 0.1;
+///
+/// let salt = Salt::SodiumChloride;
+/// assert_eq!(salt.valencies(), [1, -1]);
+/// assert_eq!(salt.stoichiometry().unwrap(), [1, 1]);
+/// assert_eq!(salt.ionic_strength(molarity).unwrap(), 0.1);
+/// ~~~
+///
+/// We can also define arbitrary salts where the stoichiometry is
+/// automatically deduced from the valencies:
+///
+/// ~~~
+/// # use coulomb::Salt;
+/// # let molarity = 0.1;
+/// let alum = Salt::Custom(vec![1, 3, -2]); // e.g. KAl(SO₄)₂
+/// assert_eq!(alum.stoichiometry().unwrap(), [1, 1, 2]);
+/// assert_eq!(alum.ionic_strength(molarity).unwrap(), 0.9);
+/// ~~~
+///
+/// Salts can also be parsed from chemical formulas, with ASCII or subscript digits:
+///
+/// ~~~
+/// # use coulomb::Salt;
+/// let alum: Salt = "KAl(SO4)2".parse().unwrap();
+/// assert_eq!(alum.valencies(), [1, 3, -2]);
+/// assert_eq!(alum.stoichiometry().unwrap(), [1, 1, 2]);
+/// ~~~
+///
+/// The `Display` trait is implemented to pretty print the salt type:
+/// ~~~
+/// # use coulomb::Salt;
+/// assert_eq!(Salt::PotassiumAlum.to_string(), "🧂Salt = KAl(SO₄)₂");
+/// ~~~
+///
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Salt {
+    /// Sodium chloride, NaCl. This is an example of a 1:1 electrolyte and is the default salt type.
+    #[cfg_attr(feature = "serde", serde(rename = "NaCl"))]
+    #[default]
+    SodiumChloride,
+    /// Potassium chloride, KCl
+    #[cfg_attr(feature = "serde", serde(rename = "KCl"))]
+    PotassiumChloride,
+    /// Lithium chloride, LiCl
+    #[cfg_attr(feature = "serde", serde(rename = "LiCl"))]
+    LithiumChloride,
+    /// Sodium bromide, NaBr
+    #[cfg_attr(feature = "serde", serde(rename = "NaBr"))]
+    SodiumBromide,
+    /// Potassium bromide, KBr
+    #[cfg_attr(feature = "serde", serde(rename = "KBr"))]
+    PotassiumBromide,
+    /// Sodium nitrate, NaNO₃
+    #[cfg_attr(feature = "serde", serde(rename = "NaNO₃"))]
+    SodiumNitrate,
+    /// Calcium chloride, CaCl₂
+    #[cfg_attr(feature = "serde", serde(rename = "CaCl₂"))]
+    CalciumChloride,
+    /// Magnesium chloride, MgCl₂
+    #[cfg_attr(feature = "serde", serde(rename = "MgCl₂"))]
+    MagnesiumChloride,
+    /// Barium chloride, BaCl₂
+    #[cfg_attr(feature = "serde", serde(rename = "BaCl₂"))]
+    BariumChloride,
+    /// Calcium sulfate, CaSO₄
+    #[cfg_attr(feature = "serde", serde(rename = "CaSO₄"))]
+    CalciumSulfate,
+    /// Magnesium sulfate, MgSO₄
+    #[cfg_attr(feature = "serde", serde(rename = "MgSO₄"))]
+    MagnesiumSulfate,
+    /// Potassium alum, KAl(SO₄)₂
+    #[cfg_attr(feature = "serde", serde(rename = "KAl(SO₄)₂"))]
+    PotassiumAlum,
+    /// Sodium sulfate, Na₂SO₄
+    #[cfg_attr(feature = "serde", serde(rename = "Na₂SO₄"))]
+    SodiumSulfate,
+    /// Potassium sulfate, K₂SO₄
+    #[cfg_attr(feature = "serde", serde(rename = "K₂SO₄"))]
+    PotassiumSulfate,
+    /// Lanthanum chloride, LaCl₃
+    #[cfg_attr(feature = "serde", serde(rename = "LaCl₃"))]
+    LanthanumChloride,
+    /// Salt with custom valencies
+    Custom(Vec<isize>),
+    /// Salt of explicit ions and their stoichiometric coefficients, νᵢ
+    Ions(Vec<(Ion, usize)>),
+}
+
+impl Salt {
+    /// Named salts recognized by [`Salt::from_name`]
+    pub const NAMED: [Salt; 15] = [
+        Salt::SodiumChloride,
+        Salt::PotassiumChloride,
+        Salt::LithiumChloride,
+        Salt::SodiumBromide,
+        Salt::PotassiumBromide,
+        Salt::SodiumNitrate,
+        Salt::CalciumChloride,
+        Salt::MagnesiumChloride,
+        Salt::BariumChloride,
+        Salt::CalciumSulfate,
+        Salt::MagnesiumSulfate,
+        Salt::PotassiumAlum,
+        Salt::SodiumSulfate,
+        Salt::PotassiumSulfate,
+        Salt::LanthanumChloride,
+    ];
+
+    /// Valencies of participating ions, zᵢ
+    pub fn valencies(&self) -> Vec<isize> {
+        match self {
+            Salt::SodiumChloride
+            | Salt::PotassiumChloride
+            | Salt::LithiumChloride
+            | Salt::SodiumBromide
+            | Salt::PotassiumBromide
+            | Salt::SodiumNitrate => vec![1, -1],
+            Salt::CalciumChloride | Salt::MagnesiumChloride | Salt::BariumChloride => vec![2, -1],
+            Salt::CalciumSulfate | Salt::MagnesiumSulfate => vec![2, -2],
+            Salt::PotassiumAlum => vec![1, 3, -2],
+            Salt::SodiumSulfate | Salt::PotassiumSulfate => vec![1, -2],
+            Salt::LanthanumChloride => vec![3, -1],
+            Salt::Custom(valencies) => valencies.clone(),
+            Salt::Ions(ions) => ions.iter().map(|(ion, _)| ion.valency).collect(),
+        }
+    }
+
+    /// Salt of explicit ions with sizes and stoichiometric coefficients.
+    ///
+    /// Use this for electrolytes whose stoichiometry cannot be deduced from the valencies alone,
+    /// or when the size of each ion is needed.
+    ///
+    /// # Examples
+    /// ~~~
+    /// use coulomb::{Ion, Salt};
+    /// let potassium = Ion::new(1, 1.5).with_label("K");
+    /// let magnesium = Ion::new(2, 4.0).with_label("Mg");
+    /// let sulfate = Ion::new(-2, 2.0).with_label("SO₄");
+    /// let schoenite = Salt::from_ions(vec![(potassium, 2), (magnesium, 1), (sulfate, 2)]).unwrap();
+    /// assert_eq!(schoenite.stoichiometry().unwrap(), [2, 1, 2]);
+    /// approx::assert_abs_diff_eq!(schoenite.ionic_strength(0.1).unwrap(), 0.7);
+    /// ~~~
+    ///
+    /// # Errors
+    /// Returns an error if the ions are not electroneutral or lack either cations or anions.
+    pub fn from_ions(ions: Vec<(Ion, usize)>) -> crate::Result<Self> {
+        let salt = Salt::Ions(ions);
+        salt.stoichiometry()?;
+        Ok(salt)
+    }
+
+    /// Explicit ions and their stoichiometric coefficients, if defined
+    pub fn ions(&self) -> Option<&[(Ion, usize)]> {
+        match self {
+            Salt::Ions(ions) => Some(ions),
+            _ => None,
+        }
+    }
+
+    /// Chemical formula and common name of a named salt
+    fn names(&self) -> Option<(&'static str, &'static str)> {
+        let names = match self {
+            Salt::SodiumChloride => ("NaCl", "sodium chloride"),
+            Salt::PotassiumChloride => ("KCl", "potassium chloride"),
+            Salt::LithiumChloride => ("LiCl", "lithium chloride"),
+            Salt::SodiumBromide => ("NaBr", "sodium bromide"),
+            Salt::PotassiumBromide => ("KBr", "potassium bromide"),
+            Salt::SodiumNitrate => ("NaNO₃", "sodium nitrate"),
+            Salt::CalciumChloride => ("CaCl₂", "calcium chloride"),
+            Salt::MagnesiumChloride => ("MgCl₂", "magnesium chloride"),
+            Salt::BariumChloride => ("BaCl₂", "barium chloride"),
+            Salt::CalciumSulfate => ("CaSO₄", "calcium sulfate"),
+            Salt::MagnesiumSulfate => ("MgSO₄", "magnesium sulfate"),
+            Salt::PotassiumAlum => ("KAl(SO₄)₂", "potassium alum"),
+            Salt::SodiumSulfate => ("Na₂SO₄", "sodium sulfate"),
+            Salt::PotassiumSulfate => ("K₂SO₄", "potassium sulfate"),
+            Salt::LanthanumChloride => ("LaCl₃", "lanthanum chloride"),
+            Salt::Custom(_) | Salt::Ions(_) => return None,
+        };
+        Some(names)
+    }
+
+    /// Recommended ion size, å, for the extended Debye-Hückel equation, UNIT: [ angstrom ]
+    ///
+    /// The ion size is the mean of the cation and anion sizes of Kielland,
+    /// <https://doi.org/10.1021/ja01292a027>, and is `None` for custom salts and
+    /// salts with more than two ions.
+    /// For explicit ions, it is the sum of the cation and anion radii.
+    ///
+    /// # Examples
+    /// ~~~
+    /// use coulomb::{activity::ActivityModel, Salt};
+    /// let ion_size = Salt::CalciumChloride.ion_size().unwrap();
+    /// assert_eq!(ion_size, 4.5);
+    /// let model = ActivityModel::Extended { ion_size };
+    /// ~~~
+    pub fn ion_size(&self) -> Option<f64> {
+        let ion_size = match self {
+            Salt::SodiumChloride | Salt::SodiumBromide | Salt::SodiumNitrate => 3.75,
+            Salt::PotassiumChloride | Salt::PotassiumBromide => 3.0,
+            Salt::LithiumChloride => 4.5,
+            Salt::CalciumChloride => 4.5,
+            Salt::MagnesiumChloride => 5.5,
+            Salt::BariumChloride => 4.0,
+            Salt::CalciumSulfate => 5.0,
+            Salt::MagnesiumSulfate => 6.0,
+            Salt::SodiumSulfate => 4.25,
+            Salt::PotassiumSulfate => 3.5,
+            Salt::LanthanumChloride => 6.0,
+            Salt::Ions(ions) => match ions.as_slice() {
+                [(first, _), (second, _)] => first.radius + second.radius,
+                _ => return None,
+            },
+            Salt::PotassiumAlum | Salt::Custom(_) => return None,
+        };
+        Some(ion_size)
+    }
+
+    /// Look up a named salt from its chemical formula, e.g. `"CaCl2"` or `"CaCl₂"`,
+    /// or its common name, e.g. `"calcium chloride"`.
+    ///
+    /// See [`Salt::NAMED`] for the available salts, and [`Salt::from_formula`] for arbitrary salts.
+    ///
+    /// # Errors
+    /// Returns [`Error::UnknownSalt`](crate::Error::UnknownSalt) if the salt is not named.
+    pub fn from_name(name: &str) -> crate::Result<Self> {
+        let formula = formula::normalize(name);
+        Self::NAMED
+            .into_iter()
+            .find(|salt| {
+                salt.names().is_some_and(|(symbol, common)| {
+                    formula::normalize(symbol) == formula
+                        || common.eq_ignore_ascii_case(name.trim())
+                })
+            })
+            .ok_or_else(|| crate::Error::UnknownSalt(name.to_string()))
+    }
+
+    /// Deduce stoichiometry of the salt, νᵢ
+    ///
+    /// For explicit ions, the given stoichiometric coefficients are used.
+    ///
+    /// # Errors
+    /// Returns an error if the salt does not contain both positive and negative ions,
+    /// or if explicit ions are not electroneutral.
+    pub fn stoichiometry(&self) -> crate::Result<Vec<usize>> {
+        let valencies = self.valencies();
+        let sum_positive: isize = valencies.iter().filter(|i| i.is_positive()).sum();
+        let sum_negative: isize = valencies.iter().filter(|i| i.is_negative()).sum();
+        let gcd = gcd(sum_positive, sum_negative);
+        if sum_positive == 0 || sum_negative == 0 || gcd == 0 {
+            return Err(crate::Error::Stoichiometry);
+        }
+        if let Salt::Ions(ions) = self {
+            let net_charge: isize = ions
+                .iter()
+                .map(|(ion, nu)| ion.valency * *nu as isize)
+                .sum();
+            if net_charge != 0 || ions.iter().any(|(_, nu)| *nu == 0) {
+                return Err(crate::Error::Stoichiometry);
+            }
+            return Ok(ions.iter().map(|(_, nu)| *nu).collect());
+        }
+        Ok(valencies
+            .iter()
+            .map(|valency| {
+                ((match valency.is_positive() {
+                    true => -sum_negative,
+                    false => sum_positive,
+                }) / gcd) as usize
+            })
+            .collect())
+    }
+
+    /// Parse a chemical formula such as `"MgCl2"`, `"Na2SO4"`, or `"KAl(SO4)2"`.
+    ///
+    /// Ions are recognized from a list of common cations and anions.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidFormula`](crate::Error::InvalidFormula) if the formula contains
+    /// unknown ions, and [`Error::Stoichiometry`](crate::Error::Stoichiometry) if the salt is not
+    /// electroneutral or if its stoichiometry cannot be deduced from the valencies.
+    pub fn from_formula(formula: &str) -> crate::Result<Self> {
+        let ions = formula::parse(formula)?;
+        let salt = Salt::Custom(ions.iter().map(|(valency, _)| *valency).collect());
+        let counts: Vec<usize> = ions.iter().map(|(_, count)| *count).collect();
+        let divisor = counts.iter().copied().reduce(gcd).unwrap_or(1);
+        let reduced: Vec<usize> = counts.iter().map(|count| count / divisor).collect();
+        if salt.stoichiometry()? != reduced {
+            return Err(crate::Error::Stoichiometry);
+        }
+        Ok(salt)
+    }
+
+    /// Calculate ionic strength from the salt molarity (mol/l), I = ½m∑(νᵢzᵢ²)
+    ///
+    /// # Errors
+    /// Returns an error if the stoichiometry cannot be resolved.
+    pub fn ionic_strength(&self, molarity: f64) -> crate::Result<f64> {
+        Ok(0.5
+            * molarity
+            * std::iter::zip(self.valencies(), self.stoichiometry()?.iter().copied())
+                .map(|(valency, nu)| nu * valency.pow(2) as usize)
+                .sum::<usize>() as f64)
+    }
+}
+
+impl FromStr for Salt {
+    type Err = crate::Error;
+    /// Named salt, see [`Salt::from_name`], or else a salt parsed from its formula
+    fn from_str(formula: &str) -> crate::Result<Self> {
+        Self::from_name(formula).or_else(|_| Self::from_formula(formula))
+    }
+}
+
+impl Display for Salt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "🧂Salt = ")?;
+        if let Some((formula, _)) = self.names() {
+            return write!(f, "{}", formula);
+        }
+        if let Salt::Ions(ions) = self {
+            write!(f, "Ions(")?;
+            for (i, (ion, nu)) in ions.iter().enumerate() {
+                match nu {
+                    1 => write!(f, "{}", ion)?,
+                    _ => write!(f, "{} {}", nu, ion)?,
+                }
+                if i < ions.len() - 1 {
+                    write!(f, ", ")?;
+                }
+            }
+            return write!(f, ")");
+        }
+        let valencies = self.valencies();
+        write!(f, "Custom(")?;
+        for (i, valency) in valencies.iter().enumerate() {
+            write!(f, "{}", valency)?;
+            if i < valencies.len() - 1 {
+                write!(f, ", ")?;
+            }
+        }
+        write!(f, ")")
+    }
+}
+
+#[test]
+fn test_salt() {
+    let molarity = 0.15;
+
+    // NaCl
+    assert_eq!(Salt::SodiumChloride.valencies(), [1, -1]);
+    assert_eq!(Salt::SodiumChloride.stoichiometry().unwrap(), [1, 1]);
+    approx::assert_abs_diff_eq!(
+        Salt::SodiumChloride.ionic_strength(molarity).unwrap(),
+        molarity
+    );
+
+    // CaSO₄
+    assert_eq!(Salt::CalciumSulfate.valencies(), [2, -2]);
+    assert_eq!(Salt::CalciumSulfate.stoichiometry().unwrap(), [1, 1]);
+    approx::assert_abs_diff_eq!(
+        Salt::CalciumSulfate.ionic_strength(molarity).unwrap(),
+        0.5 * (molarity * 4.0 + molarity * 4.0)
+    );
+
+    // CaCl₂
+    assert_eq!(Salt::CalciumChloride.valencies(), [2, -1]);
+    assert_eq!(Salt::CalciumChloride.stoichiometry().unwrap(), [1, 2]);
+    approx::assert_abs_diff_eq!(
+        Salt::CalciumChloride.ionic_strength(molarity).unwrap(),
+        0.5 * (molarity * 4.0 + 2.0 * molarity)
+    );
+
+    // KAl(SO₄)₂
+    assert_eq!(Salt::PotassiumAlum.valencies(), [1, 3, -2]);
+    assert_eq!(Salt::PotassiumAlum.stoichiometry().unwrap(), [1, 1, 2]);
+    approx::assert_abs_diff_eq!(
+        Salt::PotassiumAlum.ionic_strength(molarity).unwrap(),
+        0.5 * (molarity * 1.0 + molarity * 9.0 + 2.0 * molarity * 4.0)
+    );
+
+    // Test invalid salt returns error
+    let invalid_salt = Salt::Custom(vec![1, 1]); // All positive
+    assert!(invalid_salt.stoichiometry().is_err());
+    assert!(invalid_salt.ionic_strength(0.1).is_err());
+
+    // Chemical formulas
+    assert_eq!(Salt::from_formula("MgCl2").unwrap().valencies(), [2, -1]);
+    assert_eq!(
+        Salt::from_formula("Ca3(PO4)2")
+            .unwrap()
+            .stoichiometry()
+            .unwrap(),
+        [3, 2]
+    );
+    assert_eq!(
+        "LaCl₃"
+            .parse::<Salt>()
+            .unwrap()
+            .ionic_strength(molarity)
+            .unwrap(),
+        Salt::LanthanumChloride.ionic_strength(molarity).unwrap()
+    );
+    assert!(matches!(
+        Salt::from_formula("NaCl2"),
+        Err(crate::Error::Stoichiometry)
+    ));
+    // Not expressible as valencies only: K₂Mg(SO₄)₂ would be deduced as K₂Mg₂(SO₄)₃
+    assert!(matches!(
+        Salt::from_formula("K2Mg(SO4)2"),
+        Err(crate::Error::Stoichiometry)
+    ));
+    assert!(matches!(
+        Salt::from_formula("Unobtainium"),
+        Err(crate::Error::InvalidFormula(_))
+    ));
+
+    // Named salts
+    assert_eq!(Salt::from_name("CaCl2").unwrap(), Salt::CalciumChloride);
+    assert_eq!(Salt::from_name("MgSO₄").unwrap(), Salt::MagnesiumSulfate);
+    assert_eq!(
+        Salt::from_name(" Potassium Chloride").unwrap(),
+        Salt::PotassiumChloride
+    );
+    assert!(matches!(
+        Salt::from_name("MgBr2"),
+        Err(crate::Error::UnknownSalt(_))
+    ));
+    assert_eq!("KAl(SO4)2".parse::<Salt>().unwrap(), Salt::PotassiumAlum);
+    assert_eq!("MgBr2".parse::<Salt>().unwrap(), Salt::Custom(vec![2, -1]));
+    for salt in Salt::NAMED {
+        let (formula, name) = salt.names().unwrap();
+        assert_eq!(Salt::from_name(formula).unwrap(), salt);
+        assert_eq!(Salt::from_name(name).unwrap(), salt);
+        assert_eq!(
+            Salt::from_formula(formula).unwrap().valencies(),
+            salt.valencies()
+        );
+        assert_eq!(salt.ion_size().is_some(), salt.valencies().len() == 2);
+    }
+    assert_eq!(Salt::Custom(vec![1, -1]).ion_size(), None);
+
+    // Explicit ions
+    let sodium = Ion::new(1, 2.0).with_label("Na");
+    let chloride = Ion::new(-1, 1.5).with_label("Cl");
+    let salt = Salt::from_ions(vec![(sodium.clone(), 1), (chloride.clone(), 1)]).unwrap();
+    assert_eq!(salt.valencies(), Salt::SodiumChloride.valencies());
+    assert_eq!(salt.ion_size(), Some(3.5));
+    assert_eq!(salt.ions().unwrap()[1].0, chloride);
+    assert_eq!(salt.to_string(), "🧂Salt = Ions(Na⁺, Cl⁻)");
+    assert!(Salt::from_ions(vec![(sodium.clone(), 2), (chloride.clone(), 1)]).is_err());
+    assert!(Salt::from_ions(vec![(sodium.clone(), 0), (chloride, 0)]).is_err());
+    assert!(Salt::from_ions(vec![(sodium, 1)]).is_err());
+    let alum = Salt::from_ions(vec![
+        (Ion::new(1, 1.5), 1),
+        (Ion::new(3, 4.5), 1),
+        (Ion::new(-2, 2.0), 2),
+    ])
+    .unwrap();
+    assert_eq!(alum.ion_size(), None);
+    assert_eq!(alum.to_string(), "🧂Salt = Ions(⁺, ³⁺, 2 ²⁻)");
+}
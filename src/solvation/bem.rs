@@ -0,0 +1,281 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Boundary-element solver for the linearized Poisson-Boltzmann equation.
+
+use crate::{DebyeLength, Medium, NalgebraVector3, Vector3};
+use core::f64::consts::PI;
+use nalgebra::{DMatrix, DVector};
+
+/// Flat panel of a discretized molecular surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Panel {
+    /// Center of the panel
+    pub position: Vector3,
+    /// Unit normal pointing out of the solute
+    pub normal: Vector3,
+    /// Area of the panel, UNIT: [ ( input length )^2 ]
+    pub area: f64,
+}
+
+impl Panel {
+    /// Panel from a triangle with vertices in counter-clockwise order as seen from the solvent.
+    pub fn from_triangle(vertices: [Vector3; 3]) -> Self {
+        let [a, b, c] = vertices.map(NalgebraVector3::from);
+        let cross = (b - a).cross(&(c - a));
+        Self {
+            position: ((a + b + c) / 3.0).into(),
+            normal: cross.normalize().into(),
+            area: 0.5 * cross.norm(),
+        }
+    }
+}
+
+/// Boundary-element solver for a solute cavity in a salt solution.
+///
+/// Inside the solute, with relative permittivity $\varepsilon_{in}$, the potential obeys Poisson's
+/// equation while the solvent, with $\varepsilon_{out}$ and inverse Debye length $\kappa$, obeys the
+/// linearized Poisson-Boltzmann equation.
+/// With Green's functions $G_0 = 1/4\pi r$ and $G_\kappa = e^{-\kappa r}/4\pi r$, the surface
+/// potential, $f$, and its normal derivative inside the solute, $h$, satisfy
+///
+/// $$
+/// \frac{1}{2} f + \oint \frac{\partial G_0}{\partial n'} f \, dS' - \oint G_0 h \, dS' = \sum_k \frac{q_k}{\varepsilon_{in} |\boldsymbol{x} - \boldsymbol{r}_k|}
+/// $$
+/// $$
+/// \frac{1}{2} f - \oint \frac{\partial G_\kappa}{\partial n'} f \, dS' + \frac{\varepsilon_{in}}{\varepsilon_{out}} \oint G_\kappa h \, dS' = 0
+/// $$
+///
+/// which are solved by collocation at the panel centers.
+/// Self-terms of the double-layer integrals are set to satisfy Gauss' theorem for the discretized
+/// surface, which corrects for curvature.
+/// See Yoon and Lenhoff, <https://doi.org/10.1002/jcc.540111003>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::{Medium, Salt, solvation::{BoundaryElementSolver, Panel}};
+/// let medium = Medium::salt_water(298.15, Salt::SodiumChloride, 0.1);
+/// let solver = BoundaryElementSolver::from_medium(2.0, &medium);
+/// // Tetrahedral cavity
+/// let v: [[f64; 3]; 4] = [[1.0, 1.0, 1.0], [1.0, -1.0, -1.0], [-1.0, 1.0, -1.0], [-1.0, -1.0, 1.0]];
+/// let panels = [[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]]
+///     .map(|[a, b, c]| Panel::from_triangle([v[a].into(), v[b].into(), v[c].into()]));
+/// let positions = [[0.0, 0.0, 0.0].into()];
+/// let solution = solver.solve(&panels, &positions, &[1.0]).unwrap();
+/// assert!(solution.solvation_energy(&positions, &[1.0]) < 0.0);
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoundaryElementSolver {
+    /// Relative permittivity of the solute
+    pub inner_permittivity: f64,
+    /// Relative permittivity of the solvent
+    pub outer_permittivity: f64,
+    /// Inverse Debye length of the solvent, UNIT: [ ( input length )^-1 ]
+    pub kappa: Option<f64>,
+}
+
+/// Surface potential and normal derivative from a boundary-element calculation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundarySolution {
+    /// Panels of the surface
+    pub panels: Vec<Panel>,
+    /// Potential at each panel, UNIT: [ ( input charge ) / ( input length ) ]
+    pub potential: Vec<f64>,
+    /// Normal derivative of the potential on the solute side of each panel,
+    /// UNIT: [ ( input charge ) / ( input length )^2 ]
+    pub normal_derivative: Vec<f64>,
+}
+
+impl BoundarySolution {
+    /// Reaction potential at `point` inside the solute, UNIT: [ ( input charge ) / ( input length ) ]
+    pub fn reaction_potential(&self, point: impl Into<Vector3>) -> f64 {
+        let point: NalgebraVector3 = point.into().into();
+        self.panels
+            .iter()
+            .zip(self.potential.iter().zip(&self.normal_derivative))
+            .map(|(panel, (f, h))| {
+                let r = NalgebraVector3::from(panel.position) - point;
+                let distance = r.norm();
+                let dg_dn = -r.dot(&NalgebraVector3::from(panel.normal)) / distance.powi(3);
+                (h / distance - f * dg_dn) * panel.area / (4.0 * PI)
+            })
+            .sum()
+    }
+
+    /// Electrostatic solvation energy, $\frac{1}{2} \sum_k q_k \phi_{reac}(\boldsymbol{r}_k)$,
+    /// UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn solvation_energy(&self, positions: &[Vector3], charges: &[f64]) -> f64 {
+        0.5 * positions
+            .iter()
+            .zip(charges)
+            .map(|(r, q)| q * self.reaction_potential(*r))
+            .sum::<f64>()
+    }
+}
+
+impl BoundaryElementSolver {
+    /// New solver for a solute and solvent with given relative permittivities.
+    pub const fn new(inner_permittivity: f64, outer_permittivity: f64, kappa: Option<f64>) -> Self {
+        Self {
+            inner_permittivity,
+            outer_permittivity,
+            kappa,
+        }
+    }
+
+    /// New solver with solvent permittivity and salt screening of a medium.
+    pub fn from_medium(inner_permittivity: f64, medium: &Medium) -> Self {
+        Self::new(inner_permittivity, medium.permittivity(), medium.kappa())
+    }
+
+    /// Green's function and its radial derivative times 4π
+    fn green(&self, distance: f64) -> (f64, f64) {
+        let kappa = self.kappa.unwrap_or(0.0);
+        let exp = (-kappa * distance).exp();
+        (
+            exp / distance,
+            -exp * (1.0 + kappa * distance) / (distance * distance),
+        )
+    }
+
+    /// Solve for the surface potential from charges inside the solute.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `positions` and `charges` differ in length.
+    pub fn solve(
+        &self,
+        panels: &[Panel],
+        positions: &[Vector3],
+        charges: &[f64],
+    ) -> crate::Result<BoundarySolution> {
+        assert_eq!(positions.len(), charges.len());
+        let n = panels.len();
+        let centers: Vec<NalgebraVector3> = panels.iter().map(|p| p.position.into()).collect();
+        let normals: Vec<NalgebraVector3> = panels.iter().map(|p| p.normal.into()).collect();
+        let kappa = self.kappa.unwrap_or(0.0);
+        let ratio = self.inner_permittivity / self.outer_permittivity;
+        let mut matrix = DMatrix::<f64>::zeros(2 * n, 2 * n);
+        for i in 0..n {
+            // Single layer self-terms integrated over a disc of equal area
+            let radius = (panels[i].area / PI).sqrt();
+            let g0_self = 0.5 * radius;
+            let gk_self = match kappa {
+                0.0 => g0_self,
+                _ => (1.0 - (-kappa * radius).exp()) / (2.0 * kappa),
+            };
+            let mut k0_sum = 0.0;
+            for j in (0..n).filter(|j| *j != i) {
+                let r = centers[j] - centers[i];
+                let distance = r.norm();
+                let cos = r.dot(&normals[j]) / distance;
+                let area = panels[j].area / (4.0 * PI);
+                let (gk, dgk) = self.green(distance);
+                let k0 = -cos / (distance * distance) * area;
+                k0_sum += k0;
+                matrix[(i, j)] = k0;
+                matrix[(i, n + j)] = -area / distance;
+                matrix[(n + i, j)] = -dgk * cos * area;
+                matrix[(n + i, n + j)] = ratio * gk * area;
+            }
+            // Double layer self-terms from Gauss' theorem, ∮ ∂G₀/∂n' dS' = -1/2,
+            // which also applies to the screened kernel at short range
+            let k0_self = -0.5 - k0_sum;
+            matrix[(i, i)] = 0.5 + k0_self;
+            matrix[(i, n + i)] = -g0_self;
+            matrix[(n + i, i)] = 0.5 - k0_self;
+            matrix[(n + i, n + i)] = ratio * gk_self;
+        }
+        let source = DVector::from_iterator(
+            2 * n,
+            centers
+                .iter()
+                .map(|x| {
+                    positions
+                        .iter()
+                        .zip(charges)
+                        .map(|(r, q)| q / (x - NalgebraVector3::from(*r)).norm())
+                        .sum::<f64>()
+                        / self.inner_permittivity
+                })
+                .chain(core::iter::repeat(0.0).take(n)),
+        );
+        let solution = matrix
+            .lu()
+            .solve(&source)
+            .ok_or(crate::Error::SingularMatrix)?;
+        Ok(BoundarySolution {
+            panels: panels.to_vec(),
+            potential: solution.rows(0, n).iter().copied().collect(),
+            normal_derivative: solution.rows(n, n).iter().copied().collect(),
+        })
+    }
+}
+
+#[test]
+fn test_boundary_element() {
+    use super::KirkwoodSphere;
+    use approx::assert_relative_eq;
+
+    // Equal-area panels on a sphere from a Fibonacci lattice
+    let (radius, eps_in, eps_out, n) = (4.0, 2.0, 80.0, 200);
+    let golden_angle = PI * (3.0 - 5.0f64.sqrt());
+    let panels: Vec<Panel> = (0..n)
+        .map(|i| {
+            let z = 1.0 - (2.0 * i as f64 + 1.0) / n as f64;
+            let rho = (1.0 - z * z).sqrt();
+            let phi = golden_angle * i as f64;
+            let normal = NalgebraVector3::new(rho * phi.cos(), rho * phi.sin(), z);
+            Panel {
+                position: (normal * radius).into(),
+                normal: normal.into(),
+                area: 4.0 * PI * radius * radius / n as f64,
+            }
+        })
+        .collect();
+
+    // Born ion with and without salt
+    let positions = [[0.0, 0.0, 0.0].into()];
+    for kappa in [None, Some(0.1), Some(1.0)] {
+        let solver = BoundaryElementSolver::new(eps_in, eps_out, kappa);
+        let solution = solver.solve(&panels, &positions, &[1.0]).unwrap();
+        let ka = kappa.unwrap_or(0.0) * radius;
+        assert_relative_eq!(
+            solution.solvation_energy(&positions, &[1.0]),
+            0.5 / radius * (1.0 / (eps_out * (1.0 + ka)) - 1.0 / eps_in),
+            max_relative = 0.01
+        );
+    }
+
+    // Off-center ion pair compared with the Kirkwood expansion
+    let positions = [[1.0, -0.5, 1.5].into(), [-1.2, 0.0, -0.8].into()];
+    let charges = [1.0, -0.5];
+    let solution = BoundaryElementSolver::new(eps_in, eps_out, None)
+        .solve(&panels, &positions, &charges)
+        .unwrap();
+    assert_relative_eq!(
+        solution.solvation_energy(&positions, &charges),
+        KirkwoodSphere::new(radius, eps_in, eps_out)
+            .reaction_field_energy(&positions, &charges, 60),
+        max_relative = 0.02
+    );
+
+    // Salt from a medium
+    let medium = Medium::salt_water(298.15, crate::Salt::SodiumChloride, 0.05);
+    let solver = BoundaryElementSolver::from_medium(eps_in, &medium);
+    assert_eq!(solver.kappa, medium.kappa());
+}
@@ -0,0 +1,359 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Generalized Born implicit solvent.
+
+use crate::{DebyeLength, Medium, NalgebraVector3, Vector3};
+
+/// Pairwise descreening approximation for effective Born radii.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BornRadii {
+    /// Hawkins, Cramer, and Truhlar, <https://doi.org/10.1021/jp961710n>
+    Hct,
+    /// Onufriev, Bashford, and Case, model I, <https://doi.org/10.1002/prot.20033>
+    Obc1,
+    /// Onufriev, Bashford, and Case, model II
+    #[default]
+    Obc2,
+}
+
+impl BornRadii {
+    /// Coefficients (α, β, γ) of the OBC rescaling, or `None` for HCT
+    const fn obc_parameters(&self) -> Option<(f64, f64, f64)> {
+        match self {
+            Self::Hct => None,
+            Self::Obc1 => Some((0.8, 0.0, 2.909125)),
+            Self::Obc2 => Some((1.0, 0.8, 4.85)),
+        }
+    }
+}
+
+/// Generalized Born implicit solvent.
+///
+/// The electrostatic solvation energy of charges, $q_i$, with effective Born radii, $R_i$, is
+/// estimated with Still's pairwise formula,
+///
+/// $$
+/// \Delta G = -\frac{1}{2} \sum_{i,j} \left(\frac{1}{\varepsilon_{in}} - \frac{e^{-\kappa f_{ij}}}{\varepsilon_{out}}\right)
+/// \frac{q_i q_j}{f_{ij}}, \quad
+/// f_{ij} = \sqrt{r_{ij}^2 + R_i R_j e^{-r_{ij}^2 / 4 R_i R_j}}
+/// $$
+///
+/// where the sum includes the Born self-energies, $i = j$, and $\kappa$ is the inverse Debye
+/// length for salt screening.
+/// The Born radii are found from atomic radii and overlap scaling factors with a
+/// pairwise descreening approximation, see [`BornRadii`].
+/// Still _et al._, <https://doi.org/10.1021/ja00172a038>.
+///
+/// Positions and radii should be in angstrom if the salt screening is taken from a [`Medium`].
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::{Medium, solvation::{BornRadii, GeneralizedBorn}};
+/// let gb = GeneralizedBorn::from_medium(BornRadii::Obc2, &Medium::neat_water(298.15));
+/// let positions = [[0.0, 0.0, 0.0].into(), [0.0, 0.0, 3.0].into()];
+/// let born_radii = gb.born_radii(&positions, &[1.5, 1.8], &[0.8, 0.8]);
+/// let energy = gb.energy(&positions, &[1.0, -1.0], &born_radii);
+/// assert!(energy < 0.0);
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeneralizedBorn {
+    /// Approximation for effective Born radii
+    pub method: BornRadii,
+    /// Relative permittivity of the solute
+    pub inner_permittivity: f64,
+    /// Relative permittivity of the solvent
+    pub outer_permittivity: f64,
+    /// Inverse Debye length of the solvent, UNIT: [ ( input length )^-1 ]
+    pub kappa: Option<f64>,
+    /// Offset subtracted from atomic radii, UNIT: [ input length ]
+    pub offset: f64,
+}
+
+impl GeneralizedBorn {
+    /// Offset subtracted from atomic radii in angstrom
+    pub const DEFAULT_OFFSET: f64 = 0.09;
+
+    /// New generalized Born model without salt and with a solute permittivity of one.
+    pub const fn new(method: BornRadii, outer_permittivity: f64) -> Self {
+        Self {
+            method,
+            inner_permittivity: 1.0,
+            outer_permittivity,
+            kappa: None,
+            offset: Self::DEFAULT_OFFSET,
+        }
+    }
+
+    /// New generalized Born model with the permittivity and salt screening of a medium.
+    pub fn from_medium(method: BornRadii, medium: &Medium) -> Self {
+        Self {
+            kappa: medium.kappa(),
+            ..Self::new(method, medium.permittivity())
+        }
+    }
+
+    /// Effective Born radii and their derivatives with respect to the descreening sums
+    fn born_radii_with_derivatives(
+        &self,
+        positions: &[Vector3],
+        radii: &[f64],
+        scale_factors: &[f64],
+    ) -> (Vec<f64>, Vec<f64>) {
+        assert_eq!(positions.len(), radii.len());
+        assert_eq!(positions.len(), scale_factors.len());
+        let positions: Vec<NalgebraVector3> = positions.iter().map(|&r| r.into()).collect();
+        let mut born_radii = Vec::with_capacity(radii.len());
+        let mut derivatives = Vec::with_capacity(radii.len());
+        for (i, position_i) in positions.iter().enumerate() {
+            let rho_i = radii[i] - self.offset;
+            let sum: f64 = positions
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(j, position_j)| {
+                    let scaled_radius = (radii[j] - self.offset) * scale_factors[j];
+                    descreening(rho_i, (position_j - position_i).norm(), scaled_radius).0
+                })
+                .sum();
+            // Inverse radius, 1/R = 1/ρ - Ψ, and derivative dΨ/d(sum)
+            let (psi, dpsi) = match self.method.obc_parameters() {
+                None => (0.5 * sum, 0.5),
+                Some((alpha, beta, gamma)) => {
+                    let x = 0.5 * rho_i * sum;
+                    let tanh = (alpha * x - beta * x * x + gamma * x.powi(3)).tanh();
+                    let dtanh =
+                        (1.0 - tanh * tanh) * (alpha - 2.0 * beta * x + 3.0 * gamma * x * x);
+                    (tanh / radii[i], dtanh * 0.5 * rho_i / radii[i])
+                }
+            };
+            let inverse_radius = rho_i.recip() - psi;
+            if inverse_radius > Self::MAX_BORN_RADIUS.recip() {
+                born_radii.push(inverse_radius.recip());
+                derivatives.push(dpsi);
+            } else {
+                born_radii.push(Self::MAX_BORN_RADIUS);
+                derivatives.push(0.0);
+            }
+        }
+        (born_radii, derivatives)
+    }
+
+    /// Upper bound for Born radii of deeply buried atoms, UNIT: [ input length ]
+    const MAX_BORN_RADIUS: f64 = 1.0e3;
+
+    /// Effective Born radii from atomic radii and overlap scaling factors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices differ in length.
+    pub fn born_radii(
+        &self,
+        positions: &[Vector3],
+        radii: &[f64],
+        scale_factors: &[f64],
+    ) -> Vec<f64> {
+        self.born_radii_with_derivatives(positions, radii, scale_factors)
+            .0
+    }
+
+    /// Pair function, $g(f) = \tau(f) / f$, and its derivative with respect to $f$
+    fn screened_inverse(&self, f: f64) -> (f64, f64) {
+        let exp = self.kappa.map_or(1.0, |kappa| (-kappa * f).exp());
+        let tau = self.inner_permittivity.recip() - exp / self.outer_permittivity;
+        let dtau = self
+            .kappa
+            .map_or(0.0, |kappa| kappa * exp / self.outer_permittivity);
+        (tau / f, dtau / f - tau / (f * f))
+    }
+
+    /// Solvation energy for given Born radii, UNIT: [ ( input charge )^2 / ( input length ) ]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices differ in length.
+    pub fn energy(&self, positions: &[Vector3], charges: &[f64], born_radii: &[f64]) -> f64 {
+        assert_eq!(positions.len(), charges.len());
+        assert_eq!(positions.len(), born_radii.len());
+        let mut energy = 0.0;
+        for (i, position_i) in positions.iter().enumerate() {
+            energy += charges[i] * charges[i] * self.screened_inverse(born_radii[i]).0;
+            for (j, position_j) in positions.iter().enumerate().skip(i + 1) {
+                let r2 = (NalgebraVector3::from(*position_j) - NalgebraVector3::from(*position_i))
+                    .norm_squared();
+                let (f, _, _) = still(r2, born_radii[i], born_radii[j]);
+                energy += 2.0 * charges[i] * charges[j] * self.screened_inverse(f).0;
+            }
+        }
+        -0.5 * energy
+    }
+
+    /// Solvation energy and forces including the dependence of Born radii on positions.
+    ///
+    /// Returns the energy, UNIT: [ ( input charge )^2 / ( input length ) ],
+    /// and the force on each atom, UNIT: [ ( input charge )^2 / ( input length )^2 ].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices differ in length.
+    pub fn energy_and_forces(
+        &self,
+        positions: &[Vector3],
+        charges: &[f64],
+        radii: &[f64],
+        scale_factors: &[f64],
+    ) -> (f64, Vec<Vector3>) {
+        let (born_radii, dpsi) = self.born_radii_with_derivatives(positions, radii, scale_factors);
+        let energy = self.energy(positions, charges, &born_radii);
+        let n = positions.len();
+        let positions: Vec<NalgebraVector3> = positions.iter().map(|&r| r.into()).collect();
+
+        // Derivatives of the energy with respect to Born radii
+        let mut de_dradius: Vec<f64> = (0..n)
+            .map(|i| -0.5 * charges[i] * charges[i] * self.screened_inverse(born_radii[i]).1)
+            .collect();
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let r2 = (positions[j] - positions[i]).norm_squared();
+                let (f, _, df_dradius) = still(r2, born_radii[i], born_radii[j]);
+                let dg = -charges[i] * charges[j] * self.screened_inverse(f).1;
+                de_dradius[i] += dg * df_dradius * born_radii[j];
+                de_dradius[j] += dg * df_dradius * born_radii[i];
+            }
+        }
+        // Chain factors, dE/dR dR/dΨ dΨ/d(sum)
+        let chain: Vec<f64> = (0..n)
+            .map(|i| de_dradius[i] * born_radii[i] * born_radii[i] * dpsi[i])
+            .collect();
+
+        let mut forces = vec![NalgebraVector3::zeros(); n];
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let r = positions[j] - positions[i];
+                let distance = r.norm();
+                let (f, df_dr, _) = still(distance * distance, born_radii[i], born_radii[j]);
+                let mut de_dr = -charges[i] * charges[j] * self.screened_inverse(f).1 * df_dr;
+                let (rho_i, rho_j) = (radii[i] - self.offset, radii[j] - self.offset);
+                de_dr += chain[i] * descreening(rho_i, distance, rho_j * scale_factors[j]).1;
+                de_dr += chain[j] * descreening(rho_j, distance, rho_i * scale_factors[i]).1;
+                let force = -r / distance * de_dr;
+                forces[j] += force;
+                forces[i] -= force;
+            }
+        }
+        (energy, forces.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Still's effective distance, $f$, its derivative with respect to $r$, and its derivative
+/// with respect to $R_i$ divided by $R_j$
+fn still(r2: f64, radius_i: f64, radius_j: f64) -> (f64, f64, f64) {
+    let product = radius_i * radius_j;
+    let d = r2 / (4.0 * product);
+    let exp = (-d).exp();
+    let f = (r2 + product * exp).sqrt();
+    (
+        f,
+        r2.sqrt() * (1.0 - 0.25 * exp) / f,
+        exp * (1.0 + d) / (2.0 * f),
+    )
+}
+
+/// Descreening integral of atom `i` with offset radius `rho_i` by a sphere of radius `scaled_radius`
+/// at distance `r`, and its derivative with respect to `r`
+fn descreening(rho_i: f64, r: f64, scaled_radius: f64) -> (f64, f64) {
+    let s = scaled_radius;
+    let upper = r + s;
+    if rho_i >= upper {
+        return (0.0, 0.0);
+    }
+    let (lower, dlower) = if rho_i > (r - s).abs() {
+        (rho_i, 0.0)
+    } else {
+        ((r - s).abs(), (r - s).signum())
+    };
+    let (l, u) = (lower.recip(), upper.recip());
+    let (dl, du) = (-l * l * dlower, -u * u);
+    let log = (u / l).ln();
+    let s2 = s * s;
+    let mut term =
+        l - u + 0.25 * r * (u * u - l * l) + 0.5 / r * log + 0.25 * s2 / r * (l * l - u * u);
+    let mut dterm = dl - du + 0.25 * (u * u - l * l) + 0.5 * r * (u * du - l * dl)
+        - 0.5 / (r * r) * log
+        + 0.5 / r * (du / u - dl / l)
+        - 0.25 * s2 / (r * r) * (l * l - u * u)
+        + 0.5 * s2 / r * (l * dl - u * du);
+    // Atom i is engulfed by the descreening sphere
+    if rho_i < s - r {
+        term += 2.0 * (rho_i.recip() - l);
+        dterm -= 2.0 * dl;
+    }
+    (term, dterm)
+}
+
+#[test]
+fn test_generalized_born() {
+    use approx::assert_relative_eq;
+    let positions: Vec<Vector3> = vec![
+        [0.0, 0.0, 0.0].into(),
+        [1.5, 0.2, -0.1].into(),
+        [2.1, 1.4, 0.3].into(),
+        [-0.9, 1.1, 0.8].into(),
+        [0.4, -1.3, 1.0].into(),
+    ];
+    let charges = [0.4, -0.6, 0.3, -0.5, 0.4];
+    let radii = [1.7, 1.5, 1.2, 1.55, 1.2];
+    let scale_factors = [0.72, 0.79, 0.85, 0.79, 0.85];
+
+    // Single ion recovers the Born energy
+    let gb = GeneralizedBorn::new(BornRadii::Obc2, 80.0);
+    let born_radii = gb.born_radii(&positions[..1], &radii[..1], &scale_factors[..1]);
+    assert_relative_eq!(born_radii[0], 1.7 - 0.09);
+    assert_relative_eq!(
+        gb.energy(&positions[..1], &[2.0], &born_radii),
+        -2.0 * (1.0 - 1.0 / 80.0) / 1.61
+    );
+
+    for method in [BornRadii::Hct, BornRadii::Obc1, BornRadii::Obc2] {
+        let mut gb = GeneralizedBorn::new(method, 78.0);
+        gb.kappa = Some(0.1);
+        // Descreening increases Born radii
+        let born_radii = gb.born_radii(&positions, &radii, &scale_factors);
+        for (born_radius, radius) in born_radii.iter().zip(&radii) {
+            assert!(*born_radius > radius - gb.offset);
+        }
+        // Forces are the negative gradient of the energy
+        let (energy, forces) = gb.energy_and_forces(&positions, &charges, &radii, &scale_factors);
+        assert_relative_eq!(energy, gb.energy(&positions, &charges, &born_radii));
+        let h = 1e-6;
+        for (i, force) in forces.iter().enumerate() {
+            let force = NalgebraVector3::from(*force);
+            for a in 0..3 {
+                let energy = |dx: f64| {
+                    let mut positions = positions.clone();
+                    let mut position = NalgebraVector3::from(positions[i]);
+                    position[a] += dx;
+                    positions[i] = position.into();
+                    let born_radii = gb.born_radii(&positions, &radii, &scale_factors);
+                    gb.energy(&positions, &charges, &born_radii)
+                };
+                let gradient = (energy(h) - energy(-h)) / (2.0 * h);
+                assert_relative_eq!(force[a], -gradient, epsilon = 1e-7);
+            }
+        }
+    }
+}
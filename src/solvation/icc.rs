@@ -0,0 +1,308 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Induced charges at dielectric boundaries with the ICC* method.
+
+use crate::{NalgebraVector3, Vector3};
+use core::f64::consts::PI;
+
+/// Surface element of a discretized boundary between two dielectrics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SurfaceElement {
+    /// Center of the element
+    pub position: Vector3,
+    /// Unit normal pointing from the inner to the outer dielectric
+    pub normal: Vector3,
+    /// Area of the element, UNIT: [ ( input length )^2 ]
+    pub area: f64,
+    /// Relative permittivity on the inner side
+    pub inner_permittivity: f64,
+    /// Relative permittivity on the outer side
+    pub outer_permittivity: f64,
+}
+
+impl SurfaceElement {
+    /// Element from a triangle with vertices in counter-clockwise order as seen from the outer side.
+    pub fn from_triangle(
+        vertices: [Vector3; 3],
+        inner_permittivity: f64,
+        outer_permittivity: f64,
+    ) -> Self {
+        let [a, b, c] = vertices.map(NalgebraVector3::from);
+        let cross = (b - a).cross(&(c - a));
+        let area = 0.5 * cross.norm();
+        Self {
+            position: ((a + b + c) / 3.0).into(),
+            normal: cross.normalize().into(),
+            area,
+            inner_permittivity,
+            outer_permittivity,
+        }
+    }
+
+    /// Ratio, $(\varepsilon_{in} - \varepsilon_{out}) / (\varepsilon_{in} + \varepsilon_{out})$
+    fn contrast(&self) -> f64 {
+        (self.inner_permittivity - self.outer_permittivity)
+            / (self.inner_permittivity + self.outer_permittivity)
+    }
+}
+
+/// Iterative solver for induced surface charges, ICC*.
+///
+/// Point charges, $q_k$, in regions with relative permittivity $\varepsilon_k$ polarize the
+/// boundaries between dielectrics.
+/// The resulting field is that of the screened charges, $q_k / \varepsilon_k$, and induced
+/// surface charges in vacuum.
+/// At each surface element, the normal component of the displacement field is continuous, which
+/// gives the induced charge density
+///
+/// $$ \sigma_i = \frac{1}{2\pi} \frac{\varepsilon_{in} - \varepsilon_{out}}{\varepsilon_{in} + \varepsilon_{out}} \, \boldsymbol{E}_i \cdot \hat{\boldsymbol{n}}_i $$
+///
+/// where $\boldsymbol{E}_i$ is the field from the screened charges and all other surface elements.
+/// This is solved by successive over- or under-relaxation, element by element.
+/// See Tyagi _et al._, <https://doi.org/10.1063/1.3376011>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::solvation::{IccSolver, SurfaceElement};
+/// // Two triangles forming a small patch below a charge in water
+/// let (eps_in, eps_out) = (2.0, 80.0);
+/// let elements = [
+///     SurfaceElement::from_triangle([[0.0, 0.0, 0.0].into(), [1.0, 0.0, 0.0].into(), [1.0, 1.0, 0.0].into()], eps_in, eps_out),
+///     SurfaceElement::from_triangle([[0.0, 0.0, 0.0].into(), [1.0, 1.0, 0.0].into(), [0.0, 1.0, 0.0].into()], eps_in, eps_out),
+/// ];
+/// let induced = IccSolver::default()
+///     .solve(&elements, &[[0.5, 0.5, 2.0].into()], &[1.0], &[eps_out])
+///     .unwrap();
+/// assert!(induced.charges.iter().all(|q| *q > 0.0));
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IccSolver {
+    /// Relaxation parameter, 0 < w < 2, for mixing new and old charges
+    pub relaxation: f64,
+    /// Convergence criterion on the largest change in an induced charge density,
+    /// UNIT: [ ( input charge ) / ( input length )^2 ]
+    pub tolerance: f64,
+    /// Maximum number of iterations
+    pub max_iterations: usize,
+}
+
+impl Default for IccSolver {
+    fn default() -> Self {
+        Self {
+            relaxation: 0.95,
+            tolerance: 1e-10,
+            max_iterations: 1000,
+        }
+    }
+}
+
+/// Converged induced surface charges and derived properties.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InducedCharges {
+    /// Position of each induced charge
+    pub positions: Vec<Vector3>,
+    /// Induced charge of each surface element, $\sigma_i A_i$
+    pub charges: Vec<f64>,
+    /// Number of iterations used
+    pub iterations: usize,
+}
+
+impl InducedCharges {
+    /// Potential from the induced charges at `point`, UNIT: [ ( input charge ) / ( input length ) ]
+    pub fn potential(&self, point: impl Into<Vector3>) -> f64 {
+        let point: NalgebraVector3 = point.into().into();
+        self.positions
+            .iter()
+            .zip(&self.charges)
+            .map(|(r, q)| q / (point - NalgebraVector3::from(*r)).norm())
+            .sum()
+    }
+
+    /// Field from the induced charges at `point`, UNIT: [ ( input charge ) / ( input length )^2 ]
+    pub fn field(&self, point: impl Into<Vector3>) -> Vector3 {
+        let point: NalgebraVector3 = point.into().into();
+        self.positions
+            .iter()
+            .zip(&self.charges)
+            .map(|(r, q)| {
+                let r = point - NalgebraVector3::from(*r);
+                r * (q / r.norm().powi(3))
+            })
+            .sum::<NalgebraVector3>()
+            .into()
+    }
+
+    /// Interaction energy of the source charges with their induced charges,
+    /// $\frac{1}{2} \sum_k q_k \phi_{ind}(\boldsymbol{r}_k)$.
+    ///
+    /// UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn energy(&self, positions: &[Vector3], charges: &[f64]) -> f64 {
+        0.5 * positions
+            .iter()
+            .zip(charges)
+            .map(|(r, q)| q * self.potential(*r))
+            .sum::<f64>()
+    }
+
+    /// Forces on the source charges from the induced charges,
+    /// UNIT: [ ( input charge )^2 / ( input length )^2 ]
+    pub fn forces(&self, positions: &[Vector3], charges: &[f64]) -> Vec<Vector3> {
+        positions
+            .iter()
+            .zip(charges)
+            .map(|(r, q)| (NalgebraVector3::from(self.field(*r)) * *q).into())
+            .collect()
+    }
+}
+
+impl IccSolver {
+    /// Solve for the charges induced at `elements` by source charges with `permittivities` of
+    /// their surrounding dielectrics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source slices differ in length.
+    pub fn solve(
+        &self,
+        elements: &[SurfaceElement],
+        positions: &[Vector3],
+        charges: &[f64],
+        permittivities: &[f64],
+    ) -> crate::Result<InducedCharges> {
+        assert_eq!(positions.len(), charges.len());
+        assert_eq!(positions.len(), permittivities.len());
+        let centers: Vec<NalgebraVector3> = elements.iter().map(|e| e.position.into()).collect();
+        let normals: Vec<NalgebraVector3> = elements.iter().map(|e| e.normal.into()).collect();
+
+        // Normal field from the screened source charges
+        let source_field: Vec<f64> = centers
+            .iter()
+            .zip(&normals)
+            .map(|(center, normal)| {
+                positions
+                    .iter()
+                    .zip(charges)
+                    .zip(permittivities)
+                    .map(|((r, q), eps)| {
+                        let r = center - NalgebraVector3::from(*r);
+                        q / eps * r.dot(normal) / r.norm().powi(3)
+                    })
+                    .sum()
+            })
+            .collect();
+
+        let mut induced = vec![0.0; elements.len()];
+        for iteration in 1..=self.max_iterations {
+            let mut max_change: f64 = 0.0;
+            for (i, element) in elements.iter().enumerate() {
+                let field = source_field[i]
+                    + (0..elements.len())
+                        .filter(|j| *j != i)
+                        .map(|j| {
+                            let r = centers[i] - centers[j];
+                            induced[j] * r.dot(&normals[i]) / r.norm().powi(3)
+                        })
+                        .sum::<f64>();
+                let old_density = induced[i] / element.area;
+                let density = element.contrast() * field / (2.0 * PI);
+                let new_density = old_density + self.relaxation * (density - old_density);
+                max_change = max_change.max((new_density - old_density).abs());
+                induced[i] = new_density * element.area;
+            }
+            if max_change < self.tolerance {
+                return Ok(InducedCharges {
+                    positions: elements.iter().map(|e| e.position).collect(),
+                    charges: induced,
+                    iterations: iteration,
+                });
+            }
+        }
+        Err(crate::Error::NotConverged(self.max_iterations))
+    }
+}
+
+#[test]
+fn test_icc() {
+    use super::KirkwoodSphere;
+    use approx::assert_relative_eq;
+
+    // Equal-area elements on a sphere from a Fibonacci lattice
+    let (radius, eps_in, eps_out, n) = (5.0, 2.0, 80.0, 600);
+    let golden_angle = PI * (3.0 - 5.0f64.sqrt());
+    let elements: Vec<SurfaceElement> = (0..n)
+        .map(|i| {
+            let z = 1.0 - (2.0 * i as f64 + 1.0) / n as f64;
+            let rho = (1.0 - z * z).sqrt();
+            let phi = golden_angle * i as f64;
+            let normal = NalgebraVector3::new(rho * phi.cos(), rho * phi.sin(), z);
+            SurfaceElement {
+                position: (normal * radius).into(),
+                normal: normal.into(),
+                area: 4.0 * PI * radius * radius / n as f64,
+                inner_permittivity: eps_in,
+                outer_permittivity: eps_out,
+            }
+        })
+        .collect();
+    let sphere = KirkwoodSphere::new(radius, eps_in, eps_out);
+    let solver = IccSolver::default();
+
+    // Born ion at the center induces a uniform charge
+    let positions = [[0.0, 0.0, 0.0].into()];
+    let induced = solver
+        .solve(&elements, &positions, &[1.5], &[eps_in])
+        .unwrap();
+    assert_relative_eq!(
+        induced.charges.iter().sum::<f64>(),
+        1.5 * (eps_in - eps_out) / (eps_in * eps_out),
+        max_relative = 0.03
+    );
+    assert_relative_eq!(
+        induced.energy(&positions, &[1.5]),
+        sphere.reaction_field_energy(&positions, &[1.5], 0),
+        max_relative = 0.03
+    );
+
+    // Off-center ion pair compared with the Kirkwood expansion
+    let positions = [[1.0, -0.5, 2.0].into(), [-1.5, 0.0, -1.0].into()];
+    let charges = [1.0, -0.5];
+    let induced = solver
+        .solve(&elements, &positions, &charges, &[eps_in; 2])
+        .unwrap();
+    assert_relative_eq!(
+        induced.energy(&positions, &charges),
+        sphere.reaction_field_energy(&positions, &charges, 60),
+        max_relative = 0.03
+    );
+    // Ions are attracted towards the high-dielectric solvent
+    let forces = induced.forces(&positions, &charges);
+    assert!(NalgebraVector3::from(forces[0]).dot(&NalgebraVector3::from(positions[0])) > 0.0);
+
+    // Triangle geometry
+    let triangle = SurfaceElement::from_triangle(
+        [
+            [0.0, 0.0, 1.0].into(),
+            [2.0, 0.0, 1.0].into(),
+            [0.0, 2.0, 1.0].into(),
+        ],
+        eps_in,
+        eps_out,
+    );
+    assert_relative_eq!(triangle.area, 2.0);
+    assert_eq!(triangle.normal, [0.0, 0.0, 1.0].into());
+    assert_relative_eq!(triangle.position.x, 2.0 / 3.0);
+}
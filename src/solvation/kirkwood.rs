@@ -0,0 +1,148 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Kirkwood's multipole expansion of the reaction field in a spherical cavity.
+
+use crate::{NalgebraVector3, Vector3};
+
+/// Charges in a dielectric sphere embedded in a dielectric continuum.
+///
+/// The reaction-field energy of charges, $q_k$, at positions $\boldsymbol{r}_k$ relative to the
+/// center of a sphere of radius $a$ is
+///
+/// $$
+/// W = \frac{1}{2a} \sum_{k,l} q_k q_l \sum_{n=0}^{\ell_{max}}
+/// \frac{(n + 1)(\varepsilon_i - \varepsilon_o)}{\varepsilon_i [(n + 1) \varepsilon_o + n \varepsilon_i]}
+/// \left(\frac{r_k r_l}{a^2}\right)^n P_n(\cos\theta_{kl})
+/// $$
+///
+/// where $\varepsilon_i$ and $\varepsilon_o$ are the relative permittivities inside and outside
+/// the sphere, $P_n$ are Legendre polynomials, and $\theta_{kl}$ is the angle between
+/// $\boldsymbol{r}_k$ and $\boldsymbol{r}_l$.
+/// The monopole term, $n = 0$, is the Born energy.
+/// See Kirkwood, <https://doi.org/10.1063/1.1749656>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::solvation::KirkwoodSphere;
+/// let sphere = KirkwoodSphere::new(2.0, 1.0, 80.0);
+/// let energy = sphere.reaction_field_energy(&[[0.0, 0.0, 0.0].into()], &[1.0], 10);
+/// assert_relative_eq!(energy, -0.5 / 2.0 * (1.0 - 1.0 / 80.0));
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KirkwoodSphere {
+    /// Radius of the sphere, UNIT: [ input length ]
+    pub radius: f64,
+    /// Relative permittivity inside the sphere
+    pub inner_permittivity: f64,
+    /// Relative permittivity outside the sphere
+    pub outer_permittivity: f64,
+}
+
+impl KirkwoodSphere {
+    /// New sphere of `radius` with inner and outer relative permittivities.
+    pub const fn new(radius: f64, inner_permittivity: f64, outer_permittivity: f64) -> Self {
+        Self {
+            radius,
+            inner_permittivity,
+            outer_permittivity,
+        }
+    }
+
+    /// Reaction-field coefficient of order `n`
+    fn coefficient(&self, n: usize) -> f64 {
+        let (eps_i, eps_o) = (self.inner_permittivity, self.outer_permittivity);
+        let n = n as f64;
+        (n + 1.0) * (eps_i - eps_o) / (eps_i * ((n + 1.0) * eps_o + n * eps_i))
+    }
+
+    /// Reaction-field energy of charges inside the sphere up to multipole order `max_order`.
+    ///
+    /// Positions are relative to the center of the sphere.
+    /// UNIT: [ ( input charge )^2 / ( input length ) ]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `positions` and `charges` differ in length.
+    pub fn reaction_field_energy(
+        &self,
+        positions: &[Vector3],
+        charges: &[f64],
+        max_order: usize,
+    ) -> f64 {
+        assert_eq!(positions.len(), charges.len());
+        let coefficients: Vec<f64> = (0..=max_order).map(|n| self.coefficient(n)).collect();
+        let positions: Vec<NalgebraVector3> = positions.iter().map(|&r| r.into()).collect();
+        let mut energy = 0.0;
+        for (k, (r_k, q_k)) in positions.iter().zip(charges).enumerate() {
+            for (l, (r_l, q_l)) in positions.iter().zip(charges).enumerate().skip(k) {
+                let x = r_k.norm() * r_l.norm() / (self.radius * self.radius);
+                let cos_theta = match x {
+                    0.0 => 1.0,
+                    _ => r_k.dot(r_l) / (r_k.norm() * r_l.norm()),
+                };
+                // Legendre polynomials by upward recursion
+                let (mut p_previous, mut p) = (0.0, 1.0);
+                let mut x_n = 1.0;
+                let mut sum = 0.0;
+                for (n, coefficient) in coefficients.iter().enumerate() {
+                    sum += coefficient * x_n * p;
+                    let n = n as f64;
+                    (p_previous, p) = (
+                        p,
+                        ((2.0 * n + 1.0) * cos_theta * p - n * p_previous) / (n + 1.0),
+                    );
+                    x_n *= x;
+                }
+                let multiplicity = if k == l { 1.0 } else { 2.0 };
+                energy += multiplicity * q_k * q_l * sum;
+            }
+        }
+        0.5 * energy / self.radius
+    }
+}
+
+#[test]
+fn test_kirkwood() {
+    use approx::assert_relative_eq;
+    let (radius, charge) = (3.0, 1.5);
+    let position: Vector3 = [0.6, -0.9, 1.2].into();
+    let s2 = 0.6f64.powi(2) + 0.9f64.powi(2) + 1.2f64.powi(2);
+
+    // Grounded conducting sphere with the Kelvin image charge
+    let conductor = KirkwoodSphere::new(radius, 1.0, 1e12);
+    assert_relative_eq!(
+        conductor.reaction_field_energy(&[position], &[charge], 200),
+        -charge * charge * radius / (2.0 * (radius * radius - s2)),
+        max_relative = 1e-8
+    );
+
+    // No reaction field without a dielectric discontinuity
+    let uniform = KirkwoodSphere::new(radius, 4.0, 4.0);
+    let positions = [position, [-1.0, 0.5, 0.0].into()];
+    assert_eq!(
+        uniform.reaction_field_energy(&positions, &[1.0, -1.0], 10),
+        0.0
+    );
+
+    // A neutral dipole has no Born term and converges with the multipole order
+    let sphere = KirkwoodSphere::new(radius, 2.0, 80.0);
+    let charges = [1.0, -1.0];
+    let energy = |order| sphere.reaction_field_energy(&positions, &charges, order);
+    assert!(energy(0).abs() < 1e-15);
+    assert!(energy(1) < 0.0);
+    assert_relative_eq!(energy(60), energy(80), max_relative = 1e-10);
+}
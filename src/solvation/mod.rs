@@ -0,0 +1,31 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Continuum solvation models.
+//!
+//! Charges in a low-dielectric cavity polarize the surrounding high-dielectric solvent,
+//! which acts back on the charges through a _reaction field_.
+//! As in the [`image_charge`](crate::image_charge) module, energies include the relative
+//! permittivities and should only be multiplied by _e.g._ [`TO_CHEMISTRY_UNIT`](crate::TO_CHEMISTRY_UNIT).
+
+mod bem;
+mod generalized_born;
+mod icc;
+mod kirkwood;
+mod onsager;
+pub use bem::{BoundaryElementSolver, BoundarySolution, Panel};
+pub use generalized_born::{BornRadii, GeneralizedBorn};
+pub use icc::{IccSolver, InducedCharges, SurfaceElement};
+pub use kirkwood::KirkwoodSphere;
+pub use onsager::OnsagerCavity;
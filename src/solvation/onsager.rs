@@ -0,0 +1,160 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Onsager's reaction field for a point dipole in a spherical cavity.
+
+use crate::{permittivity::RelativePermittivity, NalgebraVector3, Result, Vector3};
+
+/// Polarizable point dipole in a spherical vacuum cavity embedded in a dielectric continuum.
+///
+/// A dipole, $\boldsymbol{\mu}$, at the center of a cavity of radius $a$ polarizes the
+/// surrounding medium with relative permittivity $\varepsilon$, giving the reaction field
+///
+/// $$
+/// \boldsymbol{R} = f \boldsymbol{\mu}^{*}, \quad f = \frac{2(\varepsilon - 1)}{(2\varepsilon + 1) a^3}
+/// $$
+///
+/// where $\boldsymbol{\mu}^{*} = \boldsymbol{\mu} / (1 - f\alpha)$ is the total dipole including
+/// the moment induced in a molecule of polarizability $\alpha$.
+/// This is the $\ell = 1$ term of the [`KirkwoodSphere`](super::KirkwoodSphere) expansion, here with
+/// polarizability feedback.
+/// See Onsager, <https://doi.org/10.1021/ja01299a050>.
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::solvation::OnsagerCavity;
+/// let cavity = OnsagerCavity::new(2.0, 80.0);
+/// let energy = cavity.solvation_energy([0.0, 0.0, 1.0], 0.0);
+/// assert_relative_eq!(energy, -0.5 * 2.0 * 79.0 / (161.0 * 8.0));
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnsagerCavity {
+    /// Radius of the cavity, UNIT: [ input length ]
+    pub radius: f64,
+    /// Relative permittivity outside the cavity
+    pub permittivity: f64,
+}
+
+impl OnsagerCavity {
+    /// New cavity of `radius` in a medium with relative permittivity, `permittivity`.
+    pub const fn new(radius: f64, permittivity: f64) -> Self {
+        Self {
+            radius,
+            permittivity,
+        }
+    }
+
+    /// New cavity of `radius` in a medium described by a permittivity model at `temperature`.
+    pub fn from_permittivity_model(
+        radius: f64,
+        model: &impl RelativePermittivity,
+        temperature: f64,
+    ) -> Result<Self> {
+        Ok(Self::new(radius, model.permittivity(temperature)?))
+    }
+
+    /// Reaction field factor, $f$, UNIT: [ ( input length )^-3 ]
+    pub fn reaction_field_factor(&self) -> f64 {
+        let eps = self.permittivity;
+        2.0 * (eps - 1.0) / ((2.0 * eps + 1.0) * self.radius.powi(3))
+    }
+
+    /// Cavity field factor, $g = 3\varepsilon / (2\varepsilon + 1)$, relating a uniform field in
+    /// the medium to the field inside an empty cavity.
+    pub fn cavity_field_factor(&self) -> f64 {
+        3.0 * self.permittivity / (2.0 * self.permittivity + 1.0)
+    }
+
+    /// Effective field factor, $g / (1 - f\alpha)$, relating a uniform field in the medium to the
+    /// directing field acting on the permanent dipole of a molecule with `polarizability`.
+    pub fn effective_field_factor(&self, polarizability: f64) -> f64 {
+        self.cavity_field_factor() / self.polarizability_feedback(polarizability)
+    }
+
+    /// Denominator, $1 - f\alpha$, from the mutual polarization of molecule and medium
+    fn polarizability_feedback(&self, polarizability: f64) -> f64 {
+        1.0 - self.reaction_field_factor() * polarizability
+    }
+
+    /// Total dipole moment, $\boldsymbol{\mu}^{*}$, including the moment induced by the reaction field.
+    pub fn total_dipole(&self, dipole: impl Into<Vector3>, polarizability: f64) -> Vector3 {
+        let dipole: NalgebraVector3 = dipole.into().into();
+        (dipole / self.polarizability_feedback(polarizability)).into()
+    }
+
+    /// Reaction field, $\boldsymbol{R}$, at the center of the cavity.
+    ///
+    /// UNIT: [ ( input charge ) / ( input length )^2 ]
+    pub fn reaction_field(&self, dipole: impl Into<Vector3>, polarizability: f64) -> Vector3 {
+        let total: NalgebraVector3 = self.total_dipole(dipole, polarizability).into();
+        (total * self.reaction_field_factor()).into()
+    }
+
+    /// Solvation free energy, $-\frac{1}{2} f \mu^2 / (1 - f\alpha)$, of a dipole with `polarizability`.
+    ///
+    /// UNIT: [ ( input charge )^2 / ( input length ) ]
+    pub fn solvation_energy(&self, dipole: impl Into<Vector3>, polarizability: f64) -> f64 {
+        let dipole: NalgebraVector3 = dipole.into().into();
+        let field: NalgebraVector3 = self.reaction_field(dipole, polarizability).into();
+        -0.5 * dipole.dot(&field)
+    }
+}
+
+#[test]
+fn test_onsager() {
+    use super::KirkwoodSphere;
+    use approx::assert_relative_eq;
+    let (radius, permittivity) = (3.0, 78.0);
+    let cavity = OnsagerCavity::new(radius, permittivity);
+
+    // Dipolar term of the Kirkwood expansion for two charges near the center
+    let positions = [[0.1, -0.2, 0.05].into(), [-0.1, 0.15, 0.1].into()];
+    let charges = [0.8, -0.8];
+    let dipole = [0.8 * 0.2, -0.8 * 0.35, -0.8 * 0.05];
+    let sphere = KirkwoodSphere::new(radius, 1.0, permittivity);
+    assert_relative_eq!(
+        cavity.solvation_energy(dipole, 0.0),
+        sphere.reaction_field_energy(&positions, &charges, 1),
+        max_relative = 1e-12
+    );
+
+    // Self-consistency of the induced dipole
+    let polarizability = 4.5;
+    let total = NalgebraVector3::from(cavity.total_dipole(dipole, polarizability));
+    let field = NalgebraVector3::from(cavity.reaction_field(dipole, polarizability));
+    assert_relative_eq!(
+        total,
+        NalgebraVector3::from(dipole) + polarizability * field
+    );
+    assert!(cavity.solvation_energy(dipole, polarizability) < cavity.solvation_energy(dipole, 0.0));
+
+    // No reaction field in vacuum and the conductor limit of the cavity field factor
+    let vacuum = OnsagerCavity::new(radius, 1.0);
+    assert_eq!(vacuum.reaction_field_factor(), 0.0);
+    assert_eq!(vacuum.effective_field_factor(polarizability), 1.0);
+    assert_relative_eq!(
+        OnsagerCavity::new(radius, 1e12).cavity_field_factor(),
+        1.5,
+        max_relative = 1e-10
+    );
+    let medium = crate::Medium::neat_water(298.15);
+    assert_relative_eq!(
+        OnsagerCavity::from_permittivity_model(radius, &medium, 298.15)
+            .unwrap()
+            .permittivity,
+        medium.permittivity()
+    );
+}
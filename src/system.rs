@@ -0,0 +1,1468 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Whole-system energies, forces, and fields.
+//!
+//! A [`ParticleSystem`] holds positions, charges, and optional dipole moments of a
+//! set of particles, possibly in a periodic cell, and sums the pairwise interactions
+//! of any scheme from the [`pairwise`](crate::pairwise) module.
+//! Distances in periodic cells follow the minimum image convention, see [`Cell`].
+//! For schemes with a finite cutoff, interacting pairs are found with a [`CellList`].
+//! A persistent cell list, see [`ParticleSystem::with_cell_list`], is kept up to date as
+//! particles move, allowing efficient single-particle energies for Monte Carlo moves.
+//! Interactions between bonded neighbors can be excluded or scaled, as for 1-2, 1-3, and
+//! 1-4 pairs in molecular force fields.
+//! Particles can be collected in groups, _e.g._ molecules or charge groups, and the
+//! cutoff applied between group centers, see [`CutoffPolicy`].
+//! With Ewald summation, the reciprocal-space sum is added through
+//! [`ParticleSystem::with_reciprocal`], and [`ParticleSystem::energy_decomposition`]
+//! reports each contribution to the total energy.
+//! The virial and pressure tensors, needed for constant pressure simulations, include the
+//! same contributions.
+//!
+//! # Examples
+//! ~~~
+//! # use approx::assert_relative_eq;
+//! use coulomb::{pairwise::Plain, system::ParticleSystem};
+//! let positions = vec![[0.0, 0.0, 0.0].into(), [0.0, 0.0, 9.0].into()];
+//! let system = ParticleSystem::new(positions, vec![1.0, -1.0]).with_box_length([10.0; 3]);
+//! let plain = Plain::new(5.0, None);
+//! assert_relative_eq!(system.total_energy(&plain), -1.0);
+//! assert_relative_eq!(system.forces(&plain)[0].z, -1.0);
+//! ~~~
+
+use crate::neighbor::CellList;
+use crate::pairwise::{Multipole, MultipoleEnergy, MultipoleField, MultipoleForce, Plain};
+use crate::pbc::Cell;
+use crate::reciprocal::{EwaldReciprocal, ReciprocalEnergy, ReciprocalForce, StructureFactorCache};
+use crate::{Matrix3, NalgebraMatrix3, NalgebraVector3, Vector3};
+use core::fmt::{self, Display, Formatter};
+use core::ops::Range;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Policy for applying the cutoff of a scheme in a [`ParticleSystem`]
+///
+/// With group-based cutoffs, all atom pairs between two groups are included or excluded
+/// together, based on the distance between the group centers.
+/// For neutral groups, such as dipolar molecules, this avoids the artificial charges
+/// created by cutting through groups, improving energy conservation with truncated
+/// schemes like reaction field.
+/// Atom pairs further apart than the cutoff still vanish as they are evaluated by the scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CutoffPolicy {
+    /// Cutoff between atoms (default)
+    #[default]
+    Atomic,
+    /// Cutoff between geometric centers of groups; ungrouped particles form their own groups
+    GroupCenter,
+}
+
+/// Contributions to the total energy of a [`ParticleSystem`]
+///
+/// All energies are in UNIT: [ ( input charge )^2 / ( input length ) ]
+///
+/// # Examples
+/// ~~~
+/// # use approx::assert_relative_eq;
+/// use coulomb::{pairwise::Plain, system::ParticleSystem};
+/// let positions = vec![[0.0, 0.0, 0.0].into(), [0.0, 0.0, 2.0].into()];
+/// let system = ParticleSystem::new(positions, vec![1.0, -1.0]);
+/// let energy = system.energy_decomposition(&Plain::without_cutoff());
+/// assert_relative_eq!(energy.real_space, -0.5);
+/// assert_eq!(energy.total(), system.total_energy(&Plain::without_cutoff()));
+/// ~~~
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct EnergyDecomposition {
+    /// Pairwise energy from the scheme, including scaled pairs
+    pub real_space: f64,
+    /// Reciprocal-space energy
+    pub reciprocal: f64,
+    /// Self-energy of the scheme
+    pub self_energy: f64,
+    /// Surface energy from the dipole moment of the cell
+    pub surface: f64,
+    /// Reciprocal-space corrections for excluded and scaled pairs
+    pub exclusion_correction: f64,
+    /// Energy of a uniform background neutralizing a net charge
+    pub background: f64,
+}
+
+impl EnergyDecomposition {
+    /// Sum of all contributions
+    pub fn total(&self) -> f64 {
+        self.real_space
+            + self.reciprocal
+            + self.self_energy
+            + self.surface
+            + self.exclusion_correction
+            + self.background
+    }
+}
+
+impl Display for EnergyDecomposition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "real-space           = {:.6e}", self.real_space)?;
+        writeln!(f, "reciprocal           = {:.6e}", self.reciprocal)?;
+        writeln!(f, "self-energy          = {:.6e}", self.self_energy)?;
+        writeln!(f, "surface              = {:.6e}", self.surface)?;
+        writeln!(
+            f,
+            "exclusion correction = {:.6e}",
+            self.exclusion_correction
+        )?;
+        writeln!(f, "background           = {:.6e}", self.background)?;
+        write!(f, "total                = {:.6e}", self.total())
+    }
+}
+
+/// Group membership and centers of all particles for group-based cutoffs
+struct GroupCenters {
+    /// Group index of each particle
+    membership: Vec<usize>,
+    /// Center of each group
+    centers: Vec<Vector3>,
+    /// Squared cutoff between group centers
+    cutoff_squared: f64,
+}
+
+impl GroupCenters {
+    /// True if particles `i` and `j` are in the same group or in groups within the cutoff
+    fn within(&self, cell: &Cell, i: usize, j: usize) -> bool {
+        let (a, b) = (self.membership[i], self.membership[j]);
+        a == b
+            || NalgebraVector3::from(cell.distance(self.centers[a], self.centers[b])).norm_squared()
+                < self.cutoff_squared
+    }
+}
+
+/// Point charges and dipoles, optionally in a periodic cell.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct ParticleSystem {
+    /// Particle positions, UNIT: [ input length ]
+    positions: Vec<Vector3>,
+    /// Point charges, UNIT: [ input charge ]
+    charges: Vec<f64>,
+    /// Dipole moments, UNIT: [ ( input length ) x ( input charge ) ]
+    dipoles: Option<Vec<Vector3>>,
+    /// Simulation cell
+    #[cfg_attr(feature = "serde", serde(default))]
+    cell: Cell,
+    /// Excluded and scaled pairs, (i, j) with i < j, sorted and with scaling factors
+    #[cfg_attr(feature = "serde", serde(default))]
+    scaled_pairs: Vec<((usize, usize), f64)>,
+    /// Groups of consecutive particles, _e.g._ molecules
+    #[cfg_attr(feature = "serde", serde(default))]
+    groups: Vec<Range<usize>>,
+    /// Policy for applying the cutoff
+    #[cfg_attr(feature = "serde", serde(default))]
+    cutoff_policy: CutoffPolicy,
+    /// Persistent cell list updated as particles move
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cell_list: Option<CellList>,
+    /// Reciprocal-space sum of Ewald summation
+    #[cfg_attr(feature = "serde", serde(default))]
+    reciprocal: Option<EwaldReciprocal>,
+    /// Structure factors of the reciprocal-space sum updated as particles move
+    #[cfg_attr(feature = "serde", serde(skip))]
+    structure_factors: Option<StructureFactorCache>,
+}
+
+impl ParticleSystem {
+    /// New, non-periodic system of point charges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `positions` and `charges` differ in length.
+    pub fn new(positions: Vec<Vector3>, charges: Vec<f64>) -> Self {
+        assert_eq!(positions.len(), charges.len());
+        Self {
+            positions,
+            charges,
+            dipoles: None,
+            cell: Cell::Open,
+            scaled_pairs: Vec::new(),
+            groups: Vec::new(),
+            cutoff_policy: CutoffPolicy::Atomic,
+            cell_list: None,
+            reciprocal: None,
+            structure_factors: None,
+        }
+    }
+
+    /// Set point dipole moments of all particles.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of dipoles differs from the number of particles.
+    pub fn with_dipoles(mut self, dipoles: Vec<Vector3>) -> Self {
+        assert_eq!(dipoles.len(), self.len());
+        self.dipoles = Some(dipoles);
+        self.rebuild_structure_factors();
+        self
+    }
+
+    /// Set the simulation cell.
+    pub fn with_cell(mut self, cell: Cell) -> Self {
+        self.cell = cell;
+        self.rebuild_cell_list();
+        self.rebuild_structure_factors();
+        self
+    }
+
+    /// Keep a cell list for the given cutoff, updated as particles move.
+    ///
+    /// The cell list is used by all methods with schemes having a cutoff no larger than
+    /// `cutoff`.
+    pub fn with_cell_list(mut self, cutoff: f64) -> Self {
+        self.cell_list = Some(CellList::new(&self.positions, &self.cell, cutoff));
+        self
+    }
+
+    /// Add the reciprocal-space sum of Ewald summation to energies and forces.
+    ///
+    /// The real-space part is given by the scheme, _e.g._
+    /// [`RealSpaceEwald`](crate::pairwise::RealSpaceEwald), passed to each method.
+    /// Energies and forces panic if the cell is not periodic.
+    /// In periodic cells, the structure factors are kept and updated as particles move, so
+    /// that [`ParticleSystem::delta_energy_for_move`] avoids the full reciprocal-space sum.
+    pub fn with_reciprocal(mut self, reciprocal: EwaldReciprocal) -> Self {
+        self.reciprocal = Some(reciprocal);
+        self.rebuild_structure_factors();
+        self
+    }
+
+    /// Reciprocal-space sum, if any
+    pub const fn reciprocal(&self) -> Option<&EwaldReciprocal> {
+        self.reciprocal.as_ref()
+    }
+
+    /// Exclude interactions between pairs, _e.g._ bonded 1-2 and 1-3 neighbors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a pair refers to the same particle or to a non-existing particle.
+    pub fn with_exclusions(self, pairs: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        self.with_scaled_pairs(pairs, 0.0)
+    }
+
+    /// Scale interactions between pairs, _e.g._ 1-4 neighbors, by a factor.
+    ///
+    /// A factor of zero excludes the pairs and a pair given several times keeps the last factor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a pair refers to the same particle or to a non-existing particle.
+    pub fn with_scaled_pairs(
+        mut self,
+        pairs: impl IntoIterator<Item = (usize, usize)>,
+        scale: f64,
+    ) -> Self {
+        for (i, j) in pairs {
+            assert!(i != j && i.max(j) < self.len());
+            let pair = (i.min(j), i.max(j));
+            match self.scaled_pairs.binary_search_by(|(p, _)| p.cmp(&pair)) {
+                Ok(n) => self.scaled_pairs[n].1 = scale,
+                Err(n) => self.scaled_pairs.insert(n, (pair, scale)),
+            }
+        }
+        self
+    }
+
+    /// Excluded and scaled pairs, (i, j) with i < j, with their scaling factors
+    pub fn scaled_pairs(&self) -> &[((usize, usize), f64)] {
+        &self.scaled_pairs
+    }
+
+    /// Collect consecutive particles in groups, _e.g._ molecules or charge groups.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a group is empty, refers to non-existing particles, or overlaps another group.
+    pub fn with_groups(mut self, groups: impl IntoIterator<Item = Range<usize>>) -> Self {
+        self.groups = groups.into_iter().collect();
+        self.groups.sort_by_key(|group| group.start);
+        assert!(self
+            .groups
+            .iter()
+            .all(|group| !group.is_empty() && group.end <= self.len()));
+        assert!(self.groups.windows(2).all(|w| w[0].end <= w[1].start));
+        self
+    }
+
+    /// Set the policy for applying the cutoff of schemes.
+    pub const fn with_cutoff_policy(mut self, cutoff_policy: CutoffPolicy) -> Self {
+        self.cutoff_policy = cutoff_policy;
+        self
+    }
+
+    /// Groups of consecutive particles
+    pub fn groups(&self) -> &[Range<usize>] {
+        &self.groups
+    }
+
+    /// Policy for applying the cutoff of schemes
+    pub const fn cutoff_policy(&self) -> CutoffPolicy {
+        self.cutoff_policy
+    }
+
+    /// Group membership and centers, or `None` with atomic cutoffs
+    ///
+    /// Groups are followed by single particles not in any group.
+    fn group_centers(&self, positions: &[Vector3], cutoff: f64) -> Option<GroupCenters> {
+        if self.cutoff_policy == CutoffPolicy::Atomic {
+            return None;
+        }
+        let mut membership: Vec<usize> =
+            (self.groups.len()..self.groups.len() + self.len()).collect();
+        let mut centers = Vec::with_capacity(self.groups.len() + self.len());
+        for (index, group) in self.groups.iter().enumerate() {
+            // Geometric center of the unwrapped group
+            let first = NalgebraVector3::from(positions[group.start]);
+            let sum: NalgebraVector3 = positions[group.clone()]
+                .iter()
+                .map(|position| NalgebraVector3::from(self.cell.distance(first, *position)))
+                .sum();
+            centers.push(self.cell.wrap(first + sum / group.len() as f64));
+            membership[group.clone()].fill(index);
+        }
+        centers.extend_from_slice(positions);
+        Some(GroupCenters {
+            membership,
+            centers,
+            cutoff_squared: cutoff * cutoff,
+        })
+    }
+
+    /// Scaling factor of the interaction between two particles
+    fn pair_scale(&self, i: usize, j: usize) -> f64 {
+        let pair = (i.min(j), i.max(j));
+        self.scaled_pairs
+            .binary_search_by(|(p, _)| p.cmp(&pair))
+            .map_or(1.0, |n| self.scaled_pairs[n].1)
+    }
+
+    /// Unique, non-excluded pairs that may be within the cutoff, with their scaling factors
+    fn interacting_pairs(&self, cutoff: f64) -> Vec<(usize, usize, f64)> {
+        let groups = self.group_centers(&self.positions, cutoff);
+        self.pairs(cutoff)
+            .into_iter()
+            .filter(|(i, j)| {
+                groups
+                    .as_ref()
+                    .map_or(true, |g| g.within(&self.cell, *i, *j))
+            })
+            .map(|(i, j)| (i, j, self.pair_scale(i, j)))
+            .filter(|(_, _, scale)| *scale != 0.0)
+            .collect()
+    }
+
+    fn rebuild_cell_list(&mut self) {
+        if let Some(cell_list) = &self.cell_list {
+            self.cell_list = Some(CellList::new(
+                &self.positions,
+                &self.cell,
+                cell_list.cutoff(),
+            ));
+        }
+    }
+
+    fn rebuild_structure_factors(&mut self) {
+        self.structure_factors = self
+            .reciprocal
+            .as_ref()
+            .filter(|_| self.cell.volume().is_some())
+            .map(|reciprocal| reciprocal.structure_factor_cache(self));
+    }
+
+    /// Structure factors of the reciprocal-space sum, built from scratch if not kept
+    fn structure_factor_cache(
+        &self,
+        reciprocal: &EwaldReciprocal,
+    ) -> Cow<'_, StructureFactorCache> {
+        match &self.structure_factors {
+            Some(cache) => Cow::Borrowed(cache),
+            None => Cow::Owned(reciprocal.structure_factor_cache(self)),
+        }
+    }
+
+    /// Set side lengths of an orthorhombic, periodic cell.
+    pub fn with_box_length(self, box_length: impl Into<Vector3>) -> Self {
+        self.with_cell(Cell::Orthorhombic(box_length.into()))
+    }
+
+    /// Number of particles
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// True if there are no particles
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Particle positions
+    pub fn positions(&self) -> &[Vector3] {
+        &self.positions
+    }
+
+    /// Move a particle to a new position
+    pub fn set_position(&mut self, index: usize, position: impl Into<Vector3>) {
+        let position = position.into();
+        if let Some(cell_list) = &mut self.cell_list {
+            cell_list.move_particle(index, self.positions[index], position);
+        }
+        if let Some(mut cache) = self.structure_factors.take() {
+            cache.update_for_move(self, index, position);
+            self.structure_factors = Some(cache);
+        }
+        self.positions[index] = position;
+    }
+
+    /// Set all particle positions
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of positions differs from the number of particles.
+    pub fn set_positions(&mut self, positions: Vec<Vector3>) {
+        assert_eq!(positions.len(), self.len());
+        self.positions = positions;
+        self.rebuild_cell_list();
+        self.rebuild_structure_factors();
+    }
+
+    /// Point charges
+    pub fn charges(&self) -> &[f64] {
+        &self.charges
+    }
+
+    /// Dipole moments, if any
+    pub fn dipoles(&self) -> Option<&[Vector3]> {
+        self.dipoles.as_deref()
+    }
+
+    /// Simulation cell
+    pub const fn cell(&self) -> &Cell {
+        &self.cell
+    }
+
+    /// Net charge, 𝑄 = ∑𝑞ᵢ
+    pub fn net_charge(&self) -> f64 {
+        self.charges.iter().sum()
+    }
+
+    /// Dipole moment, 𝐌 = ∑𝑞ᵢ𝐫ᵢ + ∑𝛍ᵢ, of all particles at their positions as given
+    pub fn dipole_moment(&self) -> Vector3 {
+        let moment: NalgebraVector3 = self
+            .positions
+            .iter()
+            .zip(&self.charges)
+            .map(|(position, charge)| *charge * NalgebraVector3::from(*position))
+            .sum();
+        let dipoles: NalgebraVector3 = self
+            .dipoles()
+            .unwrap_or_default()
+            .iter()
+            .map(|mu| NalgebraVector3::from(*mu))
+            .sum();
+        (moment + dipoles).into()
+    }
+
+    /// Multipole of a particle
+    pub fn multipole(&self, index: usize) -> Multipole {
+        let multipole = Multipole::new(self.charges[index]);
+        match &self.dipoles {
+            Some(dipoles) => multipole.with_dipole(dipoles[index]),
+            None => multipole,
+        }
+    }
+
+    /// Minimum image distance vector, 𝐫ⱼ - 𝐫ᵢ, from particle `i` to particle `j`
+    pub fn distance(&self, i: usize, j: usize) -> Vector3 {
+        self.minimum_image(i, j).into()
+    }
+
+    fn minimum_image(&self, i: usize, j: usize) -> NalgebraVector3 {
+        self.cell
+            .distance(self.positions[i], self.positions[j])
+            .into()
+    }
+
+    /// Persistent cell list, if it covers the cutoff
+    fn cell_list(&self, cutoff: f64) -> Option<&CellList> {
+        self.cell_list
+            .as_ref()
+            .filter(|cell_list| cutoff <= cell_list.cutoff())
+    }
+
+    /// Unique pairs, (i, j) with i < j, that may be within the cutoff
+    fn pairs(&self, cutoff: f64) -> Vec<(usize, usize)> {
+        if let Some(cell_list) = self.cell_list(cutoff) {
+            cell_list.pairs()
+        } else if cutoff.is_finite() {
+            CellList::new(&self.positions, &self.cell, cutoff).pairs()
+        } else {
+            let n = self.len();
+            (0..n)
+                .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+                .collect()
+        }
+    }
+
+    /// Total interaction energy including the self-energy of the scheme.
+    ///
+    /// Returns the energy, UNIT: [ ( input charge )^2 / ( input length ) ]
+    ///
+    /// This is the sum of [`ParticleSystem::energy_decomposition`].
+    pub fn total_energy<T: MultipoleEnergy>(&self, scheme: &T) -> f64 {
+        self.energy_decomposition(scheme).total()
+    }
+
+    /// Contributions to the total interaction energy.
+    ///
+    /// Reciprocal-space, surface, exclusion correction, and background energies are zero
+    /// unless a reciprocal-space sum is added, see [`ParticleSystem::with_reciprocal`].
+    pub fn energy_decomposition<T: MultipoleEnergy>(&self, scheme: &T) -> EnergyDecomposition {
+        let dipole_moments: Vec<f64> = self
+            .dipoles()
+            .map(|dipoles| {
+                dipoles
+                    .iter()
+                    .map(|mu| NalgebraVector3::from(*mu).norm())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let pair_energy: f64 = self
+            .interacting_pairs(scheme.cutoff())
+            .into_iter()
+            .map(|(i, j, scale)| {
+                scale
+                    * scheme.pair_energy(
+                        &self.multipole(i),
+                        &self.multipole(j),
+                        self.minimum_image(i, j),
+                    )
+            })
+            .sum();
+        let mut energy = EnergyDecomposition {
+            real_space: pair_energy,
+            self_energy: scheme.self_energy(&self.charges, &dipole_moments, &[]),
+            ..Default::default()
+        };
+        if let Some(reciprocal) = &self.reciprocal {
+            energy.reciprocal = self.structure_factor_cache(reciprocal).energy();
+            energy.surface = reciprocal.surface_energy(self);
+            energy.exclusion_correction = self.reciprocal_exclusion_energy(scheme);
+            energy.background = reciprocal.background_energy(self);
+        }
+        energy
+    }
+
+    /// Symmetric matrix of pair energies, see [`ParticleSystem::energy_matrix_into`].
+    pub fn energy_matrix<T: MultipoleEnergy>(&self, scheme: &T) -> Vec<f64> {
+        let mut matrix = vec![0.0; self.len() * self.len()];
+        self.energy_matrix_into(scheme, &mut matrix);
+        matrix
+    }
+
+    /// Fill a symmetric, row-major 𝑁 × 𝑁 matrix with the energy of each pair.
+    ///
+    /// The energy between particles `i` and `j`, UNIT: [ ( input charge )^2 / ( input length ) ],
+    /// is stored at `i * N + j` and `j * N + i`, and the diagonal is zero.
+    /// Excluded and scaled pairs as well as the cutoff policy are respected, so that the
+    /// upper triangle sums to the real-space energy of [`ParticleSystem::energy_decomposition`].
+    /// Self-energies and reciprocal-space contributions are not included.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `matrix` is not 𝑁².
+    ///
+    /// # Examples
+    /// ~~~
+    /// # use approx::assert_relative_eq;
+    /// use coulomb::{pairwise::Plain, system::ParticleSystem};
+    /// let positions = vec![[0.0; 3].into(), [0.0, 0.0, 2.0].into(), [0.0, 0.0, 4.0].into()];
+    /// let system = ParticleSystem::new(positions, vec![1.0, -1.0, 1.0]);
+    /// let mut matrix = [0.0; 9];
+    /// system.energy_matrix_into(&Plain::new(3.0, None), &mut matrix);
+    /// assert_relative_eq!(matrix[1], -0.5);
+    /// assert_relative_eq!(matrix[3], -0.5);
+    /// assert_eq!(matrix[2], 0.0); // beyond the cutoff
+    /// ~~~
+    pub fn energy_matrix_into<T: MultipoleEnergy>(&self, scheme: &T, matrix: &mut [f64]) {
+        let n = self.len();
+        assert_eq!(matrix.len(), n * n);
+        matrix.fill(0.0);
+        for (i, j, scale) in self.interacting_pairs(scheme.cutoff()) {
+            let energy = scale
+                * scheme.pair_energy(
+                    &self.multipole(i),
+                    &self.multipole(j),
+                    self.minimum_image(i, j),
+                );
+            matrix[i * n + j] = energy;
+            matrix[j * n + i] = energy;
+        }
+    }
+
+    /// Fill a list with the energy of each pair, (i, j) with i < j, with non-zero energy.
+    ///
+    /// Energies are in UNIT: [ ( input charge )^2 / ( input length ) ]
+    ///
+    /// This is a sparse alternative to [`ParticleSystem::energy_matrix_into`] for schemes with
+    /// a cutoff. The list is cleared first, so it can be reused to avoid allocations.
+    pub fn pair_energies_into<T: MultipoleEnergy>(
+        &self,
+        scheme: &T,
+        pairs: &mut Vec<(usize, usize, f64)>,
+    ) {
+        pairs.clear();
+        pairs.extend(
+            self.interacting_pairs(scheme.cutoff())
+                .into_iter()
+                .map(|(i, j, scale)| {
+                    let energy = scale
+                        * scheme.pair_energy(
+                            &self.multipole(i),
+                            &self.multipole(j),
+                            self.minimum_image(i, j),
+                        );
+                    (i, j, energy)
+                })
+                .filter(|(_, _, energy)| *energy != 0.0),
+        );
+    }
+
+    /// Interaction energy of a particle with all other particles.
+    ///
+    /// Returns the energy, UNIT: [ ( input charge )^2 / ( input length ) ]
+    ///
+    /// Only neighbors are visited if a cell list covering the cutoff is kept, see
+    /// [`ParticleSystem::with_cell_list`].
+    /// Self-energies and reciprocal-space contributions are not included.
+    pub fn particle_energy<T: MultipoleEnergy>(&self, index: usize, scheme: &T) -> f64 {
+        let groups = self.group_centers(&self.positions, scheme.cutoff());
+        self.energy_at(index, self.positions[index], groups.as_ref(), scheme)
+    }
+
+    /// Energy change when moving a particle to a new position.
+    ///
+    /// Returns the energy difference, new minus old, UNIT: [ ( input charge )^2 / ( input length ) ]
+    ///
+    /// This is the change in [`ParticleSystem::total_energy`] as used in the Metropolis
+    /// criterion. The system is not modified, see [`ParticleSystem::set_position`].
+    /// With group-based cutoffs, the move also shifts the group center, and all pairs
+    /// involving the group of the particle are evaluated.
+    /// Reciprocal-space, surface, and exclusion correction energies are updated from the kept
+    /// structure factors and dipole moment and the scaled pairs of the particle, see
+    /// [`ParticleSystem::with_reciprocal`].
+    pub fn delta_energy_for_move<T: MultipoleEnergy>(
+        &self,
+        index: usize,
+        new_position: impl Into<Vector3>,
+        scheme: &T,
+    ) -> f64 {
+        let new_position = new_position.into();
+        let reciprocal = self.reciprocal.as_ref().map_or(0.0, |reciprocal| {
+            let cache = self.structure_factor_cache(reciprocal);
+            cache.delta_energy_for_move(self, index, new_position)
+                + cache.delta_surface_energy_for_move(self, index, new_position)
+                + self.exclusion_energy_at(index, new_position, scheme)
+                - self.exclusion_energy_at(index, self.positions[index], scheme)
+        });
+        if self.cutoff_policy == CutoffPolicy::GroupCenter {
+            let mut positions = self.positions.clone();
+            positions[index] = new_position;
+            return reciprocal + self.group_energy(index, &positions, scheme)
+                - self.group_energy(index, &self.positions, scheme);
+        }
+        reciprocal + self.energy_at(index, new_position, None, scheme)
+            - self.particle_energy(index, scheme)
+    }
+
+    /// Interaction energy of a particle, if placed at `position`, with all other particles
+    fn energy_at<T: MultipoleEnergy>(
+        &self,
+        index: usize,
+        position: Vector3,
+        groups: Option<&GroupCenters>,
+        scheme: &T,
+    ) -> f64 {
+        let multipole = self.multipole(index);
+        let energy = |j: usize| {
+            let scale = self.pair_scale(index, j);
+            let within = groups.map_or(true, |groups| groups.within(&self.cell, index, j));
+            if scale == 0.0 || !within {
+                return 0.0;
+            }
+            let r = self.cell.distance(position, self.positions[j]);
+            scale * scheme.pair_energy(&multipole, &self.multipole(j), r)
+        };
+        match self.cell_list(scheme.cutoff()) {
+            Some(cell_list) => cell_list
+                .neighbors(position)
+                .into_iter()
+                .filter(|j| *j != index)
+                .map(energy)
+                .sum(),
+            None => (0..self.len()).filter(|j| *j != index).map(energy).sum(),
+        }
+    }
+
+    /// Energy of all pairs involving the group of a particle, given all positions
+    fn group_energy<T: MultipoleEnergy>(
+        &self,
+        index: usize,
+        positions: &[Vector3],
+        scheme: &T,
+    ) -> f64 {
+        let Some(groups) = self.group_centers(positions, scheme.cutoff()) else {
+            return 0.0;
+        };
+        let group = groups.membership[index];
+        let members = (0..self.len()).filter(|k| groups.membership[*k] == group);
+        let mut energy = 0.0;
+        for k in members {
+            for l in 0..self.len() {
+                let same_group = groups.membership[l] == group;
+                if l == k || (same_group && l < k) || !groups.within(&self.cell, k, l) {
+                    continue;
+                }
+                let scale = self.pair_scale(k, l);
+                if scale != 0.0 {
+                    let r = self.cell.distance(positions[k], positions[l]);
+                    energy += scale * scheme.pair_energy(&self.multipole(k), &self.multipole(l), r);
+                }
+            }
+        }
+        energy
+    }
+
+    /// Force on each particle from all other particles.
+    ///
+    /// Returns forces, UNIT: [ ( input charge )^2 / ( input length )^2 ]
+    ///
+    /// Reciprocal-space, surface, and exclusion correction forces are included if a
+    /// reciprocal-space sum is added, see [`ParticleSystem::with_reciprocal`].
+    pub fn forces<T: MultipoleForce>(&self, scheme: &T) -> Vec<Vector3> {
+        let mut forces = vec![NalgebraVector3::zeros(); self.len()];
+        for (i, j, scale) in self.interacting_pairs(scheme.cutoff()) {
+            let force = scale
+                * NalgebraVector3::from(scheme.pair_force(
+                    &self.multipole(i),
+                    &self.multipole(j),
+                    self.minimum_image(i, j),
+                ));
+            forces[j] += force;
+            forces[i] -= force;
+        }
+        if let Some(reciprocal) = &self.reciprocal {
+            let corrections = [
+                reciprocal.reciprocal_forces(self),
+                reciprocal.surface_forces(self),
+                self.reciprocal_exclusion_forces(scheme),
+            ];
+            for correction in corrections {
+                for (force, extra) in forces.iter_mut().zip(correction) {
+                    *force += NalgebraVector3::from(extra);
+                }
+            }
+        }
+        forces.into_iter().map(Into::into).collect()
+    }
+
+    /// Virial tensor, 𝐖 = ∑𝐅ᵢⱼ ⊗ 𝐫ᵢⱼ, summed over interacting pairs.
+    ///
+    /// Returns the virial, UNIT: [ ( input charge )^2 / ( input length ) ]
+    ///
+    /// This is minus the derivative of the total energy with respect to a homogeneous strain
+    /// of the cell and positions, see [`MultipoleForce::pair_virial`].
+    /// Reciprocal-space, surface, exclusion correction, and background terms are included if
+    /// a reciprocal-space sum is added, see [`ParticleSystem::with_reciprocal`].
+    pub fn virial<T: MultipoleForce>(&self, scheme: &T) -> Matrix3 {
+        let mut virial = NalgebraMatrix3::zeros();
+        for (i, j, scale) in self.interacting_pairs(scheme.cutoff()) {
+            let r = self.minimum_image(i, j);
+            virial += scale
+                * NalgebraMatrix3::from(scheme.pair_virial(
+                    &self.multipole(i),
+                    &self.multipole(j),
+                    r,
+                ));
+        }
+        if let Some(reciprocal) = &self.reciprocal {
+            let coulomb = Plain::new(f64::INFINITY, scheme.debye_length());
+            for ((i, j), scale) in &self.scaled_pairs {
+                let (a, b, r) = (
+                    self.multipole(*i),
+                    self.multipole(*j),
+                    self.minimum_image(*i, *j),
+                );
+                virial += (scale - 1.0)
+                    * (NalgebraMatrix3::from(coulomb.pair_virial(&a, &b, r))
+                        - NalgebraMatrix3::from(scheme.pair_virial(&a, &b, r)));
+            }
+            virial += NalgebraMatrix3::from(reciprocal.virial(self))
+                + NalgebraMatrix3::from(reciprocal.surface_virial(self))
+                + NalgebraMatrix3::identity() * reciprocal.background_energy(self);
+        }
+        virial.into()
+    }
+
+    /// Electrostatic contribution to the pressure tensor, 𝐖/𝑉, or `None` if not periodic.
+    ///
+    /// Returns the pressure, UNIT: [ ( input charge )^2 / ( input length )^4 ]
+    ///
+    /// The kinetic contribution is not included.
+    /// The scalar pressure is a third of the trace.
+    ///
+    /// # Examples
+    /// ~~~
+    /// # use approx::assert_relative_eq;
+    /// use coulomb::{pairwise::Plain, system::ParticleSystem};
+    /// let positions = vec![[0.0, 0.0, 0.0].into(), [0.0, 0.0, 2.0].into()];
+    /// let system = ParticleSystem::new(positions, vec![1.0, 1.0]).with_box_length([10.0; 3]);
+    /// let pressure = system.pressure_tensor(&Plain::new(5.0, None)).unwrap();
+    /// assert_relative_eq!(pressure.z.z, 0.5 / 1000.0);
+    /// assert_eq!(pressure.x.x, 0.0);
+    /// ~~~
+    pub fn pressure_tensor<T: MultipoleForce>(&self, scheme: &T) -> Option<Matrix3> {
+        let volume = self.cell.volume()?;
+        Some((NalgebraMatrix3::from(self.virial(scheme)) / volume).into())
+    }
+
+    /// Electric field at each particle from all other particles.
+    ///
+    /// Returns fields, UNIT: [ ( input charge ) / ( input length )^2 ]
+    ///
+    /// Reciprocal-space contributions are not included.
+    pub fn fields<T: MultipoleField>(&self, scheme: &T) -> Vec<Vector3> {
+        let mut fields = vec![NalgebraVector3::zeros(); self.len()];
+        for (i, j, scale) in self.interacting_pairs(scheme.cutoff()) {
+            let r = self.minimum_image(i, j);
+            let mut field_j = NalgebraVector3::from(scheme.ion_field(self.charges[i], r));
+            let mut field_i = NalgebraVector3::from(scheme.ion_field(self.charges[j], -r));
+            if let Some(dipoles) = &self.dipoles {
+                field_j += NalgebraVector3::from(scheme.dipole_field(dipoles[i], r));
+                field_i += NalgebraVector3::from(scheme.dipole_field(dipoles[j], -r));
+            }
+            fields[j] += scale * field_j;
+            fields[i] += scale * field_i;
+        }
+        fields.into_iter().map(Into::into).collect()
+    }
+
+    /// Reciprocal-space energy correction for excluded and scaled pairs.
+    ///
+    /// Returns the energy, UNIT: [ ( input charge )^2 / ( input length ) ]
+    ///
+    /// Reciprocal-space sums, as in Ewald summation, include the long-range part, 𝑢 - 𝑢ₛ,
+    /// of all pair interactions, where 𝑢 is the full Coulomb energy and 𝑢ₛ the short-range
+    /// energy from `scheme`.
+    /// For pairs scaled by 𝑓, the correction, -(1 - 𝑓)(𝑢 - 𝑢ₛ), should be added to the
+    /// reciprocal-space energy so that the pair interacts with 𝑓𝑢 in total.
+    pub fn reciprocal_exclusion_energy<T: MultipoleEnergy>(&self, scheme: &T) -> f64 {
+        let coulomb = Plain::new(f64::INFINITY, scheme.debye_length());
+        self.scaled_pairs
+            .iter()
+            .map(|((i, j), scale)| {
+                let (a, b, r) = (
+                    self.multipole(*i),
+                    self.multipole(*j),
+                    self.minimum_image(*i, *j),
+                );
+                (scale - 1.0) * (coulomb.pair_energy(&a, &b, r) - scheme.pair_energy(&a, &b, r))
+            })
+            .sum()
+    }
+
+    /// Reciprocal-space energy correction for the excluded and scaled pairs of a particle, if
+    /// placed at `position`, see [`ParticleSystem::reciprocal_exclusion_energy`].
+    fn exclusion_energy_at<T: MultipoleEnergy>(
+        &self,
+        index: usize,
+        position: Vector3,
+        scheme: &T,
+    ) -> f64 {
+        let coulomb = Plain::new(f64::INFINITY, scheme.debye_length());
+        let multipole = self.multipole(index);
+        self.scaled_pairs
+            .iter()
+            .filter_map(|((i, j), scale)| match (*i == index, *j == index) {
+                (true, _) => Some((*j, scale)),
+                (_, true) => Some((*i, scale)),
+                _ => None,
+            })
+            .map(|(other, scale)| {
+                let (b, r) = (
+                    self.multipole(other),
+                    self.cell.distance(position, self.positions[other]),
+                );
+                (scale - 1.0)
+                    * (coulomb.pair_energy(&multipole, &b, r)
+                        - scheme.pair_energy(&multipole, &b, r))
+            })
+            .sum()
+    }
+
+    /// Reciprocal-space force corrections for excluded and scaled pairs.
+    ///
+    /// Returns forces, UNIT: [ ( input charge )^2 / ( input length )^2 ]
+    ///
+    /// These are the forces from [`ParticleSystem::reciprocal_exclusion_energy`].
+    pub fn reciprocal_exclusion_forces<T: MultipoleForce>(&self, scheme: &T) -> Vec<Vector3> {
+        let coulomb = Plain::new(f64::INFINITY, scheme.debye_length());
+        let mut forces = vec![NalgebraVector3::zeros(); self.len()];
+        for ((i, j), scale) in &self.scaled_pairs {
+            let (a, b, r) = (
+                self.multipole(*i),
+                self.multipole(*j),
+                self.minimum_image(*i, *j),
+            );
+            let force = (scale - 1.0)
+                * (NalgebraVector3::from(coulomb.pair_force(&a, &b, r))
+                    - NalgebraVector3::from(scheme.pair_force(&a, &b, r)));
+            forces[*j] += force;
+            forces[*i] -= force;
+        }
+        forces.into_iter().map(Into::into).collect()
+    }
+}
+
+#[test]
+fn test_particle_system() {
+    use crate::pairwise::{Plain, ReactionField};
+    use approx::assert_relative_eq;
+    let positions: Vec<Vector3> = vec![
+        [0.0, 0.0, 0.0].into(),
+        [1.0, 2.0, 0.5].into(),
+        [-2.0, 0.5, 1.5].into(),
+        [0.5, -1.5, -2.0].into(),
+    ];
+    let charges = vec![1.0, -1.0, 0.5, -0.5];
+    let dipoles: Vec<Vector3> = vec![
+        [0.1, 0.0, 0.2].into(),
+        [0.0, -0.3, 0.0].into(),
+        [0.2, 0.2, 0.0].into(),
+        [0.0, 0.0, -0.1].into(),
+    ];
+    let system = ParticleSystem::new(positions.clone(), charges.clone());
+    assert_eq!(system.len(), 4);
+    assert!(!system.is_empty());
+
+    // Plain Coulomb energy from direct summation
+    let plain = Plain::without_cutoff();
+    let mut energy = 0.0;
+    for i in 0..4 {
+        for j in i + 1..4 {
+            let r = NalgebraVector3::from(positions[j]) - NalgebraVector3::from(positions[i]);
+            energy += charges[i] * charges[j] / r.norm();
+        }
+    }
+    assert_relative_eq!(system.total_energy(&plain), energy);
+
+    // Forces are minus the energy gradient, and equal to charge times field
+    let scheme = ReactionField::new(8.0, 80.0, 1.0, true);
+    for system in [system.clone(), system.with_dipoles(dipoles)] {
+        let forces = system.forces(&scheme);
+        let sum: NalgebraVector3 = forces.iter().map(|f| NalgebraVector3::from(*f)).sum();
+        assert_relative_eq!(sum.norm(), 0.0, epsilon = 1e-12);
+        let h = 1e-5;
+        let mut displaced = system.clone();
+        let mut position = system.positions()[2];
+        position.y += h;
+        displaced.set_position(2, position);
+        let energy_plus = displaced.total_energy(&scheme);
+        position.y -= 2.0 * h;
+        displaced.set_position(2, position);
+        let energy_minus = displaced.total_energy(&scheme);
+        assert_relative_eq!(
+            forces[2].y,
+            -(energy_plus - energy_minus) / (2.0 * h),
+            epsilon = 1e-6
+        );
+        if system.dipoles().is_none() {
+            let fields = system.fields(&scheme);
+            for ((force, field), charge) in forces.iter().zip(&fields).zip(system.charges()) {
+                assert_relative_eq!(force.x, charge * field.x, epsilon = 1e-12);
+                assert_relative_eq!(force.z, charge * field.z, epsilon = 1e-12);
+            }
+        }
+    }
+
+    // Minimum image convention
+    let system = ParticleSystem::new(
+        vec![[0.5, 0.5, 0.5].into(), [9.5, 0.5, 0.5].into()],
+        vec![1.0, 1.0],
+    )
+    .with_box_length([10.0, 10.0, 10.0]);
+    assert_relative_eq!(system.distance(0, 1).x, -1.0);
+    assert_relative_eq!(system.total_energy(&plain), 1.0);
+    assert_relative_eq!(system.forces(&plain)[1].x, -1.0);
+    assert_relative_eq!(system.fields(&plain)[0].x, 1.0);
+    assert_eq!(system.cell(), &Cell::Orthorhombic([10.0; 3].into()));
+
+    // Triclinic cell where the nearest image is across the skewed face
+    let cell = crate::pbc::TriclinicCell::new([10.0, 0.0, 0.0], [5.0, 8.0, 0.0], [0.0, 0.0, 10.0]);
+    let system = system.with_cell(Cell::Triclinic(cell.unwrap()));
+    assert_relative_eq!(system.distance(0, 1).x, -1.0);
+}
+
+#[test]
+fn test_particle_moves() {
+    use crate::pairwise::ReactionField;
+    use approx::assert_relative_eq;
+    let mut random = crate::test_utils::Random::new(2023);
+    let positions = random.positions(200, [-15.0; 3], [15.0; 3]);
+    let charges = (0..200)
+        .map(|i| if i % 2 == 0 { 1.0 } else { -1.0 })
+        .collect();
+    let cutoff = 7.0;
+    let scheme = ReactionField::new(cutoff, 80.0, 1.0, true);
+    let system = ParticleSystem::new(positions, charges).with_box_length([30.0; 3]);
+    let mut tracked = system.clone().with_cell_list(cutoff);
+
+    // Sum of particle energies is twice the pair energy
+    let pair_energy = system.total_energy(&scheme) - scheme.self_energy(system.charges(), &[], &[]);
+    let sum: f64 = (0..system.len())
+        .map(|i| tracked.particle_energy(i, &scheme))
+        .sum();
+    assert_relative_eq!(0.5 * sum, pair_energy, max_relative = 1e-10);
+
+    // Energy change of moves equals the change in total energy
+    let mut untracked = system;
+    for _ in 0..20 {
+        let index = (random.uniform() * 200.0) as usize;
+        let new_position = random.positions(1, [-15.0; 3], [15.0; 3])[0];
+        let old_energy = untracked.total_energy(&scheme);
+        let delta = tracked.delta_energy_for_move(index, new_position, &scheme);
+        assert_relative_eq!(
+            delta,
+            untracked.delta_energy_for_move(index, new_position, &scheme),
+            epsilon = 1e-10
+        );
+        tracked.set_position(index, new_position);
+        untracked.set_position(index, new_position);
+        assert_relative_eq!(
+            untracked.total_energy(&scheme) - old_energy,
+            delta,
+            epsilon = 1e-9
+        );
+    }
+    assert_relative_eq!(
+        tracked.total_energy(&scheme),
+        untracked.total_energy(&scheme),
+        epsilon = 1e-9
+    );
+}
+
+/// Sum of |zᵢzⱼ| / rᵢⱼ over all pairs, bounding the energy without damping
+#[cfg(test)]
+fn bare_energy(system: &ParticleSystem) -> f64 {
+    let mut energy = 0.0;
+    for i in 0..system.len() {
+        for j in i + 1..system.len() {
+            let r = NalgebraVector3::from(system.distance(i, j)).norm();
+            energy += (system.charges()[i] * system.charges()[j]).abs() / r;
+        }
+    }
+    energy
+}
+
+#[test]
+fn test_exclusions() {
+    use crate::pairwise::RealSpaceEwald;
+    use crate::test_utils::{erfc_step as step, ERFC_ERROR};
+    use approx::assert_relative_eq;
+    let positions: Vec<Vector3> = vec![
+        [0.0, 0.0, 0.0].into(),
+        [1.0, 0.2, 0.0].into(),
+        [1.8, 1.1, 0.3].into(),
+        [2.9, 1.0, 1.2].into(),
+        [-3.0, 4.0, 2.0].into(),
+    ];
+    let charges = vec![-0.8, 0.4, 0.3, -0.2, 0.6];
+    let scale14 = 0.5;
+    let system = ParticleSystem::new(positions, charges)
+        .with_exclusions([(0, 1), (1, 2), (0, 2), (2, 1)])
+        .with_scaled_pairs([(3, 0)], scale14);
+    assert_eq!(
+        system.scaled_pairs(),
+        &[
+            ((0, 1), 0.0),
+            ((0, 2), 0.0),
+            ((0, 3), scale14),
+            ((1, 2), 0.0)
+        ]
+    );
+    let scaled_coulomb = |system: &ParticleSystem| {
+        let mut energy = 0.0;
+        for i in 0..system.len() {
+            for j in i + 1..system.len() {
+                let r = NalgebraVector3::from(system.distance(i, j)).norm();
+                energy += system.pair_scale(i, j) * system.charges()[i] * system.charges()[j] / r;
+            }
+        }
+        energy
+    };
+
+    // Exclusions with plain Coulomb
+    let plain = Plain::without_cutoff();
+    assert_relative_eq!(system.total_energy(&plain), scaled_coulomb(&system));
+    assert_eq!(system.reciprocal_exclusion_energy(&plain), 0.0);
+    let sum: f64 = (0..system.len())
+        .map(|i| system.particle_energy(i, &plain))
+        .sum();
+    assert_relative_eq!(0.5 * sum, scaled_coulomb(&system));
+
+    // Real-space Ewald, where the long-range part of all pairs, 𝑢 - 𝑢ₛ, is added as
+    // a stand-in for the reciprocal-space energy in the limit of a large, open cell
+    let ewald = RealSpaceEwald::new(4.0, 0.3, None);
+    let unscaled = ParticleSystem::new(system.positions().to_vec(), system.charges().to_vec());
+    let long_range = unscaled.total_energy(&plain) - unscaled.total_energy(&ewald)
+        + ewald.self_energy(system.charges(), &[], &[]);
+    let energy = system.total_energy(&ewald) - ewald.self_energy(system.charges(), &[], &[])
+        + system.reciprocal_exclusion_energy(&ewald)
+        + long_range;
+    // An approximate `erfc_x` adds an error up to the bound times the bare energy for each of
+    // the two systems
+    let bare = bare_energy(&system);
+    assert_relative_eq!(
+        energy,
+        scaled_coulomb(&system),
+        max_relative = 1e-12,
+        epsilon = 2.0 * ERFC_ERROR * bare
+    );
+
+    // Forces are minus the energy gradient
+    let total = |system: &ParticleSystem| {
+        system.total_energy(&ewald) + system.reciprocal_exclusion_energy(&ewald)
+    };
+    let forces = system.forces(&ewald);
+    let corrections = system.reciprocal_exclusion_forces(&ewald);
+    let h = step(1e-5, 1e-2);
+    for index in [0, 3] {
+        let mut displaced = system.clone();
+        let mut position = system.positions()[index];
+        position.x += h;
+        displaced.set_position(index, position);
+        let energy_plus = total(&displaced);
+        position.x -= 2.0 * h;
+        displaced.set_position(index, position);
+        let energy_minus = total(&displaced);
+        assert_relative_eq!(
+            forces[index].x + corrections[index].x,
+            -(energy_plus - energy_minus) / (2.0 * h),
+            epsilon = 1e-6 + ERFC_ERROR * bare / h
+        );
+    }
+}
+
+#[test]
+fn test_group_cutoff() {
+    use crate::pairwise::ReactionField;
+    use approx::assert_relative_eq;
+    // Three water-like dipolar molecules with partial charges
+    let molecule = |x: f64, y: f64| -> Vec<Vector3> {
+        vec![
+            [x, y, 0.0].into(),
+            [x + 0.8, y + 0.6, 0.0].into(),
+            [x - 0.8, y + 0.6, 0.0].into(),
+        ]
+    };
+    let positions = [molecule(0.0, 0.0), molecule(6.5, 0.0), molecule(-2.0, 3.0)].concat();
+    let charges = [-0.8, 0.4, 0.4].repeat(3);
+    let cutoff = 6.6;
+    let scheme = ReactionField::new(cutoff, 80.0, 1.0, true);
+    let atomic = ParticleSystem::new(positions, charges)
+        .with_groups([0..3, 3..6, 6..9])
+        .with_box_length([20.0; 3]);
+    let grouped = atomic.clone().with_cutoff_policy(CutoffPolicy::GroupCenter);
+    assert_eq!(grouped.cutoff_policy(), CutoffPolicy::GroupCenter);
+    assert_eq!(grouped.groups().len(), 3);
+
+    let pairs = |system: &ParticleSystem| -> Vec<(usize, usize)> {
+        let pairs = system.interacting_pairs(cutoff);
+        pairs.into_iter().map(|(i, j, _)| (i, j)).collect()
+    };
+    assert!(pairs(&atomic).contains(&(0, 3)));
+    assert!(pairs(&grouped).contains(&(0, 3)));
+
+    // Moving the second molecule away puts its center beyond the cutoff
+    let mut atomic = atomic;
+    let mut grouped = grouped;
+    for index in 3..6 {
+        let mut position = atomic.positions()[index];
+        position.x += 0.2;
+        atomic.set_position(index, position);
+        grouped.set_position(index, position);
+    }
+    let between = |(i, j): &(usize, usize)| *i < 3 && (3..6).contains(j);
+    assert!(pairs(&atomic).iter().any(between));
+    assert!(!pairs(&grouped).iter().any(between));
+    assert!(atomic.total_energy(&scheme) != grouped.total_energy(&scheme));
+
+    // Energy changes of moves, including group center shifts across the cutoff
+    for (index, dx) in [(3, -0.9), (1, 0.3), (7, 1.0), (4, 0.1)] {
+        let old_energy = grouped.total_energy(&scheme);
+        let mut position = grouped.positions()[index];
+        position.x += dx;
+        let delta = grouped.delta_energy_for_move(index, position, &scheme);
+        grouped.set_position(index, position);
+        assert_relative_eq!(
+            grouped.total_energy(&scheme) - old_energy,
+            delta,
+            epsilon = 1e-12
+        );
+    }
+    let sum: f64 = (0..grouped.len())
+        .map(|i| grouped.particle_energy(i, &scheme))
+        .sum();
+    assert_relative_eq!(
+        0.5 * sum,
+        grouped.total_energy(&scheme) - scheme.self_energy(grouped.charges(), &[], &[]),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_energy_decomposition() {
+    use crate::pairwise::RealSpaceEwald;
+    use crate::reciprocal::BoundaryPermittivity;
+    use crate::test_utils::{erfc_step as step, ERFC_ERROR};
+    use approx::assert_relative_eq;
+    let positions: Vec<Vector3> = vec![
+        [0.0, 0.0, 0.0].into(),
+        [1.0, 0.5, -0.5].into(),
+        [3.0, -2.0, 1.0].into(),
+        [-3.5, 2.5, 3.0].into(),
+        [2.0, 4.0, -3.0].into(),
+    ];
+    let scheme = RealSpaceEwald::new(4.0, 0.8, None);
+    let reciprocal =
+        EwaldReciprocal::new(0.8, 8, None).with_surface_permittivity(BoundaryPermittivity::Vacuum);
+    let system = ParticleSystem::new(positions, vec![1.0, -1.0, 0.5, 1.0, -0.5])
+        .with_box_length([9.0; 3])
+        .with_exclusions([(0, 1)])
+        .with_reciprocal(reciprocal.clone());
+
+    let energy = system.energy_decomposition(&scheme);
+    assert_relative_eq!(energy.reciprocal, reciprocal.reciprocal_energy(&system));
+    assert_relative_eq!(energy.surface, reciprocal.surface_energy(&system));
+    assert_relative_eq!(energy.background, reciprocal.background_energy(&system));
+    assert_relative_eq!(
+        energy.exclusion_correction,
+        system.reciprocal_exclusion_energy(&scheme)
+    );
+    assert!(energy.background < 0.0 && energy.surface > 0.0);
+    assert_eq!(energy.total(), system.total_energy(&scheme));
+    assert_eq!(energy.to_string().lines().count(), 7);
+    assert!(energy.to_string().starts_with("real-space"));
+
+    // Without reciprocal space, only real-space and self-energies remain
+    let real_space = ParticleSystem::new(system.positions().to_vec(), system.charges().to_vec())
+        .with_box_length([9.0; 3])
+        .with_exclusions([(0, 1)])
+        .energy_decomposition(&scheme);
+    assert_eq!(real_space.real_space, energy.real_space);
+    assert_eq!(real_space.self_energy, energy.self_energy);
+    assert_eq!(real_space.reciprocal, 0.0);
+    assert_eq!(real_space.exclusion_correction, 0.0);
+
+    // Forces and move energies are consistent with the total energy, where the forward
+    // difference has an error of order the step, and an approximate `erfc_x` adds an error up
+    // to the bound times the bare energy over the step
+    let forces = system.forces(&scheme);
+    let bare = bare_energy(&system);
+    let h = step(1e-6, 1e-3);
+    for index in [0, 2] {
+        let mut position = system.positions()[index];
+        position.z += h;
+        let delta = system.delta_energy_for_move(index, position, &scheme);
+        let mut moved = system.clone();
+        moved.set_position(index, position);
+        assert_relative_eq!(
+            delta,
+            moved.total_energy(&scheme) - system.total_energy(&scheme),
+            epsilon = 1e-12 + 2.0 * ERFC_ERROR * bare
+        );
+        assert_relative_eq!(
+            -delta / h,
+            forces[index].z,
+            epsilon = 1e-5 + h + 2.0 * ERFC_ERROR * bare / h
+        );
+    }
+}
+
+#[test]
+fn test_cached_structure_factors() {
+    use crate::pairwise::RealSpaceEwald;
+    use crate::reciprocal::BoundaryPermittivity;
+    use approx::assert_relative_eq;
+    let mut random = crate::test_utils::Random::new(5);
+    let positions = random.positions(8, [0.0; 3], [9.0; 3]);
+    let dipoles = random.positions(8, [-0.3; 3], [0.3; 3]);
+    let charges = vec![1.0, -1.0, 0.5, 1.0, -0.5, -1.0, 0.5, -0.5];
+    let scheme = RealSpaceEwald::new(4.0, 0.8, None);
+    let reciprocal =
+        EwaldReciprocal::new(0.8, 8, None).with_surface_permittivity(BoundaryPermittivity::Vacuum);
+    let mut system = ParticleSystem::new(positions, charges)
+        .with_dipoles(dipoles)
+        .with_box_length([9.0; 3])
+        .with_exclusions([(0, 1), (2, 5)])
+        .with_scaled_pairs([(1, 2)], 0.5)
+        .with_reciprocal(reciprocal.clone());
+
+    // Cached structure factors and dipole moment follow accepted moves and agree with a
+    // full rebuild
+    for _ in 0..20 {
+        let index = (random.uniform() * system.len() as f64) as usize;
+        let position = random.positions(1, [-1.0; 3], [10.0; 3])[0];
+        let delta = system.delta_energy_for_move(index, position, &scheme);
+        let old_energy = system.total_energy(&scheme);
+        system.set_position(index, position);
+        let mut rebuilt = system.clone();
+        rebuilt.set_positions(system.positions().to_vec());
+        let energy = rebuilt.energy_decomposition(&scheme);
+        assert_relative_eq!(
+            system.energy_decomposition(&scheme).reciprocal,
+            energy.reciprocal,
+            epsilon = 1e-12
+        );
+        let cache = system.structure_factors.as_ref().unwrap();
+        assert_relative_eq!(cache.surface_energy(), energy.surface, epsilon = 1e-12);
+        assert_relative_eq!(delta, energy.total() - old_energy, epsilon = 1e-10);
+    }
+    assert!(system.structure_factors.is_some());
+
+    // Structure factors are only kept in periodic cells
+    let open = ParticleSystem::new(vec![[0.0; 3].into()], vec![1.0]).with_reciprocal(reciprocal);
+    assert!(open.structure_factors.is_none());
+}
+
+#[test]
+fn test_virial() {
+    use crate::pairwise::RealSpaceEwald;
+    use crate::pbc::TriclinicCell;
+    use crate::reciprocal::BoundaryPermittivity;
+    use crate::test_utils::{erfc_step as step, ERFC_ERROR};
+    use approx::assert_relative_eq;
+    let positions: Vec<Vector3> = vec![
+        [0.0, 0.0, 0.0].into(),
+        [1.0, 0.5, -0.5].into(),
+        [3.0, -2.0, 1.0].into(),
+        [-3.5, 2.5, 3.0].into(),
+        [2.0, 4.0, -3.0].into(),
+    ];
+    let dipoles: Vec<Vector3> = vec![
+        [0.1, 0.0, 0.2].into(),
+        [0.0, -0.3, 0.0].into(),
+        [0.2, 0.2, 0.0].into(),
+        [0.0, 0.0, -0.1].into(),
+        [0.3, -0.1, 0.1].into(),
+    ];
+    let scheme = RealSpaceEwald::new(4.0, 0.9, None);
+    let reciprocal =
+        EwaldReciprocal::new(0.9, 8, None).with_surface_permittivity(BoundaryPermittivity::Vacuum);
+
+    // Homogeneously strained cell and positions
+    let strained = |strain: NalgebraMatrix3| {
+        let deformation = NalgebraMatrix3::identity() + strain;
+        let matrix: Matrix3 = (deformation * 9.0).into();
+        let positions = positions
+            .iter()
+            .map(|position| (deformation * NalgebraVector3::from(*position)).into())
+            .collect();
+        ParticleSystem::new(positions, vec![1.0, -1.0, 0.5, 1.0, -0.5])
+            .with_dipoles(dipoles.clone())
+            .with_cell(Cell::Triclinic(TriclinicCell::try_from(matrix).unwrap()))
+            .with_exclusions([(0, 1)])
+            .with_reciprocal(reciprocal.clone())
+    };
+    let system = strained(NalgebraMatrix3::zeros());
+    let virial = NalgebraMatrix3::from(system.virial(&scheme));
+    // An approximate `erfc_x` adds an error up to the bound times the bare energy over the step
+    let bare = bare_energy(&system);
+    let h = step(1e-6, 1e-3);
+    for i in 0..3 {
+        for j in 0..3 {
+            let mut strain = NalgebraMatrix3::zeros();
+            strain[(i, j)] = h;
+            let derivative = (strained(strain).total_energy(&scheme)
+                - strained(-strain).total_energy(&scheme))
+                / (2.0 * h);
+            assert_relative_eq!(
+                virial[(i, j)],
+                -derivative,
+                epsilon = 1e-6 + ERFC_ERROR * bare / h
+            );
+        }
+    }
+
+    let pressure = NalgebraMatrix3::from(system.pressure_tensor(&scheme).unwrap());
+    assert_relative_eq!(pressure * 729.0, virial, epsilon = 1e-12);
+    let open = ParticleSystem::new(positions.clone(), vec![0.0; 5]);
+    assert!(open.pressure_tensor(&scheme).is_none());
+}
+
+#[test]
+fn test_energy_matrix() {
+    use crate::pairwise::ReactionField;
+    use approx::assert_relative_eq;
+    let mut random = crate::test_utils::Random::new(11);
+    let positions = random.positions(20, [0.0; 3], [10.0; 3]);
+    let charges = (0..20)
+        .map(|i| if i % 2 == 0 { 1.0 } else { -1.0 })
+        .collect();
+    let system = ParticleSystem::new(positions, charges)
+        .with_box_length([10.0; 3])
+        .with_scaled_pairs([(0, 1), (2, 3)], 0.5)
+        .with_exclusions([(4, 5)]);
+    let scheme = ReactionField::new(3.0, 80.0, 1.0, true);
+
+    let n = system.len();
+    let matrix = system.energy_matrix(&scheme);
+    let upper: f64 = (0..n)
+        .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+        .map(|(i, j)| matrix[i * n + j])
+        .sum();
+    let energy = system.energy_decomposition(&scheme);
+    assert_relative_eq!(upper, energy.real_space, epsilon = 1e-12);
+    assert!((0..n).all(|i| matrix[i * n + i] == 0.0));
+    assert!((0..n).all(|i| (0..n).all(|j| matrix[i * n + j] == matrix[j * n + i])));
+    assert_eq!(matrix[4 * n + 5], 0.0);
+
+    // Sparse list reusing a buffer
+    let mut pairs = vec![(0, 0, 1.0); 3];
+    system.pair_energies_into(&scheme, &mut pairs);
+    assert!(pairs.len() < n * (n - 1) / 2);
+    for (i, j, energy) in &pairs {
+        assert!(i < j);
+        assert_eq!(*energy, matrix[i * n + j]);
+    }
+    let sum: f64 = pairs.iter().map(|(_, _, energy)| energy).sum();
+    assert_relative_eq!(sum, upper, epsilon = 1e-12);
+}
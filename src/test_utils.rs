@@ -0,0 +1,77 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Shared helpers for unit tests.
+
+use crate::Vector3;
+
+/// Deterministic pseudo-random numbers from a 64-bit linear congruential generator
+pub(crate) struct Random(u64);
+
+impl Random {
+    /// New generator with a given seed
+    pub(crate) const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Uniform random number in [0, 1)
+    pub(crate) fn uniform(&mut self) -> f64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Uniform random positions in the box spanned by `lower` and `upper` corners
+    pub(crate) fn positions(&mut self, n: usize, lower: [f64; 3], upper: [f64; 3]) -> Vec<Vector3> {
+        (0..n)
+            .map(|_| {
+                let mut position = [0.0; 3];
+                for (i, x) in position.iter_mut().enumerate() {
+                    *x = lower[i] + (upper[i] - lower[i]) * self.uniform();
+                }
+                position.into()
+            })
+            .collect()
+    }
+}
+
+/// Bound on the error of `erfc_x` and `exp_x` with the enabled features, see [`crate::math`].
+///
+/// `fast-erfc` has an absolute erfc error ≤ 1.5 × 10⁻⁷. `mixed-precision` has relative errors
+/// ≤ (1 + x²) × 2 × 10⁻⁷ for erfc and ≤ (1 + |x|) × 1.2 × 10⁻⁷ for exp, _i.e._ ≤ 10⁻⁶ for
+/// erfc arguments x ≤ 2 and exp arguments |x| ≤ 7.
+/// Short-range functions then deviate from double precision by at most this bound times the
+/// bare interaction, and finite differences with step `h` by this bound times the bare
+/// interaction over `h`.
+pub(crate) const ERFC_ERROR: f64 = if cfg!(feature = "mixed-precision") {
+    1e-6
+} else if cfg!(feature = "fast-erfc") {
+    1.5e-7
+} else {
+    0.0
+};
+
+/// Finite-difference step for tests going through `erfc_x`, which is larger in mixed precision.
+///
+/// Rounding of single precision `erfc` and `exp` is noise, which difference quotients amplify
+/// by one over the step, unlike the smooth error of `fast-erfc`.
+pub(crate) fn erfc_step(exact: f64, approximate: f64) -> f64 {
+    if cfg!(feature = "mixed-precision") {
+        approximate
+    } else {
+        exact
+    }
+}
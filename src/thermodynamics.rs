@@ -0,0 +1,177 @@
+// Copyright 2023 Mikael Lund
+//
+// Licensed under the Apache license, version 2.0 (the "license");
+// you may not use this file except in compliance with the license.
+// You may obtain a copy of the license at
+//
+//     http://www.apache.org/licenses/license-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the license is distributed on an "as is" basis,
+// without warranties or conditions of any kind, either express or implied.
+// See the license for the specific language governing permissions and
+// limitations under the license.
+
+//! Enthalpy and entropy of electrostatic free energies.
+//!
+//! Electrostatic free energies depend on temperature through the relative permittivity,
+//! and, for screened interactions, through the Debye length.
+//! With the entropy, $S = -\partial G / \partial T$, and enthalpy, $H = G + TS$,
+//! a free energy scaling as $G \propto T^a \varepsilon_r^b$ decomposes as
+//!
+//! $$ -TS = G \left ( a + b \frac{\partial \ln \varepsilon_r}{\partial \ln T} \right ) \quad \text{and} \quad H = G + TS $$
+//!
+//! For water at room temperature, $\partial \ln \varepsilon_r / \partial \ln T \approx -1.38$,
+//! whereby the Coulomb energy between fixed charges is mainly entropic.
+//! Derivatives are taken at constant concentration, neglecting thermal expansion.
+//!
+//! # Examples
+//! ~~~
+//! # use approx::assert_relative_eq;
+//! use coulomb::{thermodynamics::ThermodynamicDecomposition, permittivity::Permittivity};
+//! let energy = -10.0; // e.g. kJ/mol
+//! let parts = ThermodynamicDecomposition::coulomb(energy, &Permittivity::Water, 298.15).unwrap();
+//! assert_relative_eq!(parts.enthalpy, 3.81, epsilon = 0.01);
+//! assert_relative_eq!(parts.enthalpy - 298.15 * parts.entropy, energy);
+//! ~~~
+
+use crate::permittivity::RelativePermittivity;
+use crate::{born_energy, Result};
+
+/// Free energy decomposed into enthalpy and entropy at a given temperature
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThermodynamicDecomposition {
+    /// Temperature, UNIT: [ K ]
+    pub temperature: f64,
+    /// Free energy, G
+    pub free_energy: f64,
+    /// Enthalpy, H = G + TS, in units of the free energy
+    pub enthalpy: f64,
+    /// Entropy, S = -dG/dT, in units of the free energy per kelvin
+    pub entropy: f64,
+}
+
+impl ThermodynamicDecomposition {
+    /// Decompose a free energy from its temperature derivative, dG/dT.
+    pub fn from_derivative(free_energy: f64, derivative: f64, temperature: f64) -> Self {
+        let entropy = -derivative;
+        Self {
+            temperature,
+            free_energy,
+            enthalpy: free_energy + temperature * entropy,
+            entropy,
+        }
+    }
+
+    /// Decompose a free energy scaling as G ∝ Tᵃεᵣᵇ, see the module documentation.
+    ///
+    /// # Errors
+    /// Returns an error if the temperature derivative of the permittivity is unavailable.
+    pub fn from_scaling(
+        free_energy: f64,
+        temperature_exponent: f64,
+        permittivity_exponent: f64,
+        permittivity_model: &impl RelativePermittivity,
+        temperature: f64,
+    ) -> Result<Self> {
+        let dlog_permittivity = permittivity_model
+            .dlog_permittivity_dlog_temperature(temperature)
+            .ok_or(crate::Error::Unsupported(
+                "temperature derivative of permittivity",
+            ))?;
+        let dlog_energy = temperature_exponent + permittivity_exponent * dlog_permittivity;
+        Ok(Self::from_derivative(
+            free_energy,
+            free_energy * dlog_energy / temperature,
+            temperature,
+        ))
+    }
+
+    /// Decompose an interaction energy between fixed charges, G ∝ 1/εᵣ.
+    ///
+    /// This applies to unscreened energies in absolute units, such as kJ/mol.
+    /// Energies in units of kT scale as λ𝐵 ∝ 1/(εᵣT) and must first be multiplied by kT.
+    ///
+    /// # Errors
+    /// Returns an error if the temperature derivative of the permittivity is unavailable.
+    pub fn coulomb(
+        free_energy: f64,
+        permittivity_model: &impl RelativePermittivity,
+        temperature: f64,
+    ) -> Result<Self> {
+        Self::from_scaling(free_energy, 0.0, -1.0, permittivity_model, temperature)
+    }
+
+    /// Decompose a Debye-Hückel excess free energy, G ∝ kTλ𝐵κ ∝ T^(-1/2) εᵣ^(-3/2).
+    ///
+    /// This applies to the excess chemical potential, kT ln γ, from the limiting law and
+    /// to the excess free energy density, -kTκ³/12π, of an electrolyte.
+    ///
+    /// # Errors
+    /// Returns an error if the temperature derivative of the permittivity is unavailable.
+    pub fn debye_huckel(
+        free_energy: f64,
+        permittivity_model: &impl RelativePermittivity,
+        temperature: f64,
+    ) -> Result<Self> {
+        Self::from_scaling(free_energy, -0.5, -1.5, permittivity_model, temperature)
+    }
+
+    /// Decompose the Born solvation free energy (kJ/mol) of an ion, see [`born_energy`].
+    ///
+    /// # Errors
+    /// Returns an error if the temperature is out of range or if the temperature derivative
+    /// of the permittivity is unavailable.
+    pub fn born(
+        charge: f64,
+        radius: f64,
+        permittivity_model: &impl RelativePermittivity,
+        temperature: f64,
+    ) -> Result<Self> {
+        let (free_energy, derivative) =
+            born_energy(charge, radius, permittivity_model, temperature)?;
+        let derivative = derivative.ok_or(crate::Error::Unsupported(
+            "temperature derivative of permittivity",
+        ))?;
+        Ok(Self::from_derivative(free_energy, derivative, temperature))
+    }
+}
+
+#[test]
+fn test_thermodynamic_decomposition() {
+    use crate::{activity, permittivity::Permittivity, Medium, Salt, Temperature};
+    use approx::assert_relative_eq;
+    let temperature = 298.15;
+    let h = 1e-3;
+
+    // Debye-Hückel excess chemical potential, kT ln γ, from finite differences
+    let mut medium = Medium::salt_water(temperature, Salt::SodiumChloride, 0.01);
+    let mut chemical_potential = |t: f64| {
+        medium.set_temperature(t).unwrap();
+        t * activity::activity_coefficient(1, &medium).unwrap().ln()
+    };
+    let energy = chemical_potential(temperature);
+    let derivative =
+        (chemical_potential(temperature + h) - chemical_potential(temperature - h)) / (2.0 * h);
+    let expected = ThermodynamicDecomposition::from_derivative(energy, derivative, temperature);
+    let parts = ThermodynamicDecomposition::debye_huckel(energy, &Permittivity::Water, temperature)
+        .unwrap();
+    assert_relative_eq!(parts.entropy, expected.entropy, max_relative = 1e-6);
+    assert_relative_eq!(parts.enthalpy, expected.enthalpy, max_relative = 1e-6);
+
+    // Born solvation
+    let parts =
+        ThermodynamicDecomposition::born(1.0, 2.0, &Permittivity::Water, temperature).unwrap();
+    assert!(parts.entropy < 0.0);
+    assert_relative_eq!(
+        parts.enthalpy - temperature * parts.entropy,
+        parts.free_energy
+    );
+
+    // No entropy with constant permittivity
+    let parts =
+        ThermodynamicDecomposition::coulomb(-1.0, &Permittivity::Fixed(80.0), temperature).unwrap();
+    assert_eq!((parts.enthalpy, parts.entropy), (-1.0, 0.0));
+    assert!(ThermodynamicDecomposition::coulomb(-1.0, &Permittivity::Water, 500.0).is_err());
+}
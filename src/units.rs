@@ -2,18 +2,22 @@
 pub use uom::si::{
     amount_of_substance::mole,
     electric_charge::elementary_charge,
+    electric_charge_areal_density::coulomb_per_square_meter,
     electric_dipole_moment::{atomic_unit_of_charge_centimeter, debye},
     electric_field::{atomic_unit_of_electric_field, volt_per_micrometer},
     electric_permittivity::farad_per_meter,
     electric_potential::volt,
     energy::joule,
     f64::{
-        AmountOfSubstance, ElectricCharge, ElectricChargeLinearDensity, ElectricDipoleMoment,
-        ElectricField, ElectricPermittivity, ElectricPotential, Energy, Length, MolarEnergy,
-        Volume,
+        AmountOfSubstance, ElectricCharge, ElectricChargeArealDensity, ElectricChargeLinearDensity,
+        ElectricDipoleMoment, ElectricField, ElectricPermittivity, ElectricPotential, Energy,
+        Length, MolarEnergy, Pressure, TemperatureCoefficient, ThermodynamicTemperature, Volume,
     },
     length::{angstrom, nanometer},
     molar_energy::kilojoule_per_mole,
+    pressure::pascal,
+    temperature_coefficient::per_kelvin,
+    thermodynamic_temperature::{degree_celsius, kelvin},
     volume::{cubic_centimeter, cubic_nanometer},
 };
 
@@ -21,4 +25,41 @@ unit! {
     system: uom::si;
     quantity: uom::si::electric_charge_linear_density;
     @valence_per_angstrom: 1.602_176_633_999_999_8e-9; "e/Å", "valence_per_angstrom", "valence_per_angstroms";
+    @valence_per_nanometer: 1.602_176_634e-10; "e/nm", "valence_per_nanometer", "valence_per_nanometers";
+}
+
+/// Custom units of surface charge density
+mod areal_density {
+    unit! {
+        system: uom::si;
+        quantity: uom::si::electric_charge_areal_density;
+        @valence_per_angstrom_squared: 16.021_766_34; "e/Å²", "valence_per_angstrom_squared", "valence_per_angstrom_squared";
+        @valence_per_nanometer_squared: 0.160_217_663_4; "e/nm²", "valence_per_nanometer_squared", "valence_per_nanometer_squared";
+    }
+}
+pub use areal_density::{valence_per_angstrom_squared, valence_per_nanometer_squared};
+
+/// Custom units of electric field
+mod field {
+    unit! {
+        system: uom::si;
+        quantity: uom::si::electric_field;
+        @volt_per_angstrom: 1e10; "V/Å", "volt_per_angstrom", "volts_per_angstrom";
+    }
+}
+pub use field::volt_per_angstrom;
+
+#[test]
+fn test_custom_units() {
+    use approx::assert_relative_eq;
+    let sigma = ElectricChargeArealDensity::new::<valence_per_nanometer_squared>(1.0);
+    assert_relative_eq!(sigma.get::<valence_per_angstrom_squared>(), 0.01);
+    assert_relative_eq!(
+        sigma.get::<coulomb_per_square_meter>(),
+        crate::ELEMENTARY_CHARGE * 1e18
+    );
+    let line = ElectricChargeLinearDensity::new::<valence_per_nanometer>(1.0);
+    assert_relative_eq!(line.get::<valence_per_angstrom>(), 0.1);
+    let field = ElectricField::new::<volt_per_angstrom>(1.0);
+    assert_relative_eq!(field.get::<volt_per_micrometer>(), 1e4);
 }